@@ -43,16 +43,6 @@ Object.keys(market_types).forEach((exchange) => {
     });
 });
 
-apps.push({
-    name: "logrotate",
-    script: "/usr/local/bin/logrotate.sh",
-    args: "/usr/local/etc/logrotate.bbo.conf",
-    exec_interpreter: "none",
-    exec_mode: "fork_mode",
-    cron_restart: "*/15 * * * *",
-    autorestart: false,
-});
-
 apps.push({
     name: "upload",
     script: "/usr/local/bin/upload.sh",
@@ -0,0 +1,36 @@
+// see src/market_type.rs in crypto-markets
+const market_types = {
+    bitmex: ["unknown"], // unknown means all
+};
+
+const apps = [];
+
+Object.keys(market_types).forEach((exchange) => {
+    market_types[exchange].forEach((market_ype) => {
+        const app = {
+            name: `crawler-instrument-status-${exchange}-${market_ype}`,
+            script: "carbonbot",
+            args: `${exchange} ${market_ype} instrument_status`,
+            exec_interpreter: "none",
+            exec_mode: "fork_mode",
+            instances: 1,
+            restart_delay: 5000, // 5 seconds
+        };
+
+        apps.push(app);
+    });
+});
+
+apps.push({
+    name: "upload",
+    script: "/usr/local/bin/upload.sh",
+    args: "instrument_status",
+    exec_interpreter: "bash",
+    exec_mode: "fork_mode",
+    instances: 1,
+    restart_delay: 5000, // 5 seconds
+});
+
+module.exports = {
+    apps,
+};
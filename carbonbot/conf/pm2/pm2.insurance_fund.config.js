@@ -0,0 +1,37 @@
+// see src/market_type.rs in crypto-markets
+const market_types = {
+    bitmex: ["unknown"], // unknown means all
+    bybit: ["inverse_future", "inverse_swap"],
+};
+
+const apps = [];
+
+Object.keys(market_types).forEach((exchange) => {
+    market_types[exchange].forEach((market_ype) => {
+        const app = {
+            name: `crawler-insurance-fund-${exchange}-${market_ype}`,
+            script: "carbonbot",
+            args: `${exchange} ${market_ype} insurance_fund`,
+            exec_interpreter: "none",
+            exec_mode: "fork_mode",
+            instances: 1,
+            restart_delay: 5000, // 5 seconds
+        };
+
+        apps.push(app);
+    });
+});
+
+apps.push({
+    name: "upload",
+    script: "/usr/local/bin/upload.sh",
+    args: "insurance_fund",
+    exec_interpreter: "bash",
+    exec_mode: "fork_mode",
+    instances: 1,
+    restart_delay: 5000, // 5 seconds
+});
+
+module.exports = {
+    apps,
+};
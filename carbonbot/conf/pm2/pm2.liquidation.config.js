@@ -0,0 +1,38 @@
+// see src/market_type.rs in crypto-markets
+const market_types = {
+    binance: ["inverse_swap", "linear_swap"], // !forceOrder@arr covers futures too
+    bitmex: ["unknown"], // unknown means all
+    bybit: ["inverse_future", "inverse_swap", "linear_swap"],
+};
+
+const apps = [];
+
+Object.keys(market_types).forEach((exchange) => {
+    market_types[exchange].forEach((market_ype) => {
+        const app = {
+            name: `crawler-liquidation-${exchange}-${market_ype}`,
+            script: "carbonbot",
+            args: `${exchange} ${market_ype} liquidation`,
+            exec_interpreter: "none",
+            exec_mode: "fork_mode",
+            instances: 1,
+            restart_delay: 5000, // 5 seconds
+        };
+
+        apps.push(app);
+    });
+});
+
+apps.push({
+    name: "upload",
+    script: "/usr/local/bin/upload.sh",
+    args: "liquidation",
+    exec_interpreter: "bash",
+    exec_mode: "fork_mode",
+    instances: 1,
+    restart_delay: 5000, // 5 seconds
+});
+
+module.exports = {
+    apps,
+};
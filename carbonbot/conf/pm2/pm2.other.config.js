@@ -1,8 +1,6 @@
 // see src/market_type.rs in crypto-markets
 const market_types = {
-    binance: ["inverse_swap", "linear_swap"],
     bitmex: ["unknown"],
-    bybit: ["inverse_future", "inverse_swap"],
     coinbase_pro: ["spot"],
     huobi: ["spot", "inverse_future", "linear_swap", "inverse_swap"],
 };
@@ -25,16 +23,6 @@ Object.keys(market_types).forEach((exchange) => {
     });
 });
 
-apps.push({
-    name: "logrotate",
-    script: "/usr/local/bin/logrotate.sh",
-    args: "/usr/local/etc/logrotate.other.conf",
-    exec_interpreter: "none",
-    exec_mode: "fork_mode",
-    cron_restart: "*/15 * * * *",
-    autorestart: false,
-});
-
 apps.push({
     name: "upload",
     script: "/usr/local/bin/upload.sh",
@@ -12,7 +12,7 @@ docker rm $(docker ps -aq --filter "name=carbonbot")
 mkdir -p $LOCAL_DATA_DIR
 
 # l2_snapshot and open_interest are not included, better deploy them in a different network
-msg_types=("trade" "l2_event" "l2_topk" "l3_event" "bbo" "ticker" "candlestick" "funding_rate" "other")
+msg_types=("trade" "l2_event" "l2_topk" "l3_event" "bbo" "ticker" "candlestick" "funding_rate" "mark_price" "index_price" "liquidation" "insurance_fund" "settlement" "instrument_status" "other")
 
 for msg_type in ${msg_types[@]}; do
   docker run -d --name carbonbot-$msg_type --restart always -v $LOCAL_DATA_DIR:/data -e DATA_DIR=/data -e AWS_ACCESS_KEY_ID=$AWS_ACCESS_KEY_ID -e AWS_SECRET_ACCESS_KEY=$AWS_SECRET_ACCESS_KEY -e AWS_S3_DIR=$AWS_S3_DIR -u "$(id -u):$(id -g)" soulmachine/carbonbot pm2-runtime start pm2.$msg_type.config.js
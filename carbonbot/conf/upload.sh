@@ -1,7 +1,7 @@
 #!/bin/bash
 # Linted by https://www.shellcheck.net/
 
-# This script aims to harvest .json files generated by logrotate,
+# This script aims to harvest .json files finalized by carbonbot,
 # compress and upload them to S3.
 
 market_type=$1
@@ -14,7 +14,7 @@ fi
 while :
 do
   sleep 60
-  # Find .json files and compress them
+  # Find uncompressed .json files and compress them
   find "$DATA_DIR/$market_type" -name "*.json" -type f | xargs -r -n 1 pigz -f
-  rclone move "$DATA_DIR/$market_type" "$AWS_S3_DIR/$market_type" --include '*.json.gz' --no-traverse --transfers=8
+  rclone move "$DATA_DIR/$market_type" "$AWS_S3_DIR/$market_type" --include '*.json.gz' --include '*.json.zst' --no-traverse --transfers=8
 done
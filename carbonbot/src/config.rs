@@ -0,0 +1,546 @@
+use std::{collections::HashMap, fmt, path::Path};
+
+use crypto_crawler::{fetch_symbols_retry, sort_by_cmc_rank, CrawlJob};
+use crypto_market_type::{get_market_types, MarketType};
+use crypto_msg_type::{supports, MessageType};
+use serde::Deserialize;
+
+/// The configuration file of carbonbot, in TOML or YAML.
+///
+/// ```toml
+/// [sinks]
+/// data_dir = "/data"
+///
+/// [rotation]
+/// hourly = true
+/// max_size_mb = 1024
+/// compression = "gzip"
+///
+/// [rate_limits]
+/// rest_retry_count = 5
+/// cooldown_ms = { binance = 1000 }
+///
+/// [[jobs]]
+/// exchange = "binance"
+/// market_types = ["spot", "linear_swap"]
+/// msg_types = ["trade", "l2_event"]
+/// symbols = { contains = ["BTC", "ETH"] }
+///
+/// [[jobs]]
+/// exchange = "okex"
+/// msg_types = ["funding_rate"]
+/// ```
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub sinks: SinkConfig,
+    #[serde(default)]
+    pub rotation: RotationConfig,
+    #[serde(default)]
+    pub rate_limits: RateLimitConfig,
+    pub jobs: Vec<JobConfig>,
+}
+
+/// Where to write messages, the DATA_DIR and REDIS_URL environment variables
+/// are used if absent.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SinkConfig {
+    /// Write messages to files in this directory
+    pub data_dir: Option<String>,
+    /// Publish messages to this Redis server
+    pub redis_url: Option<String>,
+}
+
+/// How to rotate files in `data_dir`, hourly without compression by default.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// Rotate at the beginning of every UTC hour
+    #[serde(default = "default_hourly")]
+    pub hourly: bool,
+    /// Rotate once a file exceeds this size
+    pub max_size_mb: Option<u64>,
+    /// How to compress rotated files
+    #[serde(default)]
+    pub compression: Compression,
+}
+
+fn default_hourly() -> bool {
+    true
+}
+
+impl Default for RotationConfig {
+    fn default() -> Self {
+        RotationConfig {
+            hourly: default_hourly(),
+            max_size_mb: None,
+            compression: Compression::None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Retries of RESTful requests, same as the REST_RETRY_COUNT environment variable
+    pub rest_retry_count: Option<u64>,
+    /// Cooldown time in milliseconds after each RESTful request, per exchange
+    #[serde(default)]
+    pub cooldown_ms: HashMap<String, u64>,
+}
+
+/// Crawl `msg_types` in `market_types` of an exchange.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JobConfig {
+    pub exchange: String,
+    /// Empty means all market types supporting each message type,
+    /// `unknown` means all markets of the exchange, e.g., bitmex
+    #[serde(default)]
+    pub market_types: Vec<MarketType>,
+    pub msg_types: Vec<MessageType>,
+    /// None means all symbols
+    pub symbols: Option<SymbolFilter>,
+}
+
+/// Select symbols of a market.
+///
+/// A symbol is kept if it is in `exact` or contains any of `contains`, then
+/// only the top N of them by CoinMarketCap rank are kept if `top_n` is set.
+///
+/// The filter is resolved once at startup, so symbols listed later are not
+/// crawled until carbonbot restarts, unlike jobs without a filter which
+/// discover new symbols every hour.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SymbolFilter {
+    #[serde(default)]
+    pub exact: Vec<String>,
+    #[serde(default)]
+    pub contains: Vec<String>,
+    pub top_n: Option<usize>,
+}
+
+impl SymbolFilter {
+    fn matches(&self, symbol: &str) -> bool {
+        if self.exact.is_empty() && self.contains.is_empty() {
+            return true;
+        }
+        self.exact.iter().any(|x| x == symbol) || self.contains.iter().any(|x| symbol.contains(x))
+    }
+
+    /// Select symbols from all symbols of a market.
+    pub fn apply(&self, exchange: &str, symbols: &[String]) -> Vec<String> {
+        let mut selected: Vec<String> = symbols
+            .iter()
+            .filter(|symbol| self.matches(symbol))
+            .cloned()
+            .collect();
+        if let Some(top_n) = self.top_n {
+            sort_by_cmc_rank(exchange, &mut selected);
+            selected.truncate(top_n);
+        }
+        selected
+    }
+}
+
+/// One `<exchange> <market_type> <msg_type>` expanded from a `JobConfig`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobSpec {
+    pub exchange: String,
+    pub market_type: MarketType,
+    pub msg_type: MessageType,
+    pub symbols: Option<SymbolFilter>,
+}
+
+impl JobSpec {
+    /// Fetch symbols and select them by the filter.
+    ///
+    /// Symbols are resolved only once here, see `SymbolFilter`. Returns None
+    /// if no symbol is selected.
+    pub fn to_crawl_job(&self) -> Option<CrawlJob> {
+        let symbols = if let Some(filter) = self.symbols.as_ref() {
+            let all_symbols = fetch_symbols_retry(&self.exchange, self.market_type);
+            let symbols = filter.apply(&self.exchange, &all_symbols);
+            if symbols.is_empty() {
+                return None;
+            }
+            Some(symbols)
+        } else {
+            None
+        };
+        Some(CrawlJob {
+            exchange: self.exchange.clone(),
+            market_type: self.market_type,
+            msg_type: self.msg_type,
+            symbols,
+        })
+    }
+}
+
+impl fmt::Display for JobSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.exchange, self.market_type, self.msg_type
+        )
+    }
+}
+
+/// The error returned when a configuration file can't be loaded.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    /// Malformed TOML or YAML
+    Parse(String),
+    /// Not consistent with the capability matrix, etc., one reason per line
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "{}", err),
+            ConfigError::Parse(reason) => write!(f, "{}", reason),
+            ConfigError::Invalid(reasons) => write!(f, "{}", reasons.join("\n")),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Whether a message type can be crawled in a market of an exchange.
+///
+/// The capability matrix in `crypto-msg-type` is authoritative, including the
+/// `Other` message type and the unknown market type, e.g., bitmex.
+pub fn is_supported(exchange: &str, market_type: MarketType, msg_type: MessageType) -> bool {
+    supports(exchange, market_type, msg_type)
+}
+
+// Message types which crawl all symbols and ignore the symbols argument
+fn ignores_symbols(msg_type: MessageType) -> bool {
+    matches!(
+        msg_type,
+        MessageType::Candlestick
+            | MessageType::OpenInterest
+            | MessageType::InsuranceFund
+            | MessageType::Other
+    )
+}
+
+impl Config {
+    /// Load a configuration file, the format is detected by its extension.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("toml") => Config::from_toml(&text),
+            Some("yaml") | Some("yml") => Config::from_yaml(&text),
+            _ => Err(ConfigError::Parse(format!(
+                "{} is neither .toml nor .yaml",
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        let config: Config =
+            toml::from_str(text).map_err(|err| ConfigError::Parse(err.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_yaml(text: &str) -> Result<Self, ConfigError> {
+        let config: Config =
+            serde_yaml::from_str(text).map_err(|err| ConfigError::Parse(err.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Expand jobs into `<exchange> <market_type> <msg_type>` specs.
+    ///
+    /// Unsupported combinations are skipped, which are reported by `validate()`.
+    pub fn job_specs(&self) -> Vec<JobSpec> {
+        let mut specs = Vec::new();
+        for job in self.jobs.iter() {
+            for msg_type in job.msg_types.iter() {
+                let market_types = if job.market_types.is_empty() {
+                    if *msg_type == MessageType::Other {
+                        continue;
+                    }
+                    get_market_types(&job.exchange)
+                } else {
+                    job.market_types.clone()
+                };
+                for market_type in market_types {
+                    if is_supported(&job.exchange, market_type, *msg_type) {
+                        specs.push(JobSpec {
+                            exchange: job.exchange.clone(),
+                            market_type,
+                            msg_type: *msg_type,
+                            symbols: job.symbols.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        specs
+    }
+
+    /// Check jobs against the capability matrix, and check other settings.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut reasons = Vec::new();
+
+        for job in self.jobs.iter() {
+            if get_market_types(&job.exchange).is_empty() {
+                reasons.push(format!("Unknown exchange {}", job.exchange));
+                continue;
+            }
+            if job.msg_types.is_empty() {
+                reasons.push(format!("No msg_types in the {} job", job.exchange));
+            }
+            for msg_type in job.msg_types.iter() {
+                if *msg_type == MessageType::Other
+                    && job.market_types.is_empty()
+                    && (is_supported(&job.exchange, MarketType::Unknown, *msg_type)
+                        || get_market_types(&job.exchange)
+                            .into_iter()
+                            .any(|market_type| is_supported(&job.exchange, market_type, *msg_type)))
+                {
+                    reasons.push(format!(
+                        "{} other needs market_types, e.g., unknown for bitmex",
+                        job.exchange
+                    ));
+                } else if job.market_types.is_empty() {
+                    if !get_market_types(&job.exchange)
+                        .into_iter()
+                        .any(|market_type| is_supported(&job.exchange, market_type, *msg_type))
+                    {
+                        reasons.push(format!("{} does NOT support {}", job.exchange, msg_type));
+                    }
+                } else {
+                    for market_type in job.market_types.iter() {
+                        if !is_supported(&job.exchange, *market_type, *msg_type) {
+                            reasons.push(format!(
+                                "{} {} does NOT support {}",
+                                job.exchange, market_type, msg_type
+                            ));
+                        }
+                    }
+                }
+                if job.symbols.is_some() && ignores_symbols(*msg_type) {
+                    reasons.push(format!(
+                        "{} {} always crawls all symbols, remove the symbols filter",
+                        job.exchange, msg_type
+                    ));
+                }
+            }
+            if let Some(filter) = job.symbols.as_ref() {
+                if filter.exact.is_empty() && filter.contains.is_empty() && filter.top_n.is_none() {
+                    reasons.push(format!("Empty symbols filter in the {} job", job.exchange));
+                }
+                if filter.top_n == Some(0) {
+                    reasons.push(format!("top_n is 0 in the {} job", job.exchange));
+                }
+            }
+        }
+
+        let specs = self.job_specs();
+        for (i, spec) in specs.iter().enumerate() {
+            let duplicated = specs[..i].iter().any(|x| {
+                x.exchange == spec.exchange
+                    && x.market_type == spec.market_type
+                    && x.msg_type == spec.msg_type
+            });
+            if duplicated {
+                reasons.push(format!("{} is configured more than once", spec));
+            }
+        }
+
+        if !self.rotation.hourly && self.rotation.max_size_mb.is_none() {
+            reasons.push("rotation needs hourly or max_size_mb".to_string());
+        }
+        if self.rotation.max_size_mb == Some(0) {
+            reasons.push("max_size_mb is 0".to_string());
+        }
+
+        for exchange in self.rate_limits.cooldown_ms.keys() {
+            if get_market_types(exchange).is_empty() {
+                reasons.push(format!("Unknown exchange {} in cooldown_ms", exchange));
+            }
+        }
+
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(reasons))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_toml() {
+        let text = r#"
+[sinks]
+data_dir = "/data"
+
+[rotation]
+max_size_mb = 1024
+compression = "zstd"
+
+[rate_limits]
+cooldown_ms = { binance = 1000 }
+
+[[jobs]]
+exchange = "binance"
+market_types = ["spot", "linear_swap"]
+msg_types = ["trade", "l2_event"]
+symbols = { exact = ["BTCUSDT"], contains = ["ETH"] }
+
+[[jobs]]
+exchange = "bitmex"
+market_types = ["unknown"]
+msg_types = ["other"]
+"#;
+        let config = Config::from_toml(text).unwrap();
+        assert_eq!(Some("/data".to_string()), config.sinks.data_dir);
+        assert!(config.rotation.hourly);
+        assert_eq!(Compression::Zstd, config.rotation.compression);
+        assert_eq!(Some(&1000), config.rate_limits.cooldown_ms.get("binance"));
+
+        let specs = config.job_specs();
+        assert_eq!(5, specs.len());
+        assert_eq!("binance spot trade", specs[0].to_string());
+        assert_eq!("bitmex unknown other", specs[4].to_string());
+    }
+
+    #[test]
+    fn parse_yaml() {
+        let text = r#"
+sinks:
+  redis_url: redis://127.0.0.1:6379
+jobs:
+  - exchange: okex
+    msg_types: [funding_rate]
+"#;
+        let config = Config::from_yaml(text).unwrap();
+        assert_eq!(RotationConfig::default(), config.rotation);
+        let specs = config.job_specs();
+        assert!(!specs.is_empty());
+        assert!(specs.iter().all(|spec| supports(
+            "okex",
+            spec.market_type,
+            MessageType::FundingRate
+        )));
+    }
+
+    #[test]
+    fn invalid() {
+        let text = r#"
+[[jobs]]
+exchange = "binance"
+market_types = ["spot"]
+msg_types = ["funding_rate", "candlestick"]
+symbols = { top_n = 0 }
+
+[[jobs]]
+exchange = "non_existent"
+msg_types = ["trade"]
+"#;
+        match Config::from_toml(text) {
+            Err(ConfigError::Invalid(reasons)) => {
+                assert_eq!(4, reasons.len(), "{:?}", reasons);
+                assert_eq!("binance spot does NOT support funding_rate", reasons[0]);
+            }
+            _ => panic!("Invalid config is accepted"),
+        }
+
+        let text = r#"
+[[jobs]]
+exchange = "binance"
+market_types = ["spot"]
+msg_types = ["trade"]
+
+[[jobs]]
+exchange = "binance"
+msg_types = ["trade"]
+"#;
+        assert!(matches!(
+            Config::from_toml(text),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(matches!(
+            Config::from_toml("[[jobs]]\nexchange = 1"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn other_market_types() {
+        assert!(is_supported(
+            "bitmex",
+            MarketType::Unknown,
+            MessageType::Other
+        ));
+        assert!(is_supported("huobi", MarketType::Spot, MessageType::Other));
+        // no websocket lock for these
+        assert!(!is_supported(
+            "huobi",
+            MarketType::Unknown,
+            MessageType::Other
+        ));
+        assert!(!is_supported(
+            "coinbase_pro",
+            MarketType::LinearSwap,
+            MessageType::Other
+        ));
+        assert!(!is_supported(
+            "binance",
+            MarketType::Spot,
+            MessageType::Other
+        ));
+
+        let text = r#"
+[[jobs]]
+exchange = "huobi"
+msg_types = ["other"]
+"#;
+        assert!(matches!(
+            Config::from_toml(text),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn filter_symbols() {
+        let filter = SymbolFilter {
+            exact: vec!["BTCUSDT".to_string()],
+            contains: vec!["ETH".to_string()],
+            top_n: None,
+        };
+        let symbols: Vec<String> = ["BTCUSDT", "BTCBUSD", "ETHUSDT", "ETHBTC", "XRPUSDT"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(
+            vec!["BTCUSDT", "ETHUSDT", "ETHBTC"],
+            filter.apply("binance", &symbols)
+        );
+    }
+}
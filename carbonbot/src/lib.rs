@@ -1,5 +1,8 @@
-pub(crate) mod misc_crawlers;
+mod config;
 pub(crate) mod writers;
 
-pub use misc_crawlers::crawl_other;
+pub use config::{
+    is_supported, Compression, Config, ConfigError, JobConfig, JobSpec, RateLimitConfig,
+    RotationConfig, SinkConfig, SymbolFilter,
+};
 pub use writers::create_writer_threads;
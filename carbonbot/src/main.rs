@@ -1,61 +1,149 @@
-use carbonbot::{crawl_other, create_writer_threads};
+use carbonbot::{create_writer_threads, is_supported, Config, RotationConfig};
 use crypto_crawler::*;
 use crypto_market_type::MarketType;
-use crypto_msg_type::MessageType;
+use crypto_msg_type::{get_capabilities, MessageType};
 use log::*;
-use std::{env, str::FromStr};
+use std::{env, str::FromStr, time::Duration};
 
 pub fn crawl(
-    exchange: &'static str,
-    market_type: MarketType,
-    msg_type: MessageType,
+    jobs: Vec<CrawlJob>,
     data_dir: Option<String>,
     redis_url: Option<String>,
-    symbols: Option<&[String]>,
+    rotation: RotationConfig,
 ) {
     if data_dir.is_none() && redis_url.is_none() {
         error!("Both DATA_DIR and REDIS_URL are not set");
         return;
     }
     let (tx, rx) = std::sync::mpsc::channel::<Message>();
-    let writer_threads = create_writer_threads(rx, data_dir, redis_url);
-
-    if msg_type == MessageType::Candlestick {
-        crawl_candlestick(exchange, market_type, None, tx, None);
-    } else if msg_type == MessageType::OpenInterest {
-        crawl_open_interest(exchange, market_type, tx, None);
-    } else if msg_type == MessageType::Other {
-        crawl_other(exchange, market_type, tx, None);
-    } else {
-        let crawl_func = match msg_type {
-            MessageType::BBO => crawl_bbo,
-            MessageType::Trade => crawl_trade,
-            MessageType::L2Event => crawl_l2_event,
-            MessageType::L3Event => crawl_l3_event,
-            MessageType::L2Snapshot => crawl_l2_snapshot,
-            MessageType::L2TopK => crawl_l2_topk,
-            MessageType::L3Snapshot => crawl_l3_snapshot,
-            MessageType::Ticker => crawl_ticker,
-            MessageType::FundingRate => crawl_funding_rate,
-            _ => panic!("Not implemented"),
-        };
-        crawl_func(exchange, market_type, symbols, tx, None);
+    let writer_threads = create_writer_threads(rx, data_dir, redis_url, rotation);
+
+    let session = CrawlerSession::start(jobs, tx, None);
+    while !session.is_finished() {
+        std::thread::sleep(Duration::from_secs(60));
+        for (job, status) in session.jobs().iter().zip(session.statuses()) {
+            info!("{} {:?}", job, status);
+        }
     }
+    let statuses = session.join();
     for thread in writer_threads {
         thread.join().unwrap();
     }
+    // exit with an error so that the process gets restarted
+    if statuses
+        .iter()
+        .any(|status| matches!(status.state, JobState::Failed(_)))
+    {
+        std::process::exit(1);
+    }
+}
+
+// Print all supported `<exchange> <market_type>` of a message type, one per line.
+fn print_jobs(msg_type: &str) {
+    let msg_type = match MessageType::from_str(msg_type) {
+        Ok(msg_type) => msg_type,
+        Err(_) => {
+            println!("Unknown msg type: {}", msg_type);
+            return;
+        }
+    };
+    for capability in get_capabilities() {
+        if capability.msg_types.contains(&msg_type) {
+            println!("{} {}", capability.exchange, capability.market_type);
+        }
+    }
+}
+
+fn get_env(name: &str) -> Option<String> {
+    match std::env::var(name) {
+        Ok(value) => Some(value),
+        Err(_) => {
+            info!("The {} environment variable does not exist", name);
+            None
+        }
+    }
+}
+
+fn load_config(path: &str) -> Option<Config> {
+    match Config::from_file(path) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            println!("Invalid config {}:\n{}", path, err);
+            None
+        }
+    }
+}
+
+// Crawl all jobs in a configuration file.
+fn run_config(config: Config) {
+    if let Some(rest_retry_count) = config.rate_limits.rest_retry_count {
+        env::set_var("REST_RETRY_COUNT", rest_retry_count.to_string());
+    }
+    for (exchange, millis) in config.rate_limits.cooldown_ms.iter() {
+        set_cooldown_time_per_request(exchange, Duration::from_millis(*millis));
+    }
+
+    let data_dir = config
+        .sinks
+        .data_dir
+        .clone()
+        .or_else(|| get_env("DATA_DIR"));
+    let redis_url = config
+        .sinks
+        .redis_url
+        .clone()
+        .or_else(|| get_env("REDIS_URL"));
+    if data_dir.is_none() && redis_url.is_none() {
+        panic!("Neither sinks nor the environment variable DATA_DIR and REDIS_URL are set, at least one of them should be set");
+    }
+
+    let mut jobs = Vec::new();
+    for spec in config.job_specs() {
+        if let Some(job) = spec.to_crawl_job() {
+            info!("{} target symbols: {:?}", spec, job.symbols);
+            jobs.push(job);
+        } else {
+            panic!("No symbols of {} match {:?}", spec, spec.symbols);
+        }
+    }
+    crawl(jobs, data_dir, redis_url, config.rotation);
 }
 
 fn main() {
     env_logger::init();
 
     let args: Vec<String> = env::args().collect();
+    if args.len() == 3 && args[1] == "jobs" {
+        print_jobs(&args[2]);
+        return;
+    }
+    if args.len() == 3 && args[1] == "check" {
+        match load_config(&args[2]) {
+            Some(config) => {
+                for spec in config.job_specs() {
+                    println!("{}", spec);
+                }
+            }
+            None => std::process::exit(1),
+        }
+        return;
+    }
+    if args.len() == 3 && args[1] == "run" {
+        match load_config(&args[2]) {
+            Some(config) => run_config(config),
+            None => std::process::exit(1),
+        }
+        return;
+    }
     if args.len() != 4 && args.len() != 5 {
         println!("Usage: carbonbot <exchange> <market_type> <msg_type> [comma_seperated_symbols]");
+        println!("       carbonbot run <config_file>");
+        println!("       carbonbot check <config_file>");
+        println!("       carbonbot jobs <msg_type>");
         return;
     }
 
-    let exchange: &'static str = Box::leak(args[1].clone().into_boxed_str());
+    let exchange = args[1].as_str();
 
     let market_type = MarketType::from_str(&args[2]);
     if market_type.is_err() {
@@ -70,22 +158,13 @@ fn main() {
         return;
     }
     let msg_type = msg_type.unwrap();
+    if !is_supported(exchange, market_type, msg_type) {
+        println!("{} {} does NOT support {}", exchange, market_type, msg_type);
+        return;
+    }
 
-    let data_dir = if std::env::var("DATA_DIR").is_err() {
-        info!("The DATA_DIR environment variable does not exist");
-        None
-    } else {
-        let url = std::env::var("DATA_DIR").unwrap();
-        Some(url)
-    };
-
-    let redis_url = if std::env::var("REDIS_URL").is_err() {
-        info!("The REDIS_URL environment variable does not exist");
-        None
-    } else {
-        let url = std::env::var("REDIS_URL").unwrap();
-        Some(url)
-    };
+    let data_dir = get_env("DATA_DIR");
+    let redis_url = get_env("REDIS_URL");
 
     let specified_symbols = if args.len() == 4 {
         Vec::new()
@@ -100,16 +179,15 @@ fn main() {
         panic!("The environment variable DATA_DIR and REDIS_URL are not set, at least one of them should be set");
     }
 
-    crawl(
-        exchange,
+    let job = CrawlJob {
+        exchange: exchange.to_string(),
         market_type,
         msg_type,
-        data_dir,
-        redis_url,
-        if specified_symbols.is_empty() {
+        symbols: if specified_symbols.is_empty() {
             None
         } else {
-            Some(&specified_symbols)
+            Some(specified_symbols)
         },
-    );
+    };
+    crawl(vec![job], data_dir, redis_url, RotationConfig::default());
 }
@@ -1,56 +1,375 @@
 use super::Writer;
+use crate::config::{Compression, RotationConfig};
 
+use chrono::{DateTime, TimeZone, Utc};
 use log::*;
-use reopen::Reopen;
 use std::{
+    ffi::OsString,
     fs,
-    io::{Error, Write},
-    path::Path,
+    io::{BufRead, BufReader, Error, Write},
+    path::{Path, PathBuf},
+    thread::JoinHandle,
 };
 
-#[cfg(not(windows))]
-use signal_hook::consts::signal::SIGHUP;
-#[cfg(windows)] // Windows has a very limited set of signals, but make it compile at least :-(
-use signal_hook::consts::signal::SIGINT as SIGHUP;
-
 fn open<P: AsRef<Path>>(p: P) -> Result<fs::File, Error> {
-    info!("reopen {}", p.as_ref().display());
-    fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(true)
-        .open(p)
+    info!("open {}", p.as_ref().display());
+    fs::OpenOptions::new().create(true).append(true).open(p)
+}
+
+// Append a suffix to a path, e.g., `.json`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut s: OsString = path.as_os_str().to_os_string();
+    s.push(suffix);
+    PathBuf::from(s)
+}
+
+fn compression_suffix(compression: Compression) -> &'static str {
+    match compression {
+        Compression::None => "",
+        Compression::Gzip => ".gz",
+        Compression::Zstd => ".zst",
+    }
+}
+
+// Compress `src` into `dst` through a temporary file, then delete `src`.
+fn compress(src: &Path, dst: &Path, compression: Compression) -> Result<(), Error> {
+    let tmp = with_suffix(dst, ".tmp");
+    let mut reader = fs::File::open(src)?;
+    let file = fs::File::create(&tmp)?;
+    let file = match compression {
+        Compression::None => {
+            let mut file = file;
+            std::io::copy(&mut reader, &mut file)?;
+            file
+        }
+        Compression::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            std::io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()?
+        }
+        Compression::Zstd => {
+            let mut encoder = zstd::Encoder::new(file, 0)?;
+            std::io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()?
+        }
+    };
+    file.sync_all()?;
+    fs::rename(&tmp, dst)?;
+    fs::remove_file(src)
+}
+
+// The `received_at` of the first message in a file
+fn first_received_at(path: &Path) -> Option<DateTime<Utc>> {
+    let mut line = String::new();
+    BufReader::new(fs::File::open(path).ok()?)
+        .read_line(&mut line)
+        .ok()?;
+    let msg = serde_json::from_str::<serde_json::Value>(&line).ok()?;
+    let received_at = msg.get("received_at")?.as_i64()?;
+    Utc.timestamp_millis_opt(received_at).single()
 }
 
+fn spawn_compress_thread(src: PathBuf, dst: PathBuf, compression: Compression) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        if let Err(err) = compress(&src, &dst, compression) {
+            error!("Failed to compress {}, {}", src.display(), err);
+        } else {
+            info!("finalized {}", dst.display());
+        }
+    })
+}
+
+/// Write lines into `<path>`, which is renamed to
+/// `<path>.<YYYY-MM-DD-HH>.json` when rotated.
+///
+/// The UTC hour is when the file was created, files rotated by size within
+/// the same hour are suffixed by `.1`, `.2`, etc. Only finalized files end
+/// with `.json`, `.json.gz` or `.json.zst`, so they can be uploaded safely
+/// as soon as they appear. Compression runs on background threads.
 pub struct FileWriter {
-    file: Reopen<fs::File>,
-    path: String,
+    // The file being written
+    path: PathBuf,
+    rotation: RotationConfig,
+    file: Option<fs::File>,
+    // When the file was created
+    created_at: DateTime<Utc>,
+    // Bytes written into the file
+    size: u64,
+    compress_threads: Vec<JoinHandle<()>>,
 }
 
 impl FileWriter {
-    pub fn new(path: &str) -> Self {
-        let path_clone = path.to_string();
-        let file = Reopen::new(Box::new(move || open(&path_clone))).unwrap();
-        // Make sure it gets reopened on SIGHUP
-        file.handle().register_signal(SIGHUP).unwrap();
+    pub fn new(path: &str, rotation: RotationConfig) -> Self {
+        let mut writer = FileWriter {
+            path: PathBuf::from(path),
+            rotation,
+            file: None,
+            created_at: Utc::now(),
+            size: 0,
+            compress_threads: Vec::new(),
+        };
+        writer.recover();
+        writer
+    }
+
+    // Finalize files left by the previous process
+    fn recover(&mut self) {
+        let dir = self.path.parent().unwrap().to_path_buf();
+        let name = self.path.file_name().unwrap().to_str().unwrap().to_string();
+        let prefix = format!("{}.", name);
+        let file_names: Vec<String> = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.file_name().to_str().map(|x| x.to_string()))
+                    .filter(|file_name| file_name.starts_with(&prefix))
+                    .collect()
+            })
+            .unwrap_or_default();
+        // partially compressed files, whose sources are still pending
+        for file_name in file_names.iter() {
+            if [".json.tmp", ".json.gz.tmp", ".json.zst.tmp"]
+                .iter()
+                .any(|suffix| file_name.ends_with(suffix))
+            {
+                if let Err(err) = fs::remove_file(dir.join(file_name)) {
+                    error!("Failed to remove {}, {}", file_name, err);
+                }
+            }
+        }
+        // files staged for compression
+        for file_name in file_names.iter() {
+            if file_name.ends_with(".json.pending") {
+                let src = dir.join(file_name);
+                let dst = dir.join(format!(
+                    "{}{}",
+                    file_name.trim_end_matches(".pending"),
+                    compression_suffix(self.rotation.compression)
+                ));
+                self.compress_threads.push(spawn_compress_thread(
+                    src,
+                    dst,
+                    self.rotation.compression,
+                ));
+            }
+        }
+        // the file being written when the previous process exited, stamped
+        // with its first message, or its creation time if unparsable
+        if let Ok(metadata) = fs::metadata(&self.path) {
+            if metadata.len() > 0 {
+                if let Some(created_at) = first_received_at(&self.path)
+                    .or_else(|| metadata.created().ok().map(DateTime::<Utc>::from))
+                {
+                    self.created_at = created_at;
+                }
+                self.size = metadata.len();
+                self.finalize();
+            }
+        }
+    }
+
+    // Find an unused name in the form of `<path>.<YYYY-MM-DD-HH>[.N]`
+    fn finalized_path(&self) -> PathBuf {
+        let stamp = self.created_at.format("%Y-%m-%d-%H").to_string();
+        let mut index = 0;
+        loop {
+            let base = if index == 0 {
+                with_suffix(&self.path, &format!(".{}", stamp))
+            } else {
+                with_suffix(&self.path, &format!(".{}.{}", stamp, index))
+            };
+            let used = [".json", ".json.gz", ".json.zst", ".json.pending"]
+                .iter()
+                .any(|suffix| with_suffix(&base, suffix).exists());
+            if !used {
+                return base;
+            }
+            index += 1;
+        }
+    }
+
+    // Close the current file and rename it atomically
+    fn finalize(&mut self) {
+        if let Some(mut file) = self.file.take() {
+            if let Err(err) = file.flush() {
+                error!("{}, {}", self.path.display(), err);
+            }
+        }
+        if self.size == 0 {
+            return;
+        }
+        let base = self.finalized_path();
+        let result = if self.rotation.compression == Compression::None {
+            let dst = with_suffix(&base, ".json");
+            fs::rename(&self.path, &dst).map(|_| info!("finalized {}", dst.display()))
+        } else {
+            let pending = with_suffix(&base, ".json.pending");
+            let dst = with_suffix(
+                &base,
+                &format!(".json{}", compression_suffix(self.rotation.compression)),
+            );
+            fs::rename(&self.path, &pending).map(|_| {
+                self.compress_threads.push(spawn_compress_thread(
+                    pending,
+                    dst,
+                    self.rotation.compression,
+                ))
+            })
+        };
+        if let Err(err) = result {
+            error!("Failed to finalize {}, {}", self.path.display(), err);
+        }
+        self.size = 0;
+        self.compress_threads.retain(|thread| !thread.is_finished());
+    }
+
+    fn need_rotation(&self, now: DateTime<Utc>) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        let new_hour = now.timestamp() / 3600 != self.created_at.timestamp() / 3600;
+        let too_large = self
+            .rotation
+            .max_size_mb
+            .map(|max_size_mb| self.size >= max_size_mb * 1024 * 1024)
+            .unwrap_or(false);
+        (self.rotation.hourly && new_hour) || too_large
+    }
 
-        FileWriter {
-            file,
-            path: path.to_string(),
+    fn rotate_at(&mut self, now: DateTime<Utc>) {
+        if self.need_rotation(now) {
+            self.finalize();
+        }
+    }
+
+    /// Rotate the file if a new UTC hour begins, even if nothing is written.
+    pub fn rotate(&mut self) {
+        self.rotate_at(Utc::now());
+    }
+
+    fn write_at(&mut self, s: &str, now: DateTime<Utc>) {
+        self.rotate_at(now);
+        if self.file.is_none() {
+            match open(&self.path) {
+                Ok(file) => {
+                    self.file = Some(file);
+                    self.created_at = now;
+                }
+                Err(err) => {
+                    error!("{}, {}", self.path.display(), err);
+                    return;
+                }
+            }
+        }
+        if let Err(e) = writeln!(self.file.as_mut().unwrap(), "{}", s) {
+            error!("{}, {}", self.path.display(), e);
+        } else {
+            self.size += s.len() as u64 + 1;
         }
     }
 }
 
 impl Writer for FileWriter {
     fn write(&mut self, s: &str) {
-        if let Err(e) = writeln!(self.file, "{}", s) {
-            error!("{}, {}", self.path, e);
-        }
+        self.write_at(s, Utc::now());
     }
 
     fn close(&mut self) {
-        if let Err(e) = self.file.flush() {
-            error!("{}, {}", self.path, e);
+        self.finalize();
+        for thread in self.compress_threads.drain(..) {
+            thread.join().unwrap();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FileWriter, Writer};
+    use crate::config::{Compression, RotationConfig};
+    use chrono::{Duration, TimeZone, Utc};
+    use std::{fs, io::Read, path::PathBuf};
+
+    fn create_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("carbonbot-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn list_files(dir: &PathBuf) -> Vec<String> {
+        let mut files: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn rotate_by_hour_and_size() {
+        let dir = create_dir("rotate");
+        let path = dir.join("binance.spot.trade");
+        let rotation = RotationConfig {
+            hourly: true,
+            max_size_mb: Some(1),
+            compression: Compression::None,
+        };
+        let mut writer = FileWriter::new(path.to_str().unwrap(), rotation);
+        let now = Utc.with_ymd_and_hms(2022, 1, 2, 3, 4, 5).unwrap();
+        writer.write_at("a", now);
+        writer.write_at("b", now + Duration::minutes(1));
+        assert_eq!(vec!["binance.spot.trade"], list_files(&dir));
+
+        writer.write_at("c", now + Duration::hours(1));
+        writer.write_at(&"d".repeat(1024 * 1024), now + Duration::hours(1));
+        writer.write_at("e", now + Duration::hours(1));
+        writer.close();
+        assert_eq!(
+            vec![
+                "binance.spot.trade.2022-01-02-03.json",
+                "binance.spot.trade.2022-01-02-04.1.json",
+                "binance.spot.trade.2022-01-02-04.json",
+            ],
+            list_files(&dir)
+        );
+        assert_eq!(
+            "a\nb\n",
+            fs::read_to_string(dir.join("binance.spot.trade.2022-01-02-03.json")).unwrap()
+        );
+        assert_eq!(
+            "e\n",
+            fs::read_to_string(dir.join("binance.spot.trade.2022-01-02-04.1.json")).unwrap()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compress_and_recover() {
+        let dir = create_dir("compress");
+        let path = dir.join("bitmex.unknown.trade");
+        // left by a crashed process
+        let line = r#"{"exchange":"bitmex","market_type":"unknown","msg_type":"trade","received_at":1641092645000,"json":"{}"}"#;
+        fs::write(&path, format!("{}\n", line)).unwrap();
+        fs::write(
+            dir.join("bitmex.unknown.trade.2022-01-01-00.json.gz.tmp"),
+            "partial",
+        )
+        .unwrap();
+        let rotation = RotationConfig {
+            hourly: true,
+            max_size_mb: None,
+            compression: Compression::Gzip,
+        };
+        let mut writer = FileWriter::new(path.to_str().unwrap(), rotation);
+        writer.close();
+
+        let files = list_files(&dir);
+        assert_eq!(vec!["bitmex.unknown.trade.2022-01-02-03.json.gz"], files);
+        let mut decoder =
+            flate2::read::GzDecoder::new(fs::File::open(dir.join(&files[0])).unwrap());
+        let mut text = String::new();
+        decoder.read_to_string(&mut text).unwrap();
+        assert_eq!(format!("{}\n", line), text);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
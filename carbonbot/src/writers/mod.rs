@@ -1,5 +1,6 @@
 pub(super) mod file_writer;
 
+use crate::config::RotationConfig;
 use crypto_crawler::*;
 use log::*;
 use redis::{self, Commands};
@@ -7,7 +8,8 @@ use std::collections::HashMap;
 use std::thread::JoinHandle;
 use std::{
     path::Path,
-    sync::mpsc::{Receiver, Sender},
+    sync::mpsc::{Receiver, RecvTimeoutError, Sender},
+    time::{Duration, Instant},
 };
 
 pub trait Writer {
@@ -21,10 +23,24 @@ fn create_file_writer_thread(
     rx: Receiver<Message>,
     data_dir: String,
     tx_redis: Option<Sender<Message>>,
+    rotation: RotationConfig,
 ) -> JoinHandle<()> {
     std::thread::spawn(move || {
         let mut writers: HashMap<String, FileWriter> = HashMap::new();
-        for msg in rx {
+        let mut last_rotated = Instant::now();
+        loop {
+            // rotate files every second, even if no messages come
+            if last_rotated.elapsed() >= Duration::from_secs(1) {
+                for writer in writers.values_mut() {
+                    writer.rotate();
+                }
+                last_rotated = Instant::now();
+            }
+            let msg = match rx.recv_timeout(Duration::from_secs(1)) {
+                Ok(msg) => msg,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
             let file_name = format!("{}.{}.{}", msg.exchange, msg.market_type, msg.msg_type);
             if !writers.contains_key(&file_name) {
                 let data_dir = Path::new(&data_dir)
@@ -38,7 +54,7 @@ fn create_file_writer_thread(
                     .into_os_string();
                 writers.insert(
                     file_name.clone(),
-                    FileWriter::new(file_path.as_os_str().to_str().unwrap()),
+                    FileWriter::new(file_path.as_os_str().to_str().unwrap(), rotation.clone()),
                 );
             }
 
@@ -103,6 +119,7 @@ pub fn create_writer_threads(
     rx: Receiver<Message>,
     data_dir: Option<String>,
     redis_url: Option<String>,
+    rotation: RotationConfig,
 ) -> Vec<JoinHandle<()>> {
     let mut threads = Vec::new();
     if data_dir.is_none() && redis_url.is_none() {
@@ -117,10 +134,16 @@ pub fn create_writer_threads(
             rx,
             data_dir.unwrap(),
             Some(tx_redis),
+            rotation,
         ));
         threads.push(create_redis_writer_thread(rx_redis, redis_url.unwrap()));
     } else if data_dir.is_some() {
-        threads.push(create_file_writer_thread(rx, data_dir.unwrap(), None))
+        threads.push(create_file_writer_thread(
+            rx,
+            data_dir.unwrap(),
+            None,
+            rotation,
+        ))
     } else {
         threads.push(create_redis_writer_thread(rx, redis_url.unwrap()));
     }
@@ -1,6 +1,9 @@
 use crypto_market_type::MarketType;
 
 mod exchanges;
+mod registry;
+
+pub use registry::{ContractValueRecord, ContractValueRegistry};
 
 pub fn get_contract_value(exchange: &str, market_type: MarketType, pair: &str) -> Option<f64> {
     if market_type == MarketType::Spot {
@@ -22,6 +25,6 @@ pub fn get_contract_value(exchange: &str, market_type: MarketType, pair: &str) -
         "mxc" => exchanges::mxc::get_contract_value(market_type, pair),
         "okex" => exchanges::okex::get_contract_value(market_type, pair),
         "zbg" => exchanges::zbg::get_contract_value(market_type, pair),
-        _ => panic!("Unknown exchange {}", exchange),
+        _ => None,
     }
 }
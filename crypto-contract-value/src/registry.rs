@@ -0,0 +1,306 @@
+use std::{collections::HashMap, path::Path};
+
+use crypto_market_type::MarketType;
+use serde::{Deserialize, Serialize};
+
+/// A contract value valid since `timestamp`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContractValueRecord {
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+    pub contract_value: f64,
+}
+
+// The file format of the snapshot, one entry per pair
+#[derive(Serialize, Deserialize)]
+struct SnapshotEntry {
+    exchange: String,
+    market_type: MarketType,
+    pair: String,
+    history: Vec<ContractValueRecord>,
+}
+
+/// A point-in-time registry of contract values.
+///
+/// The registry is opt-in, `get_contract_value()` only uses the built-in
+/// tables. Feed it with contract values from `crypto_markets::fetch_markets()`
+/// periodically, and persist it with `save()` and `load()`.
+///
+/// # Example
+///
+/// ```
+/// use crypto_contract_value::ContractValueRegistry;
+/// use crypto_market_type::MarketType;
+///
+/// let mut registry = ContractValueRegistry::default();
+/// registry.insert("binance", MarketType::InverseSwap, "BTC/USD", 100.0, 1609459200000);
+///
+/// assert_eq!(
+///     Some(100.0),
+///     registry.get("binance", MarketType::InverseSwap, "BTC/USD", 1612137600000)
+/// );
+/// assert_eq!(
+///     None,
+///     registry.get("binance", MarketType::InverseSwap, "BTC/USD", 1577836800000)
+/// );
+/// ```
+#[derive(Default)]
+pub struct ContractValueRegistry {
+    // exchange -> market_type -> pair -> history sorted by timestamp
+    histories: HashMap<String, HashMap<MarketType, HashMap<String, Vec<ContractValueRecord>>>>,
+}
+
+impl ContractValueRegistry {
+    /// Load a snapshot saved by `save()`.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let entries = serde_json::from_str::<Vec<SnapshotEntry>>(&text)?;
+        let mut registry = ContractValueRegistry::default();
+        for entry in entries {
+            for record in entry.history {
+                registry.insert(
+                    &entry.exchange,
+                    entry.market_type,
+                    &entry.pair,
+                    record.contract_value,
+                    record.timestamp,
+                );
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Save a snapshot to `path`.
+    ///
+    /// The snapshot is written to a temporary file then renamed, so that
+    /// readers never see a partial file.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut entries: Vec<SnapshotEntry> = Vec::new();
+        for (exchange, market_types) in self.histories.iter() {
+            for (market_type, pairs) in market_types.iter() {
+                for (pair, history) in pairs.iter() {
+                    entries.push(SnapshotEntry {
+                        exchange: exchange.clone(),
+                        market_type: *market_type,
+                        pair: pair.clone(),
+                        history: history.clone(),
+                    });
+                }
+            }
+        }
+        // sorted so that the file is diff-friendly
+        entries.sort_by_cached_key(|x| {
+            (
+                x.exchange.clone(),
+                x.market_type.to_string(),
+                x.pair.clone(),
+            )
+        });
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string(&entries)?)?;
+        std::fs::rename(tmp_path, path)
+    }
+
+    /// Record a contract value valid since `timestamp`, in milliseconds.
+    ///
+    /// Older values can be backfilled too. Returns true if the history changed.
+    pub fn insert(
+        &mut self,
+        exchange: &str,
+        market_type: MarketType,
+        pair: &str,
+        contract_value: f64,
+        timestamp: u64,
+    ) -> bool {
+        let history = self
+            .histories
+            .entry(exchange.to_string())
+            .or_default()
+            .entry(market_type)
+            .or_default()
+            .entry(pair.to_string())
+            .or_default();
+        let pos = history.partition_point(|x| x.timestamp <= timestamp);
+        // skip if the value is the same as the one valid at this time
+        if pos > 0 && history[pos - 1].contract_value == contract_value {
+            return false;
+        }
+        // the next record becomes redundant if it has the same value
+        if pos < history.len() && history[pos].contract_value == contract_value {
+            history[pos].timestamp = timestamp;
+        } else {
+            history.insert(
+                pos,
+                ContractValueRecord {
+                    timestamp,
+                    contract_value,
+                },
+            );
+        }
+        true
+    }
+
+    /// Get the contract value of a pair valid at `timestamp`, in milliseconds.
+    ///
+    /// Returns None if `timestamp` is earlier than all records of the pair.
+    pub fn get(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        pair: &str,
+        timestamp: u64,
+    ) -> Option<f64> {
+        let history = self.get_history(exchange, market_type, pair);
+        let pos = history.partition_point(|x| x.timestamp <= timestamp);
+        if pos == 0 {
+            None
+        } else {
+            Some(history[pos - 1].contract_value)
+        }
+    }
+
+    /// Get the whole history of contract values of a pair, sorted by timestamp.
+    pub fn get_history(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        pair: &str,
+    ) -> &[ContractValueRecord] {
+        self.histories
+            .get(exchange)
+            .and_then(|x| x.get(&market_type))
+            .and_then(|x| x.get(pair))
+            .map(|x| x.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Record contract values of an exchange and market type seen at `timestamp`.
+    ///
+    /// `contract_values` are `(pair, contract_value)` tuples, usually taken
+    /// from `crypto_markets::fetch_markets()`, where pairs are normalized by
+    /// `crypto_pair::normalize_pair()`. Returns the number of pairs
+    /// whose contract value changed.
+    pub fn update<S: AsRef<str>>(
+        &mut self,
+        exchange: &str,
+        market_type: MarketType,
+        contract_values: impl IntoIterator<Item = (S, f64)>,
+        timestamp: u64,
+    ) -> usize {
+        contract_values
+            .into_iter()
+            .filter(|(pair, contract_value)| {
+                self.insert(
+                    exchange,
+                    market_type,
+                    pair.as_ref(),
+                    *contract_value,
+                    timestamp,
+                )
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContractValueRegistry;
+    use crypto_market_type::MarketType;
+
+    #[test]
+    fn test_point_in_time() {
+        let mut registry = ContractValueRegistry::default();
+        assert!(registry.insert("binance", MarketType::InverseSwap, "BTC/USD", 10.0, 100));
+        assert!(!registry.insert("binance", MarketType::InverseSwap, "BTC/USD", 10.0, 200));
+        assert!(registry.insert("binance", MarketType::InverseSwap, "BTC/USD", 100.0, 300));
+
+        let get =
+            |timestamp| registry.get("binance", MarketType::InverseSwap, "BTC/USD", timestamp);
+        assert_eq!(None, get(50));
+        assert_eq!(Some(10.0), get(100));
+        assert_eq!(Some(10.0), get(299));
+        assert_eq!(Some(100.0), get(300));
+        assert_eq!(Some(100.0), get(u64::MAX));
+        assert_eq!(
+            None,
+            registry.get("binance", MarketType::InverseSwap, "ETH/USD", 300)
+        );
+    }
+
+    #[test]
+    fn test_backfill() {
+        let mut registry = ContractValueRegistry::default();
+        registry.insert("okex", MarketType::LinearSwap, "BTC/USDT", 0.01, 300);
+        // an older value recorded later
+        assert!(registry.insert("okex", MarketType::LinearSwap, "BTC/USDT", 0.1, 100));
+        // the same value as the next record moves the record earlier
+        assert!(registry.insert("okex", MarketType::LinearSwap, "BTC/USDT", 0.01, 200));
+
+        let history = registry.get_history("okex", MarketType::LinearSwap, "BTC/USDT");
+        assert_eq!(2, history.len());
+        assert_eq!(
+            (100, 0.1),
+            (history[0].timestamp, history[0].contract_value)
+        );
+        assert_eq!(
+            (200, 0.01),
+            (history[1].timestamp, history[1].contract_value)
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut registry = ContractValueRegistry::default();
+        let changed = registry.update(
+            "okex",
+            MarketType::LinearSwap,
+            vec![("BTC/USDT", 0.01), ("ETH/USDT", 0.1)],
+            100,
+        );
+        assert_eq!(2, changed);
+        let changed = registry.update(
+            "okex",
+            MarketType::LinearSwap,
+            vec![("BTC/USDT", 0.01), ("ETH/USDT", 1.0)],
+            200,
+        );
+        assert_eq!(1, changed);
+        assert_eq!(
+            Some(0.1),
+            registry.get("okex", MarketType::LinearSwap, "ETH/USDT", 199)
+        );
+        assert_eq!(
+            Some(1.0),
+            registry.get("okex", MarketType::LinearSwap, "ETH/USDT", 200)
+        );
+    }
+
+    #[test]
+    fn test_save_load() {
+        let mut registry = ContractValueRegistry::default();
+        registry.insert("binance", MarketType::InverseSwap, "BTC/USD", 10.0, 100);
+        registry.insert("binance", MarketType::InverseSwap, "BTC/USD", 100.0, 300);
+        registry.insert("okex", MarketType::LinearSwap, "BTC/USDT", 0.01, 200);
+
+        let path = std::env::temp_dir()
+            .join(format!("crypto-contract-value-{}", std::process::id()))
+            .join("contract_values.json");
+        registry.save(&path).unwrap();
+        let loaded = ContractValueRegistry::load(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(
+            registry.get_history("binance", MarketType::InverseSwap, "BTC/USD"),
+            loaded.get_history("binance", MarketType::InverseSwap, "BTC/USD")
+        );
+        assert_eq!(
+            registry.get_history("okex", MarketType::LinearSwap, "BTC/USDT"),
+            loaded.get_history("okex", MarketType::LinearSwap, "BTC/USDT")
+        );
+        assert!(ContractValueRegistry::load(&path).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use core::panic;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
 
 use crate::crawlers::utils::{crawl_candlestick_ext, crawl_event};
 use crate::msg::Message;
@@ -42,9 +44,9 @@ pub(crate) fn crawl_trade(
             "BTCUSDT_P@TRADE_ALL".to_string(),
         ];
 
-        let ws_client = BinanceOptionWSClient::new(tx, None);
+        let ws_client = Arc::new(BinanceOptionWSClient::new(tx, None));
         ws_client.subscribe(&channels);
-        ws_client.run(duration);
+        run_ws_client(ws_client, duration);
     } else {
         crawl_event(
             EXCHANGE_NAME,
@@ -107,19 +109,19 @@ pub(crate) fn crawl_bbo(
         let channels = vec!["!bookTicker".to_string()]; // All Book Tickers Stream
         match market_type {
             MarketType::Spot => {
-                let ws_client = BinanceSpotWSClient::new(tx, None);
+                let ws_client = Arc::new(BinanceSpotWSClient::new(tx, None));
                 ws_client.subscribe(&channels);
-                ws_client.run(duration);
+                run_ws_client(ws_client, duration);
             }
             MarketType::InverseFuture | MarketType::InverseSwap => {
-                let ws_client = BinanceInverseWSClient::new(tx, None);
+                let ws_client = Arc::new(BinanceInverseWSClient::new(tx, None));
                 ws_client.subscribe(&channels);
-                ws_client.run(duration);
+                run_ws_client(ws_client, duration);
             }
             MarketType::LinearFuture | MarketType::LinearSwap => {
-                let ws_client = BinanceLinearWSClient::new(tx, None);
+                let ws_client = Arc::new(BinanceLinearWSClient::new(tx, None));
                 ws_client.subscribe(&channels);
-                ws_client.run(duration);
+                run_ws_client(ws_client, duration);
             }
             _ => panic!(
                 "Binance {} market does NOT have the BBO channel",
@@ -194,19 +196,19 @@ pub(crate) fn crawl_ticker(
 
         match market_type {
             MarketType::Spot => {
-                let ws_client = BinanceSpotWSClient::new(tx, None);
+                let ws_client = Arc::new(BinanceSpotWSClient::new(tx, None));
                 ws_client.subscribe(&channels);
-                ws_client.run(duration);
+                run_ws_client(ws_client, duration);
             }
             MarketType::InverseFuture | MarketType::InverseSwap => {
-                let ws_client = BinanceInverseWSClient::new(tx, None);
+                let ws_client = Arc::new(BinanceInverseWSClient::new(tx, None));
                 ws_client.subscribe(&channels);
-                ws_client.run(duration);
+                run_ws_client(ws_client, duration);
             }
             MarketType::LinearFuture | MarketType::LinearSwap => {
-                let ws_client = BinanceLinearWSClient::new(tx, None);
+                let ws_client = Arc::new(BinanceLinearWSClient::new(tx, None));
                 ws_client.subscribe(&channels);
-                ws_client.run(duration);
+                run_ws_client(ws_client, duration);
             }
             _ => panic!(
                 "Binance {} market does NOT have the ticker channel",
@@ -263,19 +265,110 @@ pub(crate) fn crawl_funding_rate(
 
     match market_type {
         MarketType::InverseSwap => {
-            let ws_client = BinanceInverseWSClient::new(tx, None);
+            let ws_client = Arc::new(BinanceInverseWSClient::new(tx, None));
             ws_client.subscribe(&channels);
-            ws_client.run(duration);
+            run_ws_client(ws_client, duration);
         }
         MarketType::LinearSwap => {
-            let ws_client = BinanceLinearWSClient::new(tx, None);
+            let ws_client = Arc::new(BinanceLinearWSClient::new(tx, None));
             ws_client.subscribe(&channels);
-            ws_client.run(duration);
+            run_ws_client(ws_client, duration);
         }
         _ => panic!("Binance {} does NOT have funding rates", market_type),
     }
 }
 
+#[allow(clippy::unnecessary_unwrap)]
+pub(crate) fn crawl_liquidation(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    // All symbols for websocket are lowercase while for REST they are uppercase
+    let symbols = symbols
+        .unwrap_or_default()
+        .iter()
+        .map(|symbol| symbol.to_lowercase())
+        .collect::<Vec<String>>();
+
+    // !forceOrder@arr contains both swap and future symbols of the same endpoint
+    let channels: Vec<String> = if symbols.is_empty() {
+        vec!["!forceOrder@arr".to_string()]
+    } else {
+        symbols
+            .iter()
+            .map(|symbol| format!("{}@forceOrder", symbol))
+            .collect()
+    };
+
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::Liquidation,
+        market_type,
+        tx,
+    );
+
+    match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            let ws_client = Arc::new(BinanceInverseWSClient::new(tx, None));
+            ws_client.subscribe(&channels);
+            run_ws_client(ws_client, duration);
+        }
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            let ws_client = Arc::new(BinanceLinearWSClient::new(tx, None));
+            ws_client.subscribe(&channels);
+            run_ws_client(ws_client, duration);
+        }
+        _ => panic!("Binance {} does NOT have liquidation orders", market_type),
+    }
+}
+
+pub(crate) fn crawl_mark_price(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    // All symbols for websocket are lowercase while for REST they are uppercase
+    let symbols = symbols
+        .unwrap_or_default()
+        .iter()
+        .map(|symbol| symbol.to_lowercase())
+        .collect::<Vec<String>>();
+
+    // !markPrice@arr contains both swap and future symbols of the same endpoint
+    let channels: Vec<String> = if symbols.is_empty() {
+        vec!["!markPrice@arr".to_string()]
+    } else {
+        symbols
+            .iter()
+            .map(|symbol| format!("{}@markPrice", symbol))
+            .collect()
+    };
+
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::MarkPrice,
+        market_type,
+        tx,
+    );
+
+    match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            let ws_client = Arc::new(BinanceInverseWSClient::new(tx, None));
+            ws_client.subscribe(&channels);
+            run_ws_client(ws_client, duration);
+        }
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            let ws_client = Arc::new(BinanceLinearWSClient::new(tx, None));
+            ws_client.subscribe(&channels);
+            run_ws_client(ws_client, duration);
+        }
+        _ => panic!("Binance {} does NOT have mark prices", market_type),
+    }
+}
+
 pub(crate) fn crawl_candlestick(
     market_type: MarketType,
     symbol_interval_list: Option<&[(String, usize)]>,
@@ -3,7 +3,9 @@ use crate::{crawlers::utils::create_conversion_thread, msg::Message};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 use crypto_ws_client::*;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
 
 const EXCHANGE_NAME: &str = "bitget";
 
@@ -37,9 +39,9 @@ pub(crate) fn crawl_funding_rate(
 
     match market_type {
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            let ws_client = BitgetSwapWSClient::new(tx, None);
+            let ws_client = Arc::new(BitgetSwapWSClient::new(tx, None));
             ws_client.subscribe(&channels);
-            ws_client.run(duration);
+            run_ws_client(ws_client, duration);
         }
         _ => panic!("Bitget {} does NOT have funding rates", market_type),
     }
@@ -6,7 +6,9 @@ use crate::{crawlers::utils::create_conversion_thread, msg::Message};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 use crypto_ws_client::*;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
 
 const EXCHANGE_NAME: &str = "bitmex";
 
@@ -20,13 +22,14 @@ fn crawl_all(msg_type: MessageType, tx: Sender<Message>, duration: Option<u64>)
         MessageType::BBO => "quote",
         MessageType::L2Snapshot => "orderBookL2",
         MessageType::FundingRate => "funding",
+        MessageType::Liquidation => "liquidation",
         _ => panic!("unsupported message type {}", msg_type),
     };
     let channels = vec![channel.to_string()];
 
-    let ws_client = BitmexWSClient::new(tx, None);
+    let ws_client = Arc::new(BitmexWSClient::new(tx, None));
     ws_client.subscribe(channels.as_slice());
-    ws_client.run(duration);
+    run_ws_client(ws_client, duration);
 }
 
 pub(crate) fn crawl_trade(
@@ -158,15 +161,155 @@ pub(crate) fn crawl_funding_rate(
 
         match market_type {
             MarketType::InverseSwap | MarketType::QuantoSwap => {
-                let ws_client = BitmexWSClient::new(tx, None);
+                let ws_client = Arc::new(BitmexWSClient::new(tx, None));
                 ws_client.subscribe(&channels);
-                ws_client.run(duration);
+                run_ws_client(ws_client, duration);
             }
             _ => panic!("BitMEX {} does NOT have funding rates", market_type),
         }
     }
 }
 
+#[allow(clippy::unnecessary_unwrap)]
+pub(crate) fn crawl_liquidation(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    if market_type == MarketType::Unknown {
+        // crawl all symbols
+        crawl_all(MessageType::Liquidation, tx, duration);
+    } else {
+        let is_empty = match symbols {
+            Some(list) => {
+                if list.is_empty() {
+                    true
+                } else {
+                    check_args(EXCHANGE_NAME, market_type, list);
+                    false
+                }
+            }
+            None => true,
+        };
+
+        let real_symbols = if is_empty {
+            fetch_symbols_retry(EXCHANGE_NAME, market_type)
+        } else {
+            symbols.unwrap().to_vec()
+        };
+        if real_symbols.is_empty() {
+            panic!("real_symbols is empty");
+        }
+        let tx = create_conversion_thread(
+            EXCHANGE_NAME.to_string(),
+            MessageType::Liquidation,
+            market_type,
+            tx,
+        );
+
+        let channels: Vec<String> = real_symbols
+            .iter()
+            .map(|symbol| format!("liquidation:{}", symbol))
+            .collect();
+
+        let ws_client = Arc::new(BitmexWSClient::new(tx, None));
+        ws_client.subscribe(&channels);
+        run_ws_client(ws_client, duration);
+    }
+}
+
+pub(crate) fn crawl_insurance_fund(
+    market_type: MarketType,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::InsuranceFund,
+        market_type,
+        tx,
+    );
+    let ws_client = Arc::new(BitmexWSClient::new(tx, None));
+    ws_client.subscribe_insurance();
+    run_ws_client(ws_client, duration);
+}
+
+// Returns an empty list, which means all symbols, if market_type is Unknown.
+fn get_real_symbols(market_type: MarketType, symbols: Option<&[String]>) -> Vec<String> {
+    if market_type == MarketType::Unknown {
+        return Vec::new();
+    }
+    match symbols {
+        Some(list) if !list.is_empty() => {
+            check_args(EXCHANGE_NAME, market_type, list);
+            list.to_vec()
+        }
+        _ => {
+            let real_symbols = fetch_symbols_retry(EXCHANGE_NAME, market_type);
+            if real_symbols.is_empty() {
+                panic!("real_symbols is empty");
+            }
+            real_symbols
+        }
+    }
+}
+
+pub(crate) fn crawl_settlement(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let real_symbols = get_real_symbols(market_type, symbols);
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::Settlement,
+        market_type,
+        tx,
+    );
+    let ws_client = Arc::new(BitmexWSClient::new(tx, None));
+    ws_client.subscribe_settlement(&real_symbols);
+    run_ws_client(ws_client, duration);
+}
+
+pub(crate) fn crawl_instrument_status(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let real_symbols = get_real_symbols(market_type, symbols);
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::InstrumentStatus,
+        market_type,
+        tx,
+    );
+    let ws_client = Arc::new(BitmexWSClient::new(tx, None));
+    ws_client.subscribe_instrument(&real_symbols);
+    run_ws_client(ws_client, duration);
+}
+
+// Mark prices are pushed by the instrument channel along with other fields
+pub(crate) fn crawl_mark_price(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let real_symbols = get_real_symbols(market_type, symbols);
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::MarkPrice,
+        market_type,
+        tx,
+    );
+    let ws_client = Arc::new(BitmexWSClient::new(tx, None));
+    ws_client.subscribe_instrument(&real_symbols);
+    run_ws_client(ws_client, duration);
+}
+
 pub(crate) fn crawl_candlestick(
     market_type: MarketType,
     symbol_interval_list: Option<&[(String, usize)]>,
@@ -183,9 +326,9 @@ pub(crate) fn crawl_candlestick(
 
         let channels = vec!["tradeBin1m".to_string(), "tradeBin5m".to_string()];
 
-        let ws_client = BitmexWSClient::new(tx, None);
+        let ws_client = Arc::new(BitmexWSClient::new(tx, None));
         ws_client.subscribe(channels.as_slice());
-        ws_client.run(duration);
+        run_ws_client(ws_client, duration);
     } else {
         crawl_candlestick_ext(
             EXCHANGE_NAME,
@@ -0,0 +1,103 @@
+use super::utils::{check_args, fetch_symbols_retry};
+use crate::{crawlers::utils::create_conversion_thread, msg::Message};
+use crypto_market_type::MarketType;
+use crypto_msg_type::MessageType;
+use crypto_ws_client::*;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
+
+const EXCHANGE_NAME: &str = "bybit";
+
+#[allow(clippy::unnecessary_unwrap)]
+pub(crate) fn crawl_liquidation(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let is_empty = match symbols {
+        Some(list) => {
+            if list.is_empty() {
+                true
+            } else {
+                check_args(EXCHANGE_NAME, market_type, list);
+                false
+            }
+        }
+        None => true,
+    };
+
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::Liquidation,
+        market_type,
+        tx,
+    );
+
+    // https://bybit-exchange.github.io/docs/inverse/#t-websocketliquidation
+    let channels: Vec<String> = if is_empty && market_type != MarketType::LinearSwap {
+        // subscribe to all symbols
+        vec!["liquidation".to_string()]
+    } else {
+        let real_symbols = if is_empty {
+            fetch_symbols_retry(EXCHANGE_NAME, market_type)
+        } else {
+            symbols.unwrap().to_vec()
+        };
+        if real_symbols.is_empty() {
+            panic!("real_symbols is empty");
+        }
+        real_symbols
+            .iter()
+            .map(|symbol| format!("liquidation.{}", symbol))
+            .collect()
+    };
+
+    match market_type {
+        MarketType::InverseSwap => {
+            let ws_client = Arc::new(BybitInverseSwapWSClient::new(tx, None));
+            ws_client.subscribe(&channels);
+            run_ws_client(ws_client, duration);
+        }
+        MarketType::InverseFuture => {
+            let ws_client = Arc::new(BybitInverseFutureWSClient::new(tx, None));
+            ws_client.subscribe(&channels);
+            run_ws_client(ws_client, duration);
+        }
+        MarketType::LinearSwap => {
+            let ws_client = Arc::new(BybitLinearSwapWSClient::new(tx, None));
+            ws_client.subscribe(&channels);
+            run_ws_client(ws_client, duration);
+        }
+        _ => panic!("Bybit {} does NOT have liquidation orders", market_type),
+    }
+}
+
+pub(crate) fn crawl_insurance_fund(
+    market_type: MarketType,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::InsuranceFund,
+        market_type,
+        tx,
+    );
+
+    // An empty list means all currencies
+    match market_type {
+        MarketType::InverseSwap => {
+            let ws_client = Arc::new(BybitInverseSwapWSClient::new(tx, None));
+            ws_client.subscribe_insurance(&[]);
+            run_ws_client(ws_client, duration);
+        }
+        MarketType::InverseFuture => {
+            let ws_client = Arc::new(BybitInverseFutureWSClient::new(tx, None));
+            ws_client.subscribe_insurance(&[]);
+            run_ws_client(ws_client, duration);
+        }
+        _ => panic!("Bybit {} does NOT have insurance funds", market_type),
+    }
+}
@@ -3,7 +3,9 @@ use crate::{crawlers::utils::create_conversion_thread, msg::Message};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 use crypto_ws_client::*;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
 
 const EXCHANGE_NAME: &str = "deribit";
 
@@ -32,9 +34,9 @@ pub(crate) fn crawl_trade(
         .map(|x| x.to_string())
         .collect();
 
-        let ws_client = DeribitWSClient::new(tx, None);
+        let ws_client = Arc::new(DeribitWSClient::new(tx, None));
         ws_client.subscribe(&channels);
-        ws_client.run(duration);
+        run_ws_client(ws_client, duration);
     } else {
         crawl_event(
             EXCHANGE_NAME,
@@ -5,7 +5,9 @@ use crate::msg::Message;
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 use crypto_ws_client::*;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
 
 const EXCHANGE_NAME: &str = "huobi";
 
@@ -31,9 +33,9 @@ pub(crate) fn crawl_l2_event(
             };
             // Huobi Spot market.$symbol.mbp.$levels must use wss://api.huobi.pro/feed
             // or wss://api-aws.huobi.pro/feed
-            let ws_client = HuobiSpotWSClient::new(tx, Some("wss://api.huobi.pro/feed"));
+            let ws_client = Arc::new(HuobiSpotWSClient::new(tx, Some("wss://api.huobi.pro/feed")));
             ws_client.subscribe_orderbook(&symbols);
-            ws_client.run(duration);
+            run_ws_client(ws_client, duration);
         }
         MarketType::InverseFuture
         | MarketType::LinearSwap
@@ -76,18 +78,20 @@ pub(crate) fn crawl_funding_rate(
 
     match market_type {
         MarketType::InverseSwap => {
-            let ws_client =
-                HuobiInverseSwapWSClient::new(tx, Some("wss://api.hbdm.com/swap-notification"));
+            let ws_client = Arc::new(HuobiInverseSwapWSClient::new(
+                tx,
+                Some("wss://api.hbdm.com/swap-notification"),
+            ));
             ws_client.subscribe(&channels);
-            ws_client.run(duration);
+            run_ws_client(ws_client, duration);
         }
         MarketType::LinearSwap => {
-            let ws_client = HuobiLinearSwapWSClient::new(
+            let ws_client = Arc::new(HuobiLinearSwapWSClient::new(
                 tx,
                 Some("wss://api.hbdm.com/linear-swap-notification"),
-            );
+            ));
             ws_client.subscribe(&channels);
-            ws_client.run(duration);
+            run_ws_client(ws_client, duration);
         }
         _ => panic!("Huobi {} does NOT have funding rates", market_type),
     }
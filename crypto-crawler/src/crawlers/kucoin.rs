@@ -2,7 +2,9 @@ use crate::{crawlers::utils::create_conversion_thread, msg::Message};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 use crypto_ws_client::*;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
 
 use super::crawl_event;
 
@@ -21,9 +23,9 @@ pub(crate) fn crawl_bbo(
         // https://docs.kucoin.com/#all-symbols-ticker
         let channels: Vec<String> = vec!["/market/ticker:all".to_string()];
 
-        let ws_client = KuCoinSpotWSClient::new(tx, None);
+        let ws_client = Arc::new(KuCoinSpotWSClient::new(tx, None));
         ws_client.subscribe(&channels);
-        ws_client.run(duration);
+        run_ws_client(ws_client, duration);
     } else {
         crawl_event(
             EXCHANGE_NAME,
@@ -0,0 +1,452 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::mpsc::{Receiver, Sender},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use crypto_market_type::MarketType;
+use crypto_msg_type::MessageType;
+use crypto_rest_client::fetch_l2_snapshot;
+use log::*;
+use serde_json::Value;
+
+use super::utils::get_cooldown_time_per_request;
+use crate::{msg::Message, stop, utils::REST_LOCKS};
+
+/// The error returned when `crawl_l2_book()` doesn't support an exchange.
+#[derive(Debug)]
+pub enum L2BookError {
+    /// The websocket channel has no sequence IDs to align with a RESTful
+    /// snapshot, but sends a snapshot by itself, use `crawl_l2_event()`
+    SnapshotInChannel { exchange: String },
+    /// The exchange doesn't need to merge snapshots with update events
+    Unsupported { exchange: String },
+}
+
+impl fmt::Display for L2BookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            L2BookError::SnapshotInChannel { exchange } => write!(
+                f,
+                "{} level2 events have no sequence IDs, use crawl_l2_event() which \
+                 receives a snapshot from the websocket first",
+                exchange
+            ),
+            L2BookError::Unsupported { exchange } => write!(
+                f,
+                "{} does NOT need to merge orderbook snapshots with update events",
+                exchange
+            ),
+        }
+    }
+}
+
+impl std::error::Error for L2BookError {}
+
+// Events buffered per symbol while waiting for a snapshot, older ones are dropped
+const MAX_BUFFERED_EVENTS: usize = 4096;
+// Backoff between snapshot requests of a symbol which keeps finding gaps
+const MIN_SNAPSHOT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_SNAPSHOT_BACKOFF: Duration = Duration::from_secs(60);
+
+// Sequence IDs of an incremental update, which covers updates in [first, last]
+struct SeqIds {
+    first: u64,
+    last: u64,
+    // The last update ID of the previous event, only Binance futures have it
+    prev: Option<u64>,
+}
+
+enum BookInput {
+    Event(Message),
+    // (symbol, raw snapshot), None means failed to fetch the snapshot
+    Snapshot(String, Option<String>),
+    // No more events
+    End,
+}
+
+#[derive(Default)]
+struct BookState {
+    // The last update ID applied, None means not synchronized with a snapshot
+    last_seq: Option<u64>,
+    // Whether last_seq comes from a snapshot rather than an event
+    from_snapshot: bool,
+    // Events received while waiting for a snapshot
+    buffer: VecDeque<Message>,
+    // A snapshot has been requested and not received yet
+    pending: bool,
+    // Don't request a snapshot before this time
+    next_request: Option<Instant>,
+    // Doubled on every request, and reset if no request for a while
+    backoff: Duration,
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|x| x.parse::<u64>().ok()))
+}
+
+// Extract the symbol for RESTful APIs and sequence IDs from an incremental update
+fn extract_event(exchange: &str, market_type: MarketType, json: &str) -> Option<(String, SeqIds)> {
+    let obj = serde_json::from_str::<Value>(json).ok()?;
+    let data = obj.get("data")?;
+    match exchange {
+        // https://binance-docs.github.io/apidocs/spot/en/#how-to-manage-a-local-order-book-correctly
+        "binance" => Some((
+            data["s"].as_str()?.to_string(),
+            SeqIds {
+                first: as_u64(&data["U"])?,
+                last: as_u64(&data["u"])?,
+                prev: as_u64(&data["pu"]),
+            },
+        )),
+        // https://docs.kucoin.com/#level-2-market-data
+        "kucoin" => {
+            if market_type == MarketType::Spot {
+                Some((
+                    data["symbol"].as_str()?.to_string(),
+                    SeqIds {
+                        first: as_u64(&data["sequenceStart"])?,
+                        last: as_u64(&data["sequenceEnd"])?,
+                        prev: None,
+                    },
+                ))
+            } else {
+                let topic = obj["topic"].as_str()?;
+                let sequence = as_u64(&data["sequence"])?;
+                Some((
+                    topic.split(':').next_back()?.to_string(),
+                    SeqIds {
+                        first: sequence,
+                        last: sequence,
+                        prev: None,
+                    },
+                ))
+            }
+        }
+        _ => None,
+    }
+}
+
+// Extract the last update ID from a RESTful snapshot
+fn extract_snapshot_seq(exchange: &str, json: &str) -> Option<u64> {
+    let obj = serde_json::from_str::<Value>(json).ok()?;
+    match exchange {
+        "binance" => as_u64(&obj["lastUpdateId"]),
+        "kucoin" => as_u64(&obj["data"]["sequence"]),
+        _ => None,
+    }
+}
+
+// Fetch snapshots one by one, holding the REST lock just like crawl_snapshot()
+fn create_snapshot_thread(
+    exchange: String,
+    market_type: MarketType,
+    rx_symbols: Receiver<String>,
+    tx: Sender<BookInput>,
+) -> JoinHandle<()> {
+    stop::spawn(std::thread::Builder::new(), move || {
+        let cooldown_time = get_cooldown_time_per_request(&exchange, market_type);
+        let lock = REST_LOCKS
+            .get(&exchange)
+            .unwrap()
+            .get(&market_type)
+            .unwrap()
+            .clone();
+        for symbol in rx_symbols {
+            let mut lock_ = lock.lock().unwrap();
+            if !lock_.owns_lock() {
+                lock_.lock().unwrap();
+            }
+            let resp = fetch_l2_snapshot(&exchange, market_type, &symbol, Some(3));
+            stop::sleep(cooldown_time);
+            if lock_.owns_lock() {
+                lock_.unlock().unwrap();
+            }
+            let snapshot = match resp {
+                Ok(json) => Some(json),
+                Err(err) => {
+                    warn!(
+                        "Failed to fetch the snapshot of {} {} {}, error: {}",
+                        exchange, market_type, symbol, err
+                    );
+                    None
+                }
+            };
+            if tx.send(BookInput::Snapshot(symbol, snapshot)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+struct Merger {
+    exchange: String,
+    market_type: MarketType,
+    books: HashMap<String, BookState>,
+    tx_symbols: Sender<String>,
+    tx: Sender<Message>,
+}
+
+impl Merger {
+    fn on_event(&mut self, msg: Message) {
+        let (symbol, seq_ids) = match extract_event(&self.exchange, self.market_type, &msg.json) {
+            Some(x) => x,
+            None => {
+                // subscription responses, etc.
+                debug!("No sequence ID in {}", msg.json);
+                return;
+            }
+        };
+        let book = self.books.entry(symbol.clone()).or_default();
+        if let Some(last_seq) = book.last_seq {
+            if seq_ids.last <= last_seq {
+                return; // already included
+            }
+            let is_next = match seq_ids.prev {
+                Some(prev) if !book.from_snapshot => prev == last_seq,
+                _ => seq_ids.first <= last_seq + 1,
+            };
+            if is_next {
+                book.last_seq = Some(seq_ids.last);
+                book.from_snapshot = false;
+                self.tx.send(msg).unwrap();
+                return;
+            }
+            warn!(
+                "Found a gap after {} in {} {} {}, re-fetching the snapshot",
+                last_seq, self.exchange, self.market_type, symbol
+            );
+            book.last_seq = None;
+        }
+        if book.buffer.len() >= MAX_BUFFERED_EVENTS {
+            // the snapshot will be newer than stale events anyway
+            book.buffer.pop_front();
+        }
+        book.buffer.push_back(msg);
+        let now = Instant::now();
+        let (ready, stale) = match book.next_request {
+            Some(x) => (x <= now, x + MAX_SNAPSHOT_BACKOFF <= now),
+            None => (true, false),
+        };
+        if !book.pending && ready {
+            if stale {
+                book.backoff = Duration::ZERO;
+            }
+            book.pending = true;
+            book.next_request = Some(now + book.backoff);
+            book.backoff = (book.backoff * 2).clamp(MIN_SNAPSHOT_BACKOFF, MAX_SNAPSHOT_BACKOFF);
+            self.tx_symbols.send(symbol).unwrap();
+        }
+    }
+
+    fn on_snapshot(&mut self, symbol: String, snapshot: Option<String>) {
+        let book = self.books.entry(symbol.clone()).or_default();
+        book.pending = false;
+        let snapshot_seq = snapshot
+            .as_ref()
+            .and_then(|json| extract_snapshot_seq(&self.exchange, json));
+        let (json, snapshot_seq) = match (snapshot, snapshot_seq) {
+            (Some(json), Some(snapshot_seq)) => (json, snapshot_seq),
+            (Some(json), None) => {
+                warn!("No sequence ID in {}", json);
+                return;
+            }
+            _ => return, // retry on the next event
+        };
+        book.last_seq = Some(snapshot_seq);
+        book.from_snapshot = true;
+        let buffer = std::mem::take(&mut book.buffer);
+        self.tx
+            .send(Message::new(
+                self.exchange.clone(),
+                self.market_type,
+                MessageType::L2Snapshot,
+                json,
+            ))
+            .unwrap();
+        for msg in buffer {
+            self.on_event(msg);
+        }
+    }
+}
+
+/// Crawl level2 orderbook snapshots and incremental updates aligned by sequence IDs.
+pub(crate) fn crawl_l2_book(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let (tx_input, rx_input) = std::sync::mpsc::channel::<BookInput>();
+
+    // forward incremental updates
+    let (tx_events, rx_events) = std::sync::mpsc::channel::<Message>();
+    let forward_thread = {
+        let tx_input = tx_input.clone();
+        stop::spawn(std::thread::Builder::new(), move || {
+            for msg in rx_events {
+                if tx_input.send(BookInput::Event(msg)).is_err() {
+                    return;
+                }
+            }
+            let _ = tx_input.send(BookInput::End);
+        })
+    };
+    let event_thread = {
+        let exchange = exchange.to_string();
+        let symbols = symbols.map(|x| x.to_vec());
+        stop::spawn(std::thread::Builder::new(), move || {
+            crate::crawl_l2_event(
+                &exchange,
+                market_type,
+                symbols.as_deref(),
+                tx_events,
+                duration,
+            )
+        })
+    };
+
+    let (tx_symbols, rx_symbols) = std::sync::mpsc::channel::<String>();
+    let snapshot_thread =
+        create_snapshot_thread(exchange.to_string(), market_type, rx_symbols, tx_input);
+
+    let mut merger = Merger {
+        exchange: exchange.to_string(),
+        market_type,
+        books: HashMap::new(),
+        tx_symbols,
+        tx,
+    };
+    let mut ended = false;
+    for input in rx_input.iter() {
+        match input {
+            BookInput::Event(msg) => merger.on_event(msg),
+            BookInput::Snapshot(symbol, snapshot) => merger.on_snapshot(symbol, snapshot),
+            BookInput::End => ended = true,
+        }
+        // wait for pending snapshots so that buffered events are flushed
+        if ended && merger.books.values().all(|book| !book.pending) {
+            break;
+        }
+    }
+    drop(merger); // stop the snapshot thread
+    snapshot_thread.join().unwrap();
+    forward_thread.join().unwrap();
+    event_thread.join().unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_event, extract_snapshot_seq, Merger, MAX_BUFFERED_EVENTS};
+    use crate::Message;
+    use crypto_market_type::MarketType;
+    use crypto_msg_type::MessageType;
+    use std::collections::HashMap;
+
+    fn binance_event(first: u64, last: u64, prev: u64) -> Message {
+        Message::new(
+            "binance".to_string(),
+            MarketType::LinearSwap,
+            MessageType::L2Event,
+            format!(
+                r#"{{"stream":"btcusdt@depth@100ms","data":{{"e":"depthUpdate","E":1622363903670,"T":1622363903662,"s":"BTCUSDT","U":{},"u":{},"pu":{},"b":[],"a":[]}}}}"#,
+                first, last, prev
+            ),
+        )
+    }
+
+    #[test]
+    fn extract() {
+        let msg = binance_event(10, 12, 9);
+        let (symbol, seq_ids) =
+            extract_event("binance", MarketType::LinearSwap, &msg.json).unwrap();
+        assert_eq!("BTCUSDT", symbol);
+        assert_eq!(
+            (10, 12, Some(9)),
+            (seq_ids.first, seq_ids.last, seq_ids.prev)
+        );
+
+        let raw_msg = r#"{"data":{"sequenceStart":1616157473469,"symbol":"BTC-USDT","changes":{"asks":[],"bids":[]},"sequenceEnd":1616157473470},"subject":"trade.l2update","topic":"/market/level2:BTC-USDT","type":"message"}"#;
+        let (symbol, seq_ids) = extract_event("kucoin", MarketType::Spot, raw_msg).unwrap();
+        assert_eq!("BTC-USDT", symbol);
+        assert_eq!(
+            (1616157473469, 1616157473470),
+            (seq_ids.first, seq_ids.last)
+        );
+
+        assert_eq!(
+            Some(1616157473468),
+            extract_snapshot_seq(
+                "kucoin",
+                r#"{"code":"200000","data":{"time":1616157473468,"sequence":"1616157473468","bids":[],"asks":[]}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let (tx_symbols, rx_symbols) = std::sync::mpsc::channel();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut merger = Merger {
+            exchange: "binance".to_string(),
+            market_type: MarketType::LinearSwap,
+            books: HashMap::new(),
+            tx_symbols,
+            tx,
+        };
+        merger.on_event(binance_event(1, 5, 0));
+        merger.on_event(binance_event(6, 9, 5));
+        assert_eq!("BTCUSDT", rx_symbols.try_recv().unwrap());
+        assert!(rx_symbols.try_recv().is_err()); // requested only once
+
+        let snapshot =
+            r#"{"lastUpdateId":7,"E":1622363903670,"T":1622363903662,"bids":[],"asks":[]}"#;
+        merger.on_snapshot("BTCUSDT".to_string(), Some(snapshot.to_string()));
+        merger.on_event(binance_event(10, 12, 9));
+        // a gap
+        merger.on_event(binance_event(15, 16, 14));
+        assert_eq!("BTCUSDT", rx_symbols.try_recv().unwrap());
+
+        drop(merger);
+        let messages = rx.into_iter().collect::<Vec<Message>>();
+        assert_eq!(3, messages.len());
+        assert_eq!(MessageType::L2Snapshot, messages[0].msg_type);
+        assert!(messages[1].json.contains(r#""U":6"#));
+        assert!(messages[2].json.contains(r#""U":10"#));
+    }
+
+    #[test]
+    fn backoff() {
+        let (tx_symbols, rx_symbols) = std::sync::mpsc::channel();
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let mut merger = Merger {
+            exchange: "binance".to_string(),
+            market_type: MarketType::LinearSwap,
+            books: HashMap::new(),
+            tx_symbols,
+            tx,
+        };
+        merger.on_event(binance_event(1, 5, 0));
+        assert_eq!("BTCUSDT", rx_symbols.try_recv().unwrap());
+        // failed to fetch the snapshot
+        merger.on_snapshot("BTCUSDT".to_string(), None);
+        merger.on_event(binance_event(6, 9, 5));
+        assert_eq!("BTCUSDT", rx_symbols.try_recv().unwrap());
+        merger.on_snapshot("BTCUSDT".to_string(), None);
+        // not re-requested within the backoff
+        merger.on_event(binance_event(10, 12, 9));
+        assert!(rx_symbols.try_recv().is_err());
+
+        for i in 0..MAX_BUFFERED_EVENTS as u64 {
+            merger.on_event(binance_event(13 + i, 13 + i, 12 + i));
+        }
+        let book = &merger.books["BTCUSDT"];
+        assert_eq!(MAX_BUFFERED_EVENTS, book.buffer.len());
+        assert!(book.buffer[0].json.contains(r#""U":13"#));
+    }
+}
@@ -4,13 +4,16 @@ mod utils;
 pub(super) mod binance;
 pub(super) mod bitget;
 pub(super) mod bitmex;
+pub(super) mod bybit;
 pub(super) mod deribit;
 pub(super) mod huobi;
 pub(super) mod kucoin;
+pub(super) mod l2_book;
 pub(super) mod okex;
 
-pub use utils::fetch_symbols_retry;
+pub use l2_book::L2BookError;
 pub(super) use utils::{
-    crawl_candlestick_ext, crawl_event, crawl_open_interest, crawl_snapshot,
-    create_ws_client_symbol,
+    can_share_connection, crawl_candlestick_ext, crawl_channels, crawl_event, crawl_events,
+    crawl_open_interest, crawl_snapshot, create_ws_client_symbol,
 };
+pub use utils::{fetch_symbols_retry, set_cooldown_time_per_request};
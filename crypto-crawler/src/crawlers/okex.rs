@@ -4,7 +4,9 @@ use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 use crypto_rest_client::*;
 use crypto_ws_client::*;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
+
+use crate::stop::run_ws_client;
 
 const EXCHANGE_NAME: &str = "okex";
 
@@ -31,9 +33,9 @@ pub(crate) fn crawl_trade(
             .map(|x| format!("option/trades:{}", x))
             .collect();
 
-        let ws_client = OkexWSClient::new(tx, None);
+        let ws_client = Arc::new(OkexWSClient::new(tx, None));
         ws_client.subscribe(&channels);
-        ws_client.run(duration);
+        run_ws_client(ws_client, duration);
     } else {
         crawl_event(
             EXCHANGE_NAME,
@@ -72,10 +74,43 @@ pub(crate) fn crawl_funding_rate(
 
     match market_type {
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            let ws_client = OkexWSClient::new(tx, None);
+            let ws_client = Arc::new(OkexWSClient::new(tx, None));
             ws_client.subscribe(&channels);
-            ws_client.run(duration);
+            run_ws_client(ws_client, duration);
         }
         _ => panic!("OKEx {} does NOT have funding rates", market_type),
     }
 }
+
+#[allow(clippy::unnecessary_unwrap)]
+pub(crate) fn crawl_index_price(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::IndexPrice,
+        market_type,
+        tx,
+    );
+
+    let symbols: Vec<String> = if symbols.is_none() || symbols.unwrap().is_empty() {
+        fetch_symbols_retry(EXCHANGE_NAME, market_type)
+    } else {
+        symbols.unwrap().to_vec()
+    };
+
+    match market_type {
+        MarketType::InverseFuture
+        | MarketType::LinearFuture
+        | MarketType::InverseSwap
+        | MarketType::LinearSwap => {
+            let ws_client = Arc::new(OkexWSClient::new(tx, None));
+            ws_client.subscribe_index_price(&symbols);
+            run_ws_client(ws_client, duration);
+        }
+        _ => panic!("OKEx {} does NOT have index prices", market_type),
+    }
+}
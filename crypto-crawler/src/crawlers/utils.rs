@@ -1,13 +1,15 @@
 use std::{
+    collections::HashMap,
+    marker::PhantomData,
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc::{
             self, {Receiver, Sender},
         },
-        Arc,
+        Arc, RwLock,
     },
     thread::JoinHandle,
-    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime},
 };
 
 use crate::utils::{REST_LOCKS, WS_LOCKS};
@@ -16,10 +18,16 @@ use crypto_market_type::MarketType;
 use crypto_markets::fetch_symbols;
 use crypto_rest_client::{fetch_l2_snapshot, fetch_l3_snapshot, fetch_open_interest};
 use crypto_ws_client::*;
+use lazy_static::lazy_static;
 use log::*;
-use rand::Rng;
 
-use crate::{get_hot_spot_symbols, utils::cmc_rank::sort_by_cmc_rank, Message, MessageType};
+use crate::{
+    get_hot_spot_symbols,
+    parser::{parse_message, ParseError},
+    stop::{self, run_ws_client},
+    utils::cmc_rank::sort_by_cmc_rank,
+    Message, MessageType,
+};
 
 pub fn fetch_symbols_retry(exchange: &str, market_type: MarketType) -> Vec<String> {
     let retry_count = std::env::var("REST_RETRY_COUNT")
@@ -59,7 +67,7 @@ pub fn fetch_symbols_retry(exchange: &str, market_type: MarketType) -> Vec<Strin
         }
         // Cooldown after each request, and make all other processes wait
         // on the lock to avoid parallel requests, thus avoid 429 error
-        std::thread::sleep(cooldown_time * backoff_factor);
+        stop::sleep(cooldown_time * backoff_factor);
         if lock_.owns_lock() {
             lock_.unlock().unwrap();
         }
@@ -97,7 +105,26 @@ pub(super) fn check_args(exchange: &str, market_type: MarketType, symbols: &[Str
     }
 }
 
-fn get_cooldown_time_per_request(exchange: &str, market_type: MarketType) -> Duration {
+lazy_static! {
+    // Set by set_cooldown_time_per_request(), which takes precedence over the defaults below
+    static ref COOLDOWN_TIMES: RwLock<HashMap<String, Duration>> = RwLock::new(HashMap::new());
+}
+
+/// Override the cooldown time after each RESTful request to an exchange.
+///
+/// Longer cooldown time makes 429 errors less likely, shorter cooldown time
+/// makes `crawl_l2_snapshot()`, etc. faster.
+pub fn set_cooldown_time_per_request(exchange: &str, cooldown_time: Duration) {
+    COOLDOWN_TIMES
+        .write()
+        .unwrap()
+        .insert(exchange.to_string(), cooldown_time);
+}
+
+pub(super) fn get_cooldown_time_per_request(exchange: &str, market_type: MarketType) -> Duration {
+    if let Some(cooldown_time) = COOLDOWN_TIMES.read().unwrap().get(exchange) {
+        return *cooldown_time;
+    }
     let millis = match exchange {
         "binance" => 500,      // spot weitht 1200, contract weight 2400
         "bitget" => 100,       // 20 requests per 2 seconds
@@ -168,7 +195,7 @@ pub(crate) fn crawl_snapshot(
         let mut index = 0_usize;
         let mut success_count = 0_u64;
         let mut backoff_factor = 1;
-        while index < real_symbols.len() {
+        while index < real_symbols.len() && !stop::is_stopped() {
             let symbol = &real_symbols[index];
             let mut lock_ = lock.lock().unwrap();
             if !lock_.owns_lock() {
@@ -181,7 +208,7 @@ pub(crate) fn crawl_snapshot(
             };
             // Cooldown after each request, and make all other processes wait
             // on the lock to avoid parallel requests, thus avoid 429 error
-            std::thread::sleep(cooldown_time);
+            stop::sleep(cooldown_time);
             if lock_.owns_lock() {
                 lock_.unlock().unwrap();
             }
@@ -209,7 +236,7 @@ pub(crate) fn crawl_snapshot(
                         err,
                         (backoff_factor * cooldown_time).as_millis()
                     );
-                    std::thread::sleep(backoff_factor * cooldown_time);
+                    stop::sleep(backoff_factor * cooldown_time);
                     success_count = 0;
                     if err.0.contains("429") || err.0.contains("509") {
                         backoff_factor += 1;
@@ -220,12 +247,15 @@ pub(crate) fn crawl_snapshot(
                 }
             }
         }
+        if stop::is_stopped() {
+            break;
+        }
         if let Some(seconds) = duration {
             if now.elapsed() > Duration::from_secs(seconds) {
                 break;
             }
         }
-        std::thread::sleep(cooldown_time * 2); // if real_symbols is empty, CPU will be 100% without this line
+        stop::sleep(cooldown_time * 2); // if real_symbols is empty, CPU will be 100% without this line
     }
 }
 
@@ -264,7 +294,7 @@ pub(crate) fn crawl_open_interest(
                 }
                 // Cooldown after each request, and make all other processes wait
                 // on the lock to avoid parallel requests, thus avoid 429 error
-                std::thread::sleep(cooldown_time);
+                stop::sleep(cooldown_time);
                 if lock_.owns_lock() {
                     lock_.unlock().unwrap();
                 }
@@ -275,7 +305,7 @@ pub(crate) fn crawl_open_interest(
                 let mut index = 0_usize;
                 let mut success_count = 0_u64;
                 let mut backoff_factor = 1;
-                while index < real_symbols.len() {
+                while index < real_symbols.len() && !stop::is_stopped() {
                     let symbol = &real_symbols[index];
                     let mut lock_ = lock.lock().unwrap();
                     if !lock_.owns_lock() {
@@ -284,7 +314,7 @@ pub(crate) fn crawl_open_interest(
                     let resp = fetch_open_interest(exchange, market_type, Some(symbol));
                     // Cooldown after each request, and make all other processes wait
                     // on the lock to avoid parallel requests, thus avoid 429 error
-                    std::thread::sleep(cooldown_time);
+                    stop::sleep(cooldown_time);
                     if lock_.owns_lock() {
                         lock_.unlock().unwrap();
                     }
@@ -318,7 +348,7 @@ pub(crate) fn crawl_open_interest(
                                 err,
                                 (backoff_factor * cooldown_time).as_millis()
                             );
-                            std::thread::sleep(backoff_factor * cooldown_time);
+                            stop::sleep(backoff_factor * cooldown_time);
                             success_count = 0;
                             if err.0.contains("429") || err.0.contains("509") {
                                 backoff_factor += 1;
@@ -332,12 +362,15 @@ pub(crate) fn crawl_open_interest(
             }
             _ => panic!("{} does NOT have open interest RESTful API", exchange),
         }
+        if stop::is_stopped() {
+            break;
+        }
         if let Some(seconds) = duration {
             if now.elapsed() > Duration::from_secs(seconds) {
                 break;
             }
         }
-        std::thread::sleep(cooldown_time * 2); // if real_symbols is empty, CPU will be 100% without this line
+        stop::sleep(cooldown_time * 2); // if real_symbols is empty, CPU will be 100% without this line
     }
 }
 
@@ -366,13 +399,16 @@ fn subscribe_with_lock(
         MessageType::L3Event => ws_client.subscribe_l3_orderbook(symbols),
         MessageType::L2TopK => ws_client.subscribe_orderbook_topk(symbols),
         MessageType::Ticker => ws_client.subscribe_ticker(symbols),
+        MessageType::MarkPrice => ws_client.subscribe_mark_price(symbols),
+        // symbols are raw channels
+        MessageType::Other => ws_client.subscribe(symbols),
         _ => panic!(
             "{} {} does NOT have {} websocket channel",
             exchange, market_type, msg_type
         ),
     };
     if let Some(interval) = interval {
-        std::thread::sleep(Duration::from_millis(interval));
+        stop::sleep(Duration::from_millis(interval));
         if lock.owns_lock() {
             lock.unlock().unwrap();
         }
@@ -398,7 +434,7 @@ fn subscribe_candlestick_with_lock(
     }
     ws_client.subscribe_candlestick(symbol_interval_list);
     if let Some(interval) = interval {
-        std::thread::sleep(Duration::from_millis(interval));
+        stop::sleep(Duration::from_millis(interval));
         if lock.owns_lock() {
             lock.unlock().unwrap();
         }
@@ -524,10 +560,12 @@ fn create_ws_client_internal(
     }
 }
 
+// Messages are tagged by `crypto_msg_parser::get_msg_type()` if there are
+// multiple message types.
 fn create_ws_client(
     exchange: &str,
     market_type: MarketType,
-    msg_type: MessageType,
+    msg_types: &[MessageType],
     tx: Sender<Message>,
 ) -> Arc<dyn WSClient + Send + Sync> {
     let lock = WS_LOCKS
@@ -541,10 +579,14 @@ fn create_ws_client(
     if let Some(interval) = interval {
         if !lock.owns_lock() {
             lock.lock().unwrap();
-            std::thread::sleep(Duration::from_millis(interval));
+            stop::sleep(Duration::from_millis(interval));
         }
     }
-    let tx = create_conversion_thread(exchange.to_string(), msg_type, market_type, tx);
+    let tx = if msg_types.len() == 1 {
+        create_conversion_thread(exchange.to_string(), msg_types[0], market_type, tx)
+    } else {
+        create_classifier_thread(exchange.to_string(), msg_types.to_vec(), market_type, tx)
+    };
     let ws_client = create_ws_client_internal(exchange, market_type, tx);
     if interval.is_some() && lock.owns_lock() {
         lock.unlock().unwrap();
@@ -568,15 +610,16 @@ fn create_symbol_discovery_thread(
     subscribed_symbols: Vec<String>,
     tx: Sender<Vec<String>>, // send out new symbols
 ) -> JoinHandle<()> {
-    let num_topics_per_connection = get_num_subscriptions_per_connection(&exchange);
-    std::thread::spawn(move || {
+    stop::spawn(std::thread::Builder::new(), move || {
         let mut subscribed_symbols = subscribed_symbols;
-        let mut num_subscribed_of_last_client =
-            subscribed_symbols.len() % num_topics_per_connection;
-        let mut rng = rand::thread_rng();
         while !should_stop.load(Ordering::Acquire) {
-            // update symbols every hour
-            std::thread::sleep(Duration::from_secs(3600));
+            // update symbols every hour, checking should_stop every second
+            for _ in 0..3600 {
+                if should_stop.load(Ordering::Acquire) || stop::is_stopped() {
+                    return;
+                }
+                stop::sleep(Duration::from_secs(1));
+            }
             let latest_symbols = if exchange == "binance" {
                 fetch_symbols_retry(&exchange, market_type)
                     .into_iter()
@@ -593,68 +636,196 @@ fn create_symbol_discovery_thread(
 
             if !new_symbols.is_empty() {
                 warn!("Found new symbols: {}", new_symbols.join(", "));
-                tx.send(new_symbols.clone()).unwrap();
-                num_subscribed_of_last_client += new_symbols.len();
+                if tx.send(new_symbols.clone()).is_err() {
+                    return;
+                }
                 subscribed_symbols.append(&mut new_symbols);
             }
-            if num_subscribed_of_last_client >= num_topics_per_connection {
-                warn!(
-                    "The last connection has subscribed {} topics, which is more than {}, restarting the process",
-                    num_subscribed_of_last_client, num_topics_per_connection,
-                );
-                let millis = rng.gen_range(3000_u64..10000_u64);
-                std::thread::sleep(Duration::from_millis(millis)); // sleep for a random time
-                std::process::exit(0); // pm2 will restart the whole process
-            }
         }
     })
 }
 
-fn create_new_symbol_receiver_thread(
-    exchange: String,
-    msg_type: MessageType,
-    market_type: MarketType,
-    rx: Receiver<Vec<String>>,
-    ws_client: Arc<dyn WSClient + Send + Sync>,
-) -> JoinHandle<()> {
-    std::thread::spawn(move || {
-        for new_symbols in rx {
-            subscribe_with_lock(
-                &exchange,
-                market_type,
-                msg_type,
-                &new_symbols,
-                ws_client.clone(),
-            );
+// A topic of websocket connections
+trait Topic: Clone + Send + 'static {
+    fn subscribe(
+        exchange: &str,
+        market_type: MarketType,
+        topics: &[Self],
+        ws_client: Arc<dyn WSClient + Send + Sync>,
+    );
+}
+
+// (msg_type, symbol)
+impl Topic for (MessageType, String) {
+    fn subscribe(
+        exchange: &str,
+        market_type: MarketType,
+        topics: &[Self],
+        ws_client: Arc<dyn WSClient + Send + Sync>,
+    ) {
+        // one subscription per message type
+        let mut topics = topics;
+        while !topics.is_empty() {
+            let msg_type = topics[0].0;
+            let n = topics.iter().take_while(|t| t.0 == msg_type).count();
+            let symbols: Vec<String> = topics[..n].iter().map(|t| t.1.clone()).collect();
+            subscribe_with_lock(exchange, market_type, msg_type, &symbols, ws_client.clone());
+            topics = &topics[n..];
         }
-    })
+    }
 }
 
-fn create_new_symbol_receiver_thread_candlestick(
+// (symbol, interval)
+impl Topic for (String, usize) {
+    fn subscribe(
+        exchange: &str,
+        market_type: MarketType,
+        topics: &[Self],
+        ws_client: Arc<dyn WSClient + Send + Sync>,
+    ) {
+        subscribe_candlestick_with_lock(exchange, market_type, topics, ws_client);
+    }
+}
+
+// Websocket connections of a crawler, each running on its own thread.
+//
+// New topics fill up the last connection first, then go to new connections,
+// so that no connection subscribes more topics than the exchange allows.
+struct Connections<T: Topic> {
     exchange: String,
     market_type: MarketType,
-    intervals: Vec<usize>,
+    msg_types: Vec<MessageType>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+    // the last connection and the number of its topics
+    last: Option<(Arc<dyn WSClient + Send + Sync>, usize)>,
+    threads: Vec<(Arc<dyn WSClient + Send + Sync>, JoinHandle<()>)>,
+    _topic: PhantomData<T>,
+}
+
+impl<T: Topic> Connections<T> {
+    fn new(
+        exchange: &str,
+        market_type: MarketType,
+        msg_types: Vec<MessageType>,
+        tx: Sender<Message>,
+        duration: Option<u64>,
+    ) -> Self {
+        Connections {
+            exchange: exchange.to_string(),
+            market_type,
+            msg_types,
+            tx,
+            duration,
+            last: None,
+            threads: Vec::new(),
+            _topic: PhantomData,
+        }
+    }
+
+    // Share the last connection, but not the threads of other connections.
+    fn with_last(&self) -> Self {
+        Connections {
+            exchange: self.exchange.clone(),
+            market_type: self.market_type,
+            msg_types: self.msg_types.clone(),
+            tx: self.tx.clone(),
+            duration: self.duration,
+            last: self.last.clone(),
+            threads: Vec::new(),
+            _topic: PhantomData,
+        }
+    }
+
+    fn add(&mut self, mut topics: &[T]) {
+        let capacity = get_num_subscriptions_per_connection(&self.exchange);
+        while !topics.is_empty() {
+            let n = match self.last.as_mut() {
+                Some((ws_client, num_topics)) if *num_topics < capacity => {
+                    let n = std::cmp::min(capacity - *num_topics, topics.len());
+                    T::subscribe(
+                        &self.exchange,
+                        self.market_type,
+                        &topics[..n],
+                        ws_client.clone(),
+                    );
+                    *num_topics += n;
+                    n
+                }
+                _ => {
+                    let n = std::cmp::min(capacity, topics.len());
+                    let ws_client = create_ws_client(
+                        &self.exchange,
+                        self.market_type,
+                        &self.msg_types,
+                        self.tx.clone(),
+                    );
+                    T::subscribe(
+                        &self.exchange,
+                        self.market_type,
+                        &topics[..n],
+                        ws_client.clone(),
+                    );
+                    let thread = {
+                        let ws_client = ws_client.clone();
+                        let duration = self.duration;
+                        stop::spawn(
+                            std::thread::Builder::new().name(format!(
+                                "websocket.{}.{}.{}.{}",
+                                self.exchange,
+                                self.msg_types
+                                    .iter()
+                                    .map(|x| x.to_string())
+                                    .collect::<Vec<String>>()
+                                    .join("+"),
+                                self.market_type,
+                                self.threads.len()
+                            )),
+                            move || {
+                                run_ws_client(ws_client.clone(), duration);
+                                ws_client.close();
+                            },
+                        )
+                    };
+                    self.threads.push((ws_client.clone(), thread));
+                    self.last = Some((ws_client, n));
+                    n
+                }
+            };
+            topics = &topics[n..];
+        }
+    }
+
+    fn close(&self) {
+        for (ws_client, _) in self.threads.iter() {
+            ws_client.close();
+        }
+    }
+
+    fn join(self) {
+        for (_, thread) in self.threads {
+            thread.join().unwrap();
+        }
+    }
+}
+
+// Subscribe new symbols, opening new connections when the last one is full.
+fn create_new_symbol_receiver_thread<T, F>(
     rx: Receiver<Vec<String>>,
-    ws_client: Arc<dyn WSClient + Send + Sync>,
-) -> JoinHandle<()> {
-    std::thread::spawn(move || {
+    to_topics: F,
+    mut connections: Connections<T>,
+) -> JoinHandle<()>
+where
+    T: Topic,
+    F: Fn(&[String]) -> Vec<T> + Send + 'static,
+{
+    stop::spawn(std::thread::Builder::new(), move || {
         for new_symbols in rx {
-            let new_symbol_interval_list = new_symbols
-                .iter()
-                .flat_map(|symbol| {
-                    intervals
-                        .clone()
-                        .into_iter()
-                        .map(move |interval| (symbol.clone(), interval))
-                })
-                .collect::<Vec<(String, usize)>>();
-            subscribe_candlestick_with_lock(
-                &exchange,
-                market_type,
-                new_symbol_interval_list.as_slice(),
-                ws_client.clone(),
-            );
+            connections.add(&to_topics(&new_symbols));
         }
+        // the discovery thread has exited, close connections opened here
+        connections.close();
+        connections.join();
     })
 }
 
@@ -666,7 +837,7 @@ pub(crate) fn create_conversion_thread(
     tx: Sender<Message>,
 ) -> Sender<String> {
     let (tx_raw, rx_raw) = std::sync::mpsc::channel();
-    std::thread::spawn(move || {
+    stop::spawn(std::thread::Builder::new(), move || {
         for json in rx_raw {
             let msg = Message::new(exchange.clone(), market_type, msg_type, json);
             tx.send(msg).unwrap();
@@ -675,6 +846,31 @@ pub(crate) fn create_conversion_thread(
     tx_raw
 }
 
+// create a thread to convert Sender<Message> Sender<String>, telling message
+// types apart by `crypto_msg_parser::get_msg_type()`
+fn create_classifier_thread(
+    exchange: String,
+    msg_types: Vec<MessageType>,
+    market_type: MarketType,
+    tx: Sender<Message>,
+) -> Sender<String> {
+    let (tx_raw, rx_raw) = std::sync::mpsc::channel::<String>();
+    stop::spawn(std::thread::Builder::new(), move || {
+        for json in rx_raw {
+            let msg_type = crypto_msg_parser::get_msg_type(&exchange, &json);
+            // subscription responses, heartbeats, etc.
+            let msg_type = if msg_types.contains(&msg_type) {
+                msg_type
+            } else {
+                msg_types[0]
+            };
+            let msg = Message::new(exchange.clone(), market_type, msg_type, json);
+            tx.send(msg).unwrap();
+        }
+    });
+    tx_raw
+}
+
 // create a thread to call `crypto-msg-parser`
 fn create_parser_thread(
     exchange: String,
@@ -682,35 +878,16 @@ fn create_parser_thread(
     tx: Sender<String>,
 ) -> Sender<String> {
     let (tx_raw, rx_raw) = std::sync::mpsc::channel::<String>();
-    std::thread::spawn(move || {
+    stop::spawn(std::thread::Builder::new(), move || {
         for json in rx_raw {
             let msg_type = crypto_msg_parser::get_msg_type(&exchange, &json);
-            let parsed = match msg_type {
-                MessageType::Trade => serde_json::to_string(
-                    &crypto_msg_parser::parse_trade(&exchange, market_type, &json).unwrap(),
-                )
-                .unwrap(),
-                MessageType::L2Event => {
-                    let received_at = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis()
-                        .try_into()
-                        .unwrap();
-                    serde_json::to_string(
-                        &crypto_msg_parser::parse_l2(
-                            &exchange,
-                            market_type,
-                            &json,
-                            Some(received_at),
-                        )
-                        .unwrap(),
-                    )
-                    .unwrap()
-                }
-                _ => panic!("unknown msg type {}", msg_type),
-            };
-            tx.send(parsed).unwrap();
+            let msg = Message::new(exchange.clone(), market_type, msg_type, json);
+            match parse_message(&msg) {
+                Ok(parsed) => tx.send(serde_json::to_string(&parsed).unwrap()).unwrap(),
+                // subscription responses, heartbeats, etc.
+                Err(ParseError::Unsupported { .. }) => (),
+                Err(err) => error!("{}", err),
+            }
         }
     });
     tx_raw
@@ -724,152 +901,209 @@ pub(crate) fn crawl_event(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
-    let num_topics_per_connection = get_num_subscriptions_per_connection(exchange);
-    let is_empty = match symbols {
-        Some(list) => {
-            if list.is_empty() {
-                true
-            } else {
-                check_args(exchange, market_type, list);
-                false
-            }
-        }
-        None => true,
-    };
-    let automatic_symbol_discovery = is_empty && duration.is_none();
+    crawl_events(
+        exchange,
+        market_type,
+        &[(msg_type, symbols.map(|x| x.to_vec()))],
+        tx,
+        duration,
+    )
+}
 
-    let real_symbols = if is_empty {
-        if exchange == "binance" {
-            fetch_symbols_retry(exchange, market_type)
-                .into_iter()
-                .map(|s| s.to_lowercase())
-                .collect()
-        } else {
-            fetch_symbols_retry(exchange, market_type)
+/// Whether a job can share websocket connections with jobs of other message
+/// types of the same exchange and market type.
+///
+/// Its crawl function must subscribe through `crawl_event()`, and
+/// `crypto_msg_parser::get_msg_type()` must tell its messages apart.
+pub(crate) fn can_share_connection(
+    exchange: &str,
+    market_type: MarketType,
+    msg_type: MessageType,
+    symbols: Option<&[String]>,
+) -> bool {
+    let has_symbols = symbols.map(|x| !x.is_empty()).unwrap_or(false);
+    match exchange {
+        "binance" => {
+            market_type != MarketType::EuropeanOption
+                && (matches!(
+                    msg_type,
+                    MessageType::Trade | MessageType::L2Event | MessageType::L2TopK
+                ) || (matches!(msg_type, MessageType::BBO | MessageType::Ticker)
+                    && has_symbols))
         }
-    } else {
-        symbols.unwrap().to_vec()
-    };
-    if real_symbols.is_empty() {
+        "bitget" => matches!(
+            msg_type,
+            MessageType::Trade | MessageType::L2Event | MessageType::L2TopK
+        ),
+        "bitmex" => {
+            market_type != MarketType::Unknown
+                && matches!(
+                    msg_type,
+                    MessageType::Trade
+                        | MessageType::L2Event
+                        | MessageType::L2TopK
+                        | MessageType::BBO
+                )
+        }
+        "bybit" | "ftx" => matches!(msg_type, MessageType::Trade | MessageType::L2Event),
+        "deribit" => {
+            matches!(
+                msg_type,
+                MessageType::L2Event | MessageType::L2TopK | MessageType::BBO
+            ) || (msg_type == MessageType::Trade && has_symbols)
+        }
+        "huobi" => {
+            matches!(
+                msg_type,
+                MessageType::Trade | MessageType::L2TopK | MessageType::BBO
+            ) || (msg_type == MessageType::L2Event && market_type != MarketType::Spot)
+        }
+        "okex" => {
+            matches!(msg_type, MessageType::L2Event | MessageType::L2TopK)
+                || (msg_type == MessageType::Trade
+                    && (market_type != MarketType::EuropeanOption || has_symbols))
+        }
+        _ => false,
+    }
+}
+
+/// Crawl multiple message types of an exchange and market type over shared
+/// websocket connections, see `can_share_connection()`.
+///
+/// `jobs` are `(msg_type, symbols)` tuples, None or empty symbols mean all
+/// symbols, just like `crawl_event()`.
+pub(crate) fn crawl_events(
+    exchange: &str,
+    market_type: MarketType,
+    jobs: &[(MessageType, Option<Vec<String>>)],
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let mut all_symbols: Option<Vec<String>> = None;
+    let mut topics: Vec<(MessageType, String)> = Vec::new();
+    // message types crawling all symbols, which subscribe new symbols too
+    let mut discovery_msg_types: Vec<MessageType> = Vec::new();
+    for (msg_type, symbols) in jobs.iter() {
+        let symbols = match symbols {
+            Some(list) if !list.is_empty() => {
+                check_args(exchange, market_type, list);
+                if exchange == "binance" {
+                    // All symbols for websocket are lowercase while for REST they are uppercase
+                    list.iter().map(|s| s.to_lowercase()).collect()
+                } else {
+                    list.clone()
+                }
+            }
+            _ => {
+                if duration.is_none() {
+                    discovery_msg_types.push(*msg_type);
+                }
+                all_symbols
+                    .get_or_insert_with(|| {
+                        if exchange == "binance" {
+                            fetch_symbols_retry(exchange, market_type)
+                                .into_iter()
+                                .map(|s| s.to_lowercase())
+                                .collect()
+                        } else {
+                            fetch_symbols_retry(exchange, market_type)
+                        }
+                    })
+                    .clone()
+            }
+        };
+        topics.extend(symbols.into_iter().map(|symbol| (*msg_type, symbol)));
+    }
+    if topics.is_empty() {
         error!("real_symbols is empty due to fetch_symbols_retry() failure");
         return;
     }
 
-    // create a thread to discover new symbols
-    let (tx_symbols, rx_symbols): (Sender<Vec<String>>, Receiver<Vec<String>>) = mpsc::channel();
+    let msg_types: Vec<MessageType> = jobs.iter().map(|job| job.0).collect();
+    let mut connections = Connections::new(exchange, market_type, msg_types, tx, duration);
+    connections.add(&topics);
+
+    // create a thread to discover new symbols, and a thread to subscribe them
     let symbol_discovery_thread_stop = Arc::new(AtomicBool::new(false));
-    let symbol_discovery_thread = if automatic_symbol_discovery {
-        let thread = create_symbol_discovery_thread(
+    let symbol_threads = if discovery_msg_types.is_empty() {
+        None
+    } else {
+        let (tx_symbols, rx_symbols) = mpsc::channel();
+        let symbol_discovery_thread = create_symbol_discovery_thread(
             exchange.to_string(),
             market_type,
             symbol_discovery_thread_stop.clone(),
-            real_symbols.clone(),
+            all_symbols.unwrap_or_default(),
             tx_symbols,
         );
-        Some(thread)
-    } else {
-        None
+        let new_symbol_receiver_thread = create_new_symbol_receiver_thread(
+            rx_symbols,
+            move |symbols| {
+                discovery_msg_types
+                    .iter()
+                    .flat_map(|msg_type| {
+                        symbols
+                            .iter()
+                            .map(move |symbol| (*msg_type, symbol.clone()))
+                    })
+                    .collect()
+            },
+            connections.with_last(),
+        );
+        Some((symbol_discovery_thread, new_symbol_receiver_thread))
     };
 
-    // create a thread to convert Sender<String> to Sender<Message>
-
-    let new_symbol_receiver_thread = if real_symbols.len() <= num_topics_per_connection {
-        let ws_client = create_ws_client(exchange, market_type, msg_type, tx);
-        subscribe_with_lock(
-            exchange,
-            market_type,
-            msg_type,
-            &real_symbols,
-            ws_client.clone(),
-        );
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread(
-                exchange.to_string(),
-                msg_type,
-                market_type,
-                rx_symbols,
-                ws_client.clone(),
-            );
-            Some(thread)
-        } else {
-            None
-        };
-        ws_client.run(duration);
-        ws_client.close();
-        new_symbol_receiver_thread
-    } else {
-        // split to chunks
-        let mut chunks: Vec<Vec<String>> = Vec::new();
-        for i in (0..real_symbols.len()).step_by(num_topics_per_connection) {
-            let chunk = (&real_symbols
-                [i..(std::cmp::min(i + num_topics_per_connection, real_symbols.len()))])
-                .to_vec();
-            chunks.push(chunk);
-        }
-        assert!(chunks.len() > 1);
-        let n = chunks.len();
+    connections.join();
+    symbol_discovery_thread_stop.store(true, Ordering::Release);
+    if let Some((symbol_discovery_thread, new_symbol_receiver_thread)) = symbol_threads {
+        symbol_discovery_thread.join().unwrap();
+        new_symbol_receiver_thread.join().unwrap();
+    }
+}
 
-        let last_ws_client = create_ws_client(exchange, market_type, msg_type, tx.clone());
-        let mut join_handles: Vec<std::thread::JoinHandle<()>> = Vec::new();
-        for (index, chunk) in chunks.into_iter().enumerate() {
-            let exchange_clone = exchange.to_string();
-            let tx_clone = tx.clone();
-            let last_ws_client_clone = last_ws_client.clone();
-            let handle = std::thread::Builder::new()
-                .name(format!(
-                    "websocket.{}.{}.{}.{}",
-                    exchange, msg_type, market_type, index
-                ))
-                .spawn(move || {
-                    let exchange: &str = exchange_clone.as_str();
-                    if index == n - 1 {
-                        subscribe_with_lock(
-                            exchange,
-                            market_type,
-                            msg_type,
-                            &chunk,
-                            last_ws_client_clone.clone(),
-                        );
-                        last_ws_client_clone.run(duration);
-                        last_ws_client_clone.close();
-                    } else {
-                        let ws_client = create_ws_client(exchange, market_type, msg_type, tx_clone);
-                        subscribe_with_lock(
-                            exchange,
-                            market_type,
-                            msg_type,
-                            &chunk,
-                            ws_client.clone(),
-                        );
-                        ws_client.run(duration);
-                        ws_client.close();
-                    }
-                })
-                .unwrap();
-            join_handles.push(handle);
-        }
-        drop(tx);
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread(
-                exchange.to_string(),
-                msg_type,
+/// Crawl raw channels, which are sent to `WSClient::subscribe()` directly.
+pub(crate) fn crawl_channels(
+    exchange: &str,
+    market_type: MarketType,
+    raw_channels: &[String],
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    if raw_channels.is_empty() {
+        error!("raw_channels is empty");
+        return;
+    }
+    let num_topics_per_connection = get_num_subscriptions_per_connection(exchange);
+    let mut join_handles: Vec<JoinHandle<()>> = Vec::new();
+    for (index, chunk) in raw_channels.chunks(num_topics_per_connection).enumerate() {
+        let ws_client = create_ws_client(exchange, market_type, &[MessageType::Other], tx.clone());
+        let exchange_clone = exchange.to_string();
+        let chunk = chunk.to_vec();
+        let handle = stop::spawn(
+            std::thread::Builder::new().name(format!(
+                "websocket.{}.{}.{}.{}",
+                exchange,
+                MessageType::Other,
                 market_type,
-                rx_symbols,
-                last_ws_client,
-            );
-            Some(thread)
-        } else {
-            None
-        };
-        for handle in join_handles {
-            handle.join().unwrap();
-        }
-        new_symbol_receiver_thread
-    };
-    symbol_discovery_thread_stop.store(true, Ordering::Release);
-    if let Some(thread) = symbol_discovery_thread {
-        thread.join().unwrap();
-        new_symbol_receiver_thread.unwrap().join().unwrap();
+                index
+            )),
+            move || {
+                subscribe_with_lock(
+                    &exchange_clone,
+                    market_type,
+                    MessageType::Other,
+                    &chunk,
+                    ws_client.clone(),
+                );
+                run_ws_client(ws_client.clone(), duration);
+                ws_client.close();
+            },
+        );
+        join_handles.push(handle);
+    }
+    drop(tx);
+    for handle in join_handles {
+        handle.join().unwrap();
     }
 }
 
@@ -900,7 +1134,6 @@ pub(crate) fn crawl_candlestick_ext(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
-    let num_topics_per_connection = get_num_subscriptions_per_connection(exchange);
     let is_empty = match symbol_interval_list {
         Some(list) => {
             if list.is_empty() {
@@ -942,113 +1175,51 @@ pub(crate) fn crawl_candlestick_ext(
         return;
     }
     let real_symbols: Vec<String> = symbol_interval_list.iter().map(|t| t.0.clone()).collect();
-    let real_intervals: Vec<usize> = symbol_interval_list.iter().map(|t| t.1).collect();
 
-    // create a thread to discover new symbols
-    let (tx_symbols, rx_symbols): (Sender<Vec<String>>, Receiver<Vec<String>>) = mpsc::channel();
+    let mut connections = Connections::new(
+        exchange,
+        market_type,
+        vec![MessageType::Candlestick],
+        tx,
+        duration,
+    );
+    connections.add(&symbol_interval_list);
+
+    // create a thread to discover new symbols, and a thread to subscribe them
     let symbol_discovery_thread_stop = Arc::new(AtomicBool::new(false));
-    let symbol_discovery_thread = if automatic_symbol_discovery {
-        let thread = create_symbol_discovery_thread(
+    let symbol_threads = if automatic_symbol_discovery {
+        let (tx_symbols, rx_symbols) = mpsc::channel();
+        let symbol_discovery_thread = create_symbol_discovery_thread(
             exchange.to_string(),
             market_type,
             symbol_discovery_thread_stop.clone(),
             real_symbols,
             tx_symbols,
         );
-        Some(thread)
+        let intervals = get_candlestick_intervals(exchange, market_type);
+        let new_symbol_receiver_thread = create_new_symbol_receiver_thread(
+            rx_symbols,
+            move |symbols| {
+                symbols
+                    .iter()
+                    .flat_map(|symbol| {
+                        intervals
+                            .iter()
+                            .map(move |interval| (symbol.clone(), *interval))
+                    })
+                    .collect()
+            },
+            connections.with_last(),
+        );
+        Some((symbol_discovery_thread, new_symbol_receiver_thread))
     } else {
         None
     };
 
-    let new_symbol_receiver_thread = if symbol_interval_list.len() <= num_topics_per_connection {
-        let ws_client = create_ws_client(exchange, market_type, MessageType::Candlestick, tx);
-        subscribe_candlestick_with_lock(
-            exchange,
-            market_type,
-            symbol_interval_list.as_slice(),
-            ws_client.clone(),
-        );
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread_candlestick(
-                exchange.to_string(),
-                market_type,
-                real_intervals,
-                rx_symbols,
-                ws_client.clone(),
-            );
-            Some(thread)
-        } else {
-            None
-        };
-        ws_client.run(duration);
-        ws_client.close();
-        new_symbol_receiver_thread
-    } else {
-        // split to chunks
-        let mut chunks: Vec<Vec<(String, usize)>> = Vec::new();
-        for i in (0..symbol_interval_list.len()).step_by(num_topics_per_connection) {
-            let chunk: Vec<(String, usize)> = (&symbol_interval_list
-                [i..(std::cmp::min(i + num_topics_per_connection, symbol_interval_list.len()))])
-                .to_vec();
-            chunks.push(chunk);
-        }
-        assert!(chunks.len() > 1);
-        let n = chunks.len();
-
-        let last_ws_client =
-            create_ws_client(exchange, market_type, MessageType::Candlestick, tx.clone());
-        let mut join_handles: Vec<std::thread::JoinHandle<()>> = Vec::new();
-        for (index, chunk) in chunks.into_iter().enumerate() {
-            let exchange_clone = exchange.to_string();
-            let tx_clone = tx.clone();
-            let last_ws_client_clone = last_ws_client.clone();
-            let handle = std::thread::spawn(move || {
-                let exchange: &str = exchange_clone.as_str();
-                if index == n - 1 {
-                    subscribe_candlestick_with_lock(
-                        exchange,
-                        market_type,
-                        chunk.as_slice(),
-                        last_ws_client_clone.clone(),
-                    );
-                    last_ws_client_clone.run(duration);
-                    last_ws_client_clone.close();
-                } else {
-                    let ws_client =
-                        create_ws_client(exchange, market_type, MessageType::Candlestick, tx_clone);
-                    subscribe_candlestick_with_lock(
-                        exchange,
-                        market_type,
-                        chunk.as_slice(),
-                        ws_client.clone(),
-                    );
-                    ws_client.run(duration);
-                    ws_client.close();
-                }
-            });
-            join_handles.push(handle);
-        }
-        drop(tx);
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread_candlestick(
-                exchange.to_string(),
-                market_type,
-                real_intervals,
-                rx_symbols,
-                last_ws_client,
-            );
-            Some(thread)
-        } else {
-            None
-        };
-        for handle in join_handles {
-            handle.join().unwrap();
-        }
-        new_symbol_receiver_thread
-    };
+    connections.join();
     symbol_discovery_thread_stop.store(true, Ordering::Release);
-    if let Some(thread) = symbol_discovery_thread {
-        thread.join().unwrap();
-        new_symbol_receiver_thread.unwrap().join().unwrap();
+    if let Some((symbol_discovery_thread, new_symbol_receiver_thread)) = symbol_threads {
+        symbol_discovery_thread.join().unwrap();
+        new_symbol_receiver_thread.join().unwrap();
     }
 }
@@ -159,17 +159,92 @@
 //! // Crawl funding rates for all symbols of binance COIN-margined perpetual markets, only run for 5 seconds
 //! crawl_funding_rate("binance", MarketType::InverseSwap, None, tx, Some(5));
 //! ```
+//!
+//! ## Crawl liquidation orders
+//!
+//! ```rust
+//! use crypto_crawler::{crawl_liquidation, MarketType, Message};
+//!
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! std::thread::spawn(move || {
+//!     for msg in rx {
+//!         println!("{}", msg);
+//!     }
+//! });
+//!
+//! // Crawl liquidation orders for all symbols of binance COIN-margined markets, only run for 5 seconds
+//! crawl_liquidation("binance", MarketType::InverseSwap, None, tx, Some(5));
+//! ```
+//!
+//! ## Crawl mark prices
+//!
+//! ```rust
+//! use crypto_crawler::{crawl_mark_price, MarketType, Message};
+//!
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! std::thread::spawn(move || {
+//!     for msg in rx {
+//!         println!("{}", msg);
+//!     }
+//! });
+//!
+//! // Crawl mark prices for all symbols of binance USDT-margined markets, only run for 5 seconds
+//! crawl_mark_price("binance", MarketType::LinearSwap, None, tx, Some(5));
+//! ```
+//!
+//! ## Crawl raw websocket channels
+//!
+//! ```rust
+//! use crypto_crawler::{crawl_channels, MarketType, Message};
+//!
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! std::thread::spawn(move || {
+//!     for msg in rx {
+//!         println!("{}", msg);
+//!     }
+//! });
+//!
+//! // Crawl BitMEX announcements, only run for 5 seconds
+//! let channels = vec!["announcement".to_string()];
+//! crawl_channels("bitmex", MarketType::Unknown, &channels, tx, Some(5));
+//! ```
+//!
+//! ## Crawl normalized messages
+//!
+//! ```rust
+//! use crypto_crawler::{crawl_trade, create_parser_thread, MarketType};
+//!
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! std::thread::spawn(move || {
+//!     for result in rx {
+//!         match result {
+//!             Ok(parsed) => println!("{}", serde_json::to_string(&parsed).unwrap()),
+//!             Err(err) => eprintln!("{}", err),
+//!         }
+//!     }
+//! });
+//!
+//! // Crawl realtime trades and parse them into `TradeMsg`, only run for 5 seconds
+//! let tx_raw = create_parser_thread(tx);
+//! crawl_trade("binance", MarketType::InverseSwap, None, tx_raw, Some(5));
+//! ```
 mod crawlers;
 mod msg;
+mod parser;
+mod session;
+mod stop;
 mod utils;
 
 use std::sync::mpsc::Sender;
 
-pub use crawlers::fetch_symbols_retry;
+pub use crawlers::{fetch_symbols_retry, set_cooldown_time_per_request, L2BookError};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 pub use msg::*;
-pub use utils::get_hot_spot_symbols;
+pub use parser::{create_parser_thread, parse_message, ParseError, ParsedMessage};
+pub use session::{CrawlJob, CrawlerSession, JobState, JobStatus};
+pub use stop::{spawn_crawler, CrawlerHandle};
+pub use utils::{get_hot_spot_symbols, sort_by_cmc_rank};
 
 /// Crawl realtime trades.
 ///
@@ -231,6 +306,42 @@ pub fn crawl_l2_event(
     }
 }
 
+/// Crawl level2 orderbook snapshots and update events aligned by sequence IDs.
+///
+/// Update events are buffered until a snapshot is fetched through RESTful APIs,
+/// then events already included in the snapshot are dropped. A new snapshot
+/// is fetched whenever a gap is found, so each `L2Snapshot` message resets the
+/// orderbook of its symbol and the following `L2Event` messages can be applied
+/// one by one.
+///
+/// Only Binance and KuCoin are supported, whose websocket channels send
+/// nothing but update events, an `L2BookError` is returned for other exchanges.
+///
+/// Coinbase Pro isn't supported though its update events need a snapshot too,
+/// because its `level2` channel carries no sequence IDs to align with a RESTful
+/// snapshot. Instead, the channel sends a snapshot first by itself, so just
+/// use `crawl_l2_event()`.
+pub fn crawl_l2_book(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) -> Result<(), L2BookError> {
+    match exchange {
+        "binance" | "kucoin" => {
+            crawlers::l2_book::crawl_l2_book(exchange, market_type, symbols, tx, duration);
+            Ok(())
+        }
+        "coinbase_pro" => Err(L2BookError::SnapshotInChannel {
+            exchange: exchange.to_string(),
+        }),
+        _ => Err(L2BookError::Unsupported {
+            exchange: exchange.to_string(),
+        }),
+    }
+}
+
 /// Crawl level3 orderbook update events.
 pub fn crawl_l3_event(
     exchange: &str,
@@ -390,6 +501,129 @@ pub fn crawl_funding_rate(
     func(market_type, symbols, tx, duration);
 }
 
+/// Crawl mark prices.
+///
+/// Some exchanges push index prices along with mark prices, see
+/// `crypto_msg_parser::MarkPriceMsg`.
+pub fn crawl_mark_price(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    match exchange {
+        "binance" => crawlers::binance::crawl_mark_price(market_type, symbols, tx, duration),
+        "bitmex" => crawlers::bitmex::crawl_mark_price(market_type, symbols, tx, duration),
+        "deribit" | "okex" => crawlers::crawl_event(
+            exchange,
+            MessageType::MarkPrice,
+            market_type,
+            symbols,
+            tx,
+            duration,
+        ),
+        _ => panic!(
+            "{} does NOT have the mark price websocket channel",
+            exchange
+        ),
+    }
+}
+
+/// Crawl index prices.
+///
+/// `symbols` are contract symbols, each of them is mapped to its underlying index.
+pub fn crawl_index_price(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let func = match exchange {
+        "okex" => crawlers::okex::crawl_index_price,
+        _ => panic!(
+            "{} does NOT have the index price websocket channel",
+            exchange
+        ),
+    };
+    func(market_type, symbols, tx, duration);
+}
+
+/// Crawl liquidation orders.
+pub fn crawl_liquidation(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let func = match exchange {
+        "binance" => crawlers::binance::crawl_liquidation,
+        "bitmex" => crawlers::bitmex::crawl_liquidation,
+        "bybit" => crawlers::bybit::crawl_liquidation,
+        _ => panic!(
+            "{} does NOT have the liquidation websocket channel",
+            exchange
+        ),
+    };
+    func(market_type, symbols, tx, duration);
+}
+
+/// Crawl insurance fund balances.
+pub fn crawl_insurance_fund(
+    exchange: &str,
+    market_type: MarketType,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let func = match exchange {
+        "bitmex" => crawlers::bitmex::crawl_insurance_fund,
+        "bybit" => crawlers::bybit::crawl_insurance_fund,
+        _ => panic!(
+            "{} does NOT have the insurance fund websocket channel",
+            exchange
+        ),
+    };
+    func(market_type, tx, duration);
+}
+
+/// Crawl settlement and delivery prices of futures.
+pub fn crawl_settlement(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let func = match exchange {
+        "bitmex" => crawlers::bitmex::crawl_settlement,
+        _ => panic!(
+            "{} does NOT have the settlement websocket channel",
+            exchange
+        ),
+    };
+    func(market_type, symbols, tx, duration);
+}
+
+/// Crawl instrument status changes.
+pub fn crawl_instrument_status(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let func = match exchange {
+        "bitmex" => crawlers::bitmex::crawl_instrument_status,
+        _ => panic!(
+            "{} does NOT have the instrument websocket channel",
+            exchange
+        ),
+    };
+    func(market_type, symbols, tx, duration);
+}
+
 /// Crawl candlestick(i.e., OHLCV) data.
 ///
 /// If `symbol_interval_list` is None or empty, this API will crawl candlesticks from
@@ -433,6 +667,44 @@ pub fn crawl_open_interest(
     crawlers::crawl_open_interest(exchange, market_type, tx, duration)
 }
 
+/// Crawl raw websocket channels.
+///
+/// `raw_channels` are exchange-specific and passed to `WSClient::subscribe()`
+/// directly, see the low level APIs of `crypto-ws-client`. Messages are
+/// tagged with `MessageType::Other`.
+pub fn crawl_channels(
+    exchange: &str,
+    market_type: MarketType,
+    raw_channels: &[String],
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    crawlers::crawl_channels(exchange, market_type, raw_channels, tx, duration)
+}
+
+/// Crawl miscellaneous channels which are not covered by other APIs.
+///
+/// * BitMEX `announcement`, `connected` and `publicNotifications`
+/// * CoinbasePro `status`
+/// * Huobi `market.overview`
+pub fn crawl_other(
+    exchange: &str,
+    market_type: MarketType,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let raw_channels: Vec<String> = match exchange {
+        "bitmex" => vec!["announcement", "connected", "publicNotifications"],
+        "coinbase_pro" => vec![r#"{"type": "subscribe","channels":[{ "name": "status"}]}"#],
+        "huobi" => vec!["market.overview"],
+        _ => panic!("{} does NOT have miscellaneous channels", exchange),
+    }
+    .into_iter()
+    .map(|x| x.to_string())
+    .collect();
+    crawl_channels(exchange, market_type, &raw_channels, tx, duration)
+}
+
 /// Subscribe to multiple message types of one symbol.
 ///
 /// This API is suitable for client applications such as APP, website, etc.
@@ -450,5 +722,5 @@ pub fn subscribe_symbol(
     let symbols = vec![symbol.to_string()];
     let commands = crypto_msg_type::get_ws_commands(exchange, msg_types, &symbols, true, None);
     ws_client.subscribe(&commands);
-    ws_client.run(duration);
+    stop::run_ws_client(ws_client, duration);
 }
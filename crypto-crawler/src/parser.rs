@@ -0,0 +1,193 @@
+use std::{fmt, sync::mpsc::Sender};
+
+use crypto_msg_parser::{
+    FundingRateMsg, InstrumentStatusMsg, InsuranceFundMsg, LiquidationMsg, MarkPriceMsg,
+    OrderBookMsg, SettlementMsg, TradeMsg,
+};
+use crypto_msg_type::MessageType;
+use serde::Serialize;
+
+use crate::Message;
+
+/// Normalized messages parsed by `crypto-msg-parser`.
+///
+/// It is serialized as the inner list of messages.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ParsedMessage {
+    Trade(Vec<TradeMsg>),
+    /// Level2 orderbook updates
+    L2Event(Vec<OrderBookMsg>),
+    FundingRate(Vec<FundingRateMsg>),
+    /// Both mark prices and index prices, see `MarkPriceMsg.msg_type`
+    MarkPrice(Vec<MarkPriceMsg>),
+    Liquidation(Vec<LiquidationMsg>),
+    InsuranceFund(Vec<InsuranceFundMsg>),
+    Settlement(Vec<SettlementMsg>),
+    InstrumentStatus(Vec<InstrumentStatusMsg>),
+}
+
+/// The error returned when a raw message can't be normalized.
+#[derive(Debug)]
+pub enum ParseError {
+    /// `crypto-msg-parser` doesn't support this message type
+    Unsupported {
+        exchange: String,
+        msg_type: MessageType,
+    },
+    /// `crypto-msg-parser` failed to parse the message
+    Failed {
+        exchange: String,
+        msg_type: MessageType,
+        reason: String,
+        /// the original message
+        json: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Unsupported { exchange, msg_type } => {
+                write!(f, "Parsing {} {} is not supported", exchange, msg_type)
+            }
+            ParseError::Failed {
+                exchange,
+                msg_type,
+                reason,
+                json,
+            } => write!(
+                f,
+                "Failed to parse {} {}: {}, {}",
+                exchange, msg_type, reason, json
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a raw message into normalized messages.
+pub fn parse_message(msg: &Message) -> Result<ParsedMessage, ParseError> {
+    let exchange = msg.exchange.as_str();
+    let market_type = msg.market_type;
+    let json = msg.json.as_str();
+    let received_at = Some(msg.received_at as i64);
+    let result = match msg.msg_type {
+        MessageType::Trade => {
+            crypto_msg_parser::parse_trade(exchange, market_type, json).map(ParsedMessage::Trade)
+        }
+        MessageType::L2Event => {
+            crypto_msg_parser::parse_l2(exchange, market_type, json, received_at)
+                .map(ParsedMessage::L2Event)
+        }
+        MessageType::FundingRate => {
+            crypto_msg_parser::parse_funding_rate(exchange, market_type, json)
+                .map(ParsedMessage::FundingRate)
+        }
+        MessageType::MarkPrice | MessageType::IndexPrice => {
+            crypto_msg_parser::parse_mark_price(exchange, market_type, json, received_at)
+                .map(ParsedMessage::MarkPrice)
+        }
+        MessageType::Liquidation => {
+            crypto_msg_parser::parse_liquidation(exchange, market_type, json, received_at)
+                .map(ParsedMessage::Liquidation)
+        }
+        MessageType::InsuranceFund => {
+            crypto_msg_parser::parse_insurance_fund(exchange, market_type, json)
+                .map(ParsedMessage::InsuranceFund)
+        }
+        MessageType::Settlement => crypto_msg_parser::parse_settlement(exchange, market_type, json)
+            .map(ParsedMessage::Settlement),
+        MessageType::InstrumentStatus => {
+            crypto_msg_parser::parse_instrument_status(exchange, market_type, json, received_at)
+                .map(ParsedMessage::InstrumentStatus)
+        }
+        _ => {
+            return Err(ParseError::Unsupported {
+                exchange: msg.exchange.clone(),
+                msg_type: msg.msg_type,
+            })
+        }
+    };
+    result.map_err(|err| ParseError::Failed {
+        exchange: msg.exchange.clone(),
+        msg_type: msg.msg_type,
+        reason: err.to_string(),
+        json: msg.json.clone(),
+    })
+}
+
+/// Create a thread to parse raw messages, and return a sender for raw messages.
+///
+/// Pass the returned sender to any `crawl_*` function to receive normalized
+/// messages in `tx` instead of raw messages. The thread exits once all
+/// senders of raw messages are dropped.
+pub fn create_parser_thread(tx: Sender<Result<ParsedMessage, ParseError>>) -> Sender<Message> {
+    let (tx_raw, rx_raw) = std::sync::mpsc::channel::<Message>();
+    std::thread::spawn(move || {
+        for msg in rx_raw {
+            if tx.send(parse_message(&msg)).is_err() {
+                break; // the receiver has been dropped
+            }
+        }
+    });
+    tx_raw
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_message, ParseError, ParsedMessage};
+    use crate::Message;
+    use crypto_market_type::MarketType;
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn trade() {
+        let raw_msg = r#"{"stream":"btcusdt@aggTrade","data":{"e":"aggTrade","E":1616176861895,"s":"BTCUSDT","a":640283266,"p":"58942.01000000","q":"0.00035600","f":716849523,"l":716849523,"T":1616176861893,"m":false,"M":true}}"#;
+        let msg = Message::new(
+            "binance".to_string(),
+            MarketType::Spot,
+            MessageType::Trade,
+            raw_msg.to_string(),
+        );
+        match parse_message(&msg).unwrap() {
+            ParsedMessage::Trade(trades) => {
+                assert_eq!(1, trades.len());
+                assert_eq!("BTC/USDT", trades[0].pair);
+            }
+            _ => panic!("Expected trades"),
+        }
+    }
+
+    #[test]
+    fn unsupported() {
+        let msg = Message::new(
+            "bitmex".to_string(),
+            MarketType::Unknown,
+            MessageType::Other,
+            r#"{"info":"Welcome to the BitMEX Realtime API."}"#.to_string(),
+        );
+        assert!(matches!(
+            parse_message(&msg),
+            Err(ParseError::Unsupported {
+                msg_type: MessageType::Other,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn failed() {
+        let msg = Message::new(
+            "binance".to_string(),
+            MarketType::InverseSwap,
+            MessageType::Trade,
+            "not json".to_string(),
+        );
+        match parse_message(&msg) {
+            Err(ParseError::Failed { json, .. }) => assert_eq!("not json", json),
+            _ => panic!("Expected a parse failure"),
+        }
+    }
+}
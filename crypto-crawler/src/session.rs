@@ -0,0 +1,399 @@
+use std::{
+    panic::AssertUnwindSafe,
+    sync::{mpsc::Sender, Arc, Mutex},
+    thread::JoinHandle,
+};
+
+use crypto_market_type::MarketType;
+use crypto_msg_type::MessageType;
+use log::*;
+
+use crate::{
+    stop::{spawn_crawler_with, CrawlerHandle},
+    Message,
+};
+
+/// A crawl job, i.e., one `<exchange> <market_type> <msg_type>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrawlJob {
+    pub exchange: String,
+    pub market_type: MarketType,
+    pub msg_type: MessageType,
+    /// None means all symbols, and new symbols are discovered every hour.
+    ///
+    /// Ignored by `Candlestick`, `OpenInterest`, `InsuranceFund` and `Other`.
+    pub symbols: Option<Vec<String>>,
+}
+
+impl std::fmt::Display for CrawlJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.exchange, self.market_type, self.msg_type
+        )
+    }
+}
+
+/// The state of a crawl job.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JobState {
+    Running,
+    /// The crawl function returned after `duration` seconds
+    Finished,
+    /// Stopped by `CrawlerSession::stop()`
+    Stopped,
+    /// The crawl function panicked, with the panic message
+    Failed(String),
+}
+
+/// The status of a crawl job.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobStatus {
+    pub state: JobState,
+    /// Number of messages received so far
+    pub messages: u64,
+    /// Unix timestamp in milliseconds of the last message
+    pub last_received_at: Option<u64>,
+}
+
+// Dispatch a job to the crawl_* function of its message type.
+fn run_job(job: &CrawlJob, tx: Sender<Message>, duration: Option<u64>) {
+    let exchange = job.exchange.as_str();
+    let market_type = job.market_type;
+    let symbols = job.symbols.as_deref();
+    match job.msg_type {
+        MessageType::Candlestick => {
+            crate::crawl_candlestick(exchange, market_type, None, tx, duration)
+        }
+        MessageType::OpenInterest => {
+            crate::crawl_open_interest(exchange, market_type, tx, duration)
+        }
+        MessageType::InsuranceFund => {
+            crate::crawl_insurance_fund(exchange, market_type, tx, duration)
+        }
+        MessageType::Other => crate::crawl_other(exchange, market_type, tx, duration),
+        msg_type => {
+            let crawl_func = match msg_type {
+                MessageType::BBO => crate::crawl_bbo,
+                MessageType::Trade => crate::crawl_trade,
+                MessageType::L2Event => crate::crawl_l2_event,
+                MessageType::L3Event => crate::crawl_l3_event,
+                MessageType::L2Snapshot => crate::crawl_l2_snapshot,
+                MessageType::L2TopK => crate::crawl_l2_topk,
+                MessageType::L3Snapshot => crate::crawl_l3_snapshot,
+                MessageType::Ticker => crate::crawl_ticker,
+                MessageType::FundingRate => crate::crawl_funding_rate,
+                MessageType::MarkPrice => crate::crawl_mark_price,
+                MessageType::IndexPrice => crate::crawl_index_price,
+                MessageType::Liquidation => crate::crawl_liquidation,
+                MessageType::Settlement => crate::crawl_settlement,
+                MessageType::InstrumentStatus => crate::crawl_instrument_status,
+                _ => panic!("Crawling {} is not implemented", msg_type),
+            };
+            crawl_func(exchange, market_type, symbols, tx, duration)
+        }
+    }
+}
+
+fn panic_message(err: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = err.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = err.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Run multiple crawl jobs in one process.
+///
+/// Jobs of the same exchange and market type share websocket connections and
+/// threads, as long as their messages can be told apart by
+/// `crypto_msg_parser::get_msg_type()`, e.g., trades and level2 events of
+/// Binance spot markets go through the same connections. Other jobs run on
+/// their own threads and connections, just like calling their `crawl_*`
+/// functions directly. Messages of all jobs are counted and forwarded to the
+/// same `tx` by one thread, and all jobs can be stopped together.
+///
+/// ```rust
+/// use crypto_crawler::{CrawlJob, CrawlerSession};
+/// use crypto_market_type::MarketType;
+/// use crypto_msg_type::MessageType;
+///
+/// let (tx, rx) = std::sync::mpsc::channel();
+/// std::thread::spawn(move || {
+///     for msg in rx {
+///         println!("{}", msg);
+///     }
+/// });
+///
+/// let jobs = vec![
+///     CrawlJob {
+///         exchange: "binance".to_string(),
+///         market_type: MarketType::InverseSwap,
+///         msg_type: MessageType::Trade,
+///         symbols: None,
+///     },
+///     CrawlJob {
+///         exchange: "bitmex".to_string(),
+///         market_type: MarketType::InverseSwap,
+///         msg_type: MessageType::Trade,
+///         symbols: Some(vec!["XBTUSD".to_string()]),
+///     },
+/// ];
+/// // only run for 5 seconds
+/// let session = CrawlerSession::start(jobs, tx, Some(5));
+/// for status in session.join() {
+///     println!("{:?}", status);
+/// }
+/// ```
+pub struct CrawlerSession {
+    jobs: Vec<CrawlJob>,
+    statuses: Arc<Mutex<Vec<JobStatus>>>,
+    handles: Vec<CrawlerHandle>,
+    forward_thread: JoinHandle<()>,
+}
+
+// Jobs crawled together, which share websocket connections if there are more than one
+struct JobGroup {
+    exchange: String,
+    market_type: MarketType,
+    // indices into jobs
+    indices: Vec<usize>,
+}
+
+// Group jobs which can share websocket connections by exchange and market type.
+fn group_jobs(jobs: &[CrawlJob]) -> Vec<JobGroup> {
+    let mut groups: Vec<JobGroup> = Vec::new();
+    for (i, job) in jobs.iter().enumerate() {
+        let shared = crate::crawlers::can_share_connection(
+            &job.exchange,
+            job.market_type,
+            job.msg_type,
+            job.symbols.as_deref(),
+        );
+        let group = if shared {
+            groups.iter_mut().find(|group| {
+                let first = &jobs[group.indices[0]];
+                group.exchange == job.exchange
+                    && group.market_type == job.market_type
+                    && crate::crawlers::can_share_connection(
+                        &first.exchange,
+                        first.market_type,
+                        first.msg_type,
+                        first.symbols.as_deref(),
+                    )
+            })
+        } else {
+            None
+        };
+        match group {
+            Some(group) => group.indices.push(i),
+            None => groups.push(JobGroup {
+                exchange: job.exchange.clone(),
+                market_type: job.market_type,
+                indices: vec![i],
+            }),
+        }
+    }
+    groups
+}
+
+// Run a group of jobs, a single job goes to its crawl_* function.
+fn run_group(jobs: &[CrawlJob], tx: Sender<Message>, duration: Option<u64>) {
+    if jobs.len() == 1 {
+        run_job(&jobs[0], tx, duration)
+    } else {
+        let subscriptions: Vec<(MessageType, Option<Vec<String>>)> = jobs
+            .iter()
+            .map(|job| (job.msg_type, job.symbols.clone()))
+            .collect();
+        crate::crawlers::crawl_events(
+            &jobs[0].exchange,
+            jobs[0].market_type,
+            &subscriptions,
+            tx,
+            duration,
+        )
+    }
+}
+
+impl CrawlerSession {
+    /// Start all jobs, only run for `duration` seconds if it is not None.
+    pub fn start(jobs: Vec<CrawlJob>, tx: Sender<Message>, duration: Option<u64>) -> Self {
+        let statuses = Arc::new(Mutex::new(vec![
+            JobStatus {
+                state: JobState::Running,
+                messages: 0,
+                last_received_at: None,
+            };
+            jobs.len()
+        ]));
+
+        // Messages are counted on the first job of their exchange, market type and message type
+        let job_keys: Vec<(String, MarketType, MessageType)> = jobs
+            .iter()
+            .map(|job| (job.exchange.clone(), job.market_type, job.msg_type))
+            .collect();
+        let (tx_jobs, rx_jobs) = std::sync::mpsc::channel::<Message>();
+        let forward_thread = {
+            let statuses = statuses.clone();
+            std::thread::Builder::new()
+                .name("session.forward".to_string())
+                .spawn(move || {
+                    for msg in rx_jobs {
+                        let i = job_keys
+                            .iter()
+                            .position(|(exchange, market_type, msg_type)| {
+                                *exchange == msg.exchange
+                                    && *market_type == msg.market_type
+                                    && *msg_type == msg.msg_type
+                            });
+                        if let Some(i) = i {
+                            let status = &mut statuses.lock().unwrap()[i];
+                            status.messages += 1;
+                            status.last_received_at = Some(msg.received_at);
+                        }
+                        if tx.send(msg).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .unwrap()
+        };
+
+        let handles = group_jobs(&jobs)
+            .into_iter()
+            .map(|group| {
+                let group_jobs: Vec<CrawlJob> =
+                    group.indices.iter().map(|i| jobs[*i].clone()).collect();
+                let name = group_jobs
+                    .iter()
+                    .map(|job| job.msg_type.to_string())
+                    .collect::<Vec<String>>()
+                    .join("+");
+                let builder = std::thread::Builder::new().name(format!(
+                    "session.{}.{}.{}",
+                    group.exchange, group.market_type, name
+                ));
+                let tx_jobs = tx_jobs.clone();
+                let statuses = statuses.clone();
+                spawn_crawler_with(builder, move || {
+                    for job in group_jobs.iter() {
+                        info!("Started {}", job);
+                    }
+                    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                        run_group(&group_jobs, tx_jobs, duration)
+                    }));
+                    let state = match result {
+                        Ok(()) if crate::stop::is_stopped() => JobState::Stopped,
+                        Ok(()) => JobState::Finished,
+                        Err(err) => JobState::Failed(panic_message(err.as_ref())),
+                    };
+                    let mut statuses = statuses.lock().unwrap();
+                    for (i, job) in group.indices.iter().zip(group_jobs.iter()) {
+                        if let JobState::Failed(reason) = &state {
+                            error!("{} failed, error: {}", job, reason);
+                        }
+                        info!("{} exited, {:?}", job, state);
+                        statuses[*i].state = state.clone();
+                    }
+                })
+            })
+            .collect();
+        drop(tx_jobs);
+
+        CrawlerSession {
+            jobs,
+            statuses,
+            handles,
+            forward_thread,
+        }
+    }
+
+    /// The jobs of this session.
+    pub fn jobs(&self) -> &[CrawlJob] {
+        &self.jobs
+    }
+
+    /// The current status of each job, in the same order as `jobs()`.
+    pub fn statuses(&self) -> Vec<JobStatus> {
+        self.statuses.lock().unwrap().clone()
+    }
+
+    /// Stop all jobs, call `join()` to wait for them.
+    pub fn stop(&self) {
+        for handle in self.handles.iter() {
+            handle.stop();
+        }
+    }
+
+    /// Whether all jobs have exited.
+    pub fn is_finished(&self) -> bool {
+        self.handles.iter().all(|handle| handle.is_finished())
+    }
+
+    /// Wait for all jobs and return their final status.
+    ///
+    /// `tx` is dropped after all messages are forwarded.
+    pub fn join(self) -> Vec<JobStatus> {
+        for handle in self.handles {
+            // panics have been caught in the job thread
+            handle.join().unwrap();
+        }
+        self.forward_thread.join().unwrap();
+        let statuses = self.statuses.lock().unwrap().clone();
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{group_jobs, CrawlJob, CrawlerSession, JobState};
+    use crypto_market_type::MarketType;
+    use crypto_msg_type::MessageType;
+
+    fn job(exchange: &str, market_type: MarketType, msg_type: MessageType) -> CrawlJob {
+        CrawlJob {
+            exchange: exchange.to_string(),
+            market_type,
+            msg_type,
+            symbols: None,
+        }
+    }
+
+    #[test]
+    fn shared_connections() {
+        let jobs = vec![
+            job("binance", MarketType::Spot, MessageType::Trade),
+            job("binance", MarketType::Spot, MessageType::Candlestick),
+            job("binance", MarketType::LinearSwap, MessageType::Trade),
+            job("binance", MarketType::Spot, MessageType::L2Event),
+            // get_msg_type() can't tell bitfinex messages apart
+            job("bitfinex", MarketType::Spot, MessageType::Trade),
+            job("bitfinex", MarketType::Spot, MessageType::L2Event),
+        ];
+        let groups: Vec<Vec<usize>> = group_jobs(&jobs)
+            .into_iter()
+            .map(|group| group.indices)
+            .collect();
+        assert_eq!(vec![vec![0, 3], vec![1], vec![2], vec![4], vec![5]], groups);
+    }
+
+    #[test]
+    fn failed_job() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let jobs = vec![CrawlJob {
+            exchange: "non_existent".to_string(),
+            market_type: MarketType::Spot,
+            msg_type: MessageType::Trade,
+            symbols: None,
+        }];
+        let session = CrawlerSession::start(jobs, tx, Some(1));
+        let statuses = session.join();
+        assert!(matches!(statuses[0].state, JobState::Failed(_)));
+        assert_eq!(0, statuses[0].messages);
+        assert!(rx.recv().is_err());
+    }
+}
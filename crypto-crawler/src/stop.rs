@@ -0,0 +1,201 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crypto_ws_client::WSClient;
+
+// Shared by all threads of a crawler, so that one stop() reaches all of them
+#[derive(Clone, Default)]
+pub(crate) struct StopSignal {
+    inner: Arc<StopSignalInner>,
+}
+
+#[derive(Default)]
+struct StopSignalInner {
+    stopped: AtomicBool,
+    // websocket clients running now
+    ws_clients: Mutex<Vec<Arc<dyn WSClient + Send + Sync>>>,
+}
+
+impl StopSignal {
+    fn stop(&self) {
+        self.inner.stopped.store(true, Ordering::Release);
+        for ws_client in self.inner.ws_clients.lock().unwrap().drain(..) {
+            ws_client.close();
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.inner.stopped.load(Ordering::Acquire)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<StopSignal>> = const { RefCell::new(None) };
+}
+
+fn current() -> Option<StopSignal> {
+    CURRENT.with(|x| x.borrow().clone())
+}
+
+/// Whether the crawler running on this thread has been stopped.
+pub(crate) fn is_stopped() -> bool {
+    current().map(|x| x.is_stopped()).unwrap_or(false)
+}
+
+/// Sleep for `duration`, waking up early if the crawler is stopped.
+pub(crate) fn sleep(duration: Duration) {
+    let signal = current();
+    let step = Duration::from_secs(1);
+    let mut remaining = duration;
+    while remaining > Duration::ZERO {
+        if signal.as_ref().map(|x| x.is_stopped()).unwrap_or(false) {
+            return;
+        }
+        let elapsed = std::cmp::min(step, remaining);
+        std::thread::sleep(elapsed);
+        remaining -= elapsed;
+    }
+}
+
+/// Spawn a thread which belongs to the same crawler as the current thread.
+pub(crate) fn spawn<F>(builder: std::thread::Builder, f: F) -> JoinHandle<()>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let signal = current();
+    builder
+        .spawn(move || {
+            CURRENT.with(|x| *x.borrow_mut() = signal);
+            f()
+        })
+        .unwrap()
+}
+
+/// Run a websocket client until `duration` elapses or the crawler is stopped.
+pub(crate) fn run_ws_client(ws_client: Arc<dyn WSClient + Send + Sync>, duration: Option<u64>) {
+    let signal = current();
+    if let Some(signal) = signal.as_ref() {
+        let mut ws_clients = signal.inner.ws_clients.lock().unwrap();
+        // checked with the lock held, so that stop() never misses this client
+        if signal.is_stopped() {
+            return;
+        }
+        ws_clients.push(ws_client.clone());
+    }
+    ws_client.run(duration);
+    if let Some(signal) = signal {
+        signal
+            .inner
+            .ws_clients
+            .lock()
+            .unwrap()
+            .retain(|x| !Arc::ptr_eq(x, &ws_client));
+    }
+}
+
+/// A handle to a crawler running on another thread.
+pub struct CrawlerHandle {
+    signal: StopSignal,
+    thread: JoinHandle<()>,
+}
+
+impl CrawlerHandle {
+    /// Stop the crawler.
+    ///
+    /// Websocket clients are closed, symbol discovery threads and RESTful
+    /// pollers exit at their next check, then the crawl function returns and
+    /// drops its `tx`. This function doesn't block, call `join()` to wait.
+    pub fn stop(&self) {
+        self.signal.stop();
+    }
+
+    /// Whether `stop()` has been called.
+    pub fn is_stopped(&self) -> bool {
+        self.signal.is_stopped()
+    }
+
+    /// Whether the crawl function has returned.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Wait for the crawl function to return.
+    pub fn join(self) -> std::thread::Result<()> {
+        self.thread.join()
+    }
+}
+
+/// Run a crawl function on a new thread and return a handle to stop it.
+///
+/// All `crawl_*` functions can be stopped this way, for example:
+///
+/// ```rust
+/// use crypto_crawler::{crawl_trade, spawn_crawler, MarketType};
+///
+/// let (tx, rx) = std::sync::mpsc::channel();
+/// let handle = spawn_crawler(move || {
+///     crawl_trade("binance", MarketType::InverseSwap, None, tx, None)
+/// });
+/// std::thread::sleep(std::time::Duration::from_secs(5));
+/// handle.stop();
+/// handle.join().unwrap();
+/// // all messages are still readable
+/// for msg in rx {
+///     println!("{}", msg);
+/// }
+/// ```
+pub fn spawn_crawler<F>(f: F) -> CrawlerHandle
+where
+    F: FnOnce() + Send + 'static,
+{
+    spawn_crawler_with(std::thread::Builder::new(), f)
+}
+
+/// Same as `spawn_crawler()`, with a custom thread name, stack size, etc.
+pub(crate) fn spawn_crawler_with<F>(builder: std::thread::Builder, f: F) -> CrawlerHandle
+where
+    F: FnOnce() + Send + 'static,
+{
+    let signal = StopSignal::default();
+    let signal_clone = signal.clone();
+    let thread = builder
+        .spawn(move || {
+            CURRENT.with(|x| *x.borrow_mut() = Some(signal_clone));
+            f()
+        })
+        .unwrap();
+    CrawlerHandle { signal, thread }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_stopped, sleep, spawn, spawn_crawler};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn stop_sleeping_threads() {
+        let now = Instant::now();
+        let handle = spawn_crawler(|| {
+            assert!(!is_stopped());
+            // threads spawned by a crawler inherit its stop signal
+            let thread = spawn(std::thread::Builder::new(), || {
+                sleep(Duration::from_secs(3600));
+                assert!(is_stopped());
+            });
+            sleep(Duration::from_secs(3600));
+            thread.join().unwrap();
+        });
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!handle.is_finished());
+        handle.stop();
+        handle.join().unwrap();
+        assert!(now.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -2,5 +2,6 @@ pub(crate) mod cmc_rank;
 mod lock;
 pub(crate) mod spot_symbols;
 
+pub use cmc_rank::sort_by_cmc_rank;
 pub(crate) use lock::{REST_LOCKS, WS_LOCKS};
 pub use spot_symbols::get_hot_spot_symbols;
@@ -4,7 +4,6 @@ use crypto_market_type::MarketType;
 
 pub fn get_hot_spot_symbols(exchange: &str, spot_symbols: &[String]) -> Vec<String> {
     let market_types = crypto_market_type::get_market_types(exchange);
-    let cmc_ranks = &super::cmc_rank::CMC_RANKS;
     let contract_base_coins = {
         let mut contract_base_coins = HashSet::<String>::new();
         for market_type in market_types.iter().filter(|m| *m != &MarketType::Spot) {
@@ -20,8 +19,7 @@ pub fn get_hot_spot_symbols(exchange: &str, spot_symbols: &[String]) -> Vec<Stri
     let is_hot = |symbol: &str| {
         let pair = crypto_pair::normalize_pair(symbol, exchange).unwrap();
         let base_coin = pair.split('/').next().unwrap();
-        contract_base_coins.contains(base_coin)
-            || *cmc_ranks.get(base_coin).unwrap_or(&u64::max_value()) <= 100
+        contract_base_coins.contains(base_coin) || super::cmc_rank::get_cmc_rank(base_coin) <= 100
     };
 
     spot_symbols
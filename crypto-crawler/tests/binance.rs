@@ -133,6 +133,18 @@ fn test_crawl_funding_rate(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::InverseSwap, "BTCUSD_PERP")]
+#[test_case(MarketType::LinearSwap, "BTCUSDT")]
+fn test_crawl_mark_price(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_mark_price,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::MarkPrice
+    )
+}
+
 #[test_case(MarketType::Spot)]
 #[test_case(MarketType::InverseFuture)]
 #[test_case(MarketType::LinearFuture)]
@@ -151,3 +163,83 @@ fn test_crawl_candlestick(market_type: MarketType) {
 fn test_subscribe_symbol(market_type: MarketType, symbol: &str) {
     gen_test_subscribe_symbol!(EXCHANGE_NAME, market_type, symbol)
 }
+
+#[test_case(MarketType::InverseSwap, "btcusd_perp")]
+#[test_case(MarketType::LinearSwap, "btcusdt")]
+fn test_crawl_trade_parsed(market_type: MarketType, symbol: &str) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let tx_raw = create_parser_thread(tx);
+    let symbols = vec![symbol.to_string()];
+    crawl_trade(EXCHANGE_NAME, market_type, Some(&symbols), tx_raw, Some(0));
+
+    let results = rx
+        .into_iter()
+        .collect::<Vec<Result<ParsedMessage, ParseError>>>();
+    assert!(!results.is_empty());
+    for result in results {
+        match result.unwrap() {
+            ParsedMessage::Trade(trades) => {
+                assert_eq!(trades[0].exchange, EXCHANGE_NAME.to_string());
+                assert_eq!(trades[0].market_type, market_type);
+            }
+            _ => panic!("Expected trades"),
+        }
+    }
+}
+
+#[test_case(MarketType::Spot, "BTCUSDT")]
+#[test_case(MarketType::InverseSwap, "BTCUSD_PERP")]
+#[test_case(MarketType::LinearSwap, "BTCUSDT")]
+fn test_crawl_l2_book(market_type: MarketType, symbol: &str) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let symbols = vec![symbol.to_string()];
+    crawl_l2_book(EXCHANGE_NAME, market_type, Some(&symbols), tx, Some(0)).unwrap();
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].msg_type, MessageType::L2Snapshot);
+    for msg in messages.into_iter().skip(1) {
+        assert_eq!(msg.msg_type, MessageType::L2Event);
+        assert!(parse(msg));
+    }
+}
+
+#[test]
+fn test_stop_crawler() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let handle =
+        spawn_crawler(move || crawl_trade(EXCHANGE_NAME, MarketType::LinearSwap, None, tx, None));
+    std::thread::sleep(std::time::Duration::from_secs(5));
+    handle.stop();
+    handle.join().unwrap();
+
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].msg_type, MessageType::Trade);
+}
+
+#[test]
+fn test_crawler_session() {
+    let jobs = vec![MessageType::Trade, MessageType::BBO]
+        .into_iter()
+        .map(|msg_type| CrawlJob {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type: MarketType::LinearSwap,
+            msg_type,
+            symbols: Some(vec!["BTCUSDT".to_string()]),
+        })
+        .collect();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let session = CrawlerSession::start(jobs, tx, Some(5));
+    let statuses = session.join();
+
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+    for status in statuses.iter() {
+        assert_eq!(JobState::Finished, status.state);
+        assert!(status.messages > 0);
+    }
+    assert_eq!(
+        messages.len() as u64,
+        statuses.iter().map(|status| status.messages).sum::<u64>()
+    );
+}
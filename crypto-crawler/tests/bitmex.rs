@@ -20,6 +20,10 @@ fn crawl_all(msg_type: MessageType) {
         MessageType::BBO => crawl_bbo,
         MessageType::L2TopK => crawl_l2_topk,
         MessageType::FundingRate => crawl_funding_rate,
+        MessageType::Liquidation => crawl_liquidation,
+        MessageType::Settlement => crawl_settlement,
+        MessageType::InstrumentStatus => crawl_instrument_status,
+        MessageType::MarkPrice => crawl_mark_price,
         _ => panic!("unsupported message type {}", msg_type),
     };
     crawl_func(EXCHANGE_NAME, MarketType::Unknown, None, tx, Some(0));
@@ -64,6 +68,39 @@ fn test_crawl_funding_rate_all() {
     crawl_all(MessageType::FundingRate);
 }
 
+#[test]
+fn test_crawl_liquidation_all() {
+    crawl_all(MessageType::Liquidation);
+}
+
+#[test]
+fn test_crawl_settlement_all() {
+    crawl_all(MessageType::Settlement);
+}
+
+#[test]
+fn test_crawl_instrument_status_all() {
+    crawl_all(MessageType::InstrumentStatus);
+}
+
+#[test]
+fn test_crawl_mark_price_all() {
+    crawl_all(MessageType::MarkPrice);
+}
+
+#[test]
+fn test_crawl_insurance_fund() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    crawl_insurance_fund(EXCHANGE_NAME, MarketType::Unknown, tx, Some(0));
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].msg_type, MessageType::InsuranceFund);
+    for msg in messages {
+        assert!(parse(msg));
+    }
+}
+
 #[test]
 fn test_crawl_candlestick_rate_all() {
     let (tx, rx) = std::sync::mpsc::channel();
@@ -96,3 +133,32 @@ fn test_crawl_l2_event(market_type: MarketType, symbol: &str) {
 fn test_subscribe_symbol(market_type: MarketType, symbol: &str) {
     gen_test_subscribe_symbol!(EXCHANGE_NAME, market_type, symbol)
 }
+
+#[test]
+fn test_crawl_other() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    crawl_other(EXCHANGE_NAME, MarketType::Unknown, tx, Some(0));
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].exchange, EXCHANGE_NAME.to_string());
+    assert_eq!(messages[0].msg_type, MessageType::Other);
+}
+
+#[test]
+fn test_crawl_channels() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let channels = vec!["trade:XBTUSD".to_string(), "instrument:XBTUSD".to_string()];
+    crawl_channels(
+        EXCHANGE_NAME,
+        MarketType::InverseSwap,
+        &channels,
+        tx,
+        Some(0),
+    );
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].market_type, MarketType::InverseSwap);
+    assert_eq!(messages[0].msg_type, MessageType::Other);
+}
@@ -85,3 +85,14 @@ fn test_crawl_ticker(market_type: MarketType, symbol: &str) {
         MessageType::Ticker
     )
 }
+
+#[test]
+fn test_crawl_other() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    crawl_other(EXCHANGE_NAME, MarketType::Spot, tx, Some(0));
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].exchange, EXCHANGE_NAME.to_string());
+    assert_eq!(messages[0].msg_type, MessageType::Other);
+}
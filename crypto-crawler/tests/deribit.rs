@@ -107,6 +107,18 @@ fn test_crawl_ticker(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::InverseSwap, "BTC-PERPETUAL")]
+#[test_case(MarketType::InverseFuture, "BTC-31DEC21")]
+fn test_crawl_mark_price(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_mark_price,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::MarkPrice
+    )
+}
+
 #[test_case(MarketType::InverseSwap)]
 #[test_case(MarketType::InverseFuture)]
 #[test_case(MarketType::EuropeanOption)]
@@ -142,3 +142,17 @@ fn test_crawl_candlestick(market_type: MarketType) {
 fn test_subscribe_symbol(market_type: MarketType, symbol: &str) {
     gen_test_subscribe_symbol!(EXCHANGE_NAME, market_type, symbol)
 }
+
+#[test_case(MarketType::Spot)]
+#[test_case(MarketType::InverseFuture)]
+#[test_case(MarketType::InverseSwap)]
+#[test_case(MarketType::LinearSwap)]
+fn test_crawl_other(market_type: MarketType) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    crawl_other(EXCHANGE_NAME, market_type, tx, Some(0));
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].market_type, market_type);
+    assert_eq!(messages[0].msg_type, MessageType::Other);
+}
@@ -142,3 +142,18 @@ fn test_crawl_ticker(market_type: MarketType, symbol: &str) {
 fn test_crawl_candlestick(market_type: MarketType) {
     gen_test_crawl_candlestick!(EXCHANGE_NAME, market_type)
 }
+
+#[test_case(MarketType::Spot, "BTC-USDT")]
+#[test_case(MarketType::LinearSwap, "XBTUSDTM")]
+fn test_crawl_l2_book(market_type: MarketType, symbol: &str) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let symbols = vec![symbol.to_string()];
+    crawl_l2_book(EXCHANGE_NAME, market_type, Some(&symbols), tx, Some(0)).unwrap();
+    let messages = rx.into_iter().collect::<Vec<Message>>();
+
+    assert!(!messages.is_empty());
+    assert_eq!(messages[0].msg_type, MessageType::L2Snapshot);
+    for msg in messages.iter().skip(1) {
+        assert_eq!(msg.msg_type, MessageType::L2Event);
+    }
+}
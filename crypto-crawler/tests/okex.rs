@@ -127,6 +127,31 @@ fn test_crawl_funding_rate(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::InverseFuture, "BTC-USD-211231")]
+#[test_case(MarketType::InverseSwap, "BTC-USD-SWAP")]
+#[test_case(MarketType::LinearSwap, "BTC-USDT-SWAP")]
+fn test_crawl_mark_price(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_mark_price,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::MarkPrice
+    )
+}
+
+#[test_case(MarketType::InverseSwap, "BTC-USD-SWAP")]
+#[test_case(MarketType::LinearSwap, "BTC-USDT-SWAP")]
+fn test_crawl_index_price(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_index_price,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::IndexPrice
+    )
+}
+
 #[test_case(MarketType::Spot, "BTC-USDT")]
 #[test_case(MarketType::InverseFuture, "BTC-USD-211231")]
 #[test_case(MarketType::LinearFuture, "BTC-USDT-211231")]
@@ -23,6 +23,34 @@ pub(crate) fn parse(msg: Message) -> bool {
         MessageType::FundingRate => {
             crypto_msg_parser::parse_funding_rate(&msg.exchange, msg.market_type, &msg.json).is_ok()
         }
+        MessageType::MarkPrice | MessageType::IndexPrice => crypto_msg_parser::parse_mark_price(
+            &msg.exchange,
+            msg.market_type,
+            &msg.json,
+            Some(msg.received_at as i64),
+        )
+        .is_ok(),
+        MessageType::Liquidation => crypto_msg_parser::parse_liquidation(
+            &msg.exchange,
+            msg.market_type,
+            &msg.json,
+            Some(msg.received_at as i64),
+        )
+        .is_ok(),
+        MessageType::InsuranceFund => {
+            crypto_msg_parser::parse_insurance_fund(&msg.exchange, msg.market_type, &msg.json)
+                .is_ok()
+        }
+        MessageType::Settlement => {
+            crypto_msg_parser::parse_settlement(&msg.exchange, msg.market_type, &msg.json).is_ok()
+        }
+        MessageType::InstrumentStatus => crypto_msg_parser::parse_instrument_status(
+            &msg.exchange,
+            msg.market_type,
+            &msg.json,
+            Some(msg.received_at as i64),
+        )
+        .is_ok(),
         _ => true,
     }
 }
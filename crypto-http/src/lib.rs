@@ -0,0 +1,251 @@
+//! A shared HTTP client for all crypto-* crates.
+//!
+//! crypto-pair, crypto-markets, crypto-contract-value, crypto-msg-parser and
+//! crypto-rest-client send all RESTful requests through this client, so that
+//! connections are pooled and egress behaviour can be configured in one place.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, RwLock,
+};
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use reqwest::{header, tls, Certificate, Proxy};
+
+/// Configuration of the shared HTTP client.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use crypto_http::{set_http_config, HttpConfig};
+///
+/// set_http_config(HttpConfig {
+///     timeout: Some(Duration::from_secs(10)),
+///     proxy: Some("socks5://127.0.0.1:1080".to_string()),
+///     ..Default::default()
+/// })
+/// .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct HttpConfig {
+    /// The `User-Agent` header of all requests
+    pub user_agent: String,
+    /// Timeout of a whole request, from connecting until the body is read
+    pub timeout: Option<Duration>,
+    /// Timeout of the connecting phase only
+    pub connect_timeout: Option<Duration>,
+    /// Proxy URL of all requests, e.g., `http://127.0.0.1:8080` or `socks5://127.0.0.1:1080`.
+    ///
+    /// If `None`, the system proxy in environment variables such as `HTTPS_PROXY` is used.
+    pub proxy: Option<String>,
+    /// How long an idle connection is kept alive in the pool
+    pub pool_idle_timeout: Option<Duration>,
+    /// Max number of idle connections per host, `None` means unlimited
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Minimum TLS version
+    pub min_tls_version: Option<tls::Version>,
+    /// Extra root certificates to trust, besides the system ones
+    pub root_certificates: Vec<Certificate>,
+    /// Skip certificate verification, dangerous, for debugging only
+    pub danger_accept_invalid_certs: bool,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36".to_string(),
+            timeout: Some(Duration::from_secs(10)),
+            connect_timeout: None,
+            proxy: None,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            pool_max_idle_per_host: None,
+            min_tls_version: None,
+            root_certificates: Vec::new(),
+            danger_accept_invalid_certs: false,
+        }
+    }
+}
+
+// Applies the same settings to both blocking and async builders
+macro_rules! configure_builder {
+    ($builder:expr, $config:expr) => {{
+        let config: &HttpConfig = $config;
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        let mut builder = $builder
+            .default_headers(headers)
+            .user_agent(config.user_agent.as_str())
+            .gzip(true)
+            .danger_accept_invalid_certs(config.danger_accept_invalid_certs);
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = config.proxy.as_ref() {
+            builder = builder.proxy(Proxy::all(proxy.as_str())?);
+        }
+        builder = builder.pool_idle_timeout(config.pool_idle_timeout);
+        if let Some(max_idle) = config.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(version) = config.min_tls_version {
+            builder = builder.min_tls_version(version);
+        }
+        for cert in config.root_certificates.iter() {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        builder.build()
+    }};
+}
+
+impl HttpConfig {
+    /// Build a blocking client with this configuration.
+    ///
+    /// Like all blocking clients, it must NOT be built or dropped within an async runtime.
+    pub fn build_blocking_client(&self) -> reqwest::Result<reqwest::blocking::Client> {
+        configure_builder!(reqwest::blocking::Client::builder(), self)
+    }
+
+    /// Build an async client with this configuration.
+    pub fn build_async_client(&self) -> reqwest::Result<reqwest::Client> {
+        configure_builder!(reqwest::Client::builder(), self)
+    }
+}
+
+// Building a blocking client within an async runtime panics, so the shared
+// one is always built on a separate thread.
+fn build_shared_blocking_client(config: &HttpConfig) -> reqwest::Result<reqwest::blocking::Client> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| config.build_blocking_client())
+            .join()
+            .unwrap_or_else(|err| std::panic::resume_unwind(err))
+    })
+}
+
+// Incremented by every successful set_http_config()
+static HTTP_CONFIG_VERSION: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    static ref HTTP_CONFIG: RwLock<HttpConfig> = RwLock::new(HttpConfig::default());
+    // Built lazily on first use, replaced by set_http_config()
+    static ref BLOCKING_CLIENT: RwLock<Option<Arc<reqwest::blocking::Client>>> = RwLock::new(None);
+    static ref ASYNC_CLIENT: RwLock<Option<reqwest::Client>> = RwLock::new(None);
+}
+
+/// Replace the configuration of the shared HTTP client.
+///
+/// Requests sent after this call use the new configuration. Returns an error
+/// if the configuration is invalid, e.g., a malformed proxy URL, in which case
+/// the old configuration remains.
+pub fn set_http_config(config: HttpConfig) -> reqwest::Result<()> {
+    let blocking_client = build_shared_blocking_client(&config)?;
+    let async_client = config.build_async_client()?;
+    *HTTP_CONFIG.write().unwrap() = config;
+    let old_client = BLOCKING_CLIENT
+        .write()
+        .unwrap()
+        .replace(Arc::new(blocking_client));
+    // Dropping the last handle of a blocking client within an async runtime
+    // panics, so the replaced one is released on a separate thread. Callers
+    // still holding it release it once their requests finish.
+    if let Some(old_client) = old_client {
+        std::thread::scope(|scope| {
+            scope.spawn(move || drop(old_client));
+        });
+    }
+    ASYNC_CLIENT.write().unwrap().replace(async_client);
+    HTTP_CONFIG_VERSION.fetch_add(1, Ordering::AcqRel);
+    Ok(())
+}
+
+/// Get the current configuration of the shared HTTP client.
+pub fn http_config() -> HttpConfig {
+    HTTP_CONFIG.read().unwrap().clone()
+}
+
+/// Get the version of the configuration, which changes on every [`set_http_config`].
+///
+/// Clients built from [`http_config`] can compare versions to know when to rebuild.
+pub fn http_config_version() -> u64 {
+    HTTP_CONFIG_VERSION.load(Ordering::Acquire)
+}
+
+/// Get the shared blocking client.
+///
+/// The returned client stays usable after [`set_http_config`] replaces it,
+/// and is dropped once all handles are released.
+pub fn http_client() -> reqwest::Result<Arc<reqwest::blocking::Client>> {
+    if let Some(client) = BLOCKING_CLIENT.read().unwrap().as_ref() {
+        return Ok(client.clone());
+    }
+    let mut guard = BLOCKING_CLIENT.write().unwrap();
+    if guard.is_none() {
+        *guard = Some(Arc::new(build_shared_blocking_client(
+            &HTTP_CONFIG.read().unwrap(),
+        )?));
+    }
+    Ok(guard.as_ref().unwrap().clone())
+}
+
+/// Get the shared async client.
+///
+/// Cloning a client is cheap, clones share the same connection pool.
+pub fn http_client_async() -> reqwest::Result<reqwest::Client> {
+    if let Some(client) = ASYNC_CLIENT.read().unwrap().as_ref() {
+        return Ok(client.clone());
+    }
+    let mut guard = ASYNC_CLIENT.write().unwrap();
+    if guard.is_none() {
+        *guard = Some(HTTP_CONFIG.read().unwrap().build_async_client()?);
+    }
+    Ok(guard.as_ref().unwrap().clone())
+}
+
+/// Send a GET request via the shared blocking client, and return the text in response.
+pub fn http_get(url: &str) -> reqwest::Result<String> {
+    let response = http_client()?.get(url).send()?;
+    match response.error_for_status() {
+        Ok(resp) => Ok(resp.text()?),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_proxy() {
+        let config = HttpConfig {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        assert!(config.build_blocking_client().is_err());
+        assert!(set_http_config(config).is_err());
+        assert!(http_config().proxy.is_none());
+    }
+
+    #[test]
+    fn test_default_timeout() {
+        assert_eq!(Some(Duration::from_secs(10)), HttpConfig::default().timeout);
+    }
+
+    #[tokio::test]
+    async fn test_set_http_config_within_runtime() {
+        let client = http_client().unwrap();
+        set_http_config(HttpConfig::default()).unwrap();
+        set_http_config(HttpConfig::default()).unwrap();
+        // replaced clients are not kept alive
+        assert_eq!(1, Arc::strong_count(&client));
+        // dropped on a separate thread, since it panics within a runtime
+        std::thread::spawn(move || drop(client)).join().unwrap();
+    }
+}
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
@@ -47,99 +49,217 @@ pub enum MarketType {
     BVOL,
 }
 
+/// Supported cryptocurrency exchanges.
+///
+/// This is the single place listing exchanges and their market types, new
+/// exchanges should be added here first.
+#[derive(Copy, Clone, Serialize, Deserialize, Display, Debug, EnumString, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Exchange {
+    Binance,
+    Bitfinex,
+    Bitget,
+    Bithumb,
+    Bitmex,
+    Bitstamp,
+    Bitz,
+    Bybit,
+    CoinbasePro,
+    Deribit,
+    Dydx,
+    Ftx,
+    Gate,
+    Huobi,
+    Kraken,
+    KrakenFutures,
+    Kucoin,
+    Mxc,
+    Okex,
+    Zbg,
+}
+
+impl Exchange {
+    /// All supported exchanges.
+    pub const ALL: &'static [Exchange] = &[
+        Exchange::Binance,
+        Exchange::Bitfinex,
+        Exchange::Bitget,
+        Exchange::Bithumb,
+        Exchange::Bitmex,
+        Exchange::Bitstamp,
+        Exchange::Bitz,
+        Exchange::Bybit,
+        Exchange::CoinbasePro,
+        Exchange::Deribit,
+        Exchange::Dydx,
+        Exchange::Ftx,
+        Exchange::Gate,
+        Exchange::Huobi,
+        Exchange::Kraken,
+        Exchange::KrakenFutures,
+        Exchange::Kucoin,
+        Exchange::Mxc,
+        Exchange::Okex,
+        Exchange::Zbg,
+    ];
+
+    /// Get market types of this exchange.
+    pub fn market_types(&self) -> Vec<MarketType> {
+        match self {
+            Exchange::Binance => vec![
+                MarketType::Spot,
+                MarketType::LinearFuture,
+                MarketType::InverseFuture,
+                MarketType::LinearSwap,
+                MarketType::InverseSwap,
+                MarketType::EuropeanOption,
+            ],
+            Exchange::Bitfinex => vec![MarketType::Spot, MarketType::LinearSwap],
+            Exchange::Bitget => vec![
+                MarketType::Spot,
+                MarketType::InverseSwap,
+                MarketType::LinearSwap,
+            ],
+            Exchange::Bithumb => vec![MarketType::Spot],
+            // BitMEX only handles Bitcoin. All profit and loss is in Bitcoin
+            Exchange::Bitmex => vec![
+                MarketType::LinearSwap,
+                MarketType::InverseSwap,
+                MarketType::QuantoSwap,
+                MarketType::LinearFuture,
+                MarketType::InverseFuture,
+                MarketType::QuantoFuture,
+            ],
+            Exchange::Bitstamp => vec![MarketType::Spot],
+            Exchange::Bitz => vec![
+                MarketType::Spot,
+                MarketType::InverseSwap,
+                MarketType::LinearSwap,
+            ],
+            Exchange::Bybit => vec![
+                MarketType::InverseSwap,
+                MarketType::LinearSwap,
+                MarketType::InverseFuture,
+            ],
+            Exchange::CoinbasePro => vec![MarketType::Spot],
+            // Deribit only accepts Bitcoin as funds to deposit.
+            Exchange::Deribit => vec![
+                MarketType::InverseFuture,
+                MarketType::InverseSwap,
+                MarketType::EuropeanOption,
+            ],
+            Exchange::Dydx => vec![MarketType::LinearSwap],
+            Exchange::Ftx => vec![
+                MarketType::Spot,
+                MarketType::LinearFuture,
+                MarketType::LinearSwap,
+                MarketType::Move,
+                MarketType::BVOL,
+            ],
+            Exchange::Gate => vec![
+                MarketType::Spot,
+                MarketType::InverseFuture,
+                MarketType::LinearFuture,
+                MarketType::InverseSwap,
+                MarketType::LinearSwap,
+            ],
+            Exchange::Huobi => vec![
+                MarketType::Spot,
+                MarketType::InverseFuture,
+                MarketType::LinearSwap,
+                MarketType::InverseSwap,
+                // MarketType::EuropeanOption,
+            ],
+            Exchange::Kraken => vec![MarketType::Spot],
+            Exchange::KrakenFutures => vec![MarketType::InverseFuture],
+            Exchange::Kucoin => vec![
+                MarketType::Spot,
+                MarketType::LinearSwap,
+                MarketType::InverseSwap,
+                MarketType::InverseFuture,
+            ],
+            Exchange::Mxc => vec![
+                MarketType::Spot,
+                MarketType::LinearSwap,
+                MarketType::InverseSwap,
+            ],
+            Exchange::Okex => vec![
+                MarketType::Spot,
+                MarketType::LinearFuture,
+                MarketType::InverseFuture,
+                MarketType::LinearSwap,
+                MarketType::InverseSwap,
+                MarketType::EuropeanOption,
+            ],
+            Exchange::Zbg => vec![
+                MarketType::Spot,
+                MarketType::InverseSwap,
+                MarketType::LinearSwap,
+            ],
+        }
+    }
+}
+
+/// An exchange and its market types, for tooling.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ExchangeInfo {
+    pub exchange: Exchange,
+    pub market_types: Vec<MarketType>,
+}
+
+/// Get all supported exchanges and their market types.
+///
+/// # Example
+///
+/// ```
+/// use crypto_market_type::get_exchanges;
+///
+/// let exchanges = get_exchanges();
+/// println!("{}", serde_json::to_string_pretty(&exchanges).unwrap());
+/// ```
+pub fn get_exchanges() -> Vec<ExchangeInfo> {
+    Exchange::ALL
+        .iter()
+        .map(|exchange| ExchangeInfo {
+            exchange: *exchange,
+            market_types: exchange.market_types(),
+        })
+        .collect()
+}
+
 /// Get market types of a cryptocurrency exchange.
+///
+/// Returns an empty vector if the exchange is unknown.
 pub fn get_market_types(exchange: &str) -> Vec<MarketType> {
-    match exchange {
-        "binance" => vec![
-            MarketType::Spot,
-            MarketType::LinearFuture,
-            MarketType::InverseFuture,
-            MarketType::LinearSwap,
-            MarketType::InverseSwap,
-            MarketType::EuropeanOption,
-        ],
-        "bitfinex" => vec![MarketType::Spot, MarketType::LinearSwap],
-        "bitget" => vec![
-            MarketType::Spot,
-            MarketType::InverseSwap,
-            MarketType::LinearSwap,
-        ],
-        "bithumb" => vec![MarketType::Spot],
-        // BitMEX only handles Bitcoin. All profit and loss is in Bitcoin
-        "bitmex" => vec![
-            MarketType::LinearSwap,
-            MarketType::InverseSwap,
-            MarketType::QuantoSwap,
-            MarketType::LinearFuture,
-            MarketType::InverseFuture,
-            MarketType::QuantoFuture,
-        ],
-        "bitstamp" => vec![MarketType::Spot],
-        "bitz" => vec![
-            MarketType::Spot,
-            MarketType::InverseSwap,
-            MarketType::LinearSwap,
-        ],
-        "bybit" => vec![
-            MarketType::InverseSwap,
-            MarketType::LinearSwap,
-            MarketType::InverseFuture,
-        ],
-        "coinbase_pro" => vec![MarketType::Spot],
-        // Deribit only accepts Bitcoin as funds to deposit.
-        "deribit" => vec![
-            MarketType::InverseFuture,
-            MarketType::InverseSwap,
-            MarketType::EuropeanOption,
-        ],
-        "dydx" => vec![MarketType::LinearSwap],
-        "ftx" => vec![
-            MarketType::Spot,
-            MarketType::LinearFuture,
-            MarketType::LinearSwap,
-            MarketType::Move,
-            MarketType::BVOL,
-        ],
-        "gate" => vec![
-            MarketType::Spot,
-            MarketType::InverseFuture,
-            MarketType::LinearFuture,
-            MarketType::InverseSwap,
-            MarketType::LinearSwap,
-        ],
-        "huobi" => vec![
-            MarketType::Spot,
-            MarketType::InverseFuture,
-            MarketType::LinearSwap,
-            MarketType::InverseSwap,
-            // MarketType::EuropeanOption,
-        ],
-        "kraken" => vec![MarketType::Spot],
-        "kraken_futures" => vec![MarketType::InverseFuture],
-        "kucoin" => vec![
-            MarketType::Spot,
-            MarketType::LinearSwap,
-            MarketType::InverseSwap,
-            MarketType::InverseFuture,
-        ],
-        "mxc" => vec![
-            MarketType::Spot,
-            MarketType::LinearSwap,
-            MarketType::InverseSwap,
-        ],
-        "okex" => vec![
-            MarketType::Spot,
-            MarketType::LinearFuture,
-            MarketType::InverseFuture,
-            MarketType::LinearSwap,
-            MarketType::InverseSwap,
-            MarketType::EuropeanOption,
-        ],
-        "zbg" => vec![
-            MarketType::Spot,
-            MarketType::InverseSwap,
-            MarketType::LinearSwap,
-        ],
-        _ => panic!("Unknown exchange {}", exchange),
+    Exchange::from_str(exchange)
+        .map(|exchange| exchange.market_types())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_names() {
+        assert_eq!("coinbase_pro", Exchange::CoinbasePro.to_string());
+        assert_eq!(
+            Exchange::KrakenFutures,
+            Exchange::from_str("kraken_futures").unwrap()
+        );
+        for exchange in Exchange::ALL {
+            assert_eq!(
+                *exchange,
+                Exchange::from_str(&exchange.to_string()).unwrap()
+            );
+            assert!(!exchange.market_types().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_unknown_exchange() {
+        assert!(get_market_types("unknown").is_empty());
+        assert!(!get_market_types("huobi").contains(&MarketType::EuropeanOption));
     }
 }
@@ -0,0 +1,335 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crypto_market_type::MarketType;
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Result, fetch_markets, Market};
+
+// $CRYPTO_MARKETS_CACHE_DIR if set, otherwise a directory in the system temp dir
+fn cache_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var("CRYPTO_MARKETS_CACHE_DIR") {
+        PathBuf::from(dir)
+    } else {
+        std::env::temp_dir().join("crypto-markets")
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+// The file format of cached markets
+#[derive(Serialize, Deserialize)]
+struct CachedMarkets {
+    // when markets were fetched, unix timestamp in milliseconds
+    timestamp: u64,
+    markets: Vec<Market>,
+}
+
+/// A market whose status or trading rules changed.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MarketChange {
+    pub old: Market,
+    pub new: Market,
+}
+
+/// Differences between two snapshots of markets of an exchange.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MarketDiff {
+    pub exchange: String,
+    pub market_type: MarketType,
+    /// Markets that didn't exist in the old snapshot
+    pub listed: Vec<Market>,
+    /// Markets that don't exist in the new snapshot
+    pub delisted: Vec<Market>,
+    /// Markets whose status, tick size, lot size or contract value changed
+    pub changed: Vec<MarketChange>,
+}
+
+impl MarketDiff {
+    /// Compare two snapshots of markets by symbol.
+    pub fn new(exchange: &str, market_type: MarketType, old: &[Market], new: &[Market]) -> Self {
+        let old_map: HashMap<&str, &Market> = old.iter().map(|m| (m.symbol.as_str(), m)).collect();
+        let new_map: HashMap<&str, &Market> = new.iter().map(|m| (m.symbol.as_str(), m)).collect();
+
+        let listed = new
+            .iter()
+            .filter(|m| !old_map.contains_key(m.symbol.as_str()))
+            .cloned()
+            .collect();
+        let delisted = old
+            .iter()
+            .filter(|m| !new_map.contains_key(m.symbol.as_str()))
+            .cloned()
+            .collect();
+        let changed = new
+            .iter()
+            .filter_map(|new_market| {
+                let old_market = old_map.get(new_market.symbol.as_str())?;
+                if old_market.active != new_market.active
+                    || old_market.precision.tick_size != new_market.precision.tick_size
+                    || old_market.precision.lot_size != new_market.precision.lot_size
+                    || old_market.contract_value != new_market.contract_value
+                {
+                    Some(MarketChange {
+                        old: (*old_market).clone(),
+                        new: new_market.clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        MarketDiff {
+            exchange: exchange.to_string(),
+            market_type,
+            listed,
+            delisted,
+            changed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listed.is_empty() && self.delisted.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A persistent cache of markets on disk, one file per exchange and market type.
+///
+/// Markets are fetched from the exchange only if the cached copy is older
+/// than the TTL, and the last cached copy is used if the exchange is unreachable.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use crypto_markets::MarketCache;
+/// use crypto_market_type::MarketType;
+///
+/// let cache = MarketCache::new(std::env::temp_dir().join("markets"), Duration::from_secs(3600));
+/// let markets = cache.get_markets("binance", MarketType::Spot).unwrap();
+/// assert!(!markets.is_empty());
+/// ```
+#[derive(Clone)]
+pub struct MarketCache {
+    pub(crate) dir: PathBuf,
+    pub(crate) ttl: Duration,
+}
+
+impl Default for MarketCache {
+    /// Cache in `$CRYPTO_MARKETS_CACHE_DIR`, or `crypto-markets` in the system temp dir if it's not set, with a TTL of one hour.
+    fn default() -> Self {
+        MarketCache::new(cache_dir(), Duration::from_secs(3600))
+    }
+}
+
+impl MarketCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        MarketCache {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    fn markets_file(&self, exchange: &str, market_type: MarketType) -> PathBuf {
+        self.dir
+            .join(format!("{}.{}.markets.json", exchange, market_type))
+    }
+
+    fn load_file(&self, exchange: &str, market_type: MarketType) -> Option<CachedMarkets> {
+        let text = std::fs::read_to_string(self.markets_file(exchange, market_type)).ok()?;
+        serde_json::from_str::<CachedMarkets>(&text).ok()
+    }
+
+    pub(crate) fn save_file(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        markets: &[Market],
+    ) -> Result<()> {
+        let cached = CachedMarkets {
+            timestamp: now_millis(),
+            markets: markets.to_vec(),
+        };
+        std::fs::create_dir_all(&self.dir)?;
+        // write to a temporary file then rename, so that readers never see a partial file
+        let path = self.markets_file(exchange, market_type);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string(&cached)?)?;
+        std::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Load markets from disk without any network access.
+    pub fn load(&self, exchange: &str, market_type: MarketType) -> Option<Vec<Market>> {
+        self.load_file(exchange, market_type).map(|x| x.markets)
+    }
+
+    /// Get markets, from disk if the cached copy is fresh, otherwise from the exchange.
+    ///
+    /// If the exchange is unreachable, the last cached copy is returned no matter how old it is.
+    pub fn get_markets(&self, exchange: &str, market_type: MarketType) -> Result<Vec<Market>> {
+        let cached = self.load_file(exchange, market_type);
+        if let Some(cached) = cached.as_ref() {
+            if now_millis() < cached.timestamp + self.ttl.as_millis() as u64 {
+                return Ok(cached.markets.clone());
+            }
+        }
+        match fetch_markets(exchange, market_type) {
+            Ok(markets) => {
+                // the cache is optional, failing to save it is not an error
+                let _ = self.save_file(exchange, market_type, &markets);
+                Ok(markets)
+            }
+            Err(err) => cached.map(|x| x.markets).ok_or(err),
+        }
+    }
+
+    /// Get symbols of active markets, see `get_markets()`.
+    pub fn get_symbols(&self, exchange: &str, market_type: MarketType) -> Result<Vec<String>> {
+        let symbols = self
+            .get_markets(exchange, market_type)?
+            .into_iter()
+            .filter(|m| m.active)
+            .map(|m| m.symbol)
+            .collect();
+        Ok(symbols)
+    }
+
+    /// Get symbols of active markets whose unified pair is `pair`, sorted, see `get_markets()`.
+    pub fn get_symbols_by_pair(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        pair: &str,
+    ) -> Result<Vec<String>> {
+        let pair = pair.to_uppercase();
+        let mut symbols: Vec<String> = self
+            .get_symbols(exchange, market_type)?
+            .into_iter()
+            .filter(|symbol| crypto_pair::normalize_pair(symbol, exchange).as_ref() == Some(&pair))
+            .collect();
+        symbols.sort();
+        Ok(symbols)
+    }
+
+    /// Fetch markets from the exchange regardless of the TTL, and return differences from the cached copy.
+    ///
+    /// All fetched markets are listed if there is no cached copy.
+    pub fn refresh(&self, exchange: &str, market_type: MarketType) -> Result<MarketDiff> {
+        let markets = fetch_markets(exchange, market_type)?;
+        let old_markets = self.load(exchange, market_type).unwrap_or_default();
+        self.save_file(exchange, market_type, &markets)?;
+        Ok(MarketDiff::new(
+            exchange,
+            market_type,
+            &old_markets,
+            &markets,
+        ))
+    }
+
+    /// Spawn a thread refreshing markets every TTL until `should_stop` is set.
+    ///
+    /// `on_diff` is called with every non-empty diff, and refresh errors are
+    /// logged and retried at the next round.
+    pub fn spawn_refresh_thread<F>(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        should_stop: Arc<AtomicBool>,
+        on_diff: F,
+    ) -> JoinHandle<()>
+    where
+        F: Fn(MarketDiff) + Send + 'static,
+    {
+        let cache = self.clone();
+        let exchange = exchange.to_string();
+        std::thread::spawn(move || {
+            // check should_stop every second, so that the thread exits quickly
+            let mut elapsed = cache.ttl;
+            while !should_stop.load(Ordering::Acquire) {
+                if elapsed >= cache.ttl {
+                    elapsed = Duration::ZERO;
+                    match cache.refresh(&exchange, market_type) {
+                        Ok(diff) => {
+                            if !diff.is_empty() {
+                                on_diff(diff);
+                            }
+                        }
+                        Err(err) => warn!(
+                            "Failed to refresh {} {} markets, error: {}",
+                            exchange, market_type, err
+                        ),
+                    }
+                }
+                std::thread::sleep(Duration::from_secs(1));
+                elapsed += Duration::from_secs(1);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MarketDiff;
+    use crate::{Fees, Market, Precision};
+    use crypto_market_type::MarketType;
+
+    fn new_market(symbol: &str, tick_size: f64) -> Market {
+        Market {
+            exchange: "binance".to_string(),
+            market_type: MarketType::Spot,
+            symbol: symbol.to_string(),
+            base_id: "BTC".to_string(),
+            quote_id: "USDT".to_string(),
+            settle_id: None,
+            base: "BTC".to_string(),
+            quote: "USDT".to_string(),
+            settle: None,
+            active: true,
+            margin: false,
+            fees: Fees {
+                maker: 0.001,
+                taker: 0.001,
+            },
+            precision: Precision {
+                tick_size,
+                lot_size: 0.000001,
+            },
+            quantity_limit: None,
+            contract_value: None,
+            delivery_date: None,
+            option_info: None,
+            info: serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = vec![new_market("BTCUSDT", 0.01), new_market("ETHUSDT", 0.01)];
+        let new = vec![new_market("BTCUSDT", 0.1), new_market("BNBUSDT", 0.01)];
+        let diff = MarketDiff::new("binance", MarketType::Spot, &old, &new);
+        assert!(!diff.is_empty());
+        assert_eq!("BNBUSDT", diff.listed[0].symbol);
+        assert_eq!("ETHUSDT", diff.delisted[0].symbol);
+        assert_eq!(1, diff.changed.len());
+        assert_eq!(0.01, diff.changed[0].old.precision.tick_size);
+        assert_eq!(0.1, diff.changed[0].new.precision.tick_size);
+
+        assert!(MarketDiff::new("binance", MarketType::Spot, &new, &new).is_empty());
+    }
+}
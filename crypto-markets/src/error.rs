@@ -24,3 +24,9 @@ impl From<serde_json::Error> for Error {
         Error(err.to_string())
     }
 }
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error(err.to_string())
+    }
+}
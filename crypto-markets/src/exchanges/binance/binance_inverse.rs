@@ -115,6 +115,7 @@ fn fetch_future_markets_internal() -> Result<Vec<Market>> {
                 } else {
                     Some(m.deliveryDate)
                 },
+                option_info: None,
                 info: serde_json::to_value(&m)
                     .unwrap()
                     .as_object()
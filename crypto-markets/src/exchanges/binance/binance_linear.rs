@@ -113,6 +113,7 @@ fn fetch_linear_markets() -> Result<Vec<Market>> {
                 } else {
                     Some(m.deliveryDate)
                 },
+                option_info: None,
                 info: serde_json::to_value(&m)
                     .unwrap()
                     .as_object()
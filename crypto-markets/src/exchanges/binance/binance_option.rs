@@ -1,5 +1,6 @@
 use super::utils::binance_http_get;
 use crate::{error::Result, market::*, Market, MarketType};
+use crypto_pair::OptionKind;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -22,7 +23,8 @@ struct OptionMarket {
     maxQty: String,
     priceScale: i64,
     quantityScale: i64,
-    side: String,
+    side: String, // CALL, PUT
+    strikePrice: String,
     makerFeeRate: String,
     takerFeeRate: String,
     expiryDate: u64,
@@ -94,6 +96,16 @@ pub(super) fn fetch_option_markets() -> Result<Vec<Market>> {
                 }),
                 contract_value: Some(1.0),
                 delivery_date: Some(m.expiryDate),
+                option_info: Some(OptionInfo {
+                    strike: m.strikePrice.parse::<f64>().unwrap(),
+                    option_kind: if m.side == "PUT" {
+                        OptionKind::Put
+                    } else {
+                        OptionKind::Call
+                    },
+                    underlying: m.underlying.clone(),
+                    exercise_style: ExerciseStyle::European,
+                }),
                 info: serde_json::to_value(&m)
                     .unwrap()
                     .as_object()
@@ -89,6 +89,7 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 }),
                 contract_value: None,
                 delivery_date: None,
+                option_info: None,
                 info: serde_json::to_value(&m)
                     .unwrap()
                     .as_object()
@@ -119,6 +119,7 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
                     Some(1.0)
                 },
                 delivery_date: None,
+                option_info: None,
                 info: serde_json::to_value(&m)
                     .unwrap()
                     .as_object()
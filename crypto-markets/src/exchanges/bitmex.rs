@@ -68,6 +68,7 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
                 } else {
                     None
                 },
+                option_info: None,
                 info,
             }
         })
@@ -92,6 +92,7 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 quantity_limit: None,
                 contract_value: None,
                 delivery_date: None,
+                option_info: None,
                 info,
             }
         })
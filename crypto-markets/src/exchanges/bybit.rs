@@ -187,6 +187,7 @@ fn to_market(raw_market: &BybitMarket) -> Market {
         }),
         contract_value: Some(1.0),
         delivery_date,
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
@@ -95,6 +95,7 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 }),
                 contract_value: None,
                 delivery_date: None,
+                option_info: None,
                 info,
             }
         })
@@ -1,11 +1,12 @@
 use super::super::utils::http_get;
 use crate::{
     error::{Error, Result},
-    market::{Fees, Precision, QuantityLimit},
+    market::{ExerciseStyle, Fees, OptionInfo, Precision, QuantityLimit},
     Market,
 };
 
 use crypto_market_type::MarketType;
+use crypto_pair::OptionKind;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -47,6 +48,8 @@ struct Instrument {
     tick_size: f64,
     taker_commission: f64,
     strike: Option<f64>,
+    option_type: Option<String>, // call, put
+    price_index: Option<String>,
     settlement_period: String,
     quote_currency: String,
     min_trade_amount: f64,
@@ -188,6 +191,23 @@ fn to_market(raw_market: &Instrument) -> Market {
         } else {
             Some(raw_market.expiration_timestamp)
         },
+        option_info: if market_type == MarketType::EuropeanOption {
+            Some(OptionInfo {
+                strike: raw_market.strike.unwrap(),
+                option_kind: if raw_market.option_type.as_deref() == Some("put") {
+                    OptionKind::Put
+                } else {
+                    OptionKind::Call
+                },
+                underlying: raw_market
+                    .price_index
+                    .clone()
+                    .unwrap_or_else(|| format!("{}_usd", raw_market.base_currency.to_lowercase())),
+                exercise_style: ExerciseStyle::European,
+            })
+        } else {
+            None
+        },
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
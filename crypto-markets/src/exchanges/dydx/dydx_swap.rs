@@ -93,6 +93,7 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
                 }),
                 contract_value: Some(1.0),
                 delivery_date: None,
+                option_info: None,
                 info,
             }
         })
@@ -212,6 +212,7 @@ fn to_market(raw_market: &FtxMarket) -> Market {
             Some(1.0)
         },
         delivery_date,
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
@@ -121,6 +121,7 @@ fn to_market(raw_market: &FutureMarket) -> Market {
         }),
         contract_value: Some(quanto_multiplier),
         delivery_date: Some((raw_market.expire_time * 1000) as u64),
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
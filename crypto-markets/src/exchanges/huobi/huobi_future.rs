@@ -105,6 +105,7 @@ pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
                 quantity_limit: None,
                 contract_value: Some(m.contract_size),
                 delivery_date: Some(m.delivery_time.parse::<u64>().unwrap()),
+                option_info: None,
                 info: serde_json::to_value(&m)
                     .unwrap()
                     .as_object()
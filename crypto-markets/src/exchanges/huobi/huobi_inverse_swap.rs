@@ -89,6 +89,7 @@ pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
                 quantity_limit: None,
                 contract_value: Some(m.contract_size),
                 delivery_date: None,
+                option_info: None,
                 info,
             }
         })
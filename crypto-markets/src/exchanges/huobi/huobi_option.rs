@@ -1,6 +1,13 @@
 use super::utils::huobi_http_get;
-use crate::error::Result;
+use crate::{
+    error::Result,
+    market::{ExerciseStyle, Fees, OptionInfo, Precision},
+    Market,
+};
 
+use chrono::NaiveDate;
+use crypto_market_type::MarketType;
+use crypto_pair::OptionKind;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -12,10 +19,10 @@ struct OptionMarket {
     contract_type: String,
     contract_size: f64,
     price_tick: f64,
-    delivery_date: String,
+    delivery_date: String, // e.g., 20210625
     create_date: String,
     contract_status: i64,
-    option_right_type: String,
+    option_right_type: String, // C, P
     exercise_price: f64,
     delivery_asset: String,
     quote_asset: String,
@@ -51,3 +58,64 @@ pub(super) fn fetch_option_symbols() -> Result<Vec<String>> {
         .collect::<Vec<String>>();
     Ok(symbols)
 }
+
+pub(super) fn fetch_option_markets() -> Result<Vec<Market>> {
+    let markets = fetch_option_markets_raw()?
+        .into_iter()
+        .map(|m| {
+            let pair = crypto_pair::normalize_pair(&m.contract_code, "huobi").unwrap();
+            let (base, quote) = {
+                let v: Vec<&str> = pair.split('/').collect();
+                (v[0].to_string(), v[1].to_string())
+            };
+            // options are delivered at 08:00 UTC
+            let delivery_time = NaiveDate::parse_from_str(&m.delivery_date, "%Y%m%d")
+                .unwrap()
+                .and_hms_opt(8, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp_millis();
+            Market {
+                exchange: "huobi".to_string(),
+                market_type: MarketType::EuropeanOption,
+                symbol: m.contract_code.to_string(),
+                base_id: m.delivery_asset.to_string(),
+                quote_id: m.quote_asset.to_string(),
+                settle_id: Some(m.trade_partition.to_string()),
+                base,
+                quote,
+                settle: Some(m.trade_partition.to_uppercase()),
+                active: m.contract_status == 1,
+                margin: true,
+                // see https://www.huobi.com/en-us/fee/
+                fees: Fees {
+                    maker: 0.0002,
+                    taker: 0.0003,
+                },
+                precision: Precision {
+                    tick_size: m.price_tick,
+                    lot_size: 1.0,
+                },
+                quantity_limit: None,
+                contract_value: Some(m.contract_size),
+                delivery_date: Some(delivery_time as u64),
+                option_info: Some(OptionInfo {
+                    strike: m.exercise_price,
+                    option_kind: if m.option_right_type == "P" {
+                        OptionKind::Put
+                    } else {
+                        OptionKind::Call
+                    },
+                    underlying: format!("{}-{}", m.symbol, m.trade_partition),
+                    exercise_style: ExerciseStyle::European,
+                }),
+                info: serde_json::to_value(&m)
+                    .unwrap()
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            }
+        })
+        .collect::<Vec<Market>>();
+    Ok(markets)
+}
@@ -141,6 +141,7 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 }),
                 contract_value: None,
                 delivery_date: None,
+                option_info: None,
                 info,
             }
         })
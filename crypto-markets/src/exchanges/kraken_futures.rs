@@ -152,6 +152,7 @@ fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
                 quantity_limit: None,
                 contract_value: None,
                 delivery_date: None,
+                option_info: None,
                 info,
             }
         })
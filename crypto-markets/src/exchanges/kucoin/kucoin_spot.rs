@@ -102,6 +102,7 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 }),
                 contract_value: None,
                 delivery_date: None,
+                option_info: None,
                 info,
             }
         })
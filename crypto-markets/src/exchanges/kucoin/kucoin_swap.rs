@@ -154,6 +154,7 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         quantity_limit: None,
         contract_value: Some(raw_market.multiplier.abs()),
         delivery_date: raw_market.expireDate,
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
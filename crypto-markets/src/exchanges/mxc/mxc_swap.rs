@@ -116,6 +116,7 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         }),
         contract_value: Some(raw_market.contractSize),
         delivery_date: None,
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
@@ -97,6 +97,7 @@ fn to_market(raw_market: &FutureMarket) -> Market {
         quantity_limit: None,
         contract_value: Some(raw_market.contract_val.parse::<f64>().unwrap()),
         delivery_date: Some(delivery_time as u64),
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
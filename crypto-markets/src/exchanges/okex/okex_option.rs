@@ -3,12 +3,13 @@ use std::collections::HashMap;
 use super::super::utils::http_get;
 use crate::{
     error::Result,
-    market::{Fees, Precision},
+    market::{ExerciseStyle, Fees, OptionInfo, Precision},
     Market,
 };
 
 use chrono::DateTime;
 use crypto_market_type::MarketType;
+use crypto_pair::OptionKind;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -19,7 +20,7 @@ struct OptionMarket {
     underlying: String,
     settlement_currency: String,
     contract_val: String,
-    option_type: String,
+    option_type: String, // C, P
     strike: String,
     tick_size: String,
     lot_size: String,
@@ -94,6 +95,16 @@ fn to_market(raw_market: &OptionMarket) -> Market {
         quantity_limit: None,
         contract_value: Some(raw_market.contract_val.parse::<f64>().unwrap()),
         delivery_date: Some(delivery_time as u64),
+        option_info: Some(OptionInfo {
+            strike: raw_market.strike.parse::<f64>().unwrap(),
+            option_kind: if raw_market.option_type == "P" {
+                OptionKind::Put
+            } else {
+                OptionKind::Call
+            },
+            underlying: raw_market.underlying.clone(),
+            exercise_style: ExerciseStyle::European,
+        }),
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
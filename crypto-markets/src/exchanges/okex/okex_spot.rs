@@ -73,6 +73,7 @@ fn to_market(raw_market: &SpotMarket) -> Market {
         }),
         contract_value: None,
         delivery_date: None,
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
@@ -95,6 +95,7 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         quantity_limit: None,
         contract_value: Some(raw_market.contract_val.parse::<f64>().unwrap()),
         delivery_date: None,
+        option_info: None,
         info: serde_json::to_value(raw_market)
             .unwrap()
             .as_object()
@@ -1,5 +1,3 @@
-use reqwest::header;
-
 use crate::error::{Error, Result};
 use std::collections::HashMap;
 
@@ -18,18 +16,7 @@ pub(super) fn http_get(url: &str, params: Option<&HashMap<String, String>>) -> R
     }
     // println!("{}", full_url);
 
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
-
-    let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
-    let response = client.get(full_url.as_str()).send()?;
+    let response = crypto_http::http_client()?.get(full_url.as_str()).send()?;
 
     match response.error_for_status() {
         Ok(resp) => Ok(resp.text()?),
@@ -11,13 +11,20 @@
 //! println!("{}", serde_json::to_string_pretty(&markets).unwrap())
 //! ```
 
+mod cache;
 mod error;
 mod exchanges;
 mod market;
+mod watch;
 
+pub use cache::{MarketCache, MarketChange, MarketDiff};
+pub use crypto_http::{set_http_config, HttpConfig};
 use crypto_market_type::MarketType;
+use crypto_pair::currency::canonical_id;
+pub use crypto_pair::OptionKind;
 pub use error::Error;
-pub use market::{Fees, Market, Precision, QuantityLimit};
+pub use market::{ExerciseStyle, Fees, Market, OptionInfo, Precision, QuantityLimit};
+pub use watch::{watch_markets, MarketEvent};
 
 use error::Result;
 
@@ -44,12 +51,16 @@ pub fn fetch_symbols(exchange: &str, market_type: MarketType) -> Result<Vec<Stri
         "mxc" => exchanges::mxc::fetch_symbols(market_type),
         "okex" => exchanges::okex::fetch_symbols(market_type),
         "zbg" => exchanges::zbg::fetch_symbols(market_type),
-        _ => panic!("Unsupported exchange {}", exchange),
+        _ => Err(Error(format!("Unsupported exchange {}", exchange))),
     }
 }
 
 /// Fetch trading markets of a cryptocurrency exchange.
 ///
+/// `base`, `quote` and `settle` of markets are canonical ids from
+/// `crypto_pair::currency::canonical_id()`, while `pair` is the output of
+/// `crypto_pair::normalize_pair()`.
+///
 /// # Arguments
 ///
 /// * `exchange` - The exchange name
@@ -65,7 +76,7 @@ pub fn fetch_symbols(exchange: &str, market_type: MarketType) -> Result<Vec<Stri
 /// println!("{}", serde_json::to_string_pretty(&markets).unwrap())
 /// ```
 pub fn fetch_markets(exchange: &str, market_type: MarketType) -> Result<Vec<Market>> {
-    match exchange {
+    let mut markets = match exchange {
         "binance" => exchanges::binance::fetch_markets(market_type),
         "bitfinex" => exchanges::bitfinex::fetch_markets(market_type),
         "bitget" => exchanges::bitget::fetch_markets(market_type),
@@ -86,6 +97,41 @@ pub fn fetch_markets(exchange: &str, market_type: MarketType) -> Result<Vec<Mark
         "mxc" => exchanges::mxc::fetch_markets(market_type),
         "okex" => exchanges::okex::fetch_markets(market_type),
         "zbg" => exchanges::zbg::fetch_markets(market_type),
-        _ => panic!("Unsupported exchange {}", exchange),
+        _ => Err(Error(format!("Unsupported exchange {}", exchange))),
+    }?;
+    for market in markets.iter_mut() {
+        market.base = canonical_id(&market.base, exchange);
+        market.quote = canonical_id(&market.quote, exchange);
+        market.settle = market.settle.as_ref().map(|x| canonical_id(x, exchange));
     }
+    Ok(markets)
+}
+
+/// Get exchange-specific symbols of a unified pair, the inverse of `crypto_pair::normalize_pair()`.
+///
+/// Returns all active symbols of the pair in the market, for example, all
+/// expiries of a future. Markets are read from `MarketCache::default()`, so
+/// they're fetched at most once an hour, and the cached copy is used if the
+/// exchange is unreachable.
+///
+/// # Arguments
+///
+/// * `exchange` - The exchange name
+/// * `market_type` - The market type
+/// * `pair` - The unified pair, e.g., `BTC/USDT`
+///
+/// # Example
+///
+/// ```
+/// use crypto_markets::get_symbols_by_pair;
+/// use crypto_market_type::MarketType;
+/// let symbols = get_symbols_by_pair("binance", MarketType::Spot, "BTC/USDT").unwrap();
+/// assert_eq!(vec!["BTCUSDT".to_string()], symbols);
+/// ```
+pub fn get_symbols_by_pair(
+    exchange: &str,
+    market_type: MarketType,
+    pair: &str,
+) -> Result<Vec<String>> {
+    MarketCache::default().get_symbols_by_pair(exchange, market_type, pair)
 }
@@ -1,4 +1,5 @@
 use crypto_market_type::MarketType;
+use crypto_pair::OptionKind;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
@@ -23,6 +24,28 @@ pub struct QuantityLimit {
     pub max: Option<f64>,
 }
 
+/// Exercise style of an option.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExerciseStyle {
+    /// Can be exercised only at expiry
+    European,
+    /// Can be exercised at any time before expiry
+    American,
+}
+
+/// Option-specific information of a market.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OptionInfo {
+    /// strike price
+    pub strike: f64,
+    /// call or put
+    pub option_kind: OptionKind,
+    /// exchange-specific underlying index or contract, e.g., `BTCUSDT` at Binance and `BTC-USD` at OKEx
+    pub underlying: String,
+    pub exercise_style: ExerciseStyle,
+}
+
 /// Market contains all information about a market
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Market {
@@ -39,9 +62,9 @@ pub struct Market {
     /// exchange-specific settlement currency, i.e., collateral currency, always None for spot markets
     #[serde(skip_serializing_if = "Option::is_none")]
     pub settle_id: Option<String>,
-    /// unified uppercase string of base fiat or crypto currency
+    /// canonical id of base fiat or crypto currency, see `crypto_pair::currency::canonical_id()`
     pub base: String,
-    /// unified uppercase string of quote fiat or crypto currency
+    /// canonical id of quote fiat or crypto currency
     pub quote: String,
     /// settlement currency, i.e., collateral currency, always None for spot markets
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -65,6 +88,9 @@ pub struct Market {
     /// Delivery date, unix timestamp in milliseconds, only applicable for future and option markets.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delivery_date: Option<u64>,
+    /// Strike, option kind, underlying and exercise style, only applicable for option markets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_info: Option<OptionInfo>,
     /// the original JSON string retrieved from the exchange
     pub info: Map<String, Value>,
 }
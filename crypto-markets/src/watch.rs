@@ -0,0 +1,203 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crypto_market_type::MarketType;
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{fetch_markets, Market, MarketCache, MarketDiff};
+
+/// An event about a market, emitted by `watch_markets()`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub enum MarketEvent {
+    /// A market was listed
+    NewMarket(Market),
+    /// A market disappeared from the exchange, e.g., an expired future
+    MarketDelisted(Market),
+    /// A market became active or inactive
+    StatusChanged { old: Market, new: Market },
+    /// Tick size or lot size changed
+    PrecisionChanged { old: Market, new: Market },
+    /// Contract value changed
+    ContractValueChanged { old: Market, new: Market },
+}
+
+impl MarketEvent {
+    /// Convert a diff to events, one market change might produce multiple events.
+    pub fn from_diff(diff: MarketDiff) -> Vec<MarketEvent> {
+        let mut events: Vec<MarketEvent> = Vec::new();
+        events.extend(diff.listed.into_iter().map(MarketEvent::NewMarket));
+        events.extend(diff.delisted.into_iter().map(MarketEvent::MarketDelisted));
+        for change in diff.changed {
+            let (old, new) = (change.old, change.new);
+            if old.active != new.active {
+                events.push(MarketEvent::StatusChanged {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+            if old.precision.tick_size != new.precision.tick_size
+                || old.precision.lot_size != new.precision.lot_size
+            {
+                events.push(MarketEvent::PrecisionChanged {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+            if old.contract_value != new.contract_value {
+                events.push(MarketEvent::ContractValueChanged { old, new });
+            }
+        }
+        events
+    }
+}
+
+/// Poll markets of multiple exchanges and market types, and send events to `tx`.
+///
+/// Markets are polled every TTL of `cache`, and compared with the last-known
+/// state, which is persisted in the `watch` subdirectory of `cache` rather
+/// than the cached markets, so other users of `cache` can't overwrite it and
+/// swallow events, even while the watcher isn't running. The state is only
+/// persisted after all events of a poll are sent, so that a restart doesn't
+/// lose or re-emit events. If there is no last-known state, the first
+/// successful poll only takes a baseline without emitting any event.
+///
+/// Polled markets are saved to `cache` as well, so that other users get them.
+///
+/// # Arguments
+///
+/// * `targets` - Exchanges and market types to watch
+/// * `cache` - Where polled markets and the last-known state are persisted
+/// * `tx` - The sender of events
+/// * `should_stop` - Stop all threads once it's set to true
+pub fn watch_markets(
+    targets: &[(String, MarketType)],
+    cache: MarketCache,
+    tx: Sender<MarketEvent>,
+    should_stop: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    let handles: Vec<JoinHandle<()>> = targets
+        .iter()
+        .map(|(exchange, market_type)| {
+            let (exchange, market_type) = (exchange.clone(), *market_type);
+            let (cache, tx, should_stop) = (cache.clone(), tx.clone(), should_stop.clone());
+            let state = MarketCache::new(cache.dir.join("watch"), cache.ttl);
+            std::thread::spawn(move || {
+                // None until the first successful poll if there is no last-known state
+                let mut snapshot = state.load(&exchange, market_type);
+                // check should_stop every second, so that the thread exits quickly
+                let mut elapsed = cache.ttl;
+                while !should_stop.load(Ordering::Acquire) {
+                    if elapsed >= cache.ttl {
+                        elapsed = Duration::ZERO;
+                        match fetch_markets(&exchange, market_type) {
+                            Ok(markets) => {
+                                if let Some(old_markets) = snapshot.as_ref() {
+                                    let diff = MarketDiff::new(
+                                        &exchange,
+                                        market_type,
+                                        old_markets,
+                                        &markets,
+                                    );
+                                    for event in MarketEvent::from_diff(diff) {
+                                        // the receiver has been dropped
+                                        if tx.send(event).is_err() {
+                                            return;
+                                        }
+                                    }
+                                }
+                                if let Err(err) = state.save_file(&exchange, market_type, &markets)
+                                {
+                                    warn!(
+                                        "Failed to save the state of {} {} markets, error: {}",
+                                        exchange, market_type, err
+                                    );
+                                }
+                                let _ = cache.save_file(&exchange, market_type, &markets);
+                                snapshot = Some(markets);
+                            }
+                            Err(err) => warn!(
+                                "Failed to poll {} {} markets, error: {}",
+                                exchange, market_type, err
+                            ),
+                        }
+                    }
+                    std::thread::sleep(Duration::from_secs(1));
+                    elapsed += Duration::from_secs(1);
+                }
+            })
+        })
+        .collect();
+    std::thread::spawn(move || {
+        for handle in handles {
+            let _ = handle.join();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MarketEvent;
+    use crate::{Fees, Market, MarketDiff, Precision};
+    use crypto_market_type::MarketType;
+
+    fn new_market(symbol: &str, active: bool, contract_value: f64) -> Market {
+        Market {
+            exchange: "binance".to_string(),
+            market_type: MarketType::InverseFuture,
+            symbol: symbol.to_string(),
+            base_id: "BTC".to_string(),
+            quote_id: "USD".to_string(),
+            settle_id: Some("BTC".to_string()),
+            base: "BTC".to_string(),
+            quote: "USD".to_string(),
+            settle: Some("BTC".to_string()),
+            active,
+            margin: true,
+            fees: Fees {
+                maker: 0.0001,
+                taker: 0.0005,
+            },
+            precision: Precision {
+                tick_size: 0.1,
+                lot_size: 1.0,
+            },
+            quantity_limit: None,
+            contract_value: Some(contract_value),
+            delivery_date: None,
+            option_info: None,
+            info: serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn test_from_diff() {
+        let old = vec![
+            new_market("BTCUSD_210625", true, 100.0),
+            new_market("BTCUSD_210924", true, 100.0),
+        ];
+        let new = vec![
+            new_market("BTCUSD_210924", false, 10.0),
+            new_market("BTCUSD_211231", true, 100.0),
+        ];
+        let diff = MarketDiff::new("binance", MarketType::InverseFuture, &old, &new);
+        let events = MarketEvent::from_diff(diff);
+        assert_eq!(4, events.len());
+        assert!(matches!(&events[0], MarketEvent::NewMarket(m) if m.symbol == "BTCUSD_211231"));
+        assert!(
+            matches!(&events[1], MarketEvent::MarketDelisted(m) if m.symbol == "BTCUSD_210625")
+        );
+        assert!(matches!(&events[2], MarketEvent::StatusChanged { new, .. } if !new.active));
+        assert!(
+            matches!(&events[3], MarketEvent::ContractValueChanged { new, .. } if new.contract_value == Some(10.0))
+        );
+    }
+}
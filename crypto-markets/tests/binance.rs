@@ -1,5 +1,5 @@
 use crypto_market_type::{get_market_types, MarketType};
-use crypto_markets::{fetch_markets, fetch_symbols};
+use crypto_markets::{fetch_markets, fetch_symbols, get_symbols_by_pair, ExerciseStyle};
 use crypto_pair::get_market_type;
 use test_case::test_case;
 
@@ -192,6 +192,12 @@ fn fetch_option_markets() {
     let quantity_limit = btcusd.quantity_limit.unwrap();
     assert_eq!(quantity_limit.min, 0.0002);
     assert_eq!(quantity_limit.max, Some(10000.0));
+
+    let option_info = btcusd.option_info.unwrap();
+    assert!(option_info.strike > 0.0);
+    assert!(btcusd.symbol.contains(&option_info.strike.to_string()));
+    assert_eq!(option_info.underlying, "BTCUSDT");
+    assert_eq!(option_info.exercise_style, ExerciseStyle::European);
 }
 
 #[test_case(MarketType::InverseFuture)]
@@ -202,3 +208,19 @@ fn fetch_option_markets() {
 fn test_contract_values(market_type: MarketType) {
     check_contract_values!(EXCHANGE_NAME, market_type);
 }
+
+#[test]
+fn test_get_symbols_by_pair() {
+    let symbols = get_symbols_by_pair(EXCHANGE_NAME, MarketType::Spot, "BTC/USDT").unwrap();
+    assert_eq!(vec!["BTCUSDT".to_string()], symbols);
+
+    let symbols = get_symbols_by_pair(EXCHANGE_NAME, MarketType::LinearSwap, "btc/usdt").unwrap();
+    assert_eq!(vec!["BTCUSDT".to_string()], symbols);
+
+    // all expiries
+    let symbols = get_symbols_by_pair(EXCHANGE_NAME, MarketType::InverseFuture, "BTC/USD").unwrap();
+    assert!(!symbols.is_empty());
+    for symbol in symbols.iter() {
+        assert!(symbol.starts_with("BTCUSD_"));
+    }
+}
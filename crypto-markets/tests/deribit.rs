@@ -1,5 +1,5 @@
 use crypto_market_type::MarketType;
-use crypto_markets::{fetch_markets, fetch_symbols};
+use crypto_markets::{fetch_markets, fetch_symbols, ExerciseStyle};
 use crypto_pair::get_market_type;
 use test_case::test_case;
 
@@ -106,6 +106,12 @@ fn fetch_option_markets() {
     let quantity_limit = btcusd.quantity_limit.unwrap();
     assert_eq!(quantity_limit.min, 0.1);
     assert_eq!(quantity_limit.max, None);
+
+    let option_info = btcusd.option_info.unwrap();
+    assert!(option_info.strike > 0.0);
+    assert!(btcusd.symbol.contains(&option_info.strike.to_string()));
+    assert_eq!(option_info.underlying, "btc_usd");
+    assert_eq!(option_info.exercise_style, ExerciseStyle::European);
 }
 
 #[test_case(MarketType::InverseFuture)]
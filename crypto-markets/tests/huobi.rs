@@ -1,5 +1,5 @@
 use crypto_market_type::{get_market_types, MarketType};
-use crypto_markets::{fetch_markets, fetch_symbols};
+use crypto_markets::{fetch_markets, fetch_symbols, ExerciseStyle};
 use crypto_pair::get_market_type;
 use test_case::test_case;
 
@@ -84,6 +84,26 @@ fn fetch_option_symbols() {
     }
 }
 
+#[test]
+#[ignore]
+fn fetch_option_markets() {
+    let markets = fetch_markets(EXCHANGE_NAME, MarketType::EuropeanOption).unwrap();
+    assert!(!markets.is_empty());
+
+    let btc_usdt = markets
+        .iter()
+        .find(|m| m.symbol.starts_with("BTC-USDT-"))
+        .unwrap()
+        .clone();
+    assert_eq!(btc_usdt.precision.lot_size, 1.0);
+
+    let option_info = btc_usdt.option_info.unwrap();
+    assert!(option_info.strike > 0.0);
+    assert!(btc_usdt.symbol.contains(&option_info.strike.to_string()));
+    assert_eq!(option_info.underlying, "BTC-USDT");
+    assert_eq!(option_info.exercise_style, ExerciseStyle::European);
+}
+
 #[test]
 fn fetch_spot_markets() {
     let markets = fetch_markets(EXCHANGE_NAME, MarketType::Spot).unwrap();
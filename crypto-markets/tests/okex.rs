@@ -1,5 +1,5 @@
 use crypto_market_type::{get_market_types, MarketType};
-use crypto_markets::{fetch_markets, fetch_symbols};
+use crypto_markets::{fetch_markets, fetch_symbols, ExerciseStyle};
 use crypto_pair::get_market_type;
 use test_case::test_case;
 
@@ -175,10 +175,17 @@ fn fetch_option_markets() {
     let btc_usd = markets
         .iter()
         .find(|m| m.symbol.starts_with("BTC-USD-"))
-        .unwrap();
+        .unwrap()
+        .clone();
     assert_eq!(btc_usd.precision.tick_size, 0.0005);
     assert_eq!(btc_usd.precision.lot_size, 1.0);
     assert!(btc_usd.quantity_limit.is_none());
+
+    let option_info = btc_usd.option_info.unwrap();
+    assert!(option_info.strike > 0.0);
+    assert!(btc_usd.symbol.contains(&option_info.strike.to_string()));
+    assert_eq!(option_info.underlying, "BTC-USD");
+    assert_eq!(option_info.exercise_style, ExerciseStyle::European);
 }
 
 #[test_case(MarketType::InverseFuture)]
@@ -1,9 +1,12 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::{FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    FundingRateMsg, LiquidationMsg, MarkPriceMsg, Order, OrderBookMsg, TradeMsg, TradeSide,
+};
 
 use super::super::utils::calc_quantity_and_volume;
+use crypto_pair::get_market_type;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use simple_error::SimpleError;
@@ -243,10 +246,8 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(crate) fn parse_funding_rate(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+// Returns the stream name and raw messages of markPrice streams
+fn parse_raw_mark_price(msg: &str) -> Result<(String, Vec<RawFundingRateMsg>), SimpleError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(|_e| {
         SimpleError::new(format!(
             "Failed to deserialize {} to HashMap<String, Value>",
@@ -270,6 +271,14 @@ pub(crate) fn parse_funding_rate(
             msg
         )));
     };
+    Ok((stream.to_string(), data))
+}
+
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, SimpleError> {
+    let (_, data) = parse_raw_mark_price(msg)?;
     let mut funding_rates: Vec<FundingRateMsg> = data
         .into_iter()
         .filter(|x| !x.r.is_empty())
@@ -283,6 +292,7 @@ pub(crate) fn parse_funding_rate(
             funding_rate: raw_msg.r.parse::<f64>().unwrap(),
             funding_time: raw_msg.T,
             estimated_rate: None,
+            mark_price: raw_msg.p.parse::<f64>().ok(),
             json: serde_json::to_string(&raw_msg).unwrap(),
         })
         .collect();
@@ -291,3 +301,108 @@ pub(crate) fn parse_funding_rate(
     }
     Ok(funding_rates)
 }
+
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, SimpleError> {
+    let (stream, data) = parse_raw_mark_price(msg)?;
+    let mut mark_prices: Vec<MarkPriceMsg> = data
+        .into_iter()
+        .map(|raw_msg| MarkPriceMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            // !markPrice@arr contains both swap and future symbols
+            market_type: if stream.starts_with('!') {
+                get_market_type(&raw_msg.s, EXCHANGE_NAME, Some(false))
+            } else {
+                market_type
+            },
+            symbol: raw_msg.s.clone(),
+            pair: crypto_pair::normalize_pair(&raw_msg.s, EXCHANGE_NAME).unwrap(),
+            msg_type: MessageType::MarkPrice,
+            timestamp: raw_msg.E,
+            json: serde_json::to_string(&raw_msg).unwrap(),
+            mark_price: raw_msg.p.parse::<f64>().ok(),
+            index_price: raw_msg.i.as_ref().and_then(|x| x.parse::<f64>().ok()),
+            premium_index: None,
+        })
+        .collect();
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = msg.to_string();
+    }
+    Ok(mark_prices)
+}
+
+// see https://binance-docs.github.io/apidocs/futures/en/#liquidation-order-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawForceOrder {
+    s: String,  // Symbol
+    S: String,  // Side
+    q: String,  // Original Quantity
+    p: String,  // Price
+    ap: String, // Average Price
+    T: i64,     // Order Trade Time
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawLiquidationMsg {
+    e: String, // Event type
+    E: i64,    // Event time
+    o: RawForceOrder,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawLiquidationMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawLiquidationMsg>",
+            msg
+        ))
+    })?;
+    let raw_order = ws_msg.data.o;
+    // !forceOrder@arr contains both swap and future symbols
+    let market_type = if ws_msg.stream.starts_with('!') {
+        get_market_type(&raw_order.s, EXCHANGE_NAME, Some(false))
+    } else {
+        market_type
+    };
+    let pair = crypto_pair::normalize_pair(&raw_order.s, EXCHANGE_NAME).ok_or_else(|| {
+        SimpleError::new(format!("Failed to normalize {} from {}", raw_order.s, msg))
+    })?;
+    let average_price = raw_order.ap.parse::<f64>().unwrap();
+    let price = if average_price > 0.0 {
+        average_price
+    } else {
+        raw_order.p.parse::<f64>().unwrap()
+    };
+    let quantity = raw_order.q.parse::<f64>().unwrap();
+    let (quantity_base, quantity_quote, quantity_contract) =
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+    let liquidation = LiquidationMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_order.s.clone(),
+        pair,
+        msg_type: MessageType::Liquidation,
+        timestamp: raw_order.T,
+        json: msg.to_string(),
+        side: if raw_order.S == "SELL" {
+            TradeSide::Sell
+        } else {
+            TradeSide::Buy
+        },
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+    };
+    Ok(vec![liquidation])
+}
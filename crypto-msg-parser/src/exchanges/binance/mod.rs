@@ -6,7 +6,7 @@ use std::collections::HashMap;
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::{FundingRateMsg, OrderBookMsg, TradeMsg};
+use crate::{FundingRateMsg, LiquidationMsg, MarkPriceMsg, OrderBookMsg, TradeMsg};
 
 use serde_json::Value;
 use simple_error::SimpleError;
@@ -44,6 +44,8 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
                 MessageType::Candlestick
             } else if stream.contains("markPrice") {
                 MessageType::FundingRate
+            } else if stream.contains("forceOrder") {
+                MessageType::Liquidation
             } else {
                 MessageType::Other
             }
@@ -80,6 +82,34 @@ pub(crate) fn parse_funding_rate(
     }
 }
 
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, SimpleError> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(SimpleError::new(format!(
+            "Binance {} does NOT have the markPrice channel",
+            market_type
+        )))
+    } else {
+        binance_all::parse_mark_price(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, SimpleError> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(SimpleError::new(format!(
+            "Binance {} does NOT have liquidation orders",
+            market_type
+        )))
+    } else {
+        binance_all::parse_liquidation(market_type, msg)
+    }
+}
+
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
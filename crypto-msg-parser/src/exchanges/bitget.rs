@@ -171,6 +171,7 @@ pub(crate) fn parse_funding_rate(
             funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
             funding_time: raw_msg.funding_time.parse::<i64>().unwrap(),
             estimated_rate: None,
+            mark_price: None,
             json: serde_json::to_string(&raw_msg).unwrap(),
         })
         .collect();
@@ -3,7 +3,10 @@ use crypto_msg_type::MessageType;
 use crypto_pair::get_market_type;
 
 use crate::exchanges::utils::{calc_quantity_and_volume, http_get};
-use crate::{FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    FundingRateMsg, InstrumentStatusMsg, InsuranceFundMsg, LiquidationMsg, MarkPriceMsg, Order,
+    OrderBookMsg, SettlementMsg, TradeMsg, TradeSide,
+};
 
 use chrono::prelude::*;
 use chrono::DateTime;
@@ -443,6 +446,57 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
+// see https://www.bitmex.com/app/wsAPI#Liquidation
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawLiquidationMsg {
+    orderID: String,
+    symbol: String,
+    side: String, // Sell, Buy
+    price: f64,
+    leavesQty: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://www.bitmex.com/api/explorer/#!/Insurance/Insurance_get
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawInsuranceMsg {
+    currency: String, // XBt, USDt
+    timestamp: String,
+    walletBalance: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://www.bitmex.com/api/explorer/#!/Settlement/Settlement_get
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawSettlementMsg {
+    timestamp: String,
+    symbol: String,
+    settlementType: String,
+    settledPrice: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://www.bitmex.com/api/explorer/#!/Instrument/Instrument_get
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawInstrumentMsg {
+    symbol: String,
+    state: Option<String>, // only present when changed in update messages
+    timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    markPrice: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    indicativeSettlePrice: Option<f64>, // the index price which the mark price is based on
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     table: String,
@@ -484,6 +538,14 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
             MessageType::Candlestick
         } else if table == "funding" {
             MessageType::FundingRate
+        } else if table == "liquidation" {
+            MessageType::Liquidation
+        } else if table == "insurance" {
+            MessageType::InsuranceFund
+        } else if table == "settlement" {
+            MessageType::Settlement
+        } else if table == "instrument" {
+            MessageType::InstrumentStatus
         } else {
             MessageType::Other
         }
@@ -570,6 +632,7 @@ pub(crate) fn parse_funding_rate(
                 funding_rate: raw_msg.fundingRate,
                 funding_time: settlement_time.timestamp_millis(),
                 estimated_rate: None,
+                mark_price: None,
                 json: serde_json::to_string(&raw_msg).unwrap(),
             }
         })
@@ -580,6 +643,245 @@ pub(crate) fn parse_funding_rate(
     Ok(rates)
 }
 
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+    timestamp: i64,
+) -> Result<Vec<LiquidationMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawLiquidationMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawLiquidationMsg>",
+            msg
+        ))
+    })?;
+    // update and delete actions only change leavesQty of existing liquidation orders
+    if ws_msg.action != "insert" && ws_msg.action != "partial" {
+        return Ok(Vec::new());
+    }
+    let mut liquidations: Vec<LiquidationMsg> = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None)
+            } else {
+                market_type
+            };
+            let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME).unwrap();
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                raw_msg.price,
+                raw_msg.leavesQty,
+            );
+            LiquidationMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair,
+                msg_type: MessageType::Liquidation,
+                timestamp,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+                side: if raw_msg.side == "Sell" {
+                    TradeSide::Sell
+                } else {
+                    TradeSide::Buy
+                },
+                price: raw_msg.price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+            }
+        })
+        .collect();
+    if liquidations.len() == 1 {
+        liquidations[0].json = msg.to_string();
+    }
+    Ok(liquidations)
+}
+
+// BitMEX balances are in the smallest unit of a currency, e.g., XBt(satoshi), USDt
+fn normalize_balance(currency: &str, balance: f64) -> (String, f64) {
+    let multiplier = match currency {
+        "XBt" => 1e-8,
+        "USDt" => 1e-6,
+        "Gwei" => 1e-9,
+        _ => 1.0,
+    };
+    let currency = match currency {
+        "Gwei" => "ETH".to_string(),
+        _ => crypto_pair::normalize_currency(&currency.to_uppercase(), EXCHANGE_NAME),
+    };
+    (currency, balance * multiplier)
+}
+
+pub(crate) fn parse_insurance_fund(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<InsuranceFundMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawInsuranceMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawInsuranceMsg>",
+            msg
+        ))
+    })?;
+    let mut insurance_funds: Vec<InsuranceFundMsg> = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_msg.timestamp).unwrap();
+            let (currency, balance) = normalize_balance(&raw_msg.currency, raw_msg.walletBalance);
+            InsuranceFundMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::InsuranceFund,
+                timestamp: timestamp.timestamp_millis(),
+                currency,
+                balance,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            }
+        })
+        .collect();
+    if insurance_funds.len() == 1 {
+        insurance_funds[0].json = msg.to_string();
+    }
+    Ok(insurance_funds)
+}
+
+pub(crate) fn parse_settlement(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<SettlementMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawSettlementMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawSettlementMsg>",
+            msg
+        ))
+    })?;
+    let mut settlements: Vec<SettlementMsg> = ws_msg
+        .data
+        .into_iter()
+        .filter(|x| x.settledPrice.is_some())
+        .map(|raw_msg| {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_msg.timestamp).unwrap();
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None)
+            } else {
+                market_type
+            };
+            SettlementMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME).unwrap(),
+                msg_type: MessageType::Settlement,
+                timestamp: timestamp.timestamp_millis(),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+                settlement_type: raw_msg.settlementType.clone(),
+                settlement_price: raw_msg.settledPrice.unwrap(),
+            }
+        })
+        .collect();
+    if settlements.len() == 1 {
+        settlements[0].json = msg.to_string();
+    }
+    Ok(settlements)
+}
+
+pub(crate) fn parse_instrument_status(
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<InstrumentStatusMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawInstrumentMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawInstrumentMsg>",
+            msg
+        ))
+    })?;
+    let mut statuses: Vec<InstrumentStatusMsg> = ws_msg
+        .data
+        .into_iter()
+        // most updates are prices, and indices such as .BXBT are not tradable
+        .filter(|x| x.state.is_some() && !x.symbol.starts_with('.'))
+        .map(|raw_msg| {
+            let timestamp = raw_msg
+                .timestamp
+                .as_ref()
+                .map(|x| DateTime::parse_from_rfc3339(x).unwrap().timestamp_millis())
+                .or(timestamp)
+                .expect("BitMEX instrument messages don't always have timestamp");
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None)
+            } else {
+                market_type
+            };
+            InstrumentStatusMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME).unwrap(),
+                msg_type: MessageType::InstrumentStatus,
+                timestamp,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+                status: raw_msg.state.clone().unwrap(),
+            }
+        })
+        .collect();
+    if statuses.len() == 1 {
+        statuses[0].json = msg.to_string();
+    }
+    Ok(statuses)
+}
+
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<MarkPriceMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawInstrumentMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawInstrumentMsg>",
+            msg
+        ))
+    })?;
+    let mut mark_prices: Vec<MarkPriceMsg> = ws_msg
+        .data
+        .into_iter()
+        .filter(|x| x.markPrice.is_some() && !x.symbol.starts_with('.'))
+        .map(|raw_msg| {
+            let timestamp = raw_msg
+                .timestamp
+                .as_ref()
+                .map(|x| DateTime::parse_from_rfc3339(x).unwrap().timestamp_millis())
+                .or(timestamp)
+                .expect("BitMEX instrument messages don't always have timestamp");
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None)
+            } else {
+                market_type
+            };
+            MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME).unwrap(),
+                msg_type: MessageType::MarkPrice,
+                timestamp,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+                mark_price: raw_msg.markPrice,
+                index_price: raw_msg.indicativeSettlePrice,
+                premium_index: None,
+            }
+        })
+        .collect();
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = msg.to_string();
+    }
+    Ok(mark_prices)
+}
+
 /// convert ID to price
 /// https://www.bitmex.com/app/wsAPI#OrderBookL2
 /// price = (100000000 * symbolIdx - ID) * tickSize
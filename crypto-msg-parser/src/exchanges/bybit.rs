@@ -1,8 +1,12 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::{exchanges::utils::calc_quantity_and_volume, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    exchanges::utils::calc_quantity_and_volume, InsuranceFundMsg, LiquidationMsg, Order,
+    OrderBookMsg, TradeMsg, TradeSide,
+};
 
+use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use simple_error::SimpleError;
@@ -79,6 +83,45 @@ struct RawOrderbookMsg {
     timestamp_e6: Value, // i64 or String
 }
 
+// https://bybit-exchange.github.io/docs/inverse/#t-websocketliquidation
+// https://bybit-exchange.github.io/docs/linear/#t-websocketliquidation
+#[derive(Serialize, Deserialize)]
+struct RawLiquidationMsg {
+    symbol: String,
+    side: String, // Sell, Buy, side of the liquidated position
+    price: Value, // f64 or String
+    qty: Value,   // f64 or String
+    time: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawLiquidationWebsocketMsg {
+    topic: String,
+    data: Value, // RawLiquidationMsg or Vec<RawLiquidationMsg>
+}
+
+// https://bybit-exchange.github.io/docs/inverse/#t-websocketinsurance
+#[derive(Serialize, Deserialize)]
+struct RawInsuranceMsg {
+    currency: String,
+    timestamp: String,
+    wallet_balance: f64, // in satoshi-like units, 1e-8
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+fn value_to_f64(v: &Value) -> Result<f64, SimpleError> {
+    if let Some(s) = v.as_str() {
+        s.parse::<f64>()
+            .map_err(|_e| SimpleError::new(format!("Failed to parse {} to f64", s)))
+    } else {
+        v.as_f64()
+            .ok_or_else(|| SimpleError::new(format!("{} is not a number", v)))
+    }
+}
+
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
     let ws_msg = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(|_e| {
         SimpleError::new(format!(
@@ -111,6 +154,10 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
             MessageType::Ticker
         } else if table == "klineV2" || table == "candle" {
             MessageType::Candlestick
+        } else if channel == "liquidation" {
+            MessageType::Liquidation
+        } else if channel == "insurance" {
+            MessageType::InsuranceFund
         } else {
             MessageType::Other
         }
@@ -216,6 +263,96 @@ pub(crate) fn parse_trade(
     }
 }
 
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<RawLiquidationWebsocketMsg>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to RawLiquidationWebsocketMsg",
+            msg
+        ))
+    })?;
+    let raw_msgs = if ws_msg.data.is_array() {
+        serde_json::from_value::<Vec<RawLiquidationMsg>>(ws_msg.data)
+    } else {
+        serde_json::from_value::<RawLiquidationMsg>(ws_msg.data).map(|x| vec![x])
+    }
+    .map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize the data field of {} to RawLiquidationMsg",
+            msg
+        ))
+    })?;
+
+    let mut liquidations = raw_msgs
+        .into_iter()
+        .map(|raw_msg| {
+            let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME).unwrap();
+            let price = value_to_f64(&raw_msg.price)?;
+            let quantity = value_to_f64(&raw_msg.qty)?;
+            let (quantity_base, quantity_quote, quantity_contract) =
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            Ok(LiquidationMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair,
+                msg_type: MessageType::Liquidation,
+                timestamp: raw_msg.time,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+                // Bybit publishes the side of the liquidated position, which is
+                // opposite to the side of the liquidation order
+                side: if raw_msg.side == "Buy" {
+                    TradeSide::Sell
+                } else {
+                    TradeSide::Buy
+                },
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+            })
+        })
+        .collect::<Result<Vec<LiquidationMsg>, SimpleError>>()?;
+    if liquidations.len() == 1 {
+        liquidations[0].json = msg.to_string();
+    }
+    Ok(liquidations)
+}
+
+pub(crate) fn parse_insurance_fund(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<InsuranceFundMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawInsuranceMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawInsuranceMsg>",
+            msg
+        ))
+    })?;
+    let mut insurance_funds: Vec<InsuranceFundMsg> = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_msg.timestamp).unwrap();
+            InsuranceFundMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::InsuranceFund,
+                timestamp: timestamp.timestamp_millis(),
+                currency: crypto_pair::normalize_currency(&raw_msg.currency, EXCHANGE_NAME),
+                balance: raw_msg.wallet_balance * 1e-8,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            }
+        })
+        .collect();
+    if insurance_funds.len() == 1 {
+        insurance_funds[0].json = msg.to_string();
+    }
+    Ok(insurance_funds)
+}
+
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
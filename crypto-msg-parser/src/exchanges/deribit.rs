@@ -1,7 +1,7 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::{Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{MarkPriceMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -41,6 +41,17 @@ struct RawOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
+// https://docs.deribit.com/?javascript#ticker-instrument_name-interval
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    timestamp: i64,
+    instrument_name: String,
+    mark_price: f64,
+    index_price: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct Params<T: Sized> {
     channel: String,
@@ -157,6 +168,32 @@ pub(crate) fn parse_trade(
     Ok(trades)
 }
 
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawTickerMsg>",
+            msg
+        ))
+    })?;
+    let raw_msg = ws_msg.params.data;
+    let mark_price = MarkPriceMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.instrument_name.clone(),
+        pair: crypto_pair::normalize_pair(&raw_msg.instrument_name, EXCHANGE_NAME).unwrap(),
+        msg_type: MessageType::MarkPrice,
+        timestamp: raw_msg.timestamp,
+        json: msg.to_string(),
+        mark_price: Some(raw_msg.mark_price),
+        index_price: Some(raw_msg.index_price),
+        premium_index: None,
+    };
+    Ok(vec![mark_price])
+}
+
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
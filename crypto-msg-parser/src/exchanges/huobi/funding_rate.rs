@@ -49,6 +49,7 @@ pub(super) fn parse_funding_rate(
             funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
             funding_time: raw_msg.settlement_time.parse::<i64>().unwrap(),
             estimated_rate: Some(raw_msg.estimated_rate.parse::<f64>().unwrap()),
+            mark_price: None,
             json: serde_json::to_string(&raw_msg).unwrap(),
         })
         .collect();
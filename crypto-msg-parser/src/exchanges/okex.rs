@@ -2,7 +2,7 @@ use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
 use super::utils::calc_quantity_and_volume;
-use crate::{FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{FundingRateMsg, MarkPriceMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
 use chrono::prelude::*;
 use chrono::DateTime;
@@ -56,6 +56,27 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
+// https://www.okex.com/docs/en/#futures_ws-mark_price
+// https://www.okex.com/docs/en/#ws_swap-mark_price
+#[derive(Serialize, Deserialize)]
+struct RawMarkPriceMsg {
+    instrument_id: String,
+    mark_price: String,
+    timestamp: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okex.com/docs/en/#index_ws-ticker
+#[derive(Serialize, Deserialize)]
+struct RawIndexTickerMsg {
+    instrument_id: String,
+    last: String,
+    timestamp: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     table: String,
@@ -91,7 +112,9 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
             let arr = table.split('/').collect::<Vec<&str>>();
             arr[1]
         };
-        if channel == "trade" {
+        if table == "index/ticker" {
+            MessageType::IndexPrice
+        } else if channel == "trade" {
             MessageType::Trade
         } else if channel == "depth_l2_tbt" {
             MessageType::L2Event
@@ -103,6 +126,8 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
             MessageType::Candlestick
         } else if channel == "funding_rate" {
             MessageType::FundingRate
+        } else if channel == "mark_price" {
+            MessageType::MarkPrice
         } else {
             MessageType::Other
         }
@@ -202,6 +227,7 @@ pub(crate) fn parse_funding_rate(
                 funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
                 funding_time: funding_time.timestamp_millis(),
                 estimated_rate: Some(raw_msg.estimated_rate.parse::<f64>().unwrap()),
+                mark_price: None,
                 json: serde_json::to_string(&raw_msg).unwrap(),
             }
         })
@@ -213,6 +239,79 @@ pub(crate) fn parse_funding_rate(
     Ok(rates)
 }
 
+fn parse_index_price(market_type: MarketType, msg: &str) -> Result<Vec<MarkPriceMsg>, SimpleError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawIndexTickerMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawIndexTickerMsg>",
+            msg
+        ))
+    })?;
+    let mut index_prices: Vec<MarkPriceMsg> = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_msg.timestamp).unwrap();
+            MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instrument_id.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME).unwrap(),
+                msg_type: MessageType::IndexPrice,
+                timestamp: timestamp.timestamp_millis(),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+                mark_price: None,
+                index_price: Some(raw_msg.last.parse::<f64>().unwrap()),
+                premium_index: None,
+            }
+        })
+        .collect();
+
+    if index_prices.len() == 1 {
+        index_prices[0].json = msg.to_string();
+    }
+    Ok(index_prices)
+}
+
+// Parse both mark_price and index/ticker messages
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, SimpleError> {
+    if get_msg_type(msg) == MessageType::IndexPrice {
+        return parse_index_price(market_type, msg);
+    }
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawMarkPriceMsg>>(msg).map_err(|_e| {
+        SimpleError::new(format!(
+            "Failed to deserialize {} to WebsocketMsg<RawMarkPriceMsg>",
+            msg
+        ))
+    })?;
+    let mut mark_prices: Vec<MarkPriceMsg> = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_msg.timestamp).unwrap();
+            MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instrument_id.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME).unwrap(),
+                msg_type: MessageType::MarkPrice,
+                timestamp: timestamp.timestamp_millis(),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+                mark_price: Some(raw_msg.mark_price.parse::<f64>().unwrap()),
+                index_price: None,
+                premium_index: None,
+            }
+        })
+        .collect();
+
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = msg.to_string();
+    }
+    Ok(mark_prices)
+}
+
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
@@ -152,6 +152,105 @@ pub fn parse_funding_rate(
     func(market_type, msg)
 }
 
+/// Parse mark price and index price messages.
+///
+/// The `msg_type` of each returned message is either `MarkPrice` or
+/// `IndexPrice`, depending on the channel. BitMEX instrument messages don't
+/// always have timestamp, so the caller should provide the receiving time.
+pub fn parse_mark_price(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<MarkPriceMsg>, SimpleError> {
+    match exchange {
+        "binance" => exchanges::binance::parse_mark_price(market_type, msg),
+        "bitmex" => exchanges::bitmex::parse_mark_price(market_type, msg, timestamp),
+        "deribit" => exchanges::deribit::parse_mark_price(market_type, msg),
+        "okex" => exchanges::okex::parse_mark_price(market_type, msg),
+        _ => Err(SimpleError::new(format!(
+            "{} does NOT have the mark price channel",
+            exchange
+        ))),
+    }
+}
+
+/// Parse liquidation messages.
+///
+/// BitMEX liquidation messages don't have timestamp, so the caller must
+/// provide the receiving time, otherwise an error is returned.
+pub fn parse_liquidation(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<LiquidationMsg>, SimpleError> {
+    match exchange {
+        "binance" => exchanges::binance::parse_liquidation(market_type, msg),
+        "bitmex" => {
+            let timestamp = timestamp.ok_or_else(|| {
+                SimpleError::new("BitMEX liquidation messages don't have timestamp")
+            })?;
+            exchanges::bitmex::parse_liquidation(market_type, msg, timestamp)
+        }
+        "bybit" => exchanges::bybit::parse_liquidation(market_type, msg),
+        _ => Err(SimpleError::new(format!(
+            "{} does NOT have the liquidation channel",
+            exchange
+        ))),
+    }
+}
+
+/// Parse insurance fund messages.
+pub fn parse_insurance_fund(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<InsuranceFundMsg>, SimpleError> {
+    match exchange {
+        "bitmex" => exchanges::bitmex::parse_insurance_fund(market_type, msg),
+        "bybit" => exchanges::bybit::parse_insurance_fund(market_type, msg),
+        _ => Err(SimpleError::new(format!(
+            "{} does NOT have the insurance fund channel",
+            exchange
+        ))),
+    }
+}
+
+/// Parse settlement and delivery messages.
+pub fn parse_settlement(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<SettlementMsg>, SimpleError> {
+    match exchange {
+        "bitmex" => exchanges::bitmex::parse_settlement(market_type, msg),
+        _ => Err(SimpleError::new(format!(
+            "{} does NOT have the settlement channel",
+            exchange
+        ))),
+    }
+}
+
+/// Parse instrument status messages.
+///
+/// Only status changes are returned, other updates of instruments are
+/// dropped. `timestamp` is used if a message doesn't have timestamp.
+pub fn parse_instrument_status(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<InstrumentStatusMsg>, SimpleError> {
+    match exchange {
+        "bitmex" => exchanges::bitmex::parse_instrument_status(market_type, msg, timestamp),
+        _ => Err(SimpleError::new(format!(
+            "{} does NOT have the instrument channel",
+            exchange
+        ))),
+    }
+}
+
 /// Infer the message type from the message.
 pub fn get_msg_type(exchange: &str, msg: &str) -> MessageType {
     match exchange {
@@ -142,10 +142,84 @@ pub struct FundingRateMsg {
     // Estimated funding rate between [funding_time-h, funding_time], it will be static after funding_time
     #[serde(skip_serializing_if = "Option::is_none")]
     pub estimated_rate: Option<f64>,
+    /// Mark price at `timestamp`, only available if the funding rate channel carries it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<f64>,
     /// the original JSON message
     pub json: String,
 }
 
+add_common_fields!(
+    /// Mark price, index price and premium index of a derivative contract.
+    ///
+    /// Fields not published by the exchange are `None`.
+    #[derive(Serialize, Deserialize)]
+    struct MarkPriceMsg {
+        mark_price: Option<f64>,
+        index_price: Option<f64>,
+        /// (mark_price - index_price) / index_price, or the exchange-specific premium index
+        premium_index: Option<f64>,
+    }
+);
+
+add_common_fields!(
+    /// Liquidation order, i.e., a position forcibly closed by the exchange.
+    #[derive(Serialize, Deserialize)]
+    struct LiquidationMsg {
+        /// Side of the liquidation order, Sell means a long position was liquidated
+        side: TradeSide,
+        /// Bankruptcy price or the average fill price of the liquidation order
+        price: f64,
+        /// Number of base coins
+        quantity_base: f64,
+        /// Number of quote coins(mostly USDT)
+        quantity_quote: f64,
+        /// Number of contracts
+        #[serde(skip_serializing_if = "Option::is_none")]
+        quantity_contract: Option<f64>,
+    }
+);
+
+/// Insurance fund balance of a settlement currency.
+#[derive(Serialize, Deserialize)]
+pub struct InsuranceFundMsg {
+    /// The exchange name, unique for each exchage
+    pub exchange: String,
+    /// Market type
+    pub market_type: MarketType,
+    /// Message type
+    pub msg_type: MessageType,
+    /// Unix timestamp, in milliseconds
+    pub timestamp: i64,
+
+    /// Normalized currency, e.g., BTC, USDT
+    pub currency: String,
+    /// Balance of the insurance fund, in `currency`
+    pub balance: f64,
+    /// the original JSON message
+    pub json: String,
+}
+
+add_common_fields!(
+    /// Settlement or delivery of a contract.
+    #[derive(Serialize, Deserialize)]
+    struct SettlementMsg {
+        /// Exchange-specific settlement type, e.g., BitMEX `Settlement`, `Delivery`
+        settlement_type: String,
+        /// Settlement price
+        settlement_price: f64,
+    }
+);
+
+add_common_fields!(
+    /// Status change of an instrument.
+    #[derive(Serialize, Deserialize)]
+    struct InstrumentStatusMsg {
+        /// Exchange-specific status, e.g., BitMEX `Open`, `Closed`, `Settled`, `Unlisted`
+        status: String,
+    }
+);
+
 add_common_fields!(
     /// 24hr rolling window ticker
     #[derive(Serialize, Deserialize)]
@@ -159,6 +159,7 @@ mod funding_rate {
         assert_eq!(funding_rates[0].pair, "BTC/USD".to_string());
         assert_eq!(funding_rates[0].funding_rate, 0.00073689);
         assert_eq!(funding_rates[0].funding_time, 1617321600000);
+        assert_eq!(funding_rates[0].mark_price, Some(59012.56007222));
 
         let raw_msg = r#"{"stream":"!markPrice@arr","data":[{"e":"markPriceUpdate","E":1617309501002,"s":"BTCUSD_PERP","p":"59003.37984561","P":"58896.41602208","r":"0.00073684","T":1617321600000},{"e":"markPriceUpdate","E":1617309501002,"s":"ETHUSD_PERP","p":"1981.89000000","P":"1975.18948029","r":"0.00100944","T":1617321600000}]}"#;
         let funding_rates =
@@ -448,3 +449,118 @@ mod l2_orderbook {
     #[test]
     fn option() {}
 }
+
+#[cfg(test)]
+mod liquidation {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{parse_liquidation, TradeSide};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"stream":"!forceOrder@arr","data":{"e":"forceOrder","E":1626000000123,"o":{"s":"BTCUSD_PERP","ps":"BTCUSD","S":"SELL","o":"LIMIT","f":"IOC","q":"12","p":"33000.5","ap":"33050.1","X":"FILLED","l":"12","z":"12","T":1626000000120}}}"#;
+        let liquidations =
+            &parse_liquidation("binance", MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            "binance",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            liquidation,
+            raw_msg,
+        );
+
+        assert_eq!(liquidation.timestamp, 1626000000120);
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 33050.1);
+        assert_eq!(liquidation.quantity_contract, Some(12.0));
+        assert_eq!(liquidation.quantity_quote, 12.0 * 100.0);
+    }
+
+    #[test]
+    fn inverse_future() {
+        // !forceOrder@arr contains both swap and future symbols
+        let raw_msg = r#"{"stream":"!forceOrder@arr","data":{"e":"forceOrder","E":1626000000123,"o":{"s":"ETHUSD_210924","ps":"ETHUSD","S":"BUY","o":"LIMIT","f":"IOC","q":"5","p":"2100.01","ap":"2099.5","X":"FILLED","l":"5","z":"5","T":1626000000120}}}"#;
+        let liquidation =
+            &parse_liquidation("binance", MarketType::InverseSwap, raw_msg, None).unwrap()[0];
+
+        crate::utils::check_liquidation_fields(
+            "binance",
+            MarketType::InverseFuture,
+            "ETH/USD".to_string(),
+            liquidation,
+            raw_msg,
+        );
+
+        assert_eq!(liquidation.side, TradeSide::Buy);
+        assert_eq!(liquidation.quantity_quote, 5.0 * 10.0);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"stream":"btcusdt@forceOrder","data":{"e":"forceOrder","E":1626000000123,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"33000.5","ap":"0","X":"NEW","l":"0","z":"0","T":1626000000120}}}"#;
+        let liquidation =
+            &parse_liquidation("binance", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+
+        crate::utils::check_liquidation_fields(
+            "binance",
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            liquidation,
+            raw_msg,
+        );
+
+        // falls back to the order price since the average price is 0
+        assert_eq!(liquidation.price, 33000.5);
+        assert_eq!(liquidation.quantity_base, 0.014);
+    }
+}
+
+#[cfg(test)]
+mod mark_price {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_mark_price;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"stream":"btcusd_perp@markPrice","data":{"e":"markPriceUpdate","E":1617309477000,"s":"BTCUSD_PERP","p":"59012.56007222","P":"58896.00503145","r":"0.00073689","T":1617321600000}}"#;
+        let mark_prices =
+            &parse_mark_price("binance", MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 1);
+        crate::utils::check_mark_price_fields(
+            "binance",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            &mark_prices[0],
+        );
+        assert_eq!(mark_prices[0].timestamp, 1617309477000);
+        assert_eq!(mark_prices[0].mark_price, Some(59012.56007222));
+        assert_eq!(mark_prices[0].index_price, None);
+        assert_eq!(mark_prices[0].json, raw_msg);
+    }
+
+    #[test]
+    fn linear_all() {
+        let raw_msg = r#"{"stream":"!markPrice@arr","data":[{"e":"markPriceUpdate","E":1617309501002,"s":"BTCUSDT","p":"59012.12000000","P":"58905.44123111","i":"58998.74532009","r":"0.00081223","T":1617321600000},{"e":"markPriceUpdate","E":1617309501002,"s":"BTCUSDT_210625","p":"62100.50000000","P":"62050.31000000","i":"58998.74532009","r":"","T":0}]}"#;
+        let mark_prices =
+            &parse_mark_price("binance", MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 2);
+        crate::utils::check_mark_price_fields(
+            "binance",
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            &mark_prices[0],
+        );
+        crate::utils::check_mark_price_fields(
+            "binance",
+            MarketType::LinearFuture,
+            "BTC/USDT".to_string(),
+            &mark_prices[1],
+        );
+        assert_eq!(mark_prices[0].mark_price, Some(59012.12));
+        assert_eq!(mark_prices[0].index_price, Some(58998.74532009));
+    }
+}
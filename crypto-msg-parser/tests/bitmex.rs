@@ -171,6 +171,142 @@ mod funding_rate {
     }
 }
 
+#[cfg(test)]
+mod liquidation {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{parse_liquidation, TradeSide};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"table":"liquidation","action":"insert","data":[{"orderID":"2a4c8a5a-2bd4-4b1f-9b5c-1e1f1b6b1f3c","symbol":"XBTUSD","side":"Buy","price":34523.5,"leavesQty":2400}]}"#;
+        let liquidations =
+            &parse_liquidation("bitmex", MarketType::Unknown, raw_msg, Some(1626000000123))
+                .unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            "bitmex",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            liquidation,
+            raw_msg,
+        );
+
+        assert_eq!(liquidation.timestamp, 1626000000123);
+        assert_eq!(liquidation.side, TradeSide::Buy);
+        assert_eq!(liquidation.price, 34523.5);
+        assert_eq!(liquidation.quantity_quote, 2400.0);
+        assert_eq!(liquidation.quantity_contract, Some(2400.0));
+    }
+
+    #[test]
+    fn update() {
+        let raw_msg = r#"{"table":"liquidation","action":"update","data":[{"orderID":"2a4c8a5a-2bd4-4b1f-9b5c-1e1f1b6b1f3c","symbol":"XBTUSD","side":"Buy","price":34523.5,"leavesQty":1200}]}"#;
+        let liquidations =
+            &parse_liquidation("bitmex", MarketType::Unknown, raw_msg, Some(1626000000123))
+                .unwrap();
+
+        assert!(liquidations.is_empty());
+    }
+
+    #[test]
+    fn without_timestamp() {
+        let raw_msg = r#"{"table":"liquidation","action":"insert","data":[{"orderID":"2a4c8a5a-2bd4-4b1f-9b5c-1e1f1b6b1f3c","symbol":"XBTUSD","side":"Buy","price":34523.5,"leavesQty":2400}]}"#;
+        assert!(parse_liquidation("bitmex", MarketType::Unknown, raw_msg, None).is_err());
+    }
+}
+
+#[cfg(test)]
+mod insurance_fund {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{get_msg_type, parse_insurance_fund};
+    use crypto_msg_type::MessageType;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn all() {
+        let raw_msg = r#"{"table":"insurance","action":"partial","data":[{"currency":"XBt","timestamp":"2021-07-11T12:00:00.000Z","walletBalance":9912233452377},{"currency":"USDt","timestamp":"2021-07-11T12:00:00.000Z","walletBalance":1216345001299}]}"#;
+        let insurance_funds =
+            &parse_insurance_fund("bitmex", MarketType::Unknown, raw_msg).unwrap();
+
+        assert_eq!(MessageType::InsuranceFund, get_msg_type("bitmex", raw_msg));
+        assert_eq!(insurance_funds.len(), 2);
+        assert_eq!(insurance_funds[0].currency, "BTC");
+        assert!(approx_eq!(
+            f64,
+            insurance_funds[0].balance,
+            99122.33452377,
+            epsilon = 0.0000001
+        ));
+        assert_eq!(insurance_funds[0].timestamp, 1626004800000);
+        assert_eq!(insurance_funds[1].currency, "USDT");
+        assert!(approx_eq!(
+            f64,
+            insurance_funds[1].balance,
+            1216345.001299,
+            epsilon = 0.0000001
+        ));
+    }
+}
+
+#[cfg(test)]
+mod settlement {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{get_msg_type, parse_settlement};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn inverse_future() {
+        let raw_msg = r#"{"table":"settlement","action":"insert","data":[{"timestamp":"2021-06-25T12:00:00.000Z","symbol":"XBTM21","settlementType":"Settlement","settledPrice":32111.72,"optionStrikePrice":null,"optionUnderlyingPrice":null,"bankrupt":0,"taxBase":0,"taxRate":0}]}"#;
+        let settlements = &parse_settlement("bitmex", MarketType::Unknown, raw_msg).unwrap();
+
+        assert_eq!(MessageType::Settlement, get_msg_type("bitmex", raw_msg));
+        assert_eq!(settlements.len(), 1);
+        let settlement = &settlements[0];
+        assert_eq!(settlement.market_type, MarketType::InverseFuture);
+        assert_eq!(settlement.pair, "BTC/USD");
+        assert_eq!(settlement.msg_type, MessageType::Settlement);
+        assert_eq!(settlement.timestamp, 1624622400000);
+        assert_eq!(settlement.settlement_type, "Settlement");
+        assert_eq!(settlement.settlement_price, 32111.72);
+        assert_eq!(settlement.json, raw_msg);
+    }
+}
+
+#[cfg(test)]
+mod instrument_status {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{get_msg_type, parse_instrument_status};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn state_change() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"XBTM21","state":"Settled","timestamp":"2021-06-25T12:00:00.000Z"},{"symbol":"XBTUSD","markPrice":32150.5,"timestamp":"2021-06-25T12:00:00.000Z"},{"symbol":".BXBT","state":"Unlisted","timestamp":"2021-06-25T12:00:00.000Z"}]}"#;
+        let statuses =
+            &parse_instrument_status("bitmex", MarketType::Unknown, raw_msg, None).unwrap();
+
+        assert_eq!(
+            MessageType::InstrumentStatus,
+            get_msg_type("bitmex", raw_msg)
+        );
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].symbol, "XBTM21");
+        assert_eq!(statuses[0].market_type, MarketType::InverseFuture);
+        assert_eq!(statuses[0].status, "Settled");
+        assert_eq!(statuses[0].timestamp, 1624622400000);
+    }
+
+    #[test]
+    fn price_update() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"XBTUSD","markPrice":32150.5,"timestamp":"2021-06-25T12:00:00.000Z"}]}"#;
+        let statuses =
+            &parse_instrument_status("bitmex", MarketType::Unknown, raw_msg, None).unwrap();
+
+        assert!(statuses.is_empty());
+    }
+}
+
 #[cfg(test)]
 mod l2_orderbook {
     use chrono::prelude::*;
@@ -361,3 +497,34 @@ mod l2_orderbook {
         assert_eq!(orderbook.bids[0].quantity_contract.unwrap(), 0.0);
     }
 }
+
+#[cfg(test)]
+mod mark_price {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_mark_price;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"XBTUSD","markPrice":34531.62,"indicativeSettlePrice":34510.35,"timestamp":"2021-07-11T10:40:00.000Z"}]}"#;
+        let mark_prices = &parse_mark_price("bitmex", MarketType::Unknown, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 1);
+        crate::utils::check_mark_price_fields(
+            "bitmex",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            &mark_prices[0],
+        );
+        assert_eq!(mark_prices[0].timestamp, 1626000000000);
+        assert_eq!(mark_prices[0].mark_price, Some(34531.62));
+        assert_eq!(mark_prices[0].index_price, Some(34510.35));
+    }
+
+    #[test]
+    fn no_mark_price() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"XBTUSD","lastPrice":34530,"timestamp":"2021-07-11T10:40:00.000Z"},{"symbol":".BXBT","markPrice":34510.35,"timestamp":"2021-07-11T10:40:00.000Z"}]}"#;
+        let mark_prices = &parse_mark_price("bitmex", MarketType::Unknown, raw_msg, None).unwrap();
+
+        assert!(mark_prices.is_empty());
+    }
+}
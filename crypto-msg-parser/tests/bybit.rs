@@ -83,6 +83,89 @@ mod trade {
     }
 }
 
+#[cfg(test)]
+mod liquidation {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{parse_liquidation, TradeSide};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"topic":"liquidation.BTCUSD","data":{"id":77412,"qty":1500,"side":"Buy","time":1626000000123,"symbol":"BTCUSD","price":"33012.5"}}"#;
+        let liquidations =
+            &parse_liquidation("bybit", MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            "bybit",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            liquidation,
+            raw_msg,
+        );
+
+        assert_eq!(liquidation.timestamp, 1626000000123);
+        // a long position was liquidated
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 33012.5);
+        assert_eq!(liquidation.quantity_quote, 1500.0);
+        assert_eq!(liquidation.quantity_contract, Some(1500.0));
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"topic":"liquidation.BTCUSDT","data":{"symbol":"BTCUSDT","side":"Sell","price":"33012.5","qty":"0.25","time":1626000000123}}"#;
+        let liquidation =
+            &parse_liquidation("bybit", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+
+        crate::utils::check_liquidation_fields(
+            "bybit",
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            liquidation,
+            raw_msg,
+        );
+
+        assert_eq!(liquidation.side, TradeSide::Buy);
+        assert_eq!(liquidation.quantity_base, 0.25);
+    }
+
+    #[test]
+    fn malformed_price() {
+        let raw_msg = r#"{"topic":"liquidation.BTCUSDT","data":{"symbol":"BTCUSDT","side":"Sell","price":"N/A","qty":"0.25","time":1626000000123}}"#;
+        assert!(parse_liquidation("bybit", MarketType::LinearSwap, raw_msg, None).is_err());
+    }
+}
+
+#[cfg(test)]
+mod insurance_fund {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{get_msg_type, parse_insurance_fund};
+    use crypto_msg_type::MessageType;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"topic":"insurance.BTC","data":[{"currency":"BTC","timestamp":"2021-07-11T20:00:00Z","wallet_balance":106234567890}]}"#;
+        let insurance_funds =
+            &parse_insurance_fund("bybit", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(MessageType::InsuranceFund, get_msg_type("bybit", raw_msg));
+        assert_eq!(insurance_funds.len(), 1);
+        let insurance_fund = &insurance_funds[0];
+        assert_eq!(insurance_fund.market_type, MarketType::InverseSwap);
+        assert_eq!(insurance_fund.currency, "BTC");
+        assert!(approx_eq!(
+            f64,
+            insurance_fund.balance,
+            1062.3456789,
+            epsilon = 0.0000001
+        ));
+        assert_eq!(insurance_fund.timestamp, 1626033600000);
+        assert_eq!(insurance_fund.json, raw_msg);
+    }
+}
+
 #[cfg(test)]
 mod l2_orderbook {
     use crypto_market_type::MarketType;
@@ -390,3 +390,27 @@ mod l2_orderbook {
         assert_eq!(orderbook.asks[2].quantity_contract.unwrap(), 0.5);
     }
 }
+
+#[cfg(test)]
+mod mark_price {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_mark_price;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"ticker.BTC-PERPETUAL.100ms","data":{"timestamp":1626000000123,"stats":{"volume":9863.05,"price_change":1.52,"low":33301.0,"high":34800.0},"state":"open","settlement_price":34101.57,"open_interest":1076358510,"min_price":34013.33,"max_price":35049.94,"mark_price":34531.62,"last_price":34530.0,"instrument_name":"BTC-PERPETUAL","index_price":34510.35,"funding_8h":0.00001,"estimated_delivery_price":34510.35,"current_funding":0.0,"best_bid_price":34530.0,"best_bid_amount":12000.0,"best_ask_price":34530.5,"best_ask_amount":5000.0}}}"#;
+        let mark_prices =
+            &parse_mark_price("deribit", MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 1);
+        crate::utils::check_mark_price_fields(
+            "deribit",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            &mark_prices[0],
+        );
+        assert_eq!(mark_prices[0].timestamp, 1626000000123);
+        assert_eq!(mark_prices[0].mark_price, Some(34531.62));
+        assert_eq!(mark_prices[0].index_price, Some(34510.35));
+    }
+}
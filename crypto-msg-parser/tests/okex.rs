@@ -375,3 +375,49 @@ mod l2_orderbook {
         assert_eq!(orderbook.asks[0].quantity_contract.unwrap(), 906.0);
     }
 }
+
+#[cfg(test)]
+mod mark_price {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{get_msg_type, parse_mark_price};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"table":"swap/mark_price","data":[{"instrument_id":"BTC-USD-SWAP","mark_price":"34531.6","timestamp":"2021-07-11T10:40:00.123Z"}]}"#;
+        assert_eq!(MessageType::MarkPrice, get_msg_type("okex", raw_msg));
+        let mark_prices =
+            &parse_mark_price("okex", MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 1);
+        crate::utils::check_mark_price_fields(
+            "okex",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            &mark_prices[0],
+        );
+        assert_eq!(mark_prices[0].msg_type, MessageType::MarkPrice);
+        assert_eq!(mark_prices[0].timestamp, 1626000000123);
+        assert_eq!(mark_prices[0].mark_price, Some(34531.6));
+    }
+
+    #[test]
+    fn index_price() {
+        let raw_msg = r#"{"table":"index/ticker","data":[{"last":"34510.3","open_24h":"33700.1","high_24h":"34800.0","low_24h":"33500.2","instrument_id":"BTC-USD","timestamp":"2021-07-11T10:40:00.123Z"}]}"#;
+        assert_eq!(MessageType::IndexPrice, get_msg_type("okex", raw_msg));
+        let index_prices =
+            &parse_mark_price("okex", MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(index_prices.len(), 1);
+        crate::utils::check_mark_price_fields(
+            "okex",
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            &index_prices[0],
+        );
+        assert_eq!(index_prices[0].msg_type, MessageType::IndexPrice);
+        assert_eq!(index_prices[0].symbol, "BTC-USD");
+        assert_eq!(index_prices[0].mark_price, None);
+        assert_eq!(index_prices[0].index_price, Some(34510.3));
+    }
+}
@@ -1,7 +1,9 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_msg_parser::{get_msg_type, FundingRateMsg, OrderBookMsg, TradeMsg};
+use crypto_msg_parser::{
+    get_msg_type, FundingRateMsg, LiquidationMsg, MarkPriceMsg, OrderBookMsg, TradeMsg,
+};
 use float_cmp::approx_eq;
 
 pub fn check_trade_fields(
@@ -97,3 +99,41 @@ pub fn check_funding_rate_fields(
         assert_eq!(funding_rate.funding_time % (8 * 3600000), 0);
     }
 }
+
+#[allow(dead_code)]
+pub fn check_liquidation_fields(
+    exchange: &str,
+    market_type: MarketType,
+    pair: String,
+    liquidation: &LiquidationMsg,
+    raw_msg: &str,
+) {
+    assert_eq!(liquidation.exchange, exchange);
+    assert_eq!(liquidation.market_type, market_type);
+    assert_eq!(liquidation.pair, pair);
+    assert_eq!(liquidation.msg_type, MessageType::Liquidation);
+    assert_eq!(MessageType::Liquidation, get_msg_type(exchange, raw_msg));
+    assert!(liquidation.price > 0.0);
+    assert!(liquidation.quantity_base > 0.0);
+    assert!(liquidation.quantity_quote > 0.0);
+    assert!(liquidation.quantity_contract.unwrap() > 0.0);
+    assert_eq!(liquidation.timestamp.to_string().len(), 13);
+}
+
+#[allow(dead_code)]
+pub fn check_mark_price_fields(
+    exchange: &str,
+    market_type: MarketType,
+    pair: String,
+    mark_price: &MarkPriceMsg,
+) {
+    assert_eq!(mark_price.exchange, exchange);
+    assert_eq!(mark_price.market_type, market_type);
+    assert_eq!(mark_price.pair, pair);
+    match mark_price.msg_type {
+        MessageType::MarkPrice => assert!(mark_price.mark_price.unwrap() > 0.0),
+        MessageType::IndexPrice => assert!(mark_price.index_price.unwrap() > 0.0),
+        _ => panic!("Unexpected message type {}", mark_price.msg_type),
+    }
+    assert_eq!(mark_price.timestamp.to_string().len(), 13);
+}
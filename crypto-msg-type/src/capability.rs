@@ -0,0 +1,382 @@
+use crypto_market_type::{Exchange, MarketType};
+use serde::{Deserialize, Serialize};
+
+use crate::MessageType;
+
+const CONTRACT_MARKETS: &[MarketType] = &[
+    MarketType::LinearFuture,
+    MarketType::InverseFuture,
+    MarketType::LinearSwap,
+    MarketType::InverseSwap,
+    MarketType::AmericanOption,
+    MarketType::EuropeanOption,
+    MarketType::QuantoFuture,
+    MarketType::QuantoSwap,
+    MarketType::Move,
+    MarketType::BVOL,
+];
+
+const FUTURE_MARKETS: &[MarketType] = &[
+    MarketType::LinearFuture,
+    MarketType::InverseFuture,
+    MarketType::QuantoFuture,
+];
+
+const SWAP_MARKETS: &[MarketType] = &[
+    MarketType::LinearSwap,
+    MarketType::InverseSwap,
+    MarketType::QuantoSwap,
+];
+
+// The unknown market type means all markets of an exchange, which have to be crawled together
+const UNKNOWN_MARKET: &[MarketType] = &[MarketType::Unknown];
+
+// Message types which are supported end-to-end, i.e., by crypto-crawler,
+// crypto-ws-client or crypto-rest-client, and crypto-msg-parser.
+//
+// (message type, exchanges, market types), empty market types means all market types of an exchange,
+// except the unknown market type, which is supported only if listed explicitly.
+#[allow(clippy::type_complexity)]
+const CAPABILITIES: &[(MessageType, &[&str], &[MarketType])] = &[
+    (
+        MessageType::Trade,
+        &[
+            "binance",
+            "bitfinex",
+            "bithumb",
+            "bitmex",
+            "bitstamp",
+            "bybit",
+            "coinbase_pro",
+            "deribit",
+            "dydx",
+            "ftx",
+            "gate",
+            "huobi",
+            "kraken",
+            "kucoin",
+            "mxc",
+            "okex",
+            "zbg",
+        ],
+        &[],
+    ),
+    (
+        MessageType::L2Event,
+        &[
+            "binance",
+            "bitfinex",
+            "bithumb",
+            "bitmex",
+            "bitstamp",
+            "bybit",
+            "coinbase_pro",
+            "deribit",
+            "dydx",
+            "ftx",
+            "gate",
+            "huobi",
+            "kraken",
+            "kraken_futures",
+            "kucoin",
+            "mxc",
+            "okex",
+            "zbg",
+        ],
+        &[],
+    ),
+    (
+        MessageType::L2Snapshot,
+        &[
+            "binance",
+            "bitfinex",
+            "bitget",
+            "bithumb",
+            "bitmex",
+            "bitstamp",
+            "bitz",
+            "bybit",
+            "coinbase_pro",
+            "deribit",
+            "dydx",
+            "ftx",
+            "gate",
+            "huobi",
+            "kraken",
+            "kucoin",
+            "mxc",
+            "okex",
+            "zbg",
+        ],
+        &[],
+    ),
+    (
+        MessageType::L2TopK,
+        &[
+            "binance", "bitmex", "bitstamp", "bybit", "deribit", "huobi", "kucoin", "mxc", "okex",
+        ],
+        &[],
+    ),
+    (
+        MessageType::L3Event,
+        &["bitfinex", "bitstamp", "coinbase_pro", "kucoin"],
+        &[],
+    ),
+    (
+        MessageType::L3Snapshot,
+        &["bitfinex", "bitstamp", "coinbase_pro", "kucoin"],
+        &[],
+    ),
+    (
+        MessageType::BBO,
+        &[
+            "binance", "bitfinex", "bitmex", "deribit", "ftx", "huobi", "kraken", "kucoin", "okex",
+        ],
+        &[],
+    ),
+    // Gate futures don't have the BBO channel
+    (
+        MessageType::BBO,
+        &["gate"],
+        &[
+            MarketType::Spot,
+            MarketType::InverseSwap,
+            MarketType::LinearSwap,
+        ],
+    ),
+    (
+        MessageType::Ticker,
+        &[
+            "binance",
+            "bitfinex",
+            "bithumb",
+            "bybit",
+            "coinbase_pro",
+            "deribit",
+            "gate",
+            "huobi",
+            "kraken",
+            "kucoin",
+            "mxc",
+            "okex",
+            "zbg",
+        ],
+        &[],
+    ),
+    (
+        MessageType::Candlestick,
+        &[
+            "binance", "bitfinex", "bitmex", "bybit", "deribit", "gate", "huobi", "kraken",
+            "kucoin", "mxc", "okex", "zbg",
+        ],
+        &[],
+    ),
+    (
+        MessageType::FundingRate,
+        &["binance", "bitget", "bitmex", "huobi", "okex"],
+        SWAP_MARKETS,
+    ),
+    (
+        MessageType::OpenInterest,
+        &[
+            "binance", "bitget", "bitz", "bybit", "deribit", "dydx", "ftx", "gate", "huobi",
+            "kucoin", "okex", "zbg",
+        ],
+        CONTRACT_MARKETS,
+    ),
+    (
+        MessageType::MarkPrice,
+        &["bitmex", "deribit"],
+        CONTRACT_MARKETS,
+    ),
+    (
+        MessageType::MarkPrice,
+        &["binance", "okex"],
+        &[
+            MarketType::InverseFuture,
+            MarketType::InverseSwap,
+            MarketType::LinearFuture,
+            MarketType::LinearSwap,
+        ],
+    ),
+    (
+        MessageType::IndexPrice,
+        &["okex"],
+        &[
+            MarketType::InverseFuture,
+            MarketType::InverseSwap,
+            MarketType::LinearFuture,
+            MarketType::LinearSwap,
+        ],
+    ),
+    (
+        MessageType::Liquidation,
+        &["binance", "bitmex", "bybit"],
+        CONTRACT_MARKETS,
+    ),
+    (MessageType::InsuranceFund, &["bitmex"], CONTRACT_MARKETS),
+    (
+        MessageType::InsuranceFund,
+        &["bybit"],
+        &[MarketType::InverseSwap, MarketType::InverseFuture],
+    ),
+    (MessageType::Settlement, &["bitmex"], FUTURE_MARKETS),
+    (MessageType::InstrumentStatus, &["bitmex"], CONTRACT_MARKETS),
+    // Bitget has websocket APIs only in swap markets, and Bitz only in the spot market
+    (MessageType::Trade, &["bitget"], SWAP_MARKETS),
+    (MessageType::L2Event, &["bitget"], SWAP_MARKETS),
+    (MessageType::L2TopK, &["bitget"], SWAP_MARKETS),
+    (MessageType::Ticker, &["bitget"], SWAP_MARKETS),
+    (MessageType::Candlestick, &["bitget"], SWAP_MARKETS),
+    (MessageType::Trade, &["bitz"], &[MarketType::Spot]),
+    (MessageType::L2Event, &["bitz"], &[MarketType::Spot]),
+    (MessageType::Ticker, &["bitz"], &[MarketType::Spot]),
+    (MessageType::Candlestick, &["bitz"], &[MarketType::Spot]),
+    // Miscellaneous channels, see crypto_crawler::crawl_other()
+    (
+        MessageType::Other,
+        &["bitmex", "coinbase_pro", "huobi"],
+        &[],
+    ),
+    // BitMEX channels without symbols push messages of all markets
+    (MessageType::Trade, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::L2Event, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::L2Snapshot, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::L2TopK, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::BBO, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::Candlestick, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::FundingRate, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::MarkPrice, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::Liquidation, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::InsuranceFund, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::Settlement, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::InstrumentStatus, &["bitmex"], UNKNOWN_MARKET),
+    (MessageType::Other, &["bitmex"], UNKNOWN_MARKET),
+    // One RESTful API returns open interests of all markets
+    (
+        MessageType::OpenInterest,
+        &["ftx", "kucoin"],
+        UNKNOWN_MARKET,
+    ),
+];
+
+/// Message types supported in a market of an exchange.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Capability {
+    pub exchange: Exchange,
+    pub market_type: MarketType,
+    pub msg_types: Vec<MessageType>,
+}
+
+/// Check whether a message type can be crawled and parsed in a market of an exchange.
+///
+/// Returns false if the exchange is unknown or doesn't have the market type.
+/// The unknown market type means all markets of an exchange, e.g., BitMEX.
+pub fn supports(exchange: &str, market_type: MarketType, msg_type: MessageType) -> bool {
+    if market_type != MarketType::Unknown
+        && !crypto_market_type::get_market_types(exchange).contains(&market_type)
+    {
+        return false;
+    }
+    CAPABILITIES
+        .iter()
+        .any(|(supported_msg_type, exchanges, market_types)| {
+            *supported_msg_type == msg_type
+                && exchanges.contains(&exchange)
+                && if market_type == MarketType::Unknown {
+                    market_types.contains(&market_type)
+                } else {
+                    market_types.is_empty() || market_types.contains(&market_type)
+                }
+        })
+}
+
+/// Get message types supported in a market of an exchange.
+pub fn get_msg_types(exchange: &str, market_type: MarketType) -> Vec<MessageType> {
+    let mut msg_types: Vec<MessageType> = Vec::new();
+    for (msg_type, _, _) in CAPABILITIES.iter() {
+        if !msg_types.contains(msg_type) && supports(exchange, market_type, *msg_type) {
+            msg_types.push(*msg_type);
+        }
+    }
+    msg_types
+}
+
+/// Get the whole capability matrix, one entry per market of an exchange.
+///
+/// # Example
+///
+/// ```
+/// use crypto_msg_type::get_capabilities;
+///
+/// let capabilities = get_capabilities();
+/// println!("{}", serde_json::to_string_pretty(&capabilities).unwrap());
+/// ```
+pub fn get_capabilities() -> Vec<Capability> {
+    let mut capabilities: Vec<Capability> = Vec::new();
+    for exchange in Exchange::ALL {
+        for market_type in exchange.market_types() {
+            capabilities.push(Capability {
+                exchange: *exchange,
+                market_type,
+                msg_types: get_msg_types(&exchange.to_string(), market_type),
+            });
+        }
+    }
+    capabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supports() {
+        assert!(supports("binance", MarketType::Spot, MessageType::Trade));
+        assert!(supports(
+            "binance",
+            MarketType::InverseSwap,
+            MessageType::FundingRate
+        ));
+        assert!(!supports(
+            "binance",
+            MarketType::Spot,
+            MessageType::FundingRate
+        ));
+        assert!(!supports("binance", MarketType::Spot, MessageType::L3Event));
+        assert!(supports("gate", MarketType::Spot, MessageType::BBO));
+        assert!(!supports(
+            "gate",
+            MarketType::LinearFuture,
+            MessageType::BBO
+        ));
+        assert!(!supports("bitget", MarketType::Spot, MessageType::Trade));
+        assert!(supports(
+            "bitget",
+            MarketType::LinearSwap,
+            MessageType::Trade
+        ));
+        // kraken doesn't have swap markets
+        assert!(!supports(
+            "kraken",
+            MarketType::LinearSwap,
+            MessageType::Trade
+        ));
+        assert!(!supports("unknown", MarketType::Spot, MessageType::Trade));
+        // unknown market types are listed explicitly
+        assert!(supports("bitmex", MarketType::Unknown, MessageType::Other));
+        assert!(supports("huobi", MarketType::Spot, MessageType::Other));
+        assert!(!supports("huobi", MarketType::Unknown, MessageType::Other));
+        assert!(!supports("huobi", MarketType::Unknown, MessageType::Trade));
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = get_capabilities();
+        for exchange in Exchange::ALL {
+            assert!(capabilities
+                .iter()
+                .any(|x| x.exchange == *exchange && !x.msg_types.is_empty()));
+        }
+    }
+}
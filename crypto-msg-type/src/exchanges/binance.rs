@@ -10,6 +10,8 @@ fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
         MessageType::BBO => "bookTicker",
         MessageType::Ticker => "ticker",
         MessageType::Candlestick => "kline",
+        MessageType::MarkPrice => "markPrice",
+        MessageType::Liquidation => "forceOrder",
         _ => panic!("Unknown message type {}", msg_type),
     }
 }
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+// User has the opinion to subscribe 1 or more channels, total length of multiple channel can not exceeds 4096 bytes.
+const WS_FRAME_SIZE: usize = 4096;
+
+fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
+    match msg_type {
+        MessageType::Trade => "trade",
+        MessageType::L2Event => "depth",
+        MessageType::L2TopK => "depth5",
+        MessageType::Ticker => "ticker",
+        MessageType::Candlestick => "candle",
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn channel_symbol_to_topic(
+    channel: &str,
+    symbol: &str,
+    configs: Option<&HashMap<String, String>>,
+) -> String {
+    if channel == "candle" {
+        format!(
+            "swap/candle{}s:{}",
+            configs.unwrap().get("interval").unwrap(),
+            symbol
+        )
+    } else {
+        format!("swap/{}:{}", channel, symbol)
+    }
+}
+
+fn topics_to_command(topics: &[String], subscribe: bool) -> String {
+    format!(
+        r#"{{"op":"{}","args":{}}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        serde_json::to_string(topics).unwrap()
+    )
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    let topics = msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_channel(*msg_type))
+        .flat_map(|channel| {
+            symbols
+                .iter()
+                .map(|symbol| channel_symbol_to_topic(channel, symbol, configs))
+        })
+        .collect::<Vec<String>>();
+
+    // split topics into multiple commands so that each command fits in one frame
+    let mut commands: Vec<String> = Vec::new();
+    let mut chunk: Vec<String> = Vec::new();
+    for topic in topics {
+        chunk.push(topic);
+        if chunk.len() > 1 && topics_to_command(&chunk, subscribe).len() > WS_FRAME_SIZE {
+            let topic = chunk.pop().unwrap();
+            commands.push(topics_to_command(&chunk, subscribe));
+            chunk = vec![topic];
+        }
+    }
+    if !chunk.is_empty() {
+        commands.push(topics_to_command(&chunk, subscribe));
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["btcusd".to_string(), "cmt_btcusdt".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"op":"subscribe","args":["swap/trade:btcusd","swap/trade:cmt_btcusdt"]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["cmt_btcusdt".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"op":"unsubscribe","args":["swap/trade:cmt_btcusdt","swap/depth:cmt_btcusdt"]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn candlestick() {
+        let mut configs = HashMap::new();
+        configs.insert("interval".to_string(), "60".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Candlestick],
+            &["cmt_btcusdt".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"op":"subscribe","args":["swap/candle60s:cmt_btcusdt"]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn frame_size() {
+        let symbols: Vec<String> = (0..300).map(|i| format!("cmt_coin{}usdt", i)).collect();
+        let commands = get_ws_commands(&[MessageType::Trade], &symbols, true, None);
+        assert!(commands.len() > 1);
+        assert!(commands.iter().all(|x| x.len() <= WS_FRAME_SIZE));
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
+    match msg_type {
+        MessageType::Trade => "TRADE",
+        MessageType::L2Event => "ORDERBOOK",
+        MessageType::Ticker => "TICKER",
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    _configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    let topics = msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_channel(*msg_type))
+        .flat_map(|channel| {
+            symbols
+                .iter()
+                .map(move |symbol| format!("{}:{}", channel, symbol))
+        })
+        .collect::<Vec<String>>();
+    vec![format!(
+        r#"{{"cmd":"{}","args":{}}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        serde_json::to_string(&topics).unwrap()
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["BTC-USDT".to_string(), "ETH-USDT".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"cmd":"subscribe","args":["TRADE:BTC-USDT","TRADE:ETH-USDT"]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["BTC-USDT".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"cmd":"unsubscribe","args":["TRADE:BTC-USDT","ORDERBOOK:BTC-USDT"]}"#,
+            commands[0]
+        );
+    }
+}
@@ -9,6 +9,8 @@ fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
         MessageType::L2TopK => "orderBook10",
         MessageType::BBO => "quote",
         MessageType::Candlestick => "tradeBin",
+        MessageType::MarkPrice => "instrument",
+        MessageType::Liquidation => "liquidation",
         _ => panic!("Unknown message type {}", msg_type),
     }
 }
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
+    match msg_type {
+        MessageType::Trade => "live_trades",
+        MessageType::L2Event => "diff_order_book",
+        MessageType::L2TopK => "order_book",
+        MessageType::L3Event => "live_orders",
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn channel_symbol_to_command(channel: &str, symbol: &str, subscribe: bool) -> String {
+    format!(
+        r#"{{"event":"bts:{}","data":{{"channel":"{}_{}"}}}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        channel,
+        symbol
+    )
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    _configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_channel(*msg_type))
+        .flat_map(|channel| {
+            symbols
+                .iter()
+                .map(move |symbol| channel_symbol_to_command(channel, symbol, subscribe))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["btcusd".to_string(), "ethusd".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"event":"bts:subscribe","data":{"channel":"live_trades_btcusd"}}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"event":"bts:subscribe","data":{"channel":"live_trades_ethusd"}}"#,
+            commands[1]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["btcusd".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"event":"bts:unsubscribe","data":{"channel":"live_trades_btcusd"}}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"event":"bts:unsubscribe","data":{"channel":"diff_order_book_btcusd"}}"#,
+            commands[1]
+        );
+    }
+}
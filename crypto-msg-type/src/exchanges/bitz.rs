@@ -0,0 +1,113 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::MessageType;
+
+fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
+    match msg_type {
+        MessageType::Trade => "order",
+        MessageType::L2Event => "depth",
+        MessageType::Ticker => "market",
+        MessageType::Candlestick => "kline",
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis()
+}
+
+// Channels of a symbol are subscribed in one command, except kline which needs a resolution
+fn symbol_channels_to_command(symbol: &str, channels: &[&str], subscribe: bool) -> String {
+    format!(
+        r#"{{"action":"Topic.{}", "data":{{"symbol":"{}", "type":"{}", "_CDID":"100002", "dataType":"1"}}, "msg_id":{}}}"#,
+        if subscribe { "sub" } else { "unsub" },
+        symbol,
+        channels.join(","),
+        now_millis(),
+    )
+}
+
+fn symbol_to_kline_command(symbol: &str, resolution: &str, subscribe: bool) -> String {
+    format!(
+        r#"{{"action":"Topic.{}", "data":{{"symbol":"{}", "type":"kline", "resolution":"{}", "_CDID":"100002", "dataType":"1"}}, "msg_id":{}}}"#,
+        if subscribe { "sub" } else { "unsub" },
+        symbol,
+        resolution,
+        now_millis(),
+    )
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    let channels = msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_channel(*msg_type))
+        .filter(|channel| *channel != "kline")
+        .collect::<Vec<&str>>();
+    let mut commands: Vec<String> = Vec::new();
+    for symbol in symbols.iter() {
+        if !channels.is_empty() {
+            commands.push(symbol_channels_to_command(symbol, &channels, subscribe));
+        }
+        if msg_types.contains(&MessageType::Candlestick) {
+            let resolution = configs.unwrap().get("interval").unwrap();
+            commands.push(symbol_to_kline_command(symbol, resolution, subscribe));
+        }
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["btc_usdt".to_string(), "eth_usdt".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert!(commands[0].starts_with(
+            r#"{"action":"Topic.sub", "data":{"symbol":"btc_usdt", "type":"order", "_CDID":"100002", "dataType":"1"}, "msg_id":"#
+        ));
+        assert!(commands[1].starts_with(
+            r#"{"action":"Topic.sub", "data":{"symbol":"eth_usdt", "type":"order", "_CDID":"100002", "dataType":"1"}, "msg_id":"#
+        ));
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let mut configs = HashMap::new();
+        configs.insert("interval".to_string(), "1min".to_string());
+        let commands = get_ws_commands(
+            &[
+                MessageType::Trade,
+                MessageType::L2Event,
+                MessageType::Candlestick,
+            ],
+            &["btc_usdt".to_string()],
+            false,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 2);
+        assert!(commands[0].starts_with(
+            r#"{"action":"Topic.unsub", "data":{"symbol":"btc_usdt", "type":"order,depth", "_CDID":"100002", "dataType":"1"}, "msg_id":"#
+        ));
+        assert!(commands[1].starts_with(
+            r#"{"action":"Topic.unsub", "data":{"symbol":"btc_usdt", "type":"kline", "resolution":"1min", "_CDID":"100002", "dataType":"1"}, "msg_id":"#
+        ));
+    }
+}
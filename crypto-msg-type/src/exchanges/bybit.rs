@@ -8,6 +8,7 @@ fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
         MessageType::L2Event => "orderBookL2_25",
         MessageType::Ticker => "instrument_info.100ms",
         MessageType::Candlestick => "klineV2",
+        MessageType::Liquidation => "liquidation",
         _ => panic!("Unknown message type {}", msg_type),
     }
 }
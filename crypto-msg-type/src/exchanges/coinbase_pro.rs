@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
+    match msg_type {
+        MessageType::Trade => "matches",
+        MessageType::L2Event => "level2",
+        MessageType::L3Event => "full",
+        MessageType::Ticker => "ticker",
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn channel_symbols_to_json(channel: &str, symbols: &[String]) -> String {
+    format!(
+        r#"{{"name":"{}","product_ids":{}}}"#,
+        channel,
+        serde_json::to_string(symbols).unwrap(),
+    )
+}
+
+// All channels are subscribed in one command
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    _configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    let channels = msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_channel(*msg_type))
+        .map(|channel| channel_symbols_to_json(channel, symbols))
+        .collect::<Vec<String>>();
+    vec![format!(
+        r#"{{"type":"{}","channels": [{}]}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        channels.join(",")
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["BTC-USD".to_string(), "ETH-USD".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"type":"subscribe","channels": [{"name":"matches","product_ids":["BTC-USD","ETH-USD"]}]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["BTC-USD".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"type":"unsubscribe","channels": [{"name":"matches","product_ids":["BTC-USD"]},{"name":"level2","product_ids":["BTC-USD"]}]}"#,
+            commands[0]
+        );
+    }
+}
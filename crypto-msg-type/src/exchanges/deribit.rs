@@ -17,7 +17,7 @@ fn msg_type_symbol_to_topic(
             symbol,
             configs.unwrap().get("interval").unwrap()
         ),
-        MessageType::Ticker => format!("ticker.{}.100ms", symbol),
+        MessageType::Ticker | MessageType::MarkPrice => format!("ticker.{}.100ms", symbol),
         _ => panic!("Unknown message type {}", msg_type),
     }
 }
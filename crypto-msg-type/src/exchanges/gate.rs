@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+// Spot and swap symbols look the same, e.g., BTC_USDT, so the market type is
+// read from `configs["market_type"]`, while futures have a date suffix, e.g., BTC_USD_20211231.
+fn get_channel_prefix(symbol: &str, configs: Option<&HashMap<String, String>>) -> &'static str {
+    if symbol.matches('_').count() > 1 {
+        return "futures";
+    }
+    let market_type = configs
+        .and_then(|x| x.get("market_type"))
+        .map(|x| x.as_str())
+        .unwrap_or("spot");
+    if market_type == "spot" {
+        "spot"
+    } else {
+        "futures"
+    }
+}
+
+fn msg_type_to_channel(msg_type: MessageType, symbol: &str) -> &'static str {
+    let is_future = symbol.matches('_').count() > 1;
+    match msg_type {
+        MessageType::Trade => "trades",
+        MessageType::L2Event => {
+            // Gate futures have no incremental orderbook channel
+            if is_future {
+                "order_book"
+            } else {
+                "order_book_update"
+            }
+        }
+        MessageType::L2TopK if !is_future => "order_book",
+        MessageType::BBO if !is_future => "book_ticker",
+        MessageType::Ticker => "tickers",
+        MessageType::Candlestick => "candlesticks",
+        _ => panic!("Unknown message type {} for {}", msg_type, symbol),
+    }
+}
+
+fn orderbook_payload(channel: &str, symbol: &str, prefix: &str) -> String {
+    if channel == "order_book" {
+        if prefix == "spot" {
+            serde_json::to_string(&[symbol, "20", "1000ms"]).unwrap()
+        } else {
+            serde_json::to_string(&[symbol, "20", "0"]).unwrap()
+        }
+    } else if prefix == "spot" {
+        serde_json::to_string(&[symbol, "100ms"]).unwrap()
+    } else {
+        serde_json::to_string(&[symbol, "100ms", "20"]).unwrap()
+    }
+}
+
+fn channel_to_command(prefix: &str, channel: &str, payload: &str, subscribe: bool) -> String {
+    format!(
+        r#"{{"channel":"{}.{}", "event":"{}", "payload":{}}}"#,
+        prefix,
+        channel,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        payload,
+    )
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    let mut commands: Vec<String> = Vec::new();
+    for msg_type in msg_types.iter() {
+        // group symbols by channel, since non-orderbook channels accept multiple symbols
+        let mut channel_symbols: Vec<(&str, &str, Vec<&str>)> = Vec::new();
+        for symbol in symbols.iter() {
+            let prefix = get_channel_prefix(symbol, configs);
+            let channel = msg_type_to_channel(*msg_type, symbol);
+            match channel {
+                "order_book" | "order_book_update" => {
+                    let payload = orderbook_payload(channel, symbol, prefix);
+                    commands.push(channel_to_command(prefix, channel, &payload, subscribe));
+                }
+                "candlesticks" => {
+                    let payload = serde_json::to_string(&[
+                        configs.unwrap().get("interval").unwrap().as_str(),
+                        symbol.as_str(),
+                    ])
+                    .unwrap();
+                    commands.push(channel_to_command(prefix, channel, &payload, subscribe));
+                }
+                _ => {
+                    if let Some((_, _, v)) = channel_symbols
+                        .iter_mut()
+                        .find(|(p, c, _)| *p == prefix && *c == channel)
+                    {
+                        v.push(symbol);
+                    } else {
+                        channel_symbols.push((prefix, channel, vec![symbol]));
+                    }
+                }
+            }
+        }
+        for (prefix, channel, symbols) in channel_symbols {
+            let payload = serde_json::to_string(&symbols).unwrap();
+            commands.push(channel_to_command(prefix, channel, &payload, subscribe));
+        }
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["BTC_USDT".to_string(), "ETH_USDT".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"channel":"spot.trades", "event":"subscribe", "payload":["BTC_USDT","ETH_USDT"]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let mut configs = HashMap::new();
+        configs.insert("market_type".to_string(), "linear_swap".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["BTC_USDT".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"channel":"futures.trades", "event":"subscribe", "payload":["BTC_USDT"]}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"channel":"futures.order_book_update", "event":"subscribe", "payload":["BTC_USDT","100ms","20"]}"#,
+            commands[1]
+        );
+    }
+
+    #[test]
+    fn future() {
+        let commands = get_ws_commands(
+            &[MessageType::L2Event],
+            &["BTC_USD_20211231".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"channel":"futures.order_book", "event":"unsubscribe", "payload":["BTC_USD_20211231","20","0"]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn candlestick() {
+        let mut configs = HashMap::new();
+        configs.insert("interval".to_string(), "1m".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Candlestick],
+            &["BTC_USDT".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"channel":"spot.candlesticks", "event":"subscribe", "payload":["1m","BTC_USDT"]}"#,
+            commands[0]
+        );
+    }
+}
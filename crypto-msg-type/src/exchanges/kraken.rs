@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+fn msg_type_to_subscription(
+    msg_type: MessageType,
+    configs: Option<&HashMap<String, String>>,
+) -> String {
+    match msg_type {
+        MessageType::Trade => r#"{"name":"trade"}"#.to_string(),
+        MessageType::L2Event => r#"{"name":"book", "depth":25}"#.to_string(),
+        MessageType::BBO => r#"{"name":"spread"}"#.to_string(),
+        MessageType::Ticker => r#"{"name":"ticker"}"#.to_string(),
+        MessageType::Candlestick => format!(
+            r#"{{"name":"ohlc", "interval":{}}}"#,
+            configs.unwrap().get("interval").unwrap()
+        ),
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn subscription_symbols_to_command(
+    subscription: &str,
+    symbols: &[String],
+    subscribe: bool,
+) -> String {
+    format!(
+        r#"{{"event":"{}","pair":{},"subscription":{}}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        serde_json::to_string(symbols).unwrap(),
+        subscription
+    )
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_subscription(*msg_type, configs))
+        .map(|subscription| subscription_symbols_to_command(&subscription, symbols, subscribe))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["XBT/USD".to_string(), "ETH/USD".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"event":"subscribe","pair":["XBT/USD","ETH/USD"],"subscription":{"name":"trade"}}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["XBT/USD".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"event":"unsubscribe","pair":["XBT/USD"],"subscription":{"name":"trade"}}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"event":"unsubscribe","pair":["XBT/USD"],"subscription":{"name":"book", "depth":25}}"#,
+            commands[1]
+        );
+    }
+
+    #[test]
+    fn candlestick() {
+        let mut configs = HashMap::new();
+        configs.insert("interval".to_string(), "1".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Candlestick],
+            &["XBT/USD".to_string(), "ETH/USD".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"event":"subscribe","pair":["XBT/USD","ETH/USD"],"subscription":{"name":"ohlc", "interval":1}}"#,
+            commands[0]
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+fn msg_type_to_feed(msg_type: MessageType) -> &'static str {
+    match msg_type {
+        MessageType::Trade => "trade",
+        MessageType::L2Event => "book",
+        MessageType::Ticker => "ticker",
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn feed_symbols_to_command(feed: &str, symbols: &[String], subscribe: bool) -> String {
+    format!(
+        r#"{{"event":"{}","feed":"{}","product_ids":{}}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        feed,
+        serde_json::to_string(symbols).unwrap(),
+    )
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    _configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_feed(*msg_type))
+        .map(|feed| feed_symbols_to_command(feed, symbols, subscribe))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["PI_XBTUSD".to_string(), "PI_ETHUSD".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"event":"subscribe","feed":"trade","product_ids":["PI_XBTUSD","PI_ETHUSD"]}"#,
+            commands[0]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["PI_XBTUSD".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"event":"unsubscribe","feed":"trade","product_ids":["PI_XBTUSD"]}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"event":"unsubscribe","feed":"book","product_ids":["PI_XBTUSD"]}"#,
+            commands[1]
+        );
+    }
+}
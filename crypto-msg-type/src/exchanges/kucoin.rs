@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+// Maximum number of batch subscriptions at a time: 100 topics
+// See https://docs.kucoin.com/#request-rate-limit
+const MAX_TOPICS_PER_COMMAND: usize = 100;
+
+fn msg_type_to_channel(msg_type: MessageType, symbol: &str) -> &'static str {
+    // spot symbols contain a hyphen, e.g., BTC-USDT, while contracts don't, e.g., XBTUSDTM
+    let is_spot = symbol.contains('-');
+    match msg_type {
+        MessageType::Trade => {
+            if is_spot {
+                "/market/match"
+            } else {
+                "/contractMarket/execution"
+            }
+        }
+        MessageType::L2Event => {
+            if is_spot {
+                "/market/level2"
+            } else {
+                "/contractMarket/level2"
+            }
+        }
+        MessageType::L2TopK => {
+            if is_spot {
+                "/spotMarket/level2Depth5"
+            } else {
+                "/contractMarket/level2Depth5"
+            }
+        }
+        MessageType::L3Event => {
+            if is_spot {
+                "/spotMarket/level3"
+            } else {
+                "/contractMarket/level3v2"
+            }
+        }
+        MessageType::BBO => {
+            if is_spot {
+                "/market/ticker"
+            } else {
+                "/contractMarket/tickerV2"
+            }
+        }
+        MessageType::Ticker => {
+            if is_spot {
+                "/market/snapshot"
+            } else {
+                "/contractMarket/snapshot"
+            }
+        }
+        MessageType::Candlestick => {
+            if is_spot {
+                "/market/candles"
+            } else {
+                "/contractMarket/candle"
+            }
+        }
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn topic_to_command(topic: &str, subscribe: bool) -> String {
+    format!(
+        r#"{{"id":"crypto-ws-client","type":"{}","topic":"{}","privateChannel":false,"response":true}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        topic
+    )
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    let mut commands: Vec<String> = Vec::new();
+    for msg_type in msg_types.iter() {
+        if *msg_type == MessageType::Candlestick {
+            // candlestick topics accept only one symbol, and the interval
+            // is 1min, 1hour, etc. in spot, while minutes in contracts
+            let interval = configs.unwrap().get("interval").unwrap();
+            commands.extend(symbols.iter().map(|symbol| {
+                let channel = msg_type_to_channel(*msg_type, symbol);
+                topic_to_command(&format!("{}:{}_{}", channel, symbol, interval), subscribe)
+            }));
+        } else {
+            let mut channel_symbols: Vec<(&str, Vec<&str>)> = Vec::new();
+            for symbol in symbols.iter() {
+                let channel = msg_type_to_channel(*msg_type, symbol);
+                if let Some((_, v)) = channel_symbols.iter_mut().find(|(c, _)| *c == channel) {
+                    v.push(symbol);
+                } else {
+                    channel_symbols.push((channel, vec![symbol]));
+                }
+            }
+            for (channel, symbols) in channel_symbols {
+                commands.extend(symbols.chunks(MAX_TOPICS_PER_COMMAND).map(|chunk| {
+                    topic_to_command(&format!("{}:{}", channel, chunk.join(",")), subscribe)
+                }));
+            }
+        }
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &[
+                "BTC-USDT".to_string(),
+                "ETH-USDT".to_string(),
+                "XBTUSDTM".to_string(),
+            ],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"id":"crypto-ws-client","type":"subscribe","topic":"/market/match:BTC-USDT,ETH-USDT","privateChannel":false,"response":true}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"id":"crypto-ws-client","type":"subscribe","topic":"/contractMarket/execution:XBTUSDTM","privateChannel":false,"response":true}"#,
+            commands[1]
+        );
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2Event],
+            &["BTC-USDT".to_string()],
+            false,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"id":"crypto-ws-client","type":"unsubscribe","topic":"/market/match:BTC-USDT","privateChannel":false,"response":true}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"id":"crypto-ws-client","type":"unsubscribe","topic":"/market/level2:BTC-USDT","privateChannel":false,"response":true}"#,
+            commands[1]
+        );
+    }
+
+    #[test]
+    fn candlestick() {
+        let mut configs = HashMap::new();
+        configs.insert("interval".to_string(), "1min".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Candlestick],
+            &["BTC-USDT".to_string(), "ETH-USDT".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"id":"crypto-ws-client","type":"subscribe","topic":"/market/candles:BTC-USDT_1min","privateChannel":false,"response":true}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"id":"crypto-ws-client","type":"subscribe","topic":"/market/candles:ETH-USDT_1min","privateChannel":false,"response":true}"#,
+            commands[1]
+        );
+    }
+}
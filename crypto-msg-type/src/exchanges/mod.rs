@@ -1,8 +1,20 @@
 pub(super) mod binance;
 pub(super) mod bitfinex;
+pub(super) mod bitget;
+pub(super) mod bithumb;
 pub(super) mod bitmex;
+pub(super) mod bitstamp;
+pub(super) mod bitz;
 pub(super) mod bybit;
+pub(super) mod coinbase_pro;
 pub(super) mod deribit;
+pub(super) mod dydx;
 pub(super) mod ftx;
+pub(super) mod gate;
 pub(super) mod huobi;
+pub(super) mod kraken;
+pub(super) mod kraken_futures;
+pub(super) mod kucoin;
+pub(super) mod mxc;
 pub(super) mod okex;
+pub(super) mod zbg;
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+// Spot and swap symbols look the same, e.g., BTC_USDT, so the market type is
+// read from `configs["market_type"]`, and spot is the default.
+fn is_spot(configs: Option<&HashMap<String, String>>) -> bool {
+    configs
+        .and_then(|x| x.get("market_type"))
+        .map(|x| x == "spot")
+        .unwrap_or(true)
+}
+
+fn msg_type_to_channel(msg_type: MessageType, is_spot: bool) -> &'static str {
+    match msg_type {
+        MessageType::Trade => "deal",
+        MessageType::L2Event => "depth",
+        MessageType::L2TopK if !is_spot => "depth.full",
+        MessageType::Ticker if !is_spot => "ticker",
+        MessageType::Candlestick => "kline",
+        _ => panic!("Unknown message type {}", msg_type),
+    }
+}
+
+fn channel_symbol_to_command(
+    channel: &str,
+    symbol: &str,
+    is_spot: bool,
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> String {
+    let op = if subscribe { "sub" } else { "unsub" };
+    let interval = if channel == "kline" {
+        format!(
+            r#","interval":"{}""#,
+            configs.unwrap().get("interval").unwrap()
+        )
+    } else {
+        String::new()
+    };
+    if is_spot {
+        format!(
+            r#"{{"op":"{}.{}","symbol":"{}"{}}}"#,
+            op, channel, symbol, interval
+        )
+    } else {
+        format!(
+            r#"{{"method":"{}.{}","param":{{"symbol":"{}"{}}}}}"#,
+            op, channel, symbol, interval
+        )
+    }
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    let is_spot = is_spot(configs);
+    msg_types
+        .iter()
+        .map(|msg_type| msg_type_to_channel(*msg_type, is_spot))
+        .flat_map(|channel| {
+            symbols.iter().map(move |symbol| {
+                channel_symbol_to_command(channel, symbol, is_spot, subscribe, configs)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_msg_type_multiple_symbols() {
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["BTC_USDT".to_string(), "ETH_USDT".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(r#"{"op":"sub.deal","symbol":"BTC_USDT"}"#, commands[0]);
+        assert_eq!(r#"{"op":"sub.deal","symbol":"ETH_USDT"}"#, commands[1]);
+    }
+
+    #[test]
+    fn multiple_msg_types_single_symbol() {
+        let mut configs = HashMap::new();
+        configs.insert("market_type".to_string(), "linear_swap".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Trade, MessageType::L2TopK],
+            &["BTC_USDT".to_string()],
+            false,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"method":"unsub.deal","param":{"symbol":"BTC_USDT"}}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"method":"unsub.depth.full","param":{"symbol":"BTC_USDT"}}"#,
+            commands[1]
+        );
+    }
+
+    #[test]
+    fn candlestick() {
+        let mut configs = HashMap::new();
+        configs.insert("interval".to_string(), "Min1".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Candlestick],
+            &["BTC_USDT".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"op":"sub.kline","symbol":"BTC_USDT","interval":"Min1"}"#,
+            commands[0]
+        );
+
+        configs.insert("market_type".to_string(), "linear_swap".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Candlestick],
+            &["BTC_USDT".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(
+            r#"{"method":"sub.kline","param":{"symbol":"BTC_USDT","interval":"Min1"}}"#,
+            commands[0]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::MessageType;
 
@@ -28,6 +28,8 @@ fn msg_type_to_channel(msg_type: MessageType) -> &'static str {
         MessageType::BBO => "ticker",
         MessageType::Ticker => "ticker",
         MessageType::Candlestick => "candle",
+        MessageType::MarkPrice => "mark_price",
+        MessageType::IndexPrice => "index/ticker",
         _ => panic!("Unknown message type {}", msg_type),
     }
 }
@@ -45,6 +47,10 @@ fn channel_symbol_to_topic(
             configs.unwrap().get("interval").unwrap(),
             symbol
         )
+    } else if channel == "index/ticker" {
+        // the index of a contract is its underlying, e.g., BTC-USD-SWAP -> BTC-USD
+        let underlying = symbol.split('-').take(2).collect::<Vec<&str>>().join("-");
+        format!("{}:{}", channel, underlying)
     } else {
         format!("{}/{}:{}", market_type, channel, symbol)
     }
@@ -68,7 +74,7 @@ pub(crate) fn get_ws_commands(
     subscribe: bool,
     configs: Option<&HashMap<String, String>>,
 ) -> Vec<String> {
-    let topics = msg_types
+    let mut topics = msg_types
         .iter()
         .map(|msg_type| msg_type_to_channel(*msg_type))
         .flat_map(|channel| {
@@ -77,6 +83,9 @@ pub(crate) fn get_ws_commands(
                 .map(|symbol| channel_symbol_to_topic(channel, symbol, configs))
         })
         .collect::<Vec<String>>();
+    // contracts with the same underlying share one index
+    let mut seen = HashSet::new();
+    topics.retain(|topic| seen.insert(topic.clone()));
     vec![topics_to_command(&topics, subscribe)]
 }
 
@@ -130,4 +139,19 @@ mod tests {
             commands[0]
         );
     }
+
+    #[test]
+    fn mark_price_and_index_price() {
+        let commands = get_ws_commands(
+            &vec![MessageType::MarkPrice, MessageType::IndexPrice],
+            &vec!["BTC-USD-SWAP".to_string(), "BTC-USD-210924".to_string()],
+            true,
+            None,
+        );
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            r#"{"op":"subscribe","args":["swap/mark_price:BTC-USD-SWAP","futures/mark_price:BTC-USD-210924","index/ticker:BTC-USD"]}"#,
+            commands[0]
+        );
+    }
 }
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use crate::MessageType;
+
+// ZBG topics contain numeric ids instead of symbols, i.e., symbol ids in spot
+// and contract ids in swap markets. Since ids can only be fetched from RESTful APIs,
+// they're read from `configs`, keyed by symbol.
+fn get_id<'a>(symbol: &str, configs: Option<&'a HashMap<String, String>>) -> &'a str {
+    configs
+        .and_then(|x| x.get(symbol))
+        .unwrap_or_else(|| panic!("Failed to find the id of {} in configs", symbol))
+}
+
+// Spot symbols are in lowercase, e.g., btc_usdt, while swap symbols are in uppercase, e.g., BTC_USDT
+fn msg_type_symbol_to_command(
+    msg_type: MessageType,
+    symbol: &str,
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> String {
+    let id = get_id(symbol, configs);
+    if symbol.to_lowercase() == *symbol {
+        let data_type = match msg_type {
+            MessageType::Trade => format!("{}_TRADE_{}", id, symbol.to_uppercase()),
+            MessageType::L2Event => format!("{}_ENTRUST_ADD_{}", id, symbol.to_uppercase()),
+            MessageType::Ticker => format!("{}_TRADE_STATISTIC_24H", id),
+            MessageType::Candlestick => format!(
+                "{}_KLINE_{}_{}",
+                id,
+                configs.unwrap().get("interval").unwrap(),
+                symbol.to_uppercase()
+            ),
+            _ => panic!("Unknown message type {}", msg_type),
+        };
+        format!(
+            r#"{{"action":"{}", "dataType":{}}}"#,
+            if subscribe { "ADD" } else { "DEL" },
+            data_type,
+        )
+    } else {
+        let topic = match msg_type {
+            MessageType::Trade => format!("future_tick-{}", id),
+            MessageType::L2Event => format!("future_snapshot_depth-{}", id),
+            MessageType::Ticker => format!("future_snapshot_indicator-{}", id),
+            MessageType::Candlestick => format!(
+                "future_kline-{}-{}",
+                id,
+                configs.unwrap().get("interval").unwrap()
+            ),
+            _ => panic!("Unknown message type {}", msg_type),
+        };
+        format!(
+            r#"{{"action":"{}", "topic":"{}"}}"#,
+            if subscribe { "sub" } else { "unsub" },
+            topic,
+        )
+    }
+}
+
+pub(crate) fn get_ws_commands(
+    msg_types: &[MessageType],
+    symbols: &[String],
+    subscribe: bool,
+    configs: Option<&HashMap<String, String>>,
+) -> Vec<String> {
+    msg_types
+        .iter()
+        .flat_map(|msg_type| {
+            symbols
+                .iter()
+                .map(|symbol| msg_type_symbol_to_command(*msg_type, symbol, subscribe, configs))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spot() {
+        let mut configs = HashMap::new();
+        configs.insert("btc_usdt".to_string(), "329".to_string());
+        configs.insert("interval".to_string(), "1M".to_string());
+        let commands = get_ws_commands(
+            &[
+                MessageType::Trade,
+                MessageType::Ticker,
+                MessageType::Candlestick,
+            ],
+            &["btc_usdt".to_string()],
+            true,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 3);
+        assert_eq!(
+            r#"{"action":"ADD", "dataType":329_TRADE_BTC_USDT}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"action":"ADD", "dataType":329_TRADE_STATISTIC_24H}"#,
+            commands[1]
+        );
+        assert_eq!(
+            r#"{"action":"ADD", "dataType":329_KLINE_1M_BTC_USDT}"#,
+            commands[2]
+        );
+    }
+
+    #[test]
+    fn swap() {
+        let mut configs = HashMap::new();
+        configs.insert("BTC_USDT".to_string(), "1000000".to_string());
+        configs.insert("BTC_USD-R".to_string(), "1000001".to_string());
+        let commands = get_ws_commands(
+            &[MessageType::Trade],
+            &["BTC_USDT".to_string(), "BTC_USD-R".to_string()],
+            false,
+            Some(&configs),
+        );
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            r#"{"action":"unsub", "topic":"future_tick-1000000"}"#,
+            commands[0]
+        );
+        assert_eq!(
+            r#"{"action":"unsub", "topic":"future_tick-1000001"}"#,
+            commands[1]
+        );
+    }
+}
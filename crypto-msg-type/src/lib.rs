@@ -1,3 +1,4 @@
+mod capability;
 mod exchanges;
 
 use std::collections::HashMap;
@@ -5,6 +6,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
+pub use capability::{get_capabilities, get_msg_types, supports, Capability};
+
 /// Crypto message types.
 ///
 /// L2Snapshot and L2TopK are very similar, the former is from RESTful API,
@@ -42,12 +45,31 @@ pub enum MessageType {
     FundingRate,
     /// Open interest
     OpenInterest,
+    /// Mark price
+    MarkPrice,
+    /// Index price
+    IndexPrice,
+    /// Liquidation orders
+    Liquidation,
+    /// Insurance fund balances
+    InsuranceFund,
+    /// Settlement and delivery prices
+    Settlement,
+    /// Instrument status changes, e.g., listed, delisted, settled
+    InstrumentStatus,
 }
 
 /// Translate to websocket subscribe/unsubscribe commands.
 ///
 /// `configs` Some `msg_type` requires a config, for example,
 /// `Candlestick` requires an `interval` parameter.
+///
+/// Exchange-specific configs:
+///
+/// * gate and mxc: `market_type` distinguishes swap symbols from spot
+///   symbols since they look the same, defaults to `spot`
+/// * zbg: topics contain numeric ids, so each symbol must be mapped to its
+///   symbol id(spot) or contract id(swap) in `configs`
 pub fn get_ws_commands(
     exchange: &str,
     msg_types: &[MessageType],
@@ -61,12 +83,28 @@ pub fn get_ws_commands(
     match exchange {
         "binance" => exchanges::binance::get_ws_commands(msg_types, symbols, subscribe, configs),
         "bitfinex" => exchanges::bitfinex::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "bitget" => exchanges::bitget::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "bithumb" => exchanges::bithumb::get_ws_commands(msg_types, symbols, subscribe, configs),
         "bitmex" => exchanges::bitmex::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "bitstamp" => exchanges::bitstamp::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "bitz" => exchanges::bitz::get_ws_commands(msg_types, symbols, subscribe, configs),
         "bybit" => exchanges::bybit::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "coinbase_pro" => {
+            exchanges::coinbase_pro::get_ws_commands(msg_types, symbols, subscribe, configs)
+        }
         "deribit" => exchanges::deribit::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "dydx" => exchanges::dydx::get_ws_commands(msg_types, symbols, subscribe, configs),
         "ftx" => exchanges::ftx::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "gate" => exchanges::gate::get_ws_commands(msg_types, symbols, subscribe, configs),
         "huobi" => exchanges::huobi::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "kraken" => exchanges::kraken::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "kraken_futures" => {
+            exchanges::kraken_futures::get_ws_commands(msg_types, symbols, subscribe, configs)
+        }
+        "kucoin" => exchanges::kucoin::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "mxc" => exchanges::mxc::get_ws_commands(msg_types, symbols, subscribe, configs),
         "okex" => exchanges::okex::get_ws_commands(msg_types, symbols, subscribe, configs),
+        "zbg" => exchanges::zbg::get_ws_commands(msg_types, symbols, subscribe, configs),
         _ => Vec::new(),
     }
 }
@@ -0,0 +1,546 @@
+//! A registry of currencies with canonical ids.
+//!
+//! The same asset might have different codes at different exchanges, e.g.,
+//! bitcoin is `XBT` at BitMEX, and the same code might refer to different
+//! assets, e.g., `BCC` was Bitcoin Cash at Binance but BitConnect elsewhere.
+//! The registry maps exchange-specific codes to a canonical id.
+//!
+//! `canonical_id()` applies all aliases, while `normalize_currency()` only
+//! applies the ones of the given exchange. `normalize_pair()` doesn't consult
+//! the registry, it's on the hot path of message parsing.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crypto_http::http_get;
+
+/// An alias under this exchange name applies to all exchanges.
+pub const ALL_EXCHANGES: &str = "*";
+
+/// A currency, identified by a canonical id across all exchanges.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Currency {
+    /// Canonical id, which is used in normalized pairs, e.g., `BTC`
+    pub id: String,
+    /// Full name, e.g., `Bitcoin`
+    pub name: Option<String>,
+    /// Id at CoinMarketCap
+    pub cmc_id: Option<u64>,
+    /// Rank by market cap at CoinMarketCap
+    pub cmc_rank: Option<u64>,
+    /// Exchange-specific codes of this currency, exchange -> codes
+    pub aliases: BTreeMap<String, Vec<String>>,
+}
+
+impl Currency {
+    fn new(id: &str) -> Self {
+        Currency {
+            id: id.to_uppercase(),
+            name: None,
+            cmc_id: None,
+            cmc_rank: None,
+            aliases: BTreeMap::new(),
+        }
+    }
+}
+
+// (id, name, CoinMarketCap id, [(exchange, code)])
+#[allow(clippy::type_complexity)]
+const CURRENCY_DETAILS: &[(&str, &str, u64, &[(&str, &str)])] = &[
+    (
+        "BTC",
+        "Bitcoin",
+        1,
+        &[("bitmex", "XBT"), ("kraken", "XBT"), ("kucoin", "XBT")],
+    ),
+    ("ETH", "Ethereum", 1027, &[]),
+    ("USDT", "Tether", 825, &[]),
+    (
+        "BCH",
+        "Bitcoin Cash",
+        1831,
+        &[(ALL_EXCHANGES, "BCHABC"), ("binance", "BCC")],
+    ),
+    ("BSV", "Bitcoin SV", 3602, &[(ALL_EXCHANGES, "BCHSV")]),
+    ("DOGE", "Dogecoin", 74, &[("kraken", "XDG")]),
+    ("NANO", "Nano", 1567, &[(ALL_EXCHANGES, "XRB")]),
+    ("REV", "Revain", 2135, &[("kucoin", "R")]),
+    ("WAXP", "WAX", 2300, &[("kucoin", "WAX")]),
+    ("OXEN", "Oxen", 2748, &[("kucoin", "LOKI")]),
+];
+
+// offline data, in case the network is down
+const CMC_RANKS: &[(&str, u64)] = &[
+    ("BTC", 1),
+    ("ETH", 2),
+    ("USDT", 3),
+    ("ADA", 4),
+    ("BNB", 5),
+    ("XRP", 6),
+    ("SOL", 7),
+    ("USDC", 8),
+    ("DOT", 9),
+    ("DOGE", 10),
+    ("UNI", 11),
+    ("AVAX", 12),
+    ("LUNA", 13),
+    ("BUSD", 14),
+    ("LINK", 15),
+    ("ALGO", 16),
+    ("LTC", 17),
+    ("BCH", 18),
+    ("WBTC", 19),
+    ("ATOM", 20),
+    ("MATIC", 21),
+    ("ICP", 22),
+    ("DAI", 23),
+    ("FIL", 24),
+    ("XLM", 25),
+    ("TRX", 26),
+    ("FTT", 27),
+    ("ETC", 28),
+    ("VET", 29),
+    ("XTZ", 30),
+    ("XEC", 31),
+    ("THETA", 32),
+    ("BTCB", 33),
+    ("XMR", 34),
+    ("AXS", 35),
+    ("CAKE", 36),
+    ("EGLD", 37),
+    ("CRO", 38),
+    ("EOS", 39),
+    ("AAVE", 40),
+    ("QNT", 41),
+    ("HBAR", 42),
+    ("NEAR", 43),
+    ("FTM", 44),
+    ("GRT", 45),
+    ("IOTA", 46),
+    ("SHIB", 47),
+    ("KSM", 48),
+    ("UST", 49),
+    ("KLAY", 50),
+    ("NEO", 51),
+    ("LEO", 52),
+    ("WAVES", 53),
+    ("BSV", 54),
+    ("MKR", 55),
+    ("BTT", 56),
+    ("AMP", 57),
+    ("COMP", 58),
+    ("CELO", 59),
+    ("HNT", 60),
+    ("XDC", 61),
+    ("ONE", 62),
+    ("DASH", 63),
+    ("RUNE", 64),
+    ("CHZ", 65),
+    ("TUSD", 66),
+    ("STX", 67),
+    ("TFUEL", 68),
+    ("AR", 69),
+    ("HOT", 70),
+    ("ZEC", 71),
+    ("DYDX", 72),
+    ("DCR", 73),
+    ("REV", 74),
+    ("OMG", 75),
+    ("HT", 76),
+    ("SUSHI", 77),
+    ("XEM", 78),
+    ("CEL", 79),
+    ("MANA", 80),
+    ("SNX", 81),
+    ("YFI", 82),
+    ("PERP", 83),
+    ("ENJ", 84),
+    ("MINA", 85),
+    ("CRV", 86),
+    ("FLOW", 87),
+    ("OKB", 88),
+    ("IOST", 89),
+    ("ICX", 90),
+    ("SRM", 91),
+    ("USDP", 92),
+    ("REN", 93),
+    ("CELR", 94),
+    ("RVN", 95),
+    ("ZIL", 96),
+    ("BTG", 97),
+    ("BAT", 98),
+    ("QTUM", 99),
+    ("TEL", 100),
+    ("AUDIO", 101),
+    ("KCS", 102),
+    ("NEXO", 103),
+    ("BNT", 104),
+    ("MDX", 105),
+    ("ZEN", 106),
+    ("RENBTC", 107),
+    ("ZRX", 108),
+    ("SC", 109),
+    ("VGX", 110),
+    ("RAY", 111),
+    ("ONT", 112),
+    ("DGB", 113),
+    ("CHSB", 114),
+    ("ANKR", 115),
+    ("NANO", 116),
+    ("IOTX", 117),
+    ("UMA", 118),
+    ("SAND", 119),
+    ("USDN", 120),
+    ("FET", 121),
+    ("COTI", 122),
+    ("HUSD", 123),
+    ("LRC", 124),
+    ("KAVA", 125),
+    ("1INCH", 126),
+    ("DENT", 127),
+    ("GLM", 128),
+    ("FEI", 129),
+    ("WOO", 130),
+    ("UBT", 131),
+    ("NMR", 132),
+    ("SXP", 133),
+    ("XDB", 134),
+    ("OCEAN", 135),
+    ("RSR", 136),
+    ("GNO", 137),
+    ("LPT", 138),
+    ("LSK", 139),
+    ("WAXP", 140),
+    ("ERG", 141),
+    ("DAG", 142),
+    ("XYO", 143),
+    ("SKL", 144),
+    ("ALPHA", 145),
+    ("BAKE", 146),
+    ("CFX", 147),
+    ("CKB", 148),
+    ("POLY", 149),
+    ("WRX", 150),
+    ("STORJ", 151),
+    ("VTHO", 152),
+    ("AGIX", 153),
+    ("PAXG", 154),
+    ("INJ", 155),
+    ("XVG", 156),
+    ("ELF", 157),
+    ("WIN", 158),
+    ("MED", 159),
+    ("BCD", 160),
+    ("GT", 161),
+    ("VLX", 162),
+    ("CVC", 163),
+    ("FX", 164),
+    ("ONG", 165),
+    ("RLC", 166),
+    ("MLN", 167),
+    ("PROM", 168),
+    ("ASD", 169),
+    ("ARDR", 170),
+    ("EWT", 171),
+    ("OGN", 172),
+    ("REEF", 173),
+    ("ALICE", 174),
+    ("BAND", 175),
+    ("STMX", 176),
+    ("SNT", 177),
+    ("XVS", 178),
+    ("ROSE", 179),
+    ("STRAX", 180),
+    ("CTSI", 181),
+    ("MAID", 182),
+    ("ORBS", 183),
+    ("HIVE", 184),
+    ("OXT", 185),
+    ("REP", 186),
+    ("DERO", 187),
+    ("FUN", 188),
+    ("NKN", 189),
+    ("CSPR", 190),
+    ("ARK", 191),
+    ("NU", 192),
+    ("MTL", 193),
+    ("REQ", 194),
+    ("TOMO", 195),
+    ("SYS", 196),
+    ("STEEM", 197),
+    ("PHA", 198),
+    ("ANT", 199),
+    ("BTCST", 200),
+    ("HEX", 201),
+    ("BCHA", 202),
+    ("STETH", 203),
+    ("WBNB", 204),
+    ("CCXX", 205),
+    ("HBTC", 206),
+    ("CTC", 207),
+    ("TTT", 208),
+    ("EGR", 209),
+    ("YOUC", 210),
+    ("OMI", 211),
+    ("ALT", 212),
+    ("NXM", 213),
+    ("LUSD", 214),
+    ("XWC", 215),
+    ("SAFEMOON", 216),
+    ("DFI", 217),
+    ("XYM", 218),
+    ("INO", 219),
+    ("vBNB", 220),
+    ("GALA", 221),
+    ("LN", 222),
+    ("C98", 223),
+    ("MOVR", 224),
+    ("XPRT", 225),
+    ("ARRR", 226),
+    ("ETN", 227),
+    ("YGG", 228),
+    ("AKT", 229),
+    ("BIT", 230),
+    ("FRAX", 231),
+    ("ANC", 232),
+    ("LYXe", 233),
+    ("BEST", 234),
+    ("TWT", 235),
+    ("KOK", 236),
+    ("RPL", 237),
+    ("MNGO", 238),
+    ("ILV", 239),
+    ("ORC", 240),
+    ("PUNDIX", 241),
+    ("SDN", 242),
+    ("MBOX", 243),
+    ("TRIBE", 244),
+    ("SUSD", 245),
+    ("HEDG", 246),
+    ("FIDA", 247),
+    ("MASK", 248),
+    ("TITAN", 249),
+    ("KNC", 250),
+    ("XCH", 251),
+    ("PEAK", 252),
+    ("ORN", 253),
+    ("RGT", 254),
+    ("MIR", 255),
+    ("KDA", 256),
+];
+
+struct Registry {
+    // id -> currency
+    currencies: HashMap<String, Currency>,
+    // exchange -> code -> id
+    aliases: HashMap<String, HashMap<String, String>>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        let mut registry = Registry {
+            currencies: HashMap::new(),
+            aliases: HashMap::new(),
+        };
+        for (id, rank) in CMC_RANKS.iter() {
+            let mut currency = Currency::new(id);
+            currency.cmc_rank = Some(*rank);
+            registry.currencies.insert(currency.id.clone(), currency);
+        }
+        for (id, name, cmc_id, aliases) in CURRENCY_DETAILS.iter() {
+            let mut currency = registry
+                .currencies
+                .remove(*id)
+                .unwrap_or_else(|| Currency::new(id));
+            currency.name = Some(name.to_string());
+            currency.cmc_id = Some(*cmc_id);
+            for (exchange, code) in aliases.iter() {
+                currency
+                    .aliases
+                    .entry(exchange.to_string())
+                    .or_default()
+                    .push(code.to_string());
+            }
+            registry.insert(currency);
+        }
+        registry
+    }
+
+    fn insert(&mut self, mut currency: Currency) {
+        currency.id = currency.id.to_uppercase();
+        for codes in self.aliases.values_mut() {
+            codes.retain(|_, id| id != &currency.id);
+        }
+        for (exchange, codes) in currency.aliases.iter() {
+            let mapping = self.aliases.entry(exchange.clone()).or_default();
+            for code in codes.iter() {
+                mapping.insert(code.to_uppercase(), currency.id.clone());
+            }
+        }
+        self.currencies.insert(currency.id.clone(), currency);
+    }
+
+    fn resolve_exchange(&self, code: &str, exchange: &str) -> Option<&String> {
+        self.aliases
+            .get(exchange)
+            .and_then(|mapping| mapping.get(code))
+    }
+
+    fn resolve(&self, code: &str, exchange: &str) -> Option<&String> {
+        self.resolve_exchange(code, exchange).or_else(|| {
+            self.aliases
+                .get(ALL_EXCHANGES)
+                .and_then(|mapping| mapping.get(code))
+        })
+    }
+}
+
+lazy_static! {
+    static ref REGISTRY: RwLock<Registry> = RwLock::new(Registry::new());
+}
+
+/// Get the canonical id of an exchange-specific currency code.
+///
+/// Codes without an alias in the registry are upper-cased.
+pub fn canonical_id(code: &str, exchange: &str) -> String {
+    let code = code.to_uppercase();
+    let registry = REGISTRY.read().unwrap();
+    registry.resolve(&code, exchange).cloned().unwrap_or(code)
+}
+
+// Apply the aliases of `exchange` only, aliases of all exchanges are skipped.
+pub(crate) fn exchange_alias(code: &str, exchange: &str) -> Option<String> {
+    let registry = REGISTRY.read().unwrap();
+    registry
+        .resolve_exchange(&code.to_uppercase(), exchange)
+        .cloned()
+}
+
+/// Get a currency by its canonical id.
+pub fn get_currency(id: &str) -> Option<Currency> {
+    let registry = REGISTRY.read().unwrap();
+    registry.currencies.get(&id.to_uppercase()).cloned()
+}
+
+/// Get a currency by an exchange-specific code.
+pub fn get_currency_by_code(code: &str, exchange: &str) -> Option<Currency> {
+    get_currency(&canonical_id(code, exchange))
+}
+
+/// Add a currency to the registry, or replace the one with the same id.
+pub fn register_currency(currency: Currency) {
+    let mut registry = REGISTRY.write().unwrap();
+    registry.insert(currency);
+}
+
+/// Fetch the top `limit` currencies by market cap from CoinMarketCap.
+///
+/// Currencies are sorted by rank, and ids are CoinMarketCap tickers which are
+/// not unique.
+pub fn fetch_cmc_currencies(limit: u64) -> reqwest::Result<Vec<Currency>> {
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct CmcCurrency {
+        id: u64,
+        name: String,
+        symbol: String,
+        cmcRank: u64,
+    }
+
+    let url = format!("https://api.coinmarketcap.com/data-api/v3/cryptocurrency/listing?start=1&limit={}&sortBy=market_cap&sortType=desc&convert=USD&cryptoType=all&tagType=all&audited=false", limit);
+    let txt = http_get(&url)?;
+    let json_obj = serde_json::from_str::<HashMap<String, Value>>(&txt).unwrap_or_default();
+    let mut currencies: Vec<Currency> = json_obj
+        .get("data")
+        .and_then(|data| data["cryptoCurrencyList"].as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|x| serde_json::from_value::<CmcCurrency>(x.clone()).ok())
+                .map(|x| Currency {
+                    id: x.symbol.to_uppercase(),
+                    name: Some(x.name),
+                    cmc_id: Some(x.id),
+                    cmc_rank: Some(x.cmcRank),
+                    aliases: BTreeMap::new(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    currencies.sort_by_key(|x| x.cmc_rank);
+    Ok(currencies)
+}
+
+/// Update names, ids and ranks in the registry with the latest data from CoinMarketCap.
+///
+/// If multiple assets share a ticker, the registered one is kept, or the one
+/// with the highest market cap if it's not registered yet.
+pub fn update_from_cmc(limit: u64) -> reqwest::Result<()> {
+    let cmc_currencies = fetch_cmc_currencies(limit)?;
+    let mut registry = REGISTRY.write().unwrap();
+    let mut seen: HashSet<String> = HashSet::new();
+    for cmc_currency in cmc_currencies {
+        if !seen.insert(cmc_currency.id.clone()) {
+            continue;
+        }
+        match registry.currencies.get_mut(&cmc_currency.id) {
+            Some(currency) => {
+                if currency.cmc_id.is_none() || currency.cmc_id == cmc_currency.cmc_id {
+                    currency.name = currency.name.take().or(cmc_currency.name);
+                    currency.cmc_id = cmc_currency.cmc_id;
+                    currency.cmc_rank = cmc_currency.cmc_rank;
+                }
+            }
+            None => registry.insert(cmc_currency),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canonical_id, get_currency, get_currency_by_code, REGISTRY};
+
+    #[test]
+    fn test_aliases() {
+        assert_eq!("BTC", canonical_id("XBT", "bitmex"));
+        assert_eq!("BTC", canonical_id("xbt", "kraken"));
+        assert_eq!("BSV", canonical_id("BCHSV", "kucoin"));
+        assert_eq!("BSV", canonical_id("BCHSV", "binance"));
+        // BCC was Bitcoin Cash only at Binance
+        assert_eq!("BCH", canonical_id("BCC", "binance"));
+        assert_eq!("BCC", canonical_id("BCC", "hitbtc"));
+        assert_eq!("ETH", canonical_id("eth", "huobi"));
+
+        let btc = get_currency_by_code("XBT", "bitmex").unwrap();
+        assert_eq!(Some(1), btc.cmc_id);
+        assert_eq!(Some(1), btc.cmc_rank);
+        assert_eq!(btc, get_currency("btc").unwrap());
+    }
+
+    #[test]
+    fn test_normalize_currency() {
+        use crate::normalize_currency;
+
+        assert_eq!("BTC", normalize_currency("XXBT", "kraken"));
+        assert_eq!("BCH", normalize_currency("BCC", "binance"));
+        // aliases shared by all exchanges are left to canonical_id()
+        assert_eq!("BCHSV", normalize_currency("BCHSV", "binance"));
+        assert_eq!("BCC", normalize_currency("bcc", "hitbtc"));
+    }
+
+    #[test]
+    fn test_idempotent() {
+        // normalizing a canonical id again must not change it
+        let registry = REGISTRY.read().unwrap();
+        let ids: Vec<&String> = registry.aliases.values().flat_map(|x| x.values()).collect();
+        for exchange in registry.aliases.keys() {
+            for id in ids.iter() {
+                assert_eq!(*id, registry.resolve(id, exchange).unwrap_or(id));
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crypto_market_type::MarketType;
+
+// PI_XBTUSD, PF_XBTUSD, FI_XBTUSD_210625, case-insensitive
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let v: Vec<&str> = symbol.split('_').collect();
+    if v.len() < 2 || v[1].len() <= 3 {
+        return None;
+    }
+    let (base, quote) = v[1].split_at(v[1].len() - 3);
+    let base = if base == "XBT" { "BTC" } else { base };
+
+    Some(format!("{}/{}", base, quote))
+}
+
+pub(crate) fn get_market_type(symbol: &str) -> MarketType {
+    let symbol = symbol.to_uppercase();
+    match symbol.split('_').next() {
+        Some("PI") => MarketType::InverseSwap,
+        Some("FI") => MarketType::InverseFuture,
+        Some("PF") => MarketType::LinearSwap,
+        Some("FF") => MarketType::LinearFuture,
+        _ => MarketType::Unknown,
+    }
+}
@@ -0,0 +1,463 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use crypto_market_type::MarketType;
+use serde::{Deserialize, Serialize};
+
+/// Option kind, call or put.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+/// A trading instrument parsed from an exchange-specific symbol.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Instrument {
+    /// exchange name
+    pub exchange: String,
+    /// exchange-specific trading symbol
+    pub symbol: String,
+    /// Market type, i.e., the contract type
+    pub market_type: MarketType,
+    /// unified pair, the same as `normalize_pair()`
+    pub pair: String,
+    /// unified base currency, i.e., the underlying
+    pub base: String,
+    /// unified quote currency
+    pub quote: String,
+    /// settlement currency, i.e., collateral currency, always None for spot markets
+    pub settle: Option<String>,
+    /// Expiry time, unix timestamp in milliseconds.
+    ///
+    /// None for spot and swap markets, and for symbols without a full expiry date, e.g., `BTC_CQ` at Huobi.
+    pub expiry: Option<u64>,
+    /// strike price, only applicable for option markets
+    pub strike: Option<f64>,
+    /// call or put, only applicable for option markets
+    pub option_kind: Option<OptionKind>,
+}
+
+// Exchange-specific fields, None means unknown or not applicable
+#[derive(Default)]
+struct Details {
+    settle: Option<String>,
+    expiry: Option<u64>,
+    strike: Option<f64>,
+    option_kind: Option<OptionKind>,
+}
+
+pub(crate) fn parse_instrument(
+    symbol: &str,
+    exchange: &str,
+    is_spot: Option<bool>,
+) -> Option<Instrument> {
+    let market_type = crate::get_market_type(symbol, exchange, is_spot);
+    if market_type == MarketType::Unknown {
+        return None;
+    }
+    let pair = crate::normalize_pair(symbol, exchange)?;
+    let (base, quote) = {
+        let (base, quote) = pair.split_once('/')?;
+        (base.to_string(), quote.to_string())
+    };
+
+    let details = match exchange {
+        "binance" => parse_binance(symbol, market_type),
+        "bitmex" => parse_bitmex(symbol, market_type),
+        "bybit" => parse_bybit(symbol, market_type),
+        "deribit" => parse_deribit(symbol, &base, market_type),
+        "ftx" => Details {
+            settle: Some("USD".to_string()),
+            ..Default::default()
+        },
+        "gate" => parse_gate(symbol, market_type),
+        "huobi" => parse_huobi(symbol, market_type),
+        "kraken_futures" => parse_kraken_futures(symbol, market_type),
+        "okex" => parse_okex(symbol, &base, market_type),
+        _ => Details::default(),
+    };
+    let settle = match market_type {
+        MarketType::Spot => None,
+        MarketType::InverseFuture | MarketType::InverseSwap => {
+            details.settle.or_else(|| Some(base.clone()))
+        }
+        _ => details.settle.or_else(|| Some(quote.clone())),
+    };
+
+    Some(Instrument {
+        exchange: exchange.to_string(),
+        symbol: symbol.to_string(),
+        market_type,
+        pair,
+        base,
+        quote,
+        settle,
+        expiry: details.expiry,
+        strike: details.strike,
+        option_kind: details.option_kind,
+    })
+}
+
+// Unix timestamp in milliseconds of a date and an hour in UTC
+fn to_millis(date: NaiveDate, hour: u32) -> Option<u64> {
+    Some(date.and_hms_opt(hour, 0, 0)?.and_utc().timestamp_millis() as u64)
+}
+
+// e.g., 210625
+fn yymmdd_to_date(s: &str) -> Option<NaiveDate> {
+    if s.len() != 6 {
+        return None;
+    }
+    let year = 2000 + s[..2].parse::<i32>().ok()?;
+    let month = s[2..4].parse::<u32>().ok()?;
+    let day = s[4..].parse::<u32>().ok()?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+fn parse_yymmdd(s: &str, hour: u32) -> Option<u64> {
+    to_millis(yymmdd_to_date(s)?, hour)
+}
+
+// e.g., 20210625
+fn parse_yyyymmdd(s: &str, hour: u32) -> Option<u64> {
+    if s.len() != 8 {
+        return None;
+    }
+    to_millis(NaiveDate::parse_from_str(s, "%Y%m%d").ok()?, hour)
+}
+
+// e.g., 25JUN21 and 4JUN21
+fn parse_ddmmmyy(s: &str, hour: u32) -> Option<u64> {
+    if s.len() < 6 {
+        return None;
+    }
+    let (day, rest) = s.split_at(s.len() - 5);
+    let month = match &rest[..3] {
+        "JAN" => 1,
+        "FEB" => 2,
+        "MAR" => 3,
+        "APR" => 4,
+        "MAY" => 5,
+        "JUN" => 6,
+        "JUL" => 7,
+        "AUG" => 8,
+        "SEP" => 9,
+        "OCT" => 10,
+        "NOV" => 11,
+        "DEC" => 12,
+        _ => return None,
+    };
+    let year = 2000 + rest[3..].parse::<i32>().ok()?;
+    to_millis(
+        NaiveDate::from_ymd_opt(year, month, day.parse::<u32>().ok()?)?,
+        hour,
+    )
+}
+
+// Futures month code plus a two-digit year, e.g., M21, expires on the last Friday of the month
+fn parse_month_code(s: &str, hour: u32) -> Option<u64> {
+    if s.len() != 3 {
+        return None;
+    }
+    let month = match s.chars().next()? {
+        'F' => 1,
+        'G' => 2,
+        'H' => 3,
+        'J' => 4,
+        'K' => 5,
+        'M' => 6,
+        'N' => 7,
+        'Q' => 8,
+        'U' => 9,
+        'V' => 10,
+        'X' => 11,
+        'Z' => 12,
+        _ => return None,
+    };
+    let year = 2000 + s[1..].parse::<i32>().ok()?;
+    let first_of_next_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    let mut date = first_of_next_month.pred_opt()?;
+    while date.weekday() != Weekday::Fri {
+        date = date.pred_opt()?;
+    }
+    to_millis(date, hour)
+}
+
+fn parse_option_kind(s: &str) -> Option<OptionKind> {
+    match s {
+        "C" => Some(OptionKind::Call),
+        "P" => Some(OptionKind::Put),
+        _ => None,
+    }
+}
+
+// BTCUSD_210625, BTCUSDT_210625, BTC-210625-40000-C
+fn parse_binance(symbol: &str, market_type: MarketType) -> Details {
+    match market_type {
+        MarketType::InverseFuture | MarketType::LinearFuture => Details {
+            expiry: symbol
+                .rsplit('_')
+                .next()
+                .and_then(|date| parse_yymmdd(date, 8)),
+            ..Default::default()
+        },
+        MarketType::EuropeanOption => {
+            let v: Vec<&str> = symbol.split('-').collect();
+            if v.len() != 4 {
+                return Details::default();
+            }
+            Details {
+                settle: Some("USDT".to_string()),
+                expiry: parse_yymmdd(v[1], 8),
+                strike: v[2].parse::<f64>().ok(),
+                option_kind: parse_option_kind(v[3]),
+            }
+        }
+        _ => Details::default(),
+    }
+}
+
+// XBTUSD, XBTM21, ETHUSDM21, XBTUSDTZ21; all contracts are settled in XBT except USDT ones
+fn parse_bitmex(symbol: &str, market_type: MarketType) -> Details {
+    let is_future = matches!(
+        market_type,
+        MarketType::InverseFuture | MarketType::LinearFuture | MarketType::QuantoFuture
+    );
+    let real_symbol = if is_future {
+        &symbol[..(symbol.len() - 3)]
+    } else {
+        symbol
+    };
+    Details {
+        settle: Some(
+            if real_symbol.ends_with("USDT") {
+                "USDT"
+            } else {
+                "BTC"
+            }
+            .to_string(),
+        ),
+        expiry: if is_future {
+            parse_month_code(&symbol[(symbol.len() - 3)..], 12)
+        } else {
+            None
+        },
+        ..Default::default()
+    }
+}
+
+// BTCUSD, BTCUSDT, BTCUSDM21
+fn parse_bybit(symbol: &str, market_type: MarketType) -> Details {
+    if market_type == MarketType::InverseFuture {
+        Details {
+            expiry: parse_month_code(&symbol[(symbol.len() - 3)..], 8),
+            ..Default::default()
+        }
+    } else {
+        Details::default()
+    }
+}
+
+// BTC-PERPETUAL, BTC-25JUN21, BTC-25JUN21-40000-C, all settled in the base currency
+fn parse_deribit(symbol: &str, base: &str, market_type: MarketType) -> Details {
+    let v: Vec<&str> = symbol.split('-').collect();
+    match market_type {
+        MarketType::InverseFuture if v.len() == 2 => Details {
+            settle: Some(base.to_string()),
+            expiry: parse_ddmmmyy(v[1], 8),
+            ..Default::default()
+        },
+        MarketType::EuropeanOption if v.len() == 4 => Details {
+            settle: Some(base.to_string()),
+            expiry: parse_ddmmmyy(v[1], 8),
+            strike: v[2].parse::<f64>().ok(),
+            option_kind: parse_option_kind(v[3]),
+        },
+        _ => Details {
+            settle: Some(base.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+// BTC_USD_20210625, BTC_USDT_20210625, settled at 08:00 UTC
+fn parse_gate(symbol: &str, market_type: MarketType) -> Details {
+    match market_type {
+        MarketType::InverseFuture | MarketType::LinearFuture => Details {
+            expiry: symbol
+                .rsplit('_')
+                .next()
+                .and_then(|date| parse_yyyymmdd(date, 8)),
+            ..Default::default()
+        },
+        _ => Details::default(),
+    }
+}
+
+// PI_XBTUSD, FI_XBTUSD_210625, expire at 16:00 London time
+fn parse_kraken_futures(symbol: &str, market_type: MarketType) -> Details {
+    let expiry =
+        if market_type == MarketType::InverseFuture || market_type == MarketType::LinearFuture {
+            symbol.rsplit('_').next().and_then(|date| {
+                let date = yymmdd_to_date(date)?;
+                // 15:00 UTC during British Summer Time
+                to_millis(date, if is_british_summer_time(date) { 15 } else { 16 })
+            })
+        } else {
+            None
+        };
+    Details {
+        expiry,
+        ..Default::default()
+    }
+}
+
+// British Summer Time lasts from the last Sunday of March to the last Sunday of October
+fn is_british_summer_time(date: NaiveDate) -> bool {
+    let last_sunday = |month: u32| {
+        let mut day = NaiveDate::from_ymd_opt(date.year(), month, 31).unwrap();
+        while day.weekday() != Weekday::Sun {
+            day = day.pred_opt().unwrap();
+        }
+        day
+    };
+    date >= last_sunday(3) && date < last_sunday(10)
+}
+
+// BTC_CQ, BTC-USD, BTC-USDT, BTC-USDT-210625-C-40000
+fn parse_huobi(symbol: &str, market_type: MarketType) -> Details {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if market_type == MarketType::EuropeanOption && v.len() == 5 {
+        Details {
+            settle: Some(v[1].to_uppercase()),
+            expiry: parse_yymmdd(v[2], 8),
+            strike: v[4].parse::<f64>().ok(),
+            option_kind: parse_option_kind(v[3]),
+        }
+    } else {
+        Details::default()
+    }
+}
+
+// BTC-USD-SWAP, BTC-USD-210625, BTC-USDT-210625, BTC-USD-210625-30000-P
+fn parse_okex(symbol: &str, base: &str, market_type: MarketType) -> Details {
+    let v: Vec<&str> = symbol.split('-').collect();
+    match market_type {
+        MarketType::InverseFuture | MarketType::LinearFuture if v.len() == 3 => Details {
+            expiry: parse_yymmdd(v[2], 8),
+            ..Default::default()
+        },
+        // options are settled in the base currency
+        MarketType::EuropeanOption if v.len() == 5 => Details {
+            settle: Some(base.to_string()),
+            expiry: parse_yymmdd(v[2], 8),
+            strike: v[3].parse::<f64>().ok(),
+            option_kind: parse_option_kind(v[4]),
+        },
+        _ => Details::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_instrument, OptionKind};
+    use crypto_market_type::MarketType;
+
+    #[test]
+    fn test_okex_option() {
+        let instrument = parse_instrument("BTC-USD-210604-30000-P", "okex", None).unwrap();
+        assert_eq!(MarketType::EuropeanOption, instrument.market_type);
+        assert_eq!("BTC", instrument.base);
+        assert_eq!(Some("BTC".to_string()), instrument.settle);
+        assert_eq!(Some(1622793600000), instrument.expiry); // 2021-06-04T08:00:00Z
+        assert_eq!(Some(30000.0), instrument.strike);
+        assert_eq!(Some(OptionKind::Put), instrument.option_kind);
+    }
+
+    #[test]
+    fn test_deribit_option() {
+        let instrument = parse_instrument("BTC-25JUN21-40000-C", "deribit", None).unwrap();
+        assert_eq!(MarketType::EuropeanOption, instrument.market_type);
+        assert_eq!("BTC", instrument.base);
+        assert_eq!(Some("BTC".to_string()), instrument.settle);
+        assert_eq!(Some(1624608000000), instrument.expiry); // 2021-06-25T08:00:00Z
+        assert_eq!(Some(40000.0), instrument.strike);
+        assert_eq!(Some(OptionKind::Call), instrument.option_kind);
+
+        let instrument = parse_instrument("BTC-4JUN21", "deribit", None).unwrap();
+        assert_eq!(MarketType::InverseFuture, instrument.market_type);
+        assert_eq!(Some(1622793600000), instrument.expiry);
+    }
+
+    #[test]
+    fn test_bitmex_future() {
+        let instrument = parse_instrument("XBTM21", "bitmex", None).unwrap();
+        assert_eq!(MarketType::InverseFuture, instrument.market_type);
+        assert_eq!("BTC/USD", instrument.pair);
+        assert_eq!(Some("BTC".to_string()), instrument.settle);
+        assert_eq!(Some(1624622400000), instrument.expiry); // 2021-06-25T12:00:00Z
+        assert_eq!(None, instrument.strike);
+
+        let instrument = parse_instrument("XBTUSD", "bitmex", None).unwrap();
+        assert_eq!(MarketType::InverseSwap, instrument.market_type);
+        assert_eq!(None, instrument.expiry);
+    }
+
+    #[test]
+    fn test_binance() {
+        let instrument = parse_instrument("BTCUSDT_210625", "binance", None).unwrap();
+        assert_eq!(MarketType::LinearFuture, instrument.market_type);
+        assert_eq!(Some("USDT".to_string()), instrument.settle);
+        assert_eq!(Some(1624608000000), instrument.expiry);
+
+        let instrument = parse_instrument("BTCUSD_PERP", "binance", None).unwrap();
+        assert_eq!(MarketType::InverseSwap, instrument.market_type);
+        assert_eq!(Some("BTC".to_string()), instrument.settle);
+
+        let instrument = parse_instrument("BTCUSDT", "binance", Some(true)).unwrap();
+        assert_eq!(MarketType::Spot, instrument.market_type);
+        assert_eq!(None, instrument.settle);
+    }
+
+    #[test]
+    fn test_gate_future() {
+        let instrument = parse_instrument("BTC_USD_20210625", "gate", None).unwrap();
+        assert_eq!(MarketType::InverseFuture, instrument.market_type);
+        assert_eq!(Some("BTC".to_string()), instrument.settle);
+        assert_eq!(Some(1624608000000), instrument.expiry); // 2021-06-25T08:00:00Z
+
+        let instrument = parse_instrument("BTC_USDT_20211231", "gate", None).unwrap();
+        assert_eq!(MarketType::LinearFuture, instrument.market_type);
+        assert_eq!(Some("USDT".to_string()), instrument.settle);
+        assert_eq!(Some(1640937600000), instrument.expiry); // 2021-12-31T08:00:00Z
+
+        let instrument = parse_instrument("BTC_USDT", "gate", Some(false)).unwrap();
+        assert_eq!(MarketType::LinearSwap, instrument.market_type);
+        assert_eq!(None, instrument.expiry);
+    }
+
+    #[test]
+    fn test_kraken_futures() {
+        let instrument = parse_instrument("FI_XBTUSD_210625", "kraken_futures", None).unwrap();
+        assert_eq!(MarketType::InverseFuture, instrument.market_type);
+        assert_eq!("BTC/USD", instrument.pair);
+        assert_eq!(Some("BTC".to_string()), instrument.settle);
+        assert_eq!(Some(1624633200000), instrument.expiry); // 2021-06-25T15:00:00Z
+
+        let instrument = parse_instrument("FI_ETHUSD_211231", "kraken_futures", None).unwrap();
+        assert_eq!(Some(1640966400000), instrument.expiry); // 2021-12-31T16:00:00Z
+
+        let instrument = parse_instrument("PI_XBTUSD", "kraken_futures", None).unwrap();
+        assert_eq!(MarketType::InverseSwap, instrument.market_type);
+        assert_eq!(None, instrument.expiry);
+    }
+
+    #[test]
+    fn test_unknown_exchange() {
+        assert_eq!(None, parse_instrument("BTCUSDT", "unknown", None));
+    }
+}
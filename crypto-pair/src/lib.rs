@@ -1,22 +1,43 @@
 #![allow(clippy::unnecessary_wraps)]
 
 use crypto_market_type::MarketType;
+pub mod currency;
 mod exchanges;
+mod instrument;
+
+pub use currency::{get_currency, Currency};
+pub use instrument::{Instrument, OptionKind};
 
 /// Normalize a trading currency.
 ///
+/// Exchange-specific prefixes and suffixes are stripped, then aliases of the
+/// exchange in the currency registry are applied, e.g., `XBT` at BitMEX.
+/// Aliases shared by all exchanges are not, use `currency::canonical_id()`
+/// for those.
+///
 /// # Arguments
 ///
 /// * `currency` - The exchange-specific currency
 /// * `exchange` - The normalized symbol
+///
+/// # Examples
+///
+/// ```
+/// use crypto_pair::normalize_currency;
+///
+/// assert_eq!("BTC", normalize_currency("XBT", "bitmex"));
+/// assert_eq!("BTC", normalize_currency("XXBT", "kraken"));
+/// assert_eq!("BSV", normalize_currency("BCHSV", "kucoin"));
+/// ```
 pub fn normalize_currency(currency: &str, exchange: &str) -> String {
-    match exchange {
+    let normalized = match exchange {
         "bitfinex" => exchanges::bitfinex::normalize_currency(currency),
         "bitmex" => exchanges::bitmex::normalize_currency(currency),
         "kraken" => exchanges::kraken::normalize_currency(currency),
         "kucoin" => exchanges::kucoin::normalize_currency(currency),
         _ => currency.to_uppercase(),
-    }
+    };
+    currency::exchange_alias(&normalized, exchange).unwrap_or(normalized)
 }
 
 /// Normalize a cryptocurrency trading symbol.
@@ -26,6 +47,8 @@ pub fn normalize_currency(currency: &str, exchange: &str) -> String {
 /// * `symbol` - The original pair of an exchange
 /// * `exchange` - The exchange name
 ///
+/// Returns `None` if the exchange is unknown.
+///
 /// # Examples
 ///
 /// ```
@@ -36,6 +59,7 @@ pub fn normalize_currency(currency: &str, exchange: &str) -> String {
 /// assert_eq!(Some("BTC/USDT".to_string()), normalize_pair("BTCUSDT", "binance"));
 /// assert_eq!(Some("BTC/USDT".to_string()), normalize_pair("btcusdt", "huobi"));
 /// assert_eq!(Some("BTC/USDT".to_string()), normalize_pair("BTCUST", "bitfinex"));
+/// assert_eq!(None, normalize_pair("BTCUSDT", "unknown"));
 /// ```
 pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
     match exchange {
@@ -61,6 +85,7 @@ pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
         }
         "huobi" => exchanges::huobi::normalize_pair(symbol),
         "kraken" => exchanges::kraken::normalize_pair(symbol),
+        "kraken_futures" => exchanges::kraken_futures::normalize_pair(symbol),
         "kucoin" => exchanges::kucoin::normalize_pair(symbol),
         "mxc" => Some(symbol.replace("_", "/")),
         "okex" => {
@@ -70,7 +95,7 @@ pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
         "Poloniex" => Some(symbol.replace("_", "/")),
         "Upbit" => Some(symbol.replace("-", "/")),
         "zbg" => exchanges::zbg::normalize_pair(symbol),
-        _ => panic!("Unknown exchange {}", exchange),
+        _ => None,
     }
 }
 
@@ -98,6 +123,7 @@ pub fn get_market_type(symbol: &str, exchange: &str, is_spot: Option<bool>) -> M
         "gate" => exchanges::gate::get_market_type(symbol, is_spot),
         "huobi" => exchanges::huobi::get_market_type(symbol),
         "kraken" => MarketType::Spot,
+        "kraken_futures" => exchanges::kraken_futures::get_market_type(symbol),
         "kucoin" => exchanges::kucoin::get_market_type(symbol),
         "mxc" => exchanges::mxc::get_market_type(symbol, is_spot),
         "okex" => exchanges::okex::get_market_type(symbol),
@@ -105,3 +131,27 @@ pub fn get_market_type(symbol: &str, exchange: &str, is_spot: Option<bool>) -> M
         _ => MarketType::Unknown,
     }
 }
+
+/// Parse an exchange-specific symbol into a typed instrument.
+///
+/// Besides the unified pair, it extracts the settlement currency, and the
+/// expiry, strike and option kind if they're encoded in the symbol. Returns
+/// `None` if the market type of the symbol is unknown.
+///
+/// The `is_spot` parameter is the same as in `get_market_type()`.
+///
+/// # Examples
+///
+/// ```
+/// use crypto_pair::{parse_instrument, OptionKind};
+///
+/// let instrument = parse_instrument("BTC-25JUN21-40000-C", "deribit", None).unwrap();
+/// assert_eq!("BTC", instrument.base);
+/// assert_eq!(Some("BTC".to_string()), instrument.settle);
+/// assert_eq!(Some(1624608000000), instrument.expiry);
+/// assert_eq!(Some(40000.0), instrument.strike);
+/// assert_eq!(Some(OptionKind::Call), instrument.option_kind);
+/// ```
+pub fn parse_instrument(symbol: &str, exchange: &str, is_spot: Option<bool>) -> Option<Instrument> {
+    instrument::parse_instrument(symbol, exchange, is_spot)
+}
@@ -1,21 +1 @@
-use reqwest::{header, Result};
-
-pub(super) fn http_get(url: &str) -> Result<String> {
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
-
-    let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
-    let response = client.get(url).send()?;
-
-    match response.error_for_status() {
-        Ok(resp) => Ok(resp.text()?),
-        Err(error) => Err(error),
-    }
-}
+pub(super) use crypto_http::http_get;
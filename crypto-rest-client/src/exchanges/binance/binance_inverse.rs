@@ -5,76 +5,216 @@ use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://dapi.binance.com";
 
-/// Binance Coin-margined Future and Swap market
-///
-/// * REST API doc: <https://binance-docs.github.io/apidocs/delivery/en/>
-/// * Trading at: <https://www.binance.com/en/delivery/btcusd_perpetual>
-/// Rate Limits: <https://binance-docs.github.io/apidocs/delivery/en/#limits>
-///   * 2400 request weight per minute
-pub struct BinanceInverseRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
+rest_client! {
+    /// Binance Coin-margined Future and Swap market
+    ///
+    /// * REST API doc: <https://binance-docs.github.io/apidocs/delivery/en/>
+    /// * Trading at: <https://www.binance.com/en/delivery/btcusd_perpetual>
+    /// Rate Limits: <https://binance-docs.github.io/apidocs/delivery/en/#limits>
+    ///   * 2400 request weight per minute
+    pub struct BinanceInverseRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
 
-impl BinanceInverseRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        BinanceInverseRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+    impl BinanceInverseRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            BinanceInverseRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
         }
-    }
 
-    /// Get compressed, aggregate trades.
-    ///
-    /// Equivalent to `/dapi/v1/aggTrades` with `limit=1000`
-    ///
-    /// For example:
-    ///
-    /// - <https://dapi.binance.com/dapi/v1/aggTrades?symbol=BTCUSD_PERP&limit=1000>
-    /// - <https://dapi.binance.com/dapi/v1/aggTrades?symbol=BTCUSD_210625&limit=1000>
-    pub fn fetch_agg_trades(
-        symbol: &str,
-        from_id: Option<u64>,
-        start_time: Option<u64>,
-        end_time: Option<u64>,
-    ) -> Result<String> {
-        check_symbol(symbol);
-        let symbol = Some(symbol);
-        let limit = Some(1000);
-        gen_api_binance!(
-            "/dapi/v1/aggTrades",
-            symbol,
-            from_id,
-            start_time,
-            end_time,
-            limit
-        )
-    }
+        /// Get compressed, aggregate trades.
+        ///
+        /// Equivalent to `/dapi/v1/aggTrades` with `limit=1000`
+        ///
+        /// For example:
+        ///
+        /// - <https://dapi.binance.com/dapi/v1/aggTrades?symbol=BTCUSD_PERP&limit=1000>
+        /// - <https://dapi.binance.com/dapi/v1/aggTrades?symbol=BTCUSD_210625&limit=1000>
+        #[allow(non_snake_case)]
+        pub async fn fetch_agg_trades(
+            symbol: &str,
+            from_id: Option<u64>,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let fromId = from_id;
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1000);
+            gen_api_binance!(
+                "/dapi/v1/aggTrades",
+                symbol,
+                fromId,
+                startTime,
+                endTime,
+                limit
+            )
+        }
 
-    /// Get a Level2 snapshot of orderbook.
-    ///
-    /// Equivalent to `/dapi/v1/depth` with `limit=1000`
-    ///
-    /// For example:
-    ///
-    /// - <https://dapi.binance.com/dapi/v1/depth?symbol=BTCUSD_PERP&limit=1000>
-    /// - <https://dapi.binance.com/dapi/v1/depth?symbol=BTCUSD_211231&limit=1000>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        check_symbol(symbol);
-        let symbol = Some(symbol);
-        let limit = Some(1000);
-        gen_api_binance!("/dapi/v1/depth", symbol, limit)
-    }
+        /// Get candlesticks.
+        ///
+        /// Equivalent to `/dapi/v1/klines` with `limit=1000`
+        ///
+        /// For example:
+        ///
+        /// - <https://dapi.binance.com/dapi/v1/klines?symbol=BTCUSD_PERP&interval=1m&limit=1000>
+        /// - <https://dapi.binance.com/dapi/v1/klines?symbol=BTCUSD_210625&interval=1m&limit=1000>
+        #[allow(non_snake_case)]
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            interval: &str,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1000);
+            gen_api_binance!(
+                "/dapi/v1/klines",
+                symbol,
+                interval,
+                startTime,
+                endTime,
+                limit
+            )
+        }
 
-    /// Get open interest.
-    ///
-    /// For example:
-    ///
-    /// - <https://dapi.binance.com/dapi/v1/openInterest?symbol=BTCUSD_PERP>
-    /// - <https://dapi.binance.com/dapi/v1/openInterest?symbol=BTCUSD_211231>
-    pub fn fetch_open_interest(symbol: &str) -> Result<String> {
-        check_symbol(symbol);
-        let symbol = Some(symbol);
-        gen_api_binance!("/dapi/v1/openInterest", symbol)
+        /// Get a Level2 snapshot of orderbook.
+        ///
+        /// Equivalent to `/dapi/v1/depth` with `limit=1000`
+        ///
+        /// For example:
+        ///
+        /// - <https://dapi.binance.com/dapi/v1/depth?symbol=BTCUSD_PERP&limit=1000>
+        /// - <https://dapi.binance.com/dapi/v1/depth?symbol=BTCUSD_211231&limit=1000>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let limit = Some(1000);
+            gen_api_binance!("/dapi/v1/depth", symbol, limit)
+        }
+
+        /// Get open interest.
+        ///
+        /// For example:
+        ///
+        /// - <https://dapi.binance.com/dapi/v1/openInterest?symbol=BTCUSD_PERP>
+        /// - <https://dapi.binance.com/dapi/v1/openInterest?symbol=BTCUSD_211231>
+        pub async fn fetch_open_interest(symbol: &str) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            gen_api_binance!("/dapi/v1/openInterest", symbol)
+        }
+
+        /// Get funding rate history.
+        ///
+        /// Equivalent to `/dapi/v1/fundingRate` with `limit=1000`
+        ///
+        /// For example: <https://dapi.binance.com/dapi/v1/fundingRate?symbol=BTCUSD_PERP&limit=1000>
+        #[allow(non_snake_case)]
+        pub async fn fetch_funding_rates(
+            symbol: &str,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1000);
+            gen_api_binance!("/dapi/v1/fundingRate", symbol, startTime, endTime, limit)
+        }
+
+        /// Get mark price candlesticks.
+        ///
+        /// Equivalent to `/dapi/v1/markPriceKlines` with `limit=1500`
+        ///
+        /// For example: <https://dapi.binance.com/dapi/v1/markPriceKlines?symbol=BTCUSD_PERP&interval=1m&limit=1500>
+        #[allow(non_snake_case)]
+        pub async fn fetch_mark_price_klines(
+            symbol: &str,
+            interval: &str,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1500);
+            gen_api_binance!(
+                "/dapi/v1/markPriceKlines",
+                symbol,
+                interval,
+                startTime,
+                endTime,
+                limit
+            )
+        }
+
+        /// Get premium index candlesticks.
+        ///
+        /// Equivalent to `/dapi/v1/premiumIndexKlines` with `limit=1500`
+        ///
+        /// For example: <https://dapi.binance.com/dapi/v1/premiumIndexKlines?symbol=BTCUSD_PERP&interval=1m&limit=1500>
+        #[allow(non_snake_case)]
+        pub async fn fetch_premium_index_klines(
+            symbol: &str,
+            interval: &str,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1500);
+            gen_api_binance!(
+                "/dapi/v1/premiumIndexKlines",
+                symbol,
+                interval,
+                startTime,
+                endTime,
+                limit
+            )
+        }
+
+        /// Get index price candlesticks of a pair.
+        ///
+        /// Equivalent to `/dapi/v1/indexPriceKlines` with `limit=1500`
+        ///
+        /// For example: <https://dapi.binance.com/dapi/v1/indexPriceKlines?pair=BTCUSD&interval=1m&limit=1500>
+        #[allow(non_snake_case)]
+        pub async fn fetch_index_price_klines(
+            pair: &str,
+            interval: &str,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(pair)?;
+            let pair = Some(pair);
+            let interval = Some(interval);
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1500);
+            gen_api_binance!(
+                "/dapi/v1/indexPriceKlines",
+                pair,
+                interval,
+                startTime,
+                endTime,
+                limit
+            )
+        }
     }
 }
@@ -5,59 +5,93 @@ use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api.binance.com";
 
-/// Binance Spot market.
-///
-/// * RESTful API doc: <https://binance-docs.github.io/apidocs/spot/en/>
-/// * Trading at: <https://www.binance.com/en/trade/BTC_USDT>
-/// * Rate Limits: <https://binance-docs.github.io/apidocs/spot/en/#limits>
-///   * 1200 request weight per minute
-///   * 6100 raw requests per 5 minutes
-pub struct BinanceSpotRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
+rest_client! {
+    /// Binance Spot market.
+    ///
+    /// * RESTful API doc: <https://binance-docs.github.io/apidocs/spot/en/>
+    /// * Trading at: <https://www.binance.com/en/trade/BTC_USDT>
+    /// * Rate Limits: <https://binance-docs.github.io/apidocs/spot/en/#limits>
+    ///   * 1200 request weight per minute
+    ///   * 6100 raw requests per 5 minutes
+    pub struct BinanceSpotRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
 
-impl BinanceSpotRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        BinanceSpotRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+    impl BinanceSpotRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            BinanceSpotRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
         }
-    }
 
-    /// Get compressed, aggregate trades.
-    ///
-    /// Equivalent to `/api/v3/aggTrades` with `limit=1000`
-    ///
-    /// For example: <https://api.binance.com/api/v3/aggTrades?symbol=BTCUSDT&limit=1000>
-    pub fn fetch_agg_trades(
-        symbol: &str,
-        from_id: Option<u64>,
-        start_time: Option<u64>,
-        end_time: Option<u64>,
-    ) -> Result<String> {
-        check_symbol(symbol);
-        let symbol = Some(symbol);
-        let limit = Some(1000);
-        gen_api_binance!(
-            "/api/v3/aggTrades",
-            symbol,
-            from_id,
-            start_time,
-            end_time,
-            limit
-        )
-    }
+        /// Get compressed, aggregate trades.
+        ///
+        /// Equivalent to `/api/v3/aggTrades` with `limit=1000`
+        ///
+        /// For example: <https://api.binance.com/api/v3/aggTrades?symbol=BTCUSDT&limit=1000>
+        #[allow(non_snake_case)]
+        pub async fn fetch_agg_trades(
+            symbol: &str,
+            from_id: Option<u64>,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let fromId = from_id;
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1000);
+            gen_api_binance!(
+                "/api/v3/aggTrades",
+                symbol,
+                fromId,
+                startTime,
+                endTime,
+                limit
+            )
+        }
 
-    /// Get a Level2 snapshot of orderbook.
-    ///
-    /// Equivalent to `/api/v3/depth` with `limit=1000`
-    ///
-    /// For example: <https://api.binance.com/api/v3/depth?symbol=BTCUSDT&limit=1000>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        check_symbol(symbol);
-        let symbol = Some(symbol);
-        let limit = Some(1000);
-        gen_api_binance!("/api/v3/depth", symbol, limit)
+        /// Get candlesticks.
+        ///
+        /// Equivalent to `/api/v3/klines` with `limit=1000`
+        ///
+        /// For example: <https://api.binance.com/api/v3/klines?symbol=BTCUSDT&interval=1m&limit=1000>
+        #[allow(non_snake_case)]
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            interval: &str,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(1000);
+            gen_api_binance!(
+                "/api/v3/klines",
+                symbol,
+                interval,
+                startTime,
+                endTime,
+                limit
+            )
+        }
+
+        /// Get a Level2 snapshot of orderbook.
+        ///
+        /// Equivalent to `/api/v3/depth` with `limit=1000`
+        ///
+        /// For example: <https://api.binance.com/api/v3/depth?symbol=BTCUSDT&limit=1000>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            check_symbol(symbol)?;
+            let symbol = Some(symbol);
+            let limit = Some(1000);
+            gen_api_binance!("/api/v3/depth", symbol, limit)
+        }
     }
 }
@@ -6,34 +6,525 @@ pub(crate) mod binance_linear;
 pub(crate) mod binance_option;
 pub(crate) mod binance_spot;
 
-use crate::error::Result;
+use super::utils::{calc_quantity_and_volume, new_kline_msg, time_windows};
+use crate::error::{Error, Result};
+use crate::history::TradeCursor;
 use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg, MarkPriceMsg, TradeMsg, TradeSide};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => binance_spot::BinanceSpotRestClient::fetch_l2_snapshot,
+const EXCHANGE_NAME: &str = "binance";
+
+// Binance allows at most one hour between startTime and endTime
+const AGG_TRADES_WINDOW_MS: i64 = 3600 * 1000 - 1;
+
+// see https://binance-docs.github.io/apidocs/futures/en/#get-funding-rate-history
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFundingRate {
+    symbol: String,
+    fundingTime: i64,
+    fundingRate: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://binance-docs.github.io/apidocs/spot/en/#compressed-aggregate-trades-list
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct AggTrade {
+    a: i64,    // Aggregate trade ID
+    p: String, // Price
+    q: String, // Quantity
+    f: i64,    // First trade ID
+    l: i64,    // Last trade ID
+    T: i64,    // Trade time
+    m: bool,   // Is the buyer the market maker?
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => binance_spot::BinanceSpotRestClient::fetch_l2_snapshot(symbol).await,
+        MarketType::InverseFuture | MarketType::InverseSwap => {
+            binance_inverse::BinanceInverseRestClient::fetch_l2_snapshot(symbol).await
+        }
+        MarketType::LinearFuture | MarketType::LinearSwap => {
+            binance_linear::BinanceLinearRestClient::fetch_l2_snapshot(symbol).await
+        }
+        MarketType::EuropeanOption => {
+            binance_option::BinanceOptionRestClient::fetch_l2_snapshot(symbol).await
+        }
+        _ => Err(Error(format!(
+            "Binance unknown market_type: {}",
+            market_type
+        ))),
+    }
+}
+
+pub(crate) async fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::InverseFuture | MarketType::InverseSwap => {
+            binance_inverse::BinanceInverseRestClient::fetch_open_interest(symbol).await
+        }
+        MarketType::LinearFuture | MarketType::LinearSwap => {
+            binance_linear::BinanceLinearRestClient::fetch_open_interest(symbol).await
+        }
+        _ => Err(Error(format!(
+            "Binance {} does not have open interest data",
+            market_type
+        ))),
+    }
+}
+
+async fn fetch_agg_trades(
+    market_type: MarketType,
+    symbol: &str,
+    from_id: Option<u64>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+) -> Result<String> {
+    match market_type {
+        MarketType::Spot => {
+            binance_spot::BinanceSpotRestClient::fetch_agg_trades(
+                symbol, from_id, start_time, end_time,
+            )
+            .await
+        }
         MarketType::InverseFuture | MarketType::InverseSwap => {
-            binance_inverse::BinanceInverseRestClient::fetch_l2_snapshot
+            binance_inverse::BinanceInverseRestClient::fetch_agg_trades(
+                symbol, from_id, start_time, end_time,
+            )
+            .await
         }
         MarketType::LinearFuture | MarketType::LinearSwap => {
-            binance_linear::BinanceLinearRestClient::fetch_l2_snapshot
+            binance_linear::BinanceLinearRestClient::fetch_agg_trades(
+                symbol, from_id, start_time, end_time,
+            )
+            .await
+        }
+        _ => Err(Error(format!(
+            "Binance {} does NOT have aggregate trades",
+            market_type
+        ))),
+    }
+}
+
+pub(crate) async fn fetch_trades_page(
+    market_type: MarketType,
+    symbol: &str,
+    cursor: &TradeCursor,
+) -> Result<Vec<TradeMsg>> {
+    let raw_trades = if let Some(last_id) = cursor.last_id.as_ref() {
+        let from_id = last_id
+            .parse::<u64>()
+            .map_err(|_| Error(format!("Invalid aggregate trade ID {}", last_id)))?
+            + 1;
+        let text = fetch_agg_trades(market_type, symbol, Some(from_id), None, None).await?;
+        serde_json::from_str::<Vec<AggTrade>>(&text)?
+    } else {
+        // Move the one-hour window forward until it contains trades
+        let mut window_start = cursor.start_time;
+        loop {
+            if window_start > cursor.end_time {
+                break Vec::new();
+            }
+            let window_end = std::cmp::min(window_start + AGG_TRADES_WINDOW_MS, cursor.end_time);
+            let text = fetch_agg_trades(
+                market_type,
+                symbol,
+                None,
+                Some(window_start as u64),
+                Some(window_end as u64),
+            )
+            .await?;
+            let raw_trades = serde_json::from_str::<Vec<AggTrade>>(&text)?;
+            if !raw_trades.is_empty() {
+                break raw_trades;
+            }
+            window_start = window_end + 1;
         }
-        MarketType::EuropeanOption => binance_option::BinanceOptionRestClient::fetch_l2_snapshot,
-        _ => panic!("Binance unknown market_type: {}", market_type),
     };
 
-    func(symbol)
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let trades = raw_trades
+        .into_iter()
+        .map(|raw_trade| {
+            let price = raw_trade.p.parse::<f64>().unwrap();
+            let quantity = raw_trade.q.parse::<f64>().unwrap();
+            let (quantity_base, quantity_quote, quantity_contract) =
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            TradeMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::Trade,
+                timestamp: raw_trade.T,
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                side: if raw_trade.m {
+                    TradeSide::Sell
+                } else {
+                    TradeSide::Buy
+                },
+                trade_id: raw_trade.a.to_string(),
+                json: serde_json::to_string(&raw_trade).unwrap(),
+            }
+        })
+        .collect();
+    Ok(trades)
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
+fn to_candlestick_interval(interval: usize) -> Result<&'static str> {
+    let interval_str = match interval {
+        60 => "1m",
+        180 => "3m",
+        300 => "5m",
+        900 => "15m",
+        1800 => "30m",
+        3600 => "1h",
+        7200 => "2h",
+        14400 => "4h",
+        21600 => "6h",
+        28800 => "8h",
+        43200 => "12h",
+        86400 => "1d",
+        259200 => "3d",
+        604800 => "1w",
+        _ => {
+            return Err(Error(format!(
+                "Binance does NOT support candlestick interval {}",
+                interval
+            )))
+        }
+    };
+    Ok(interval_str)
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let interval_str = to_candlestick_interval(interval)?;
+    let (start_time, end_time) = (Some(start_time as u64), Some(end_time as u64));
+    let text = match market_type {
+        MarketType::Spot => {
+            binance_spot::BinanceSpotRestClient::fetch_candlesticks(
+                symbol,
+                interval_str,
+                start_time,
+                end_time,
+            )
+            .await?
+        }
         MarketType::InverseFuture | MarketType::InverseSwap => {
-            binance_inverse::BinanceInverseRestClient::fetch_open_interest
+            binance_inverse::BinanceInverseRestClient::fetch_candlesticks(
+                symbol,
+                interval_str,
+                start_time,
+                end_time,
+            )
+            .await?
         }
         MarketType::LinearFuture | MarketType::LinearSwap => {
-            binance_linear::BinanceLinearRestClient::fetch_open_interest
+            binance_linear::BinanceLinearRestClient::fetch_candlesticks(
+                symbol,
+                interval_str,
+                start_time,
+                end_time,
+            )
+            .await?
+        }
+        _ => {
+            return Err(Error(format!(
+                "Binance {} does NOT have candlesticks",
+                market_type
+            )))
         }
-        _ => panic!("Binance {} does not have open interest data", market_type),
     };
-    func(symbol)
+    // see https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-data
+    let raw_klines = serde_json::from_str::<Vec<Vec<Value>>>(&text)?;
+
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let parse_f64 = |v: &Value| v.as_str().unwrap().parse::<f64>().unwrap();
+    let klines = raw_klines
+        .into_iter()
+        .map(|raw_kline| {
+            let ohlc = [
+                parse_f64(&raw_kline[1]),
+                parse_f64(&raw_kline[2]),
+                parse_f64(&raw_kline[3]),
+                parse_f64(&raw_kline[4]),
+            ];
+            let (volume, quote_volume) = match market_type {
+                // volume is the number of contracts, base volume is at index 7
+                MarketType::InverseFuture | MarketType::InverseSwap => {
+                    let contracts = parse_f64(&raw_kline[5]);
+                    let (_, quote_volume, _) = calc_quantity_and_volume(
+                        EXCHANGE_NAME,
+                        market_type,
+                        &pair,
+                        ohlc[3],
+                        contracts,
+                    );
+                    (parse_f64(&raw_kline[7]), quote_volume)
+                }
+                _ => (parse_f64(&raw_kline[5]), parse_f64(&raw_kline[7])),
+            };
+            new_kline_msg(
+                EXCHANGE_NAME,
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                raw_kline[0].as_i64().unwrap(),
+                ohlc,
+                volume,
+                Some(quote_volume),
+                serde_json::to_string(&raw_kline).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
+}
+
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    if market_type != MarketType::InverseSwap && market_type != MarketType::LinearSwap {
+        return Err(Error(format!(
+            "Binance {} does NOT have funding rates",
+            market_type
+        )));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    let mut funding_rates = Vec::new();
+    let mut cursor = start_time;
+    while cursor <= end_time {
+        let (page_start, page_end) = (Some(cursor as u64), Some(end_time as u64));
+        let text = if market_type == MarketType::InverseSwap {
+            binance_inverse::BinanceInverseRestClient::fetch_funding_rates(
+                symbol, page_start, page_end,
+            )
+            .await?
+        } else {
+            binance_linear::BinanceLinearRestClient::fetch_funding_rates(
+                symbol, page_start, page_end,
+            )
+            .await?
+        };
+        let raw_funding_rates = serde_json::from_str::<Vec<RawFundingRate>>(&text)?;
+        let n = raw_funding_rates.len();
+        if let Some(last) = raw_funding_rates.last() {
+            cursor = last.fundingTime + 1;
+        }
+        funding_rates.extend(raw_funding_rates.into_iter().map(|raw_funding_rate| {
+            FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::FundingRate,
+                timestamp: raw_funding_rate.fundingTime,
+                funding_rate: raw_funding_rate.fundingRate.parse::<f64>().unwrap(),
+                funding_time: raw_funding_rate.fundingTime,
+                estimated_rate: None,
+                mark_price: None,
+                json: serde_json::to_string(&raw_funding_rate).unwrap(),
+            }
+        }));
+        if n < 1000 {
+            break;
+        }
+    }
+    Ok(funding_rates)
+}
+
+#[derive(Clone, Copy)]
+enum PriceKline {
+    MarkPrice,
+    PremiumIndex,
+    IndexPrice,
+}
+
+// Fetches price candlesticks of inverse or linear contracts
+async fn fetch_price_klines(
+    market_type: MarketType,
+    kind: PriceKline,
+    symbol: &str,
+    interval: &str,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+) -> Result<String> {
+    use binance_inverse::BinanceInverseRestClient as Inverse;
+    use binance_linear::BinanceLinearRestClient as Linear;
+    match market_type {
+        MarketType::InverseFuture | MarketType::InverseSwap => match kind {
+            PriceKline::MarkPrice => {
+                Inverse::fetch_mark_price_klines(symbol, interval, start_time, end_time).await
+            }
+            PriceKline::PremiumIndex => {
+                Inverse::fetch_premium_index_klines(symbol, interval, start_time, end_time).await
+            }
+            PriceKline::IndexPrice => {
+                Inverse::fetch_index_price_klines(symbol, interval, start_time, end_time).await
+            }
+        },
+        MarketType::LinearFuture | MarketType::LinearSwap => match kind {
+            PriceKline::MarkPrice => {
+                Linear::fetch_mark_price_klines(symbol, interval, start_time, end_time).await
+            }
+            PriceKline::PremiumIndex => {
+                Linear::fetch_premium_index_klines(symbol, interval, start_time, end_time).await
+            }
+            PriceKline::IndexPrice => {
+                Linear::fetch_index_price_klines(symbol, interval, start_time, end_time).await
+            }
+        },
+        _ => Err(Error(format!(
+            "Binance {} does NOT have mark prices or index prices",
+            market_type
+        ))),
+    }
+}
+
+// Returns (open time, close price, raw candlestick) of each candlestick
+fn parse_price_klines(text: &str) -> Result<Vec<(i64, f64, Value)>> {
+    let raw_klines = serde_json::from_str::<Vec<Vec<Value>>>(text)?;
+    let mut prices = Vec::with_capacity(raw_klines.len());
+    for raw_kline in raw_klines {
+        let (timestamp, close) = match (
+            raw_kline[0].as_i64(),
+            raw_kline[4].as_str().and_then(|s| s.parse::<f64>().ok()),
+        ) {
+            (Some(timestamp), Some(close)) => (timestamp, close),
+            _ => return Err(Error(format!("Failed to parse {:?}", raw_kline))),
+        };
+        prices.push((timestamp, close, Value::Array(raw_kline)));
+    }
+    Ok(prices)
+}
+
+// Mark prices come from markPriceKlines, and premium indexes from premiumIndexKlines,
+// both are the close prices of candlesticks.
+pub(crate) async fn fetch_mark_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    let interval_str = to_candlestick_interval(interval)?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    let mut mark_prices = Vec::new();
+    for (window_start, window_end) in
+        time_windows(start_time, end_time, 1500 * interval as i64 * 1000)
+    {
+        let (window_start, window_end) = (Some(window_start as u64), Some(window_end as u64));
+        let marks = parse_price_klines(
+            &fetch_price_klines(
+                market_type,
+                PriceKline::MarkPrice,
+                symbol,
+                interval_str,
+                window_start,
+                window_end,
+            )
+            .await?,
+        )?;
+        let premiums: BTreeMap<i64, f64> = parse_price_klines(
+            &fetch_price_klines(
+                market_type,
+                PriceKline::PremiumIndex,
+                symbol,
+                interval_str,
+                window_start,
+                window_end,
+            )
+            .await?,
+        )?
+        .into_iter()
+        .map(|(timestamp, premium, _)| (timestamp, premium))
+        .collect();
+        mark_prices.extend(
+            marks
+                .into_iter()
+                .map(|(timestamp, mark_price, json)| MarkPriceMsg {
+                    exchange: EXCHANGE_NAME.to_string(),
+                    market_type,
+                    symbol: symbol.to_string(),
+                    pair: pair.clone(),
+                    msg_type: MessageType::MarkPrice,
+                    timestamp,
+                    json: json.to_string(),
+                    mark_price: Some(mark_price),
+                    index_price: None,
+                    premium_index: premiums.get(&timestamp).copied(),
+                }),
+        );
+    }
+    Ok(mark_prices)
+}
+
+pub(crate) async fn fetch_index_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    let interval_str = to_candlestick_interval(interval)?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    // BTCUSD_PERP and BTCUSD_211231 share the index of BTCUSD
+    let index_pair = symbol.split('_').next().unwrap();
+
+    let mut index_prices = Vec::new();
+    for (window_start, window_end) in
+        time_windows(start_time, end_time, 1500 * interval as i64 * 1000)
+    {
+        let text = fetch_price_klines(
+            market_type,
+            PriceKline::IndexPrice,
+            index_pair,
+            interval_str,
+            Some(window_start as u64),
+            Some(window_end as u64),
+        )
+        .await?;
+        index_prices.extend(parse_price_klines(&text)?.into_iter().map(
+            |(timestamp, index_price, json)| MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::IndexPrice,
+                timestamp,
+                json: json.to_string(),
+                mark_price: None,
+                index_price: Some(index_price),
+                premium_index: None,
+            },
+        ));
+    }
+    Ok(index_prices)
 }
@@ -10,12 +10,14 @@ lazy_static! {
     static ref SYMBOL_PATTERN: Regex = Regex::new("^[A-Z0-9-_.]{1,20}$").unwrap();
 }
 
-pub(super) fn check_symbol(symbol: &str) {
-    if !SYMBOL_PATTERN.is_match(symbol) {
-        panic!(
+pub(super) fn check_symbol(symbol: &str) -> Result<()> {
+    if SYMBOL_PATTERN.is_match(symbol) {
+        Ok(())
+    } else {
+        Err(Error(format!(
             "Illegal symbol {}, legal symbol should be '^[A-Z0-9-_.]{{1,20}}$'.",
             symbol
-        );
+        )))
     }
 }
 
@@ -47,7 +49,7 @@ macro_rules! gen_api_binance {
                     params.insert(stringify!($param_name).to_string(), param_name.to_string());
                 }
             )*
-            let ret = http_get(format!("{}{}",BASE_URL, $path).as_str(), &params);
+            let ret = http_get(format!("{}{}",BASE_URL, $path).as_str(), &params).await;
             match ret {
                 Ok(resp) => check_code_in_body(resp),
                 Err(_) => ret,
@@ -1,64 +1,205 @@
-use super::utils::http_get;
-use crate::error::Result;
+use super::utils::{calc_quantity_and_volume, http_get, new_kline_msg};
+use crate::error::{Error, Result};
+use crate::history::TradeCursor;
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{KlineMsg, TradeMsg, TradeSide};
+use crypto_msg_type::MessageType;
 use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api-pub.bitfinex.com";
 
-/// The REST client for Bitfinex, including all markets.
-///
-/// * REST API doc: <https://docs.bitfinex.com/docs/rest-general>
-/// * Spot: <https://trading.bitfinex.com/trading>
-/// * Swap: <https://trading.bitfinex.com/t/BTCF0:USTF0>
-/// * Funding: <https://trading.bitfinex.com/funding>
-pub struct BitfinexRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
+rest_client! {
+    /// The REST client for Bitfinex, including all markets.
+    ///
+    /// * REST API doc: <https://docs.bitfinex.com/docs/rest-general>
+    /// * Spot: <https://trading.bitfinex.com/trading>
+    /// * Swap: <https://trading.bitfinex.com/t/BTCF0:USTF0>
+    /// * Funding: <https://trading.bitfinex.com/funding>
+    pub struct BitfinexRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
 
-impl BitfinexRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        BitfinexRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+    impl BitfinexRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            BitfinexRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
         }
-    }
 
-    /// /v2/trades/Symbol/hist
-    pub fn fetch_trades(
-        symbol: &str,
-        limit: Option<u16>,
-        start: Option<u64>,
-        end: Option<u64>,
-        sort: Option<i8>,
-    ) -> Result<String> {
-        gen_api!(
-            format!("/v2/trades/{}/hist", symbol),
-            limit,
-            start,
-            end,
-            sort
-        )
-    }
+        /// /v2/trades/Symbol/hist
+        pub async fn fetch_trades(
+            symbol: &str,
+            limit: Option<u16>,
+            start: Option<u64>,
+            end: Option<u64>,
+            sort: Option<i8>,
+        ) -> Result<String> {
+            gen_api!(
+                format!("/v2/trades/{}/hist", symbol),
+                limit,
+                start,
+                end,
+                sort
+            )
+        }
 
-    /// Get a Level2 snapshot of orderbook.
-    ///
-    /// Equivalent to `/v2/book/Symbol/P0` with `len=100`
-    ///
-    /// For example: <https://api-pub.bitfinex.com/v2/book/tBTCUSD/P0?len=100>
-    ///
-    /// Ratelimit: 90 req/min
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        let len = Some(100);
-        gen_api!(format!("/v2/book/{}/P0", symbol), len)
+        /// Get candlesticks.
+        ///
+        /// Equivalent to `/v2/candles/trade:TimeFrame:Symbol/hist`
+        ///
+        /// For example: <https://api-pub.bitfinex.com/v2/candles/trade:1m:tBTCUSD/hist?limit=10000&sort=1>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            time_frame: &str,
+            limit: Option<u16>,
+            start: Option<u64>,
+            end: Option<u64>,
+            sort: Option<i8>,
+        ) -> Result<String> {
+            gen_api!(
+                format!("/v2/candles/trade:{}:{}/hist", time_frame, symbol),
+                limit,
+                start,
+                end,
+                sort
+            )
+        }
+
+        /// Get a Level2 snapshot of orderbook.
+        ///
+        /// Equivalent to `/v2/book/Symbol/P0` with `len=100`
+        ///
+        /// For example: <https://api-pub.bitfinex.com/v2/book/tBTCUSD/P0?len=100>
+        ///
+        /// Ratelimit: 90 req/min
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            let len = Some(100);
+            gen_api!(format!("/v2/book/{}/P0", symbol), len)
+        }
+
+        /// Get a Level3 snapshot of orderbook.
+        ///
+        /// Equivalent to `/v2/book/Symbol/R0` with `len=100`
+        ///
+        /// For example: <https://api-pub.bitfinex.com/v2/book/tBTCUSD/R0?len=100>
+        pub async fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
+            let len = Some(100);
+            gen_api!(format!("/v2/book/{}/R0", symbol), len)
+        }
     }
+}
 
-    /// Get a Level3 snapshot of orderbook.
-    ///
-    /// Equivalent to `/v2/book/Symbol/R0` with `len=100`
-    ///
-    /// For example: <https://api-pub.bitfinex.com/v2/book/tBTCUSD/R0?len=100>
-    pub fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
-        let len = Some(100);
-        gen_api!(format!("/v2/book/{}/R0", symbol), len)
+pub(crate) async fn fetch_trades_page(
+    market_type: MarketType,
+    symbol: &str,
+    cursor: &TradeCursor,
+) -> Result<Vec<TradeMsg>> {
+    // sort=1 returns trades in ascending order of time
+    let text = BitfinexRestClient::fetch_trades(
+        symbol,
+        Some(10000),
+        Some(cursor.start_time as u64),
+        Some(cursor.end_time as u64),
+        Some(1),
+    )
+    .await?;
+    // [ID, MTS, AMOUNT, PRICE], a negative amount means seller is taker
+    let raw_trades = serde_json::from_str::<Vec<[f64; 4]>>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "bitfinex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let trades = raw_trades
+        .into_iter()
+        .map(|nums| {
+            let price = nums[3];
+            let (quantity_base, quantity_quote, quantity_contract) =
+                calc_quantity_and_volume("bitfinex", market_type, &pair, price, f64::abs(nums[2]));
+            TradeMsg {
+                exchange: "bitfinex".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::Trade,
+                timestamp: nums[1] as i64,
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                side: if nums[2] < 0.0 {
+                    TradeSide::Sell
+                } else {
+                    TradeSide::Buy
+                },
+                trade_id: (nums[0] as i64).to_string(),
+                json: serde_json::to_string(&nums).unwrap(),
+            }
+        })
+        .collect();
+    Ok(trades)
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let time_frame = match interval {
+        60 => "1m",
+        300 => "5m",
+        900 => "15m",
+        1800 => "30m",
+        3600 => "1h",
+        10800 => "3h",
+        21600 => "6h",
+        43200 => "12h",
+        86400 => "1D",
+        604800 => "1W",
+        1209600 => "14D",
+        _ => {
+            return Err(Error(format!(
+                "Bitfinex does NOT support candlestick interval {}",
+                interval
+            )))
+        }
+    };
+    if !symbol.starts_with('t') {
+        return Err(Error(format!(
+            "Bitfinex {} does NOT have trading candlesticks",
+            symbol
+        )));
     }
+    let text = BitfinexRestClient::fetch_candlesticks(
+        symbol,
+        time_frame,
+        Some(10000),
+        Some(start_time as u64),
+        Some(end_time as u64),
+        Some(1),
+    )
+    .await?;
+    // [MTS, OPEN, CLOSE, HIGH, LOW, VOLUME]
+    let raw_klines = serde_json::from_str::<Vec<[f64; 6]>>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "bitfinex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = raw_klines
+        .into_iter()
+        .map(|nums| {
+            new_kline_msg(
+                "bitfinex",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                nums[0] as i64,
+                [nums[1], nums[3], nums[4], nums[2]],
+                nums[5],
+                None,
+                serde_json::to_string(&nums).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
 }
@@ -1,31 +1,30 @@
-mod bitget_spot;
-mod bitget_swap;
+pub(crate) mod bitget_spot;
+pub(crate) mod bitget_swap;
 
-pub use bitget_spot::BitgetSpotRestClient;
-pub use bitget_swap::BitgetSwapRestClient;
-
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => bitget_spot::BitgetSpotRestClient::fetch_l2_snapshot,
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => bitget_spot::BitgetSpotRestClient::fetch_l2_snapshot(symbol).await,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            bitget_swap::BitgetSwapRestClient::fetch_l2_snapshot
+            bitget_swap::BitgetSwapRestClient::fetch_l2_snapshot(symbol).await
         }
-        _ => panic!("Bitget unknown market_type: {}", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!(
+            "Bitget unknown market_type: {}",
+            market_type
+        ))),
+    }
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
+pub(crate) async fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            bitget_swap::BitgetSwapRestClient::fetch_open_interest
+            bitget_swap::BitgetSwapRestClient::fetch_open_interest(symbol).await
         }
-        _ => panic!("Bitget {} does not have open interest", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!(
+            "Bitget {} does not have open interest",
+            market_type
+        ))),
+    }
 }
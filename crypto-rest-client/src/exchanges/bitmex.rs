@@ -1,51 +1,380 @@
-use super::utils::http_get;
-use crate::error::Result;
-use std::collections::BTreeMap;
+use super::utils::{http_get, millis_to_iso, new_kline_msg};
+use crate::error::{Error, Result};
+use crate::history::TradeCursor;
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg, MarkPriceMsg, TradeMsg, TradeSide};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 const BASE_URL: &str = "https://www.bitmex.com/api/v1";
 
-/// The REST client for BitMEX.
-///
-/// BitMEX has Swap and Future markets.
-///
-/// * REST API doc: <https://www.bitmex.com/api/explorer/>
-/// * Trading at: <https://www.bitmex.com/app/trade/>
-/// * Rate Limits: <https://www.bitmex.com/app/restAPI#Limits>
-///   * 60 requests per minute on all routes (reduced to 30 when unauthenticated)
-///   * 10 requests per second on certain routes (see below)
-pub struct BitmexRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl BitmexRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        BitmexRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+rest_client! {
+    /// The REST client for BitMEX.
+    ///
+    /// BitMEX has Swap and Future markets.
+    ///
+    /// * REST API doc: <https://www.bitmex.com/api/explorer/>
+    /// * Trading at: <https://www.bitmex.com/app/trade/>
+    /// * Rate Limits: <https://www.bitmex.com/app/restAPI#Limits>
+    ///   * 60 requests per minute on all routes (reduced to 30 when unauthenticated)
+    ///   * 10 requests per second on certain routes (see below)
+    pub struct BitmexRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
+
+    impl BitmexRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            BitmexRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get trades from a beginning time.
+        ///
+        /// Equivalent to `/trade` with `count=1000`
+        ///
+        /// For example: <https://www.bitmex.com/api/v1/trade?symbol=XBTUSD&count=1000&startTime=2018-09-28T12:34:25.706Z>
+        #[allow(non_snake_case)]
+        pub async fn fetch_trades(symbol: &str, start_time: Option<String>) -> Result<String> {
+            let symbol = Some(symbol);
+            let startTime = start_time;
+            gen_api!("/trade", symbol, startTime)
+        }
+
+        /// Get candlesticks.
+        ///
+        /// Equivalent to `/trade/bucketed` with `partial=false&count=1000`
+        ///
+        /// For example: <https://www.bitmex.com/api/v1/trade/bucketed?symbol=XBTUSD&binSize=1m&partial=false&count=1000>
+        #[allow(non_snake_case)]
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            bin_size: &str,
+            start_time: Option<String>,
+            end_time: Option<String>,
+        ) -> Result<String> {
+            let symbol = Some(symbol);
+            let binSize = Some(bin_size);
+            let partial = Some(false);
+            let count = Some(1000);
+            let startTime = start_time;
+            let endTime = end_time;
+            gen_api!(
+                "/trade/bucketed",
+                symbol,
+                binSize,
+                partial,
+                count,
+                startTime,
+                endTime
+            )
+        }
+
+        /// Get funding rate history.
+        ///
+        /// Equivalent to `/funding` with `count=500`
+        ///
+        /// For example: <https://www.bitmex.com/api/v1/funding?symbol=XBTUSD&count=500>
+        #[allow(non_snake_case)]
+        pub async fn fetch_funding_rates(
+            symbol: &str,
+            start_time: Option<String>,
+            end_time: Option<String>,
+        ) -> Result<String> {
+            let symbol = Some(symbol);
+            let count = Some(500);
+            let startTime = start_time;
+            let endTime = end_time;
+            gen_api!("/funding", symbol, count, startTime, endTime)
+        }
+
+        /// Get an instrument.
+        ///
+        /// Equivalent to `/instrument`
+        ///
+        /// For example: <https://www.bitmex.com/api/v1/instrument?symbol=XBTUSD>
+        pub async fn fetch_instrument(symbol: &str) -> Result<String> {
+            let symbol = Some(symbol);
+            gen_api!("/instrument", symbol)
+        }
+
+        /// Get a full Level2 snapshot of orderbook.
+        ///
+        /// Equivalent to `/orderBook/L2` with `depth=0`
+        ///
+        /// For example: <https://www.bitmex.com/api/v1/orderBook/L2?symbol=XBTUSD&depth=0>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            let symbol = Some(symbol);
+            let depth = Some(0);
+            gen_api!("/orderBook/L2", symbol, depth)
         }
     }
+}
 
-    /// Get trades from a beginning time.
-    ///
-    /// Equivalent to `/trade` with `count=1000`
-    ///
-    /// For example: <https://www.bitmex.com/api/v1/trade?symbol=XBTUSD&count=1000&startTime=2018-09-28T12:34:25.706Z>
+// see https://www.bitmex.com/api/explorer/#!/Trade/Trade_get
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTrade {
+    timestamp: String,
+    symbol: String,
+    side: String, // Sell, Buy
+    size: f64,
+    price: f64,
+    trdMatchID: String,
+    homeNotional: f64,
+    foreignNotional: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) async fn fetch_trades_page(
+    market_type: MarketType,
+    symbol: &str,
+    cursor: &TradeCursor,
+) -> Result<Vec<TradeMsg>> {
+    // 1000 is the maximum count, the default is only 100
     #[allow(non_snake_case)]
-    pub fn fetch_trades(symbol: &str, start_time: Option<String>) -> Result<String> {
+    let text = {
         let symbol = Some(symbol);
-        let startTime = start_time;
-        gen_api!("/trade", symbol, startTime)
+        let count = Some(1000);
+        let startTime = Some(millis_to_iso(cursor.start_time));
+        gen_api!("/trade", symbol, count, startTime)?
+    };
+    let raw_trades = serde_json::from_str::<Vec<RawTrade>>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "bitmex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let trades = raw_trades
+        .into_iter()
+        .map(|raw_trade| {
+            let timestamp = chrono::DateTime::parse_from_rfc3339(&raw_trade.timestamp).unwrap();
+            TradeMsg {
+                exchange: "bitmex".to_string(),
+                market_type,
+                symbol: raw_trade.symbol.clone(),
+                pair: pair.clone(),
+                msg_type: MessageType::Trade,
+                timestamp: timestamp.timestamp_millis(),
+                price: raw_trade.price,
+                quantity_base: raw_trade.homeNotional,
+                quantity_quote: raw_trade.foreignNotional,
+                quantity_contract: Some(raw_trade.size),
+                side: if raw_trade.side == "Sell" {
+                    TradeSide::Sell
+                } else {
+                    TradeSide::Buy
+                },
+                trade_id: raw_trade.trdMatchID.clone(),
+                json: serde_json::to_string(&raw_trade).unwrap(),
+            }
+        })
+        .collect();
+    Ok(trades)
+}
+
+// see https://www.bitmex.com/api/explorer/#!/Trade/Trade_getBucketed
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawBucket {
+    timestamp: String, // close time of the bucket
+    symbol: String,
+    open: Option<f64>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    homeNotional: Option<f64>,
+    foreignNotional: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+fn to_bin_size(interval: usize) -> Result<&'static str> {
+    match interval {
+        60 => Ok("1m"),
+        300 => Ok("5m"),
+        3600 => Ok("1h"),
+        86400 => Ok("1d"),
+        _ => Err(Error(format!(
+            "BitMEX does NOT support candlestick interval {}",
+            interval
+        ))),
     }
+}
 
-    /// Get a full Level2 snapshot of orderbook.
-    ///
-    /// Equivalent to `/orderBook/L2` with `depth=0`
-    ///
-    /// For example: <https://www.bitmex.com/api/v1/orderBook/L2?symbol=XBTUSD&depth=0>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        let symbol = Some(symbol);
-        let depth = Some(0);
-        gen_api!("/orderBook/L2", symbol, depth)
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let bin_size = to_bin_size(interval)?;
+    // BitMEX buckets are labeled by close time
+    let interval_ms = (interval * 1000) as i64;
+    let text = BitmexRestClient::fetch_candlesticks(
+        symbol,
+        bin_size,
+        Some(millis_to_iso(start_time + interval_ms)),
+        Some(millis_to_iso(end_time + interval_ms)),
+    )
+    .await?;
+    let raw_buckets = serde_json::from_str::<Vec<RawBucket>>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "bitmex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = raw_buckets
+        .into_iter()
+        // buckets without any trade have no prices
+        .filter(|raw_bucket| raw_bucket.open.is_some())
+        .map(|raw_bucket| {
+            let timestamp = chrono::DateTime::parse_from_rfc3339(&raw_bucket.timestamp).unwrap();
+            new_kline_msg(
+                "bitmex",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                timestamp.timestamp_millis() - interval_ms,
+                [
+                    raw_bucket.open.unwrap(),
+                    raw_bucket.high.unwrap(),
+                    raw_bucket.low.unwrap(),
+                    raw_bucket.close.unwrap(),
+                ],
+                raw_bucket.homeNotional.unwrap_or(0.0),
+                raw_bucket.foreignNotional,
+                serde_json::to_string(&raw_bucket).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
+}
+
+// see https://www.bitmex.com/api/explorer/#!/Funding/Funding_get
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFunding {
+    timestamp: String,
+    symbol: String,
+    fundingRate: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    let pair = crypto_pair::normalize_pair(symbol, "bitmex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let mut funding_rates = Vec::new();
+    let mut cursor = start_time;
+    while cursor <= end_time {
+        let text = BitmexRestClient::fetch_funding_rates(
+            symbol,
+            Some(millis_to_iso(cursor)),
+            Some(millis_to_iso(end_time)),
+        )
+        .await?;
+        let raw_fundings = serde_json::from_str::<Vec<RawFunding>>(&text)?;
+        let n = raw_fundings.len();
+        for raw_funding in raw_fundings {
+            let funding_time = chrono::DateTime::parse_from_rfc3339(&raw_funding.timestamp)
+                .unwrap()
+                .timestamp_millis();
+            cursor = funding_time + 1;
+            funding_rates.push(FundingRateMsg {
+                exchange: "bitmex".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::FundingRate,
+                timestamp: funding_time,
+                funding_rate: raw_funding.fundingRate,
+                funding_time,
+                estimated_rate: None,
+                mark_price: None,
+                json: serde_json::to_string(&raw_funding).unwrap(),
+            });
+        }
+        if n < 500 {
+            break;
+        }
+    }
+    Ok(funding_rates)
+}
+
+// see https://www.bitmex.com/api/explorer/#!/Instrument/Instrument_get
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawInstrument {
+    symbol: String,
+    referenceSymbol: Option<String>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// Index prices are the close prices of candlesticks of the index symbol, e.g., .BXBT,
+// which is the referenceSymbol of the instrument.
+pub(crate) async fn fetch_index_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    let text = BitmexRestClient::fetch_instrument(symbol).await?;
+    let index_symbol = serde_json::from_str::<Vec<RawInstrument>>(&text)?
+        .into_iter()
+        .find(|instrument| instrument.symbol == symbol)
+        .and_then(|instrument| instrument.referenceSymbol)
+        .ok_or_else(|| Error(format!("BitMEX {} does NOT have an index", symbol)))?;
+    let pair = crypto_pair::normalize_pair(symbol, "bitmex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    let bin_size = to_bin_size(interval)?;
+    // BitMEX buckets are labeled by close time
+    let interval_ms = (interval * 1000) as i64;
+    let mut index_prices = Vec::new();
+    let mut cursor = start_time;
+    while cursor <= end_time {
+        let text = BitmexRestClient::fetch_candlesticks(
+            &index_symbol,
+            bin_size,
+            Some(millis_to_iso(cursor + interval_ms)),
+            Some(millis_to_iso(end_time + interval_ms)),
+        )
+        .await?;
+        let raw_buckets = serde_json::from_str::<Vec<RawBucket>>(&text)?;
+        let n = raw_buckets.len();
+        for raw_bucket in raw_buckets {
+            let timestamp = chrono::DateTime::parse_from_rfc3339(&raw_bucket.timestamp)
+                .map_err(|err| Error(err.to_string()))?
+                .timestamp_millis()
+                - interval_ms;
+            cursor = timestamp + 1;
+            if let Some(close) = raw_bucket.close {
+                index_prices.push(MarkPriceMsg {
+                    exchange: "bitmex".to_string(),
+                    market_type,
+                    symbol: symbol.to_string(),
+                    pair: pair.clone(),
+                    msg_type: MessageType::IndexPrice,
+                    timestamp,
+                    json: serde_json::to_string(&raw_bucket).unwrap(),
+                    mark_price: None,
+                    index_price: Some(close),
+                    premium_index: None,
+                });
+            }
+        }
+        if n < 1000 {
+            break;
+        }
     }
+    Ok(index_prices)
 }
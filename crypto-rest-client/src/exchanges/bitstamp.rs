@@ -1,57 +1,159 @@
-use super::utils::http_get;
-use crate::error::Result;
-use std::collections::BTreeMap;
+use super::utils::{http_get, new_kline_msg};
+use crate::error::{Error, Result};
+use crypto_market_type::MarketType;
+use crypto_msg_parser::KlineMsg;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 const BASE_URL: &str = "https://www.bitstamp.net/api";
 
-/// The REST client for Bitstamp.
-///
-/// Bitstamp has only Spot market.
-///
-/// * REST API doc: <https://www.bitstamp.net/api/>
-/// * Trading at: <https://www.bitstamp.net/market/tradeview/>
-/// * Rate Limits: <https://www.bitstamp.net/api/#what-is-api>
-///   * Do not make more than 8000 requests per 10 minutes or we will ban your IP address.
-pub struct BitstampRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl BitstampRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        BitstampRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
-        }
-    }
-
-    /// Get trades.
-    ///
-    /// `/v2/transactions/{symbol}/`
+rest_client! {
+    /// The REST client for Bitstamp.
     ///
-    /// `time` specifies the time interval from which we want the transactions
-    /// to be returned. Possible values are "minute", "hour" (default) or "day".
+    /// Bitstamp has only Spot market.
     ///
-    /// For example: <https://www.bitstamp.net/api/v2/transactions/btcusd/?time=hour>
-    pub fn fetch_trades(symbol: &str, time: Option<String>) -> Result<String> {
-        gen_api!(format!("/v2/transactions/{}/", symbol), time)
+    /// * REST API doc: <https://www.bitstamp.net/api/>
+    /// * Trading at: <https://www.bitstamp.net/market/tradeview/>
+    /// * Rate Limits: <https://www.bitstamp.net/api/#what-is-api>
+    ///   * Do not make more than 8000 requests per 10 minutes or we will ban your IP address.
+    pub struct BitstampRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
     }
 
-    /// Get a full Level2 orderbook snapshot.
-    ///
-    /// /// Equivalent to `/order_book/symbol` with `group=1`
-    ///
-    /// For example: <https://www.bitstamp.net/api/v2/order_book/btcusd/>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/v2/order_book/{}", symbol))
+    impl BitstampRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            BitstampRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get trades.
+        ///
+        /// `/v2/transactions/{symbol}/`
+        ///
+        /// `time` specifies the time interval from which we want the transactions
+        /// to be returned. Possible values are "minute", "hour" (default) or "day".
+        ///
+        /// For example: <https://www.bitstamp.net/api/v2/transactions/btcusd/?time=hour>
+        pub async fn fetch_trades(symbol: &str, time: Option<String>) -> Result<String> {
+            gen_api!(format!("/v2/transactions/{}/", symbol), time)
+        }
+
+        /// Get candlesticks.
+        ///
+        /// Equivalent to `/v2/ohlc/{symbol}/` with `limit=1000`
+        ///
+        /// `step` is the interval in seconds, `start` and `end` are Unix timestamps in seconds.
+        ///
+        /// For example: <https://www.bitstamp.net/api/v2/ohlc/btcusd/?step=60&limit=1000>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            step: u32,
+            start: Option<u64>,
+            end: Option<u64>,
+        ) -> Result<String> {
+            let step = Some(step);
+            let limit = Some(1000);
+            gen_api!(format!("/v2/ohlc/{}/", symbol), step, limit, start, end)
+        }
+
+        /// Get a full Level2 orderbook snapshot.
+        ///
+        /// /// Equivalent to `/order_book/symbol` with `group=1`
+        ///
+        /// For example: <https://www.bitstamp.net/api/v2/order_book/btcusd/>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/v2/order_book/{}", symbol))
+        }
+
+        /// Get a full Level3 orderbook snapshot.
+        ///
+        /// Equivalent to `/order_book/symbol` with `group=2`
+        ///
+        /// For example: <https://www.bitstamp.net/api/v2/order_book/btcusd/?group=2>
+        pub async fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/v2/order_book/{}?group=2", symbol))
+        }
     }
+}
 
-    /// Get a full Level3 orderbook snapshot.
-    ///
-    /// Equivalent to `/order_book/symbol` with `group=2`
-    ///
-    /// For example: <https://www.bitstamp.net/api/v2/order_book/btcusd/?group=2>
-    pub fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/v2/order_book/{}?group=2", symbol))
+// see https://www.bitstamp.net/api/#ohlc_data
+#[derive(Serialize, Deserialize)]
+struct RawOhlc {
+    timestamp: String,
+    open: String,
+    high: String,
+    low: String,
+    close: String,
+    volume: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OhlcData {
+    pair: String,
+    ohlc: Vec<RawOhlc>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OhlcResponse {
+    data: OhlcData,
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    if ![
+        60, 180, 300, 900, 1800, 3600, 7200, 14400, 21600, 43200, 86400, 259200,
+    ]
+    .contains(&interval)
+    {
+        return Err(Error(format!(
+            "Bitstamp does NOT support candlestick interval {}",
+            interval
+        )));
     }
+    let text = BitstampRestClient::fetch_candlesticks(
+        symbol,
+        interval as u32,
+        Some((start_time / 1000) as u64),
+        Some((end_time / 1000) as u64),
+    )
+    .await?;
+    let resp = serde_json::from_str::<OhlcResponse>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "bitstamp")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = resp
+        .data
+        .ohlc
+        .into_iter()
+        .map(|raw_ohlc| {
+            new_kline_msg(
+                "bitstamp",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                raw_ohlc.timestamp.parse::<i64>().unwrap() * 1000,
+                [
+                    raw_ohlc.open.parse::<f64>().unwrap(),
+                    raw_ohlc.high.parse::<f64>().unwrap(),
+                    raw_ohlc.low.parse::<f64>().unwrap(),
+                    raw_ohlc.close.parse::<f64>().unwrap(),
+                ],
+                raw_ohlc.volume.parse::<f64>().unwrap(),
+                None,
+                serde_json::to_string(&raw_ohlc).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
 }
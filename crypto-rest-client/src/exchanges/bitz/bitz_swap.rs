@@ -7,51 +7,33 @@ use serde_json::Value;
 
 const BASE_URL: &str = "https://apiv2.bitz.com";
 
-/// The RESTful client for BitZ swap markets.
-///
-/// * RESTful API doc: <https://apidocv2.bitz.plus/en/>
-/// * Trading at: <https://swap.bitz.plus/en/>
-/// * Rate Limits: <https://apidocv2.bitz.plus/en/#limit>
-///   * no more than 30 times within 1 sec
-pub struct BitzSwapRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl BitzSwapRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        BitzSwapRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
-        }
+rest_client! {
+    /// The RESTful client for BitZ swap markets.
+    ///
+    /// * RESTful API doc: <https://apidocv2.bitz.plus/en/>
+    /// * Trading at: <https://swap.bitz.plus/en/>
+    /// * Rate Limits: <https://apidocv2.bitz.plus/en/#limit>
+    ///   * no more than 30 times within 1 sec
+    pub struct BitzSwapRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
     }
 
-    /// Get the latest Level2 snapshot of orderbook.
-    ///
-    /// Top 100 bids and asks are returned.
-    ///
-    /// For example: <https://apiv2.bitz.com/V2/Market/getContractOrderBook?contractId=101&depth=100>,
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        let symbol_id_map = get_symbol_id_map()?;
-        if !symbol_id_map.contains_key(symbol) {
-            return Err(Error(format!(
-                "Can NOT find contractId for the pair {}",
-                symbol
-            )));
+    impl BitzSwapRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            BitzSwapRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
         }
-        let contract_id = symbol_id_map.get(symbol).unwrap();
-        gen_api!(format!(
-            "/V2/Market/getContractOrderBook?contractId={}&depth=100",
-            contract_id
-        ))
-    }
 
-    /// Get open interest.
-    ///
-    /// For example: <https://apiv2.bitz.com/V2/Market/getContractTickers>
-    pub fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
-        if let Some(symbol) = symbol {
-            let symbol_id_map = get_symbol_id_map()?;
+        /// Get the latest Level2 snapshot of orderbook.
+        ///
+        /// Top 100 bids and asks are returned.
+        ///
+        /// For example: <https://apiv2.bitz.com/V2/Market/getContractOrderBook?contractId=101&depth=100>,
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            let symbol_id_map = get_symbol_id_map().await?;
             if !symbol_id_map.contains_key(symbol) {
                 return Err(Error(format!(
                     "Can NOT find contractId for the pair {}",
@@ -60,11 +42,31 @@ impl BitzSwapRestClient {
             }
             let contract_id = symbol_id_map.get(symbol).unwrap();
             gen_api!(format!(
-                "/V2/Market/getContractTickers?contractId={}",
+                "/V2/Market/getContractOrderBook?contractId={}&depth=100",
                 contract_id
             ))
-        } else {
-            gen_api!("/V2/Market/getContractTickers")
+        }
+
+        /// Get open interest.
+        ///
+        /// For example: <https://apiv2.bitz.com/V2/Market/getContractTickers>
+        pub async fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
+            if let Some(symbol) = symbol {
+                let symbol_id_map = get_symbol_id_map().await?;
+                if !symbol_id_map.contains_key(symbol) {
+                    return Err(Error(format!(
+                        "Can NOT find contractId for the pair {}",
+                        symbol
+                    )));
+                }
+                let contract_id = symbol_id_map.get(symbol).unwrap();
+                gen_api!(format!(
+                    "/V2/Market/getContractTickers?contractId={}",
+                    contract_id
+                ))
+            } else {
+                gen_api!("/V2/Market/getContractTickers")
+            }
         }
     }
 }
@@ -89,9 +91,9 @@ struct Response {
     source: String,
 }
 
-fn get_symbol_id_map() -> Result<HashMap<String, String>> {
+async fn get_symbol_id_map() -> Result<HashMap<String, String>> {
     let params = BTreeMap::new();
-    let txt = http_get("https://apiv2.bitz.com/Market/getContractCoin", &params)?;
+    let txt = http_get("https://apiv2.bitz.com/Market/getContractCoin", &params).await?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     if resp.status != 200 {
         return Err(Error(txt));
@@ -1,31 +1,30 @@
-mod bitz_spot;
-mod bitz_swap;
+pub(crate) mod bitz_spot;
+pub(crate) mod bitz_swap;
 
-pub use bitz_spot::BitzSpotRestClient;
-pub use bitz_swap::BitzSwapRestClient;
-
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => bitz_spot::BitzSpotRestClient::fetch_l2_snapshot,
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => bitz_spot::BitzSpotRestClient::fetch_l2_snapshot(symbol).await,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            bitz_swap::BitzSwapRestClient::fetch_l2_snapshot
+            bitz_swap::BitzSwapRestClient::fetch_l2_snapshot(symbol).await
         }
-        _ => panic!("BitZ unknown market_type: {}", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!("BitZ unknown market_type: {}", market_type))),
+    }
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: Option<&str>) -> Result<String> {
-    let func = match market_type {
+pub(crate) async fn fetch_open_interest(
+    market_type: MarketType,
+    symbol: Option<&str>,
+) -> Result<String> {
+    match market_type {
         MarketType::LinearSwap | MarketType::InverseSwap => {
-            bitz_swap::BitzSwapRestClient::fetch_open_interest
+            bitz_swap::BitzSwapRestClient::fetch_open_interest(symbol).await
         }
-        _ => panic!("bitz {} does not have open interest", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!(
+            "bitz {} does not have open interest",
+            market_type
+        ))),
+    }
 }
@@ -1,57 +1,487 @@
-use super::utils::http_get;
-use crate::error::Result;
-use std::collections::BTreeMap;
+use super::utils::{http_get, new_kline_msg, time_windows};
+use crate::error::{Error, Result};
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg, MarkPriceMsg};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 const BASE_URL: &str = "https://api.bybit.com/v2";
 
-/// The RESTful client for Bybit.
-///
-/// Bybit has InverseSwap and LinearSwap markets.
-///
-/// * RESTful API doc: <https://bybit-exchange.github.io/docs/inverse/#t-marketdata>
-/// * Trading at:
-///     * InverseSwap <https://www.bybit.com/trade/inverse/>
-///     * LinearSwap <https://www.bybit.com/trade/usdt/>
-/// * Rate Limit: <https://bybit-exchange.github.io/docs/inverse/#t-ratelimits>
-///   * GET method:
-///     * 50 requests per second continuously for 2 minutes
-///     * 70 requests per second continuously for 5 seconds
-///   * POST method:
-///     * 20 requests per second continuously for 2 minutes
-///     * 50 requests per second continuously for 5 seconds
-pub struct BybitRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl BybitRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        BybitRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
-        }
-    }
-
-    /// Get the latest Level2 snapshot of orderbook.
+rest_client! {
+    /// The RESTful client for Bybit.
     ///
-    /// Top 50 bids and asks are returned.
+    /// Bybit has InverseSwap and LinearSwap markets.
     ///
-    /// For example: <https://api.bybit.com/v2/public/orderBook/L2?symbol=BTCUSD>,
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/public/orderBook/L2?symbol={}", symbol))
+    /// * RESTful API doc: <https://bybit-exchange.github.io/docs/inverse/#t-marketdata>
+    /// * Trading at:
+    ///     * InverseSwap <https://www.bybit.com/trade/inverse/>
+    ///     * LinearSwap <https://www.bybit.com/trade/usdt/>
+    /// * Rate Limit: <https://bybit-exchange.github.io/docs/inverse/#t-ratelimits>
+    ///   * GET method:
+    ///     * 50 requests per second continuously for 2 minutes
+    ///     * 70 requests per second continuously for 5 seconds
+    ///   * POST method:
+    ///     * 20 requests per second continuously for 2 minutes
+    ///     * 50 requests per second continuously for 5 seconds
+    pub struct BybitRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
     }
 
-    /// Get open interest.
-    ///
-    /// For example:
-    ///
-    /// - <https://api.bybit.com/v2/public/open-interest?symbol=BTCUSD&period=5min>
-    /// - <https://api.bybit.com/v2/public/open-interest?symbol=BTCUSDT&period=5min>
-    /// - <https://api.bybit.com/v2/public/open-interest?symbol=BTCUSDZ21&period=5min>
-    pub fn fetch_open_interest(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/public/open-interest?symbol={}&period=5min",
-            symbol
-        ))
+    impl BybitRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            BybitRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get the latest Level2 snapshot of orderbook.
+        ///
+        /// Top 50 bids and asks are returned.
+        ///
+        /// For example: <https://api.bybit.com/v2/public/orderBook/L2?symbol=BTCUSD>,
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/public/orderBook/L2?symbol={}", symbol))
+        }
+
+        /// Get candlesticks of inverse markets.
+        ///
+        /// Equivalent to `/public/kline/list` with `limit=200`, `from` is a Unix timestamp in seconds.
+        ///
+        /// For example: <https://api.bybit.com/v2/public/kline/list?symbol=BTCUSD&interval=1&limit=200&from=1633046400>
+        pub async fn fetch_inverse_candlesticks(symbol: &str, interval: &str, from: u64) -> Result<String> {
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let from = Some(from);
+            let limit = Some(200);
+            gen_api!("/public/kline/list", symbol, interval, from, limit)
+        }
+
+        /// Get candlesticks of linear markets.
+        ///
+        /// Equivalent to `/public/linear/kline` with `limit=200`, `from` is a Unix timestamp in seconds.
+        ///
+        /// For example: <https://api.bybit.com/public/linear/kline?symbol=BTCUSDT&interval=1&limit=200&from=1633046400>
+        pub async fn fetch_linear_candlesticks(symbol: &str, interval: &str, from: u64) -> Result<String> {
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let from = Some(from);
+            let limit = Some(200);
+            gen_api!(
+                "https://api.bybit.com/public/linear/kline",
+                symbol,
+                interval,
+                from,
+                limit
+            )
+        }
+
+        /// Get mark price candlesticks.
+        ///
+        /// At most 200 candlesticks are returned, `from` is a Unix timestamp in seconds.
+        ///
+        /// For example:
+        ///
+        /// - <https://api.bybit.com/v2/public/mark-price-kline?symbol=BTCUSD&interval=1&limit=200&from=1633046400>
+        /// - <https://api.bybit.com/public/linear/mark-price-kline?symbol=BTCUSDT&interval=1&limit=200&from=1633046400>
+        pub async fn fetch_mark_price_klines(symbol: &str, interval: &str, from: u64) -> Result<String> {
+            let url = price_kline_url(symbol, "mark-price-kline");
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let from = Some(from);
+            let limit = Some(200);
+            gen_api!(url, symbol, interval, from, limit)
+        }
+
+        /// Get index price candlesticks.
+        ///
+        /// At most 200 candlesticks are returned, `from` is a Unix timestamp in seconds.
+        ///
+        /// For example:
+        ///
+        /// - <https://api.bybit.com/v2/public/index-price-kline?symbol=BTCUSD&interval=1&limit=200&from=1633046400>
+        /// - <https://api.bybit.com/public/linear/index-price-kline?symbol=BTCUSDT&interval=1&limit=200&from=1633046400>
+        pub async fn fetch_index_price_klines(symbol: &str, interval: &str, from: u64) -> Result<String> {
+            let url = price_kline_url(symbol, "index-price-kline");
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let from = Some(from);
+            let limit = Some(200);
+            gen_api!(url, symbol, interval, from, limit)
+        }
+
+        /// Get premium index candlesticks.
+        ///
+        /// At most 200 candlesticks are returned, `from` is a Unix timestamp in seconds.
+        ///
+        /// For example:
+        ///
+        /// - <https://api.bybit.com/v2/public/premium-index-kline?symbol=BTCUSD&interval=1&limit=200&from=1633046400>
+        /// - <https://api.bybit.com/public/linear/premium-index-kline?symbol=BTCUSDT&interval=1&limit=200&from=1633046400>
+        pub async fn fetch_premium_index_klines(symbol: &str, interval: &str, from: u64) -> Result<String> {
+            let url = price_kline_url(symbol, "premium-index-kline");
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let from = Some(from);
+            let limit = Some(200);
+            gen_api!(url, symbol, interval, from, limit)
+        }
+
+        /// Get funding rate history, in descending order of time.
+        ///
+        /// Equivalent to `/v5/market/funding/history` with `limit=200`,
+        /// `category` is `inverse` or `linear`.
+        ///
+        /// For example: <https://api.bybit.com/v5/market/funding/history?category=linear&symbol=BTCUSDT&limit=200>
+        #[allow(non_snake_case)]
+        pub async fn fetch_funding_rates(
+            category: &str,
+            symbol: &str,
+            start_time: Option<i64>,
+            end_time: Option<i64>,
+        ) -> Result<String> {
+            let category = Some(category);
+            let symbol = Some(symbol);
+            let startTime = start_time;
+            let endTime = end_time;
+            let limit = Some(200);
+            gen_api!(
+                "https://api.bybit.com/v5/market/funding/history",
+                category,
+                symbol,
+                startTime,
+                endTime,
+                limit
+            )
+        }
+
+        /// Get open interest.
+        ///
+        /// For example:
+        ///
+        /// - <https://api.bybit.com/v2/public/open-interest?symbol=BTCUSD&period=5min>
+        /// - <https://api.bybit.com/v2/public/open-interest?symbol=BTCUSDT&period=5min>
+        /// - <https://api.bybit.com/v2/public/open-interest?symbol=BTCUSDZ21&period=5min>
+        pub async fn fetch_open_interest(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/public/open-interest?symbol={}&period=5min",
+                symbol
+            ))
+        }
+    }
+}
+
+// USDT-margined symbols are served by /public/linear, others by /v2/public
+fn price_kline_url(symbol: &str, name: &str) -> String {
+    if symbol.ends_with("USDT") {
+        format!("https://api.bybit.com/public/linear/{}", name)
+    } else {
+        format!("/public/{}", name)
+    }
+}
+
+fn to_kline_interval(interval: usize) -> Result<String> {
+    match interval {
+        60 | 180 | 300 | 900 | 1800 | 3600 | 7200 | 14400 | 21600 | 43200 => {
+            Ok((interval / 60).to_string())
+        }
+        86400 => Ok("D".to_string()),
+        604800 => Ok("W".to_string()),
+        _ => Err(Error(format!(
+            "Bybit does NOT support candlestick interval {}",
+            interval
+        ))),
+    }
+}
+
+// Bybit returns numbers in some APIs and strings in others
+fn value_to_f64(v: &Value) -> Result<f64> {
+    match v {
+        Value::String(s) => s.parse::<f64>().ok(),
+        _ => v.as_f64(),
+    }
+    .ok_or_else(|| Error(format!("Failed to parse {} as f64", v)))
+}
+
+// Returns (open time, close price, raw candlestick) of each candlestick
+fn parse_price_klines(text: &str) -> Result<Vec<(i64, f64, Value)>> {
+    let resp = serde_json::from_str::<HashMap<String, Value>>(text)?;
+    let raw_klines = match resp.get("result").and_then(|result| result.as_array()) {
+        Some(arr) => arr.clone(),
+        None => return Err(Error(text.to_string())),
+    };
+    let mut prices = Vec::with_capacity(raw_klines.len());
+    for raw_kline in raw_klines {
+        let timestamp = raw_kline
+            .get("start_at")
+            .or_else(|| raw_kline.get("open_time"))
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| Error(format!("Failed to parse open time of {}", raw_kline)))?
+            * 1000;
+        let close = value_to_f64(&raw_kline["close"])?;
+        prices.push((timestamp, close, raw_kline));
+    }
+    Ok(prices)
+}
+
+// Mark prices come from mark-price-kline, and premium indexes from premium-index-kline,
+// both are the close prices of candlesticks.
+pub(crate) async fn fetch_mark_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    fetch_price_klines(market_type, symbol, interval, start_time, end_time, true).await
+}
+
+pub(crate) async fn fetch_index_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    fetch_price_klines(market_type, symbol, interval, start_time, end_time, false).await
+}
+
+async fn fetch_price_klines(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+    mark: bool,
+) -> Result<Vec<MarkPriceMsg>> {
+    if market_type != MarketType::InverseSwap
+        && market_type != MarketType::InverseFuture
+        && market_type != MarketType::LinearSwap
+    {
+        return Err(Error(format!(
+            "Bybit {} does NOT have mark prices",
+            market_type
+        )));
+    }
+    let interval_str = to_kline_interval(interval)?;
+    let pair = crypto_pair::normalize_pair(symbol, "bybit")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    let mut prices = Vec::new();
+    for (window_start, _) in time_windows(start_time, end_time, 200 * interval as i64 * 1000) {
+        // Round up to seconds, so that no candlestick before window_start is returned
+        let from = ((window_start + 999) / 1000) as u64;
+        if mark {
+            let marks = parse_price_klines(
+                &BybitRestClient::fetch_mark_price_klines(symbol, &interval_str, from).await?,
+            )?;
+            let premiums: HashMap<i64, f64> = parse_price_klines(
+                &BybitRestClient::fetch_premium_index_klines(symbol, &interval_str, from).await?,
+            )?
+            .into_iter()
+            .map(|(timestamp, premium, _)| (timestamp, premium))
+            .collect();
+            prices.extend(
+                marks
+                    .into_iter()
+                    .map(|(timestamp, mark_price, json)| MarkPriceMsg {
+                        exchange: "bybit".to_string(),
+                        market_type,
+                        symbol: symbol.to_string(),
+                        pair: pair.clone(),
+                        msg_type: MessageType::MarkPrice,
+                        timestamp,
+                        json: json.to_string(),
+                        mark_price: Some(mark_price),
+                        index_price: None,
+                        premium_index: premiums.get(&timestamp).copied(),
+                    }),
+            );
+        } else {
+            let indexes = parse_price_klines(
+                &BybitRestClient::fetch_index_price_klines(symbol, &interval_str, from).await?,
+            )?;
+            prices.extend(
+                indexes
+                    .into_iter()
+                    .map(|(timestamp, index_price, json)| MarkPriceMsg {
+                        exchange: "bybit".to_string(),
+                        market_type,
+                        symbol: symbol.to_string(),
+                        pair: pair.clone(),
+                        msg_type: MessageType::IndexPrice,
+                        timestamp,
+                        json: json.to_string(),
+                        mark_price: None,
+                        index_price: Some(index_price),
+                        premium_index: None,
+                    }),
+            );
+        }
+    }
+    Ok(prices)
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    _end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let interval_str = to_kline_interval(interval)?;
+    // Round up to seconds, so that no candlestick before start_time is returned
+    let from = ((start_time + 999) / 1000) as u64;
+    let text = match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            BybitRestClient::fetch_inverse_candlesticks(symbol, &interval_str, from).await?
+        }
+        MarketType::LinearSwap => {
+            BybitRestClient::fetch_linear_candlesticks(symbol, &interval_str, from).await?
+        }
+        _ => {
+            return Err(Error(format!(
+                "Bybit {} does NOT have candlesticks",
+                market_type
+            )))
+        }
+    };
+    let resp = serde_json::from_str::<HashMap<String, Value>>(&text)?;
+    let raw_klines = match resp.get("result").and_then(|result| result.as_array()) {
+        Some(arr) => arr.clone(),
+        None => return Err(Error(text)),
+    };
+
+    let pair = crypto_pair::normalize_pair(symbol, "bybit")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let mut klines = Vec::with_capacity(raw_klines.len());
+    for raw_kline in raw_klines {
+        let (volume, quote_volume) = if market_type == MarketType::LinearSwap {
+            (
+                value_to_f64(&raw_kline["volume"])?,
+                value_to_f64(&raw_kline["turnover"])?,
+            )
+        } else {
+            // volume is in USD and turnover is in coins
+            (
+                value_to_f64(&raw_kline["turnover"])?,
+                value_to_f64(&raw_kline["volume"])?,
+            )
+        };
+        let open_time = raw_kline["open_time"]
+            .as_i64()
+            .ok_or_else(|| Error(format!("Failed to parse open time of {}", raw_kline)))?;
+        klines.push(new_kline_msg(
+            "bybit",
+            market_type,
+            symbol,
+            &pair,
+            interval,
+            open_time * 1000,
+            [
+                value_to_f64(&raw_kline["open"])?,
+                value_to_f64(&raw_kline["high"])?,
+                value_to_f64(&raw_kline["low"])?,
+                value_to_f64(&raw_kline["close"])?,
+            ],
+            volume,
+            Some(quote_volume),
+            serde_json::to_string(&raw_kline).unwrap(),
+        ));
+    }
+    Ok(klines)
+}
+
+// see https://bybit-exchange.github.io/docs/v5/market/history-fund-rate
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFunding {
+    symbol: String,
+    fundingRate: String,
+    fundingRateTimestamp: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FundingList {
+    list: Vec<RawFunding>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct FundingResponse {
+    retCode: i64,
+    result: Option<FundingList>,
+}
+
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    let category = match market_type {
+        MarketType::InverseSwap => "inverse",
+        MarketType::LinearSwap => "linear",
+        _ => {
+            return Err(Error(format!(
+                "Bybit {} does NOT have funding rates",
+                market_type
+            )))
+        }
+    };
+    let pair = crypto_pair::normalize_pair(symbol, "bybit")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    // Pages are in descending order of time, move endTime backwards
+    let mut funding_rates = Vec::new();
+    let mut cursor = end_time;
+    while cursor >= start_time {
+        let text =
+            BybitRestClient::fetch_funding_rates(category, symbol, Some(start_time), Some(cursor))
+                .await?;
+        let resp = serde_json::from_str::<FundingResponse>(&text)?;
+        let raw_fundings = match resp.result {
+            Some(result) if resp.retCode == 0 => result.list,
+            _ => return Err(Error(text)),
+        };
+        let n = raw_fundings.len();
+        for raw_funding in raw_fundings {
+            let funding_time = raw_funding
+                .fundingRateTimestamp
+                .parse::<i64>()
+                .map_err(|_| {
+                    Error(format!(
+                        "Failed to parse {}",
+                        raw_funding.fundingRateTimestamp
+                    ))
+                })?;
+            let funding_rate = raw_funding
+                .fundingRate
+                .parse::<f64>()
+                .map_err(|_| Error(format!("Failed to parse {}", raw_funding.fundingRate)))?;
+            cursor = funding_time - 1;
+            funding_rates.push(FundingRateMsg {
+                exchange: "bybit".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::FundingRate,
+                timestamp: funding_time,
+                funding_rate,
+                funding_time,
+                estimated_rate: None,
+                mark_price: None,
+                json: serde_json::to_string(&raw_funding).unwrap(),
+            });
+        }
+        if n < 200 {
+            break;
+        }
     }
+    Ok(funding_rates)
 }
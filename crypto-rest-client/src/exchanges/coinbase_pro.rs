@@ -1,54 +1,124 @@
-use super::utils::http_get;
-use crate::error::Result;
+use super::utils::{http_get, millis_to_iso, new_kline_msg};
+use crate::error::{Error, Result};
+use crypto_market_type::MarketType;
+use crypto_msg_parser::KlineMsg;
 use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api.pro.coinbase.com";
 
-/// The REST client for CoinbasePro.
-///
-/// CoinbasePro has only Spot market.
-///
-///   * REST API doc: <https://docs.pro.coinbase.com/#market-data>
-///   * Trading at: <https://pro.coinbase.com/>
-///   * Rate Limits: <https://docs.pro.coinbase.com/#rate-limits>
-///     * We throttle public endpoints by IP: 10 requests per second, up to 15 requests per second in bursts.
-pub struct CoinbaseProRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl CoinbaseProRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        CoinbaseProRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
-        }
-    }
-
-    /// List the latest trades for a product.
+rest_client! {
+    /// The REST client for CoinbasePro.
     ///
-    /// `/products/{symbol}/trades`
+    /// CoinbasePro has only Spot market.
     ///
-    /// For example: <https://api.pro.coinbase.com/products/BTC-USD/trades>
-    pub fn fetch_trades(symbol: &str) -> Result<String> {
-        gen_api!(format!("/products/{}/trades", symbol))
+    ///   * REST API doc: <https://docs.pro.coinbase.com/#market-data>
+    ///   * Trading at: <https://pro.coinbase.com/>
+    ///   * Rate Limits: <https://docs.pro.coinbase.com/#rate-limits>
+    ///     * We throttle public endpoints by IP: 10 requests per second, up to 15 requests per second in bursts.
+    pub struct CoinbaseProRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
     }
 
-    /// Get the latest Level2 orderbook snapshot.
-    ///
-    /// Top 50 bids and asks (aggregated) are returned.
-    ///
-    /// For example: <https://api.pro.coinbase.com/products/BTC-USD/book?level=2>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/products/{}/book?level=2", symbol))
+    impl CoinbaseProRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            CoinbaseProRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// List the latest trades for a product.
+        ///
+        /// `/products/{symbol}/trades`
+        ///
+        /// For example: <https://api.pro.coinbase.com/products/BTC-USD/trades>
+        pub async fn fetch_trades(symbol: &str) -> Result<String> {
+            gen_api!(format!("/products/{}/trades", symbol))
+        }
+
+        /// Get candlesticks.
+        ///
+        /// `/products/{symbol}/candles`, at most 300 candlesticks are returned.
+        ///
+        /// `granularity` is the interval in seconds, `start` and `end` are in ISO 8601.
+        ///
+        /// For example: <https://api.pro.coinbase.com/products/BTC-USD/candles?granularity=60>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            granularity: u32,
+            start: Option<String>,
+            end: Option<String>,
+        ) -> Result<String> {
+            let granularity = Some(granularity);
+            gen_api!(
+                format!("/products/{}/candles", symbol),
+                granularity,
+                start,
+                end
+            )
+        }
+
+        /// Get the latest Level2 orderbook snapshot.
+        ///
+        /// Top 50 bids and asks (aggregated) are returned.
+        ///
+        /// For example: <https://api.pro.coinbase.com/products/BTC-USD/book?level=2>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/products/{}/book?level=2", symbol))
+        }
+
+        /// Get the latest Level3 orderbook snapshot.
+        ///
+        /// Full order book (non aggregated) are returned.
+        ///
+        /// For example: <https://api.pro.coinbase.com/products/BTC-USD/book?level=3>
+        pub async fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/products/{}/book?level=3", symbol))
+        }
     }
+}
 
-    /// Get the latest Level3 orderbook snapshot.
-    ///
-    /// Full order book (non aggregated) are returned.
-    ///
-    /// For example: <https://api.pro.coinbase.com/products/BTC-USD/book?level=3>
-    pub fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/products/{}/book?level=3", symbol))
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    if ![60, 300, 900, 3600, 21600, 86400].contains(&interval) {
+        return Err(Error(format!(
+            "CoinbasePro does NOT support candlestick interval {}",
+            interval
+        )));
     }
+    let text = CoinbaseProRestClient::fetch_candlesticks(
+        symbol,
+        interval as u32,
+        Some(millis_to_iso(start_time)),
+        Some(millis_to_iso(end_time)),
+    )
+    .await?;
+    // [time, low, high, open, close, volume], in descending order of time
+    let raw_klines = serde_json::from_str::<Vec<[f64; 6]>>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "coinbase_pro")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = raw_klines
+        .into_iter()
+        .map(|nums| {
+            new_kline_msg(
+                "coinbase_pro",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                nums[0] as i64 * 1000,
+                [nums[3], nums[2], nums[1], nums[4]],
+                nums[5],
+                None,
+                serde_json::to_string(&nums).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
 }
@@ -1,71 +1,258 @@
-use super::utils::http_get;
-use crate::error::Result;
-use std::collections::BTreeMap;
+use super::utils::{http_get, new_kline_msg, time_windows};
+use crate::error::{Error, Result};
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 const BASE_URL: &str = "https://www.deribit.com/api/v2";
 
-/// The RESTful client for Deribit.
-///
-/// Deribit has InverseFuture, InverseSwap and Option markets.
-///
-/// * WebSocket API doc: <https://docs.deribit.com/?shell#market-data>
-/// * Trading at:
-///     * Future <https://www.deribit.com/main#/futures>
-///     * Option <https://www.deribit.com/main#/options>
-/// * Rate Limits: <https://www.deribit.com/pages/information/rate-limits>
-///   * Each sub-account has a rate limit of 20 requests per second
-pub struct DeribitRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl DeribitRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        DeribitRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
-        }
-    }
-
-    /// Get most recent trades.
+rest_client! {
+    /// The RESTful client for Deribit.
     ///
-    /// 100 trades are returned.
+    /// Deribit has InverseFuture, InverseSwap and Option markets.
     ///
-    /// For example: <https://www.deribit.com/api/v2/public/get_last_trades_by_instrument?count=100&instrument_name=BTC-PERPETUAL>
-    pub fn fetch_trades(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/public/get_last_trades_by_instrument?count=100&instrument_name={}",
-            symbol
-        ))
+    /// * WebSocket API doc: <https://docs.deribit.com/?shell#market-data>
+    /// * Trading at:
+    ///     * Future <https://www.deribit.com/main#/futures>
+    ///     * Option <https://www.deribit.com/main#/options>
+    /// * Rate Limits: <https://www.deribit.com/pages/information/rate-limits>
+    ///   * Each sub-account has a rate limit of 20 requests per second
+    pub struct DeribitRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
     }
 
-    /// Get the latest Level2 snapshot of orderbook.
-    ///
-    /// Top 2000 bids and asks are returned.
-    ///
-    /// For example: <https://www.deribit.com/api/v2/public/get_order_book?depth=2000&instrument_name=BTC-PERPETUAL>,
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/public/get_order_book?depth=2000&instrument_name={}",
-            symbol,
-        ))
-    }
+    impl DeribitRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            DeribitRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
 
-    /// Get open interest.
-    ///
-    /// For example:
-    /// - <https://www.deribit.com/api/v2/public/get_book_summary_by_currency?currency=BTC>
-    /// - <https://www.deribit.com/api/v2/public/get_book_summary_by_instrument?instrument_name=BTC-PERPETUAL>
-    pub fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
-        if let Some(symbol) = symbol {
+        /// Get most recent trades.
+        ///
+        /// 100 trades are returned.
+        ///
+        /// For example: <https://www.deribit.com/api/v2/public/get_last_trades_by_instrument?count=100&instrument_name=BTC-PERPETUAL>
+        pub async fn fetch_trades(symbol: &str) -> Result<String> {
             gen_api!(format!(
-                "/public/get_book_summary_by_instrument?instrument_name={}",
+                "/public/get_last_trades_by_instrument?count=100&instrument_name={}",
                 symbol
             ))
-        } else {
-            let btc = gen_api!("/public/get_book_summary_by_currency?currency=BTC")?;
-            let eth = gen_api!("/public/get_book_summary_by_currency?currency=ETH")?;
-            Ok(format!("{}\n{}", btc, eth))
         }
+
+        /// Get candlesticks.
+        ///
+        /// `resolution` is the interval in minutes or `1D`, timestamps are in milliseconds.
+        ///
+        /// For example: <https://www.deribit.com/api/v2/public/get_tradingview_chart_data?instrument_name=BTC-PERPETUAL&resolution=1&start_timestamp=1633046400000&end_timestamp=1633050000000>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            resolution: &str,
+            start_timestamp: u64,
+            end_timestamp: u64,
+        ) -> Result<String> {
+            let instrument_name = Some(symbol);
+            let resolution = Some(resolution);
+            let start_timestamp = Some(start_timestamp);
+            let end_timestamp = Some(end_timestamp);
+            gen_api!(
+                "/public/get_tradingview_chart_data",
+                instrument_name,
+                resolution,
+                start_timestamp,
+                end_timestamp
+            )
+        }
+
+        /// Get hourly funding rate history of perpetuals, timestamps are in milliseconds.
+        ///
+        /// For example: <https://www.deribit.com/api/v2/public/get_funding_rate_history?instrument_name=BTC-PERPETUAL&start_timestamp=1633046400000&end_timestamp=1633132800000>
+        pub async fn fetch_funding_rates(
+            symbol: &str,
+            start_timestamp: u64,
+            end_timestamp: u64,
+        ) -> Result<String> {
+            let instrument_name = Some(symbol);
+            let start_timestamp = Some(start_timestamp);
+            let end_timestamp = Some(end_timestamp);
+            gen_api!(
+                "/public/get_funding_rate_history",
+                instrument_name,
+                start_timestamp,
+                end_timestamp
+            )
+        }
+
+        /// Get the latest Level2 snapshot of orderbook.
+        ///
+        /// Top 2000 bids and asks are returned.
+        ///
+        /// For example: <https://www.deribit.com/api/v2/public/get_order_book?depth=2000&instrument_name=BTC-PERPETUAL>,
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/public/get_order_book?depth=2000&instrument_name={}",
+                symbol,
+            ))
+        }
+
+        /// Get open interest.
+        ///
+        /// For example:
+        /// - <https://www.deribit.com/api/v2/public/get_book_summary_by_currency?currency=BTC>
+        /// - <https://www.deribit.com/api/v2/public/get_book_summary_by_instrument?instrument_name=BTC-PERPETUAL>
+        pub async fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
+            if let Some(symbol) = symbol {
+                gen_api!(format!(
+                    "/public/get_book_summary_by_instrument?instrument_name={}",
+                    symbol
+                ))
+            } else {
+                let btc = gen_api!("/public/get_book_summary_by_currency?currency=BTC")?;
+                let eth = gen_api!("/public/get_book_summary_by_currency?currency=ETH")?;
+                Ok(format!("{}\n{}", btc, eth))
+            }
+        }
+    }
+}
+
+// see https://docs.deribit.com/?shell#public-get_tradingview_chart_data
+#[derive(Serialize, Deserialize)]
+struct ChartData {
+    ticks: Vec<i64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    cost: Vec<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ChartDataResponse {
+    result: ChartData,
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let resolution = match interval {
+        60 | 180 | 300 | 600 | 900 | 1800 | 3600 | 7200 | 10800 | 21600 | 43200 => {
+            (interval / 60).to_string()
+        }
+        86400 => "1D".to_string(),
+        _ => {
+            return Err(Error(format!(
+                "Deribit does NOT support candlestick interval {}",
+                interval
+            )))
+        }
+    };
+    let text = DeribitRestClient::fetch_candlesticks(
+        symbol,
+        &resolution,
+        start_time as u64,
+        end_time as u64,
+    )
+    .await?;
+    let chart_data = serde_json::from_str::<ChartDataResponse>(&text)?.result;
+    let pair = crypto_pair::normalize_pair(symbol, "deribit")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = (0..chart_data.ticks.len())
+        .map(|i| {
+            let json = serde_json::json!([
+                chart_data.ticks[i],
+                chart_data.open[i],
+                chart_data.high[i],
+                chart_data.low[i],
+                chart_data.close[i],
+                chart_data.volume[i],
+                chart_data.cost[i],
+            ]);
+            new_kline_msg(
+                "deribit",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                chart_data.ticks[i],
+                [
+                    chart_data.open[i],
+                    chart_data.high[i],
+                    chart_data.low[i],
+                    chart_data.close[i],
+                ],
+                chart_data.volume[i],
+                Some(chart_data.cost[i]),
+                json.to_string(),
+            )
+        })
+        .collect();
+    Ok(klines)
+}
+
+// see https://docs.deribit.com/?shell#public-get_funding_rate_history
+#[derive(Serialize, Deserialize)]
+struct RawFundingRate {
+    timestamp: i64,
+    interest_8h: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FundingRateResponse {
+    result: Vec<RawFundingRate>,
+}
+
+// Deribit returns at most 744 hourly records per request
+const FUNDING_RATES_WINDOW_MS: i64 = 744 * 3600 * 1000;
+
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    if market_type != MarketType::InverseSwap {
+        return Err(Error(format!(
+            "Deribit {} does NOT have funding rates",
+            market_type
+        )));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, "deribit")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let mut funding_rates = Vec::new();
+    for (window_start, window_end) in time_windows(start_time, end_time, FUNDING_RATES_WINDOW_MS) {
+        let text =
+            DeribitRestClient::fetch_funding_rates(symbol, window_start as u64, window_end as u64)
+                .await?;
+        let raw_funding_rates = serde_json::from_str::<FundingRateResponse>(&text)?.result;
+        funding_rates.extend(raw_funding_rates.into_iter().map(|raw_funding_rate| {
+            FundingRateMsg {
+                exchange: "deribit".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::FundingRate,
+                timestamp: raw_funding_rate.timestamp,
+                funding_rate: raw_funding_rate.interest_8h,
+                funding_time: raw_funding_rate.timestamp,
+                estimated_rate: None,
+                mark_price: None,
+                json: serde_json::to_string(&raw_funding_rate).unwrap(),
+            }
+        }));
     }
+    Ok(funding_rates)
 }
@@ -4,38 +4,82 @@ use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api.dydx.exchange";
 
-/// dYdX perpetual RESTful client.
-///
-/// * REST API doc: <https://docs.dydx.exchange/>
-/// * Trading at: <https://trade.dydx.exchange/trade>
-/// * Rate Limits: <https://docs.dydx.exchange/#rate-limits>
-///   * 100 requests per 10 seconds
-pub struct DydxSwapRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
+rest_client! {
+    /// dYdX perpetual RESTful client.
+    ///
+    /// * REST API doc: <https://docs.dydx.exchange/>
+    /// * Trading at: <https://trade.dydx.exchange/trade>
+    /// * Rate Limits: <https://docs.dydx.exchange/#rate-limits>
+    ///   * 100 requests per 10 seconds
+    pub struct DydxSwapRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
 
-impl DydxSwapRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        DydxSwapRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+    impl DydxSwapRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            DydxSwapRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
         }
-    }
 
-    /// Get a Level2 orderbook snapshot.
-    ///
-    /// All price levels are returned.
-    ///
-    /// For example: <https://api.dydx.exchange/v3/orderbook/BTC-USD>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/v3/orderbook/{}", symbol))
-    }
+        /// Get a Level2 orderbook snapshot.
+        ///
+        /// All price levels are returned.
+        ///
+        /// For example: <https://api.dydx.exchange/v3/orderbook/BTC-USD>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/v3/orderbook/{}", symbol))
+        }
 
-    /// Get open interest.
-    ///
-    /// For example: <https://api.dydx.exchange/v3/markets>
-    pub fn fetch_open_interest() -> Result<String> {
-        gen_api!("/v3/markets")
+        /// Get candlesticks.
+        ///
+        /// At most 100 candlesticks are returned, `from_iso` and `to_iso` are in ISO 8601.
+        ///
+        /// For example: <https://api.dydx.exchange/v3/candles/BTC-USD?resolution=1MIN&limit=100>
+        #[allow(non_snake_case)]
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            resolution: &str,
+            from_iso: Option<String>,
+            to_iso: Option<String>,
+        ) -> Result<String> {
+            let resolution = Some(resolution);
+            let fromISO = from_iso;
+            let toISO = to_iso;
+            let limit = Some(100);
+            gen_api!(
+                format!("/v3/candles/{}", symbol),
+                resolution,
+                fromISO,
+                toISO,
+                limit
+            )
+        }
+
+        /// Get hourly funding rate history, in descending order of time.
+        ///
+        /// At most 100 funding rates are returned.
+        ///
+        /// For example: <https://api.dydx.exchange/v3/historical-funding/BTC-USD>
+        #[allow(non_snake_case)]
+        pub async fn fetch_funding_rates(
+            symbol: &str,
+            effective_before_or_at: Option<String>,
+        ) -> Result<String> {
+            let effectiveBeforeOrAt = effective_before_or_at;
+            gen_api!(
+                format!("/v3/historical-funding/{}", symbol),
+                effectiveBeforeOrAt
+            )
+        }
+
+        /// Get open interest.
+        ///
+        /// For example: <https://api.dydx.exchange/v3/markets>
+        pub async fn fetch_open_interest() -> Result<String> {
+            gen_api!("/v3/markets")
+        }
     }
 }
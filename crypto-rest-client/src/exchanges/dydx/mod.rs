@@ -1,22 +1,244 @@
 pub(crate) mod dydx_swap;
 
-use crate::error::Result;
+use super::utils::{millis_to_iso, new_kline_msg};
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg, MarkPriceMsg};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::LinearSwap => dydx_swap::DydxSwapRestClient::fetch_l2_snapshot,
-        _ => panic!("dYdX does not have the {} market type", market_type),
-    };
+// see https://docs.dydx.exchange/#get-candles-for-market
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawCandle {
+    startedAt: String,
+    low: String,
+    high: String,
+    open: String,
+    close: String,
+    baseTokenVolume: String,
+    usdVolume: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
 
-    func(symbol)
+#[derive(Serialize, Deserialize)]
+struct CandlesResponse {
+    candles: Vec<RawCandle>,
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType) -> Result<String> {
+// see https://docs.dydx.exchange/#get-historical-funding
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFunding {
+    market: String,
+    rate: String,
+    price: String, // index price
+    effectiveAt: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct HistoricalFundingResponse {
+    historicalFunding: Vec<RawFunding>,
+}
+
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::LinearSwap => dydx_swap::DydxSwapRestClient::fetch_l2_snapshot(symbol).await,
+        _ => Err(Error(format!(
+            "dYdX does not have the {} market type",
+            market_type
+        ))),
+    }
+}
+
+pub(crate) async fn fetch_open_interest(market_type: MarketType) -> Result<String> {
     match market_type {
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            dydx_swap::DydxSwapRestClient::fetch_open_interest()
+            dydx_swap::DydxSwapRestClient::fetch_open_interest().await
+        }
+        _ => Err(Error(format!(
+            "dYdX {} does not have open interest",
+            market_type
+        ))),
+    }
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let resolution = match interval {
+        60 => "1MIN",
+        300 => "5MINS",
+        900 => "15MINS",
+        1800 => "30MINS",
+        3600 => "1HOUR",
+        14400 => "4HOURS",
+        86400 => "1DAY",
+        _ => {
+            return Err(Error(format!(
+                "dYdX does NOT support candlestick interval {}",
+                interval
+            )))
         }
-        _ => panic!("dYdX {} does not have open interest", market_type),
+    };
+    if market_type != MarketType::LinearSwap {
+        return Err(Error(format!(
+            "dYdX does NOT have the {} market type",
+            market_type
+        )));
+    }
+    let text = dydx_swap::DydxSwapRestClient::fetch_candlesticks(
+        symbol,
+        resolution,
+        Some(millis_to_iso(start_time)),
+        Some(millis_to_iso(end_time)),
+    )
+    .await?;
+    // in descending order of time
+    let raw_candles = serde_json::from_str::<CandlesResponse>(&text)?.candles;
+    let pair = crypto_pair::normalize_pair(symbol, "dydx")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = raw_candles
+        .into_iter()
+        .map(|raw_candle| {
+            let timestamp = chrono::DateTime::parse_from_rfc3339(&raw_candle.startedAt).unwrap();
+            new_kline_msg(
+                "dydx",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                timestamp.timestamp_millis(),
+                [
+                    raw_candle.open.parse::<f64>().unwrap(),
+                    raw_candle.high.parse::<f64>().unwrap(),
+                    raw_candle.low.parse::<f64>().unwrap(),
+                    raw_candle.close.parse::<f64>().unwrap(),
+                ],
+                raw_candle.baseTokenVolume.parse::<f64>().unwrap(),
+                Some(raw_candle.usdVolume.parse::<f64>().unwrap()),
+                serde_json::to_string(&raw_candle).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
+}
+
+// Returns (effectiveAt, raw funding) within [start_time, end_time], in descending order of time
+async fn fetch_raw_fundings(
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<(i64, RawFunding)>> {
+    // Pages are in descending order of time, move effectiveBeforeOrAt backwards
+    let mut raw_fundings = Vec::new();
+    let mut cursor = end_time;
+    while cursor >= start_time {
+        let text =
+            dydx_swap::DydxSwapRestClient::fetch_funding_rates(symbol, Some(millis_to_iso(cursor)))
+                .await?;
+        let page = serde_json::from_str::<HistoricalFundingResponse>(&text)?.historicalFunding;
+        if page.is_empty() {
+            break;
+        }
+        for raw_funding in page {
+            let funding_time = chrono::DateTime::parse_from_rfc3339(&raw_funding.effectiveAt)
+                .map_err(|err| Error(err.to_string()))?
+                .timestamp_millis();
+            cursor = funding_time - 1;
+            raw_fundings.push((funding_time, raw_funding));
+        }
+    }
+    Ok(raw_fundings)
+}
+
+fn parse_f64(s: &str) -> Result<f64> {
+    s.parse::<f64>()
+        .map_err(|_| Error(format!("Failed to parse {} as f64", s)))
+}
+
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    if market_type != MarketType::LinearSwap {
+        return Err(Error(format!(
+            "dYdX does NOT have the {} market type",
+            market_type
+        )));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, "dydx")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    let mut funding_rates = Vec::new();
+    for (funding_time, raw_funding) in fetch_raw_fundings(symbol, start_time, end_time).await? {
+        funding_rates.push(FundingRateMsg {
+            exchange: "dydx".to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair: pair.clone(),
+            msg_type: MessageType::FundingRate,
+            timestamp: funding_time,
+            funding_rate: parse_f64(&raw_funding.rate)?,
+            funding_time,
+            estimated_rate: None,
+            mark_price: None,
+            json: serde_json::to_string(&raw_funding).unwrap(),
+        });
+    }
+    Ok(funding_rates)
+}
+
+// dYdX publishes the index price along with the hourly funding rate,
+// so only the interval of 3600 seconds is supported.
+pub(crate) async fn fetch_index_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    if interval != 3600 {
+        return Err(Error(format!(
+            "dYdX only has hourly index prices, interval {} is not supported",
+            interval
+        )));
+    }
+    if market_type != MarketType::LinearSwap {
+        return Err(Error(format!(
+            "dYdX does NOT have the {} market type",
+            market_type
+        )));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, "dydx")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    let mut index_prices = Vec::new();
+    for (timestamp, raw_funding) in fetch_raw_fundings(symbol, start_time, end_time).await? {
+        index_prices.push(MarkPriceMsg {
+            exchange: "dydx".to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair: pair.clone(),
+            msg_type: MessageType::IndexPrice,
+            timestamp,
+            json: serde_json::to_string(&raw_funding).unwrap(),
+            mark_price: None,
+            index_price: Some(parse_f64(&raw_funding.price)?),
+            premium_index: None,
+        });
     }
+    Ok(index_prices)
 }
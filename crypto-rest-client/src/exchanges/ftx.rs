@@ -1,46 +1,321 @@
-use super::utils::http_get;
-use crate::error::Result;
-use std::collections::BTreeMap;
+use super::utils::{http_get, new_kline_msg, time_windows};
+use crate::error::{Error, Result};
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg, MarkPriceMsg};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 const BASE_URL: &str = "https://ftx.com/api";
 
-/// The RESTful client for FTX.
-///
-/// FTX has Spot, LinearFuture, LinearSwap, Option, Move and BVOL markets.
-///
-/// * RESTful API doc: <https://docs.ftx.com/?python#rest-api>
-/// * Trading at <https://ftx.com/markets>
-/// * Rate Limits: <https://docs.ftx.com/?python#rate-limits>
-///   * Non-order placement requests do not count towards rate limits.
-///   * Rate limits are tiered by account trading volumes.
-pub struct FtxRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
+rest_client! {
+    /// The RESTful client for FTX.
+    ///
+    /// FTX has Spot, LinearFuture, LinearSwap, Option, Move and BVOL markets.
+    ///
+    /// * RESTful API doc: <https://docs.ftx.com/?python#rest-api>
+    /// * Trading at <https://ftx.com/markets>
+    /// * Rate Limits: <https://docs.ftx.com/?python#rate-limits>
+    ///   * Non-order placement requests do not count towards rate limits.
+    ///   * Rate limits are tiered by account trading volumes.
+    pub struct FtxRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
+
+    impl FtxRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            FtxRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get the latest Level2 snapshot of orderbook.
+        ///
+        /// Top 100 bids and asks are returned.
+        ///
+        /// For example: <https://ftx.com/api/markets/BTC-PERP/orderbook?depth=100>,
+        // <https://ftx.com/api/markets/BTC/USD/orderbook?depth=100>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/markets/{}/orderbook?depth=100", symbol))
+        }
+
+        /// Get funding rate history of perpetuals, in descending order of time.
+        ///
+        /// At most 500 funding rates are returned, `start_time` and `end_time` are in seconds.
+        ///
+        /// For example: <https://ftx.com/api/funding_rates?future=BTC-PERP>
+        pub async fn fetch_funding_rates(
+            symbol: &str,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            let future = Some(symbol);
+            gen_api!("/funding_rates", future, start_time, end_time)
+        }
+
+        /// Get candlesticks.
+        ///
+        /// At most 1501 candlesticks are returned, `resolution` is in seconds,
+        /// `start_time` and `end_time` are in seconds.
+        ///
+        /// For example: <https://ftx.com/api/markets/BTC-PERP/candles?resolution=60>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            resolution: u32,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            let resolution = Some(resolution);
+            gen_api!(
+                format!("/markets/{}/candles", symbol),
+                resolution,
+                start_time,
+                end_time
+            )
+        }
+
+        /// Get index price candlesticks.
+        ///
+        /// At most 1501 candlesticks are returned, `resolution` is in seconds,
+        /// `start_time` and `end_time` are in seconds.
+        ///
+        /// For example: <https://ftx.com/api/indexes/BTC/candles?resolution=60>
+        pub async fn fetch_index_candles(
+            index_name: &str,
+            resolution: u32,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+        ) -> Result<String> {
+            let resolution = Some(resolution);
+            gen_api!(
+                format!("/indexes/{}/candles", index_name),
+                resolution,
+                start_time,
+                end_time
+            )
+        }
+
+        /// Get open interest.
+        ///
+        /// For example:
+        /// - <https://ftx.com/api/futures>
+        pub async fn fetch_open_interest() -> Result<String> {
+            gen_api!("/futures")
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawFundingRate {
+    future: String,
+    rate: f64,
+    time: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawIndexCandle {
+    startTime: String,
+    close: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
 }
 
-impl FtxRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        FtxRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawCandle {
+    startTime: String,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64, // in quote currency
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Response<T: Sized> {
+    success: bool,
+    result: Vec<T>,
+}
+
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    if market_type != MarketType::LinearSwap {
+        return Err(Error(format!(
+            "FTX {} does NOT have funding rates",
+            market_type
+        )));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, "ftx")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    // Pages are in descending order of time, move end_time backwards
+    let mut funding_rates = Vec::new();
+    let mut cursor = end_time / 1000;
+    while cursor * 1000 >= start_time {
+        let text = FtxRestClient::fetch_funding_rates(
+            symbol,
+            Some((start_time / 1000) as u64),
+            Some(cursor as u64),
+        )
+        .await?;
+        let resp = serde_json::from_str::<Response<RawFundingRate>>(&text)?;
+        if !resp.success {
+            return Err(Error(text));
+        }
+        let n = resp.result.len();
+        for raw_funding_rate in resp.result {
+            let funding_time = chrono::DateTime::parse_from_rfc3339(&raw_funding_rate.time)
+                .unwrap()
+                .timestamp_millis();
+            cursor = funding_time / 1000 - 1;
+            funding_rates.push(FundingRateMsg {
+                exchange: "ftx".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::FundingRate,
+                timestamp: funding_time,
+                funding_rate: raw_funding_rate.rate,
+                funding_time,
+                estimated_rate: None,
+                mark_price: None,
+                json: serde_json::to_string(&raw_funding_rate).unwrap(),
+            });
+        }
+        if n < 500 {
+            break;
         }
     }
+    Ok(funding_rates)
+}
 
-    /// Get the latest Level2 snapshot of orderbook.
-    ///
-    /// Top 100 bids and asks are returned.
-    ///
-    /// For example: <https://ftx.com/api/markets/BTC-PERP/orderbook?depth=100>,
-    // <https://ftx.com/api/markets/BTC/USD/orderbook?depth=100>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/markets/{}/orderbook?depth=100", symbol))
+pub(crate) async fn fetch_index_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    if ![15, 60, 300, 900, 3600, 14400, 86400].contains(&interval) {
+        return Err(Error(format!(
+            "FTX does NOT support candlestick interval {}",
+            interval
+        )));
+    }
+    if market_type != MarketType::LinearSwap && market_type != MarketType::LinearFuture {
+        return Err(Error(format!(
+            "FTX {} does NOT have index prices",
+            market_type
+        )));
     }
+    let pair = crypto_pair::normalize_pair(symbol, "ftx")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    // BTC-PERP and BTC-1231 share the index BTC
+    let index_name = &symbol[..symbol.rfind('-').unwrap()];
 
-    /// Get open interest.
-    ///
-    /// For example:
-    /// - <https://ftx.com/api/futures>
-    pub fn fetch_open_interest() -> Result<String> {
-        gen_api!("/futures")
+    let mut index_prices = Vec::new();
+    for (window_start, window_end) in
+        time_windows(start_time, end_time, 1500 * interval as i64 * 1000)
+    {
+        let text = FtxRestClient::fetch_index_candles(
+            index_name,
+            interval as u32,
+            Some(((window_start + 999) / 1000) as u64),
+            Some((window_end / 1000) as u64),
+        )
+        .await?;
+        let resp = serde_json::from_str::<Response<RawIndexCandle>>(&text)?;
+        if !resp.success {
+            return Err(Error(text));
+        }
+        index_prices.extend(resp.result.into_iter().map(|raw_candle| {
+            let timestamp = chrono::DateTime::parse_from_rfc3339(&raw_candle.startTime)
+                .unwrap()
+                .timestamp_millis();
+            MarkPriceMsg {
+                exchange: "ftx".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::IndexPrice,
+                timestamp,
+                json: serde_json::to_string(&raw_candle).unwrap(),
+                mark_price: None,
+                index_price: Some(raw_candle.close),
+                premium_index: None,
+            }
+        }));
+    }
+    Ok(index_prices)
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    if ![15, 60, 300, 900, 3600, 14400, 86400].contains(&interval) {
+        return Err(Error(format!(
+            "FTX does NOT support candlestick interval {}",
+            interval
+        )));
+    }
+    let text = FtxRestClient::fetch_candlesticks(
+        symbol,
+        interval as u32,
+        Some(((start_time + 999) / 1000) as u64),
+        Some((end_time / 1000) as u64),
+    )
+    .await?;
+    let resp = serde_json::from_str::<Response<RawCandle>>(&text)?;
+    if !resp.success {
+        return Err(Error(text));
+    }
+
+    let pair = crypto_pair::normalize_pair(symbol, "ftx")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let mut klines = Vec::with_capacity(resp.result.len());
+    for raw_candle in resp.result {
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&raw_candle.startTime)
+            .map_err(|err| Error(err.to_string()))?
+            .timestamp_millis();
+        // FTX only publishes the quote volume, the base volume is estimated by close price
+        let volume = if raw_candle.close > 0.0 {
+            raw_candle.volume / raw_candle.close
+        } else {
+            0.0
+        };
+        klines.push(new_kline_msg(
+            "ftx",
+            market_type,
+            symbol,
+            &pair,
+            interval,
+            timestamp,
+            [
+                raw_candle.open,
+                raw_candle.high,
+                raw_candle.low,
+                raw_candle.close,
+            ],
+            volume,
+            Some(raw_candle.volume),
+            serde_json::to_string(&raw_candle).unwrap(),
+        ));
     }
+    Ok(klines)
 }
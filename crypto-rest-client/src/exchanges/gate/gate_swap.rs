@@ -1,66 +1,100 @@
 use super::super::utils::http_get;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api.gateio.ws/api/v4";
 
-/// The RESTful client for Gate Swap markets.
-///
-/// * RESTful API doc: <https://www.gate.io/docs/apiv4/en/index.html#futures>
-/// * Trading at: <https://www.gateio.pro/cn/futures_trade/USDT/BTC_USDT>
-/// * Rate Limits: <https://www.gate.io/docs/apiv4/en/index.html#frequency-limit-rule>
-///   * 300 read operations per IP per second
-pub struct GateSwapRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
+rest_client! {
+    /// The RESTful client for Gate Swap markets.
+    ///
+    /// * RESTful API doc: <https://www.gate.io/docs/apiv4/en/index.html#futures>
+    /// * Trading at: <https://www.gateio.pro/cn/futures_trade/USDT/BTC_USDT>
+    /// * Rate Limits: <https://www.gate.io/docs/apiv4/en/index.html#frequency-limit-rule>
+    ///   * 300 read operations per IP per second
+    pub struct GateSwapRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
 
-impl GateSwapRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        GateSwapRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+    impl GateSwapRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            GateSwapRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
         }
-    }
 
-    /// Get the latest Level2 snapshot of orderbook.
-    ///
-    /// Top 200 asks and bids are returned.
-    ///
-    /// For example:
-    ///
-    /// - <https://api.gateio.ws/api/v4/futures/btc/order_book?contract=BTC_USD&limit=200>
-    /// - <https://api.gateio.ws/api/v4/futures/usdt/order_book?contract=BTC_USDT&limit=200>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        let settle = if symbol.ends_with("_USD") {
-            "btc"
-        } else if symbol.ends_with("_USDT") {
-            "usdt"
-        } else {
-            panic!("Unknown symbol {}", symbol);
-        };
-        gen_api!(format!(
-            "/futures/{}/order_book?contract={}&limit=200",
-            settle, symbol
-        ))
-    }
+        /// Get the latest Level2 snapshot of orderbook.
+        ///
+        /// Top 200 asks and bids are returned.
+        ///
+        /// For example:
+        ///
+        /// - <https://api.gateio.ws/api/v4/futures/btc/order_book?contract=BTC_USD&limit=200>
+        /// - <https://api.gateio.ws/api/v4/futures/usdt/order_book?contract=BTC_USDT&limit=200>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            let settle = if symbol.ends_with("_USD") {
+                "btc"
+            } else if symbol.ends_with("_USDT") {
+                "usdt"
+            } else {
+                panic!("Unknown symbol {}", symbol);
+            };
+            gen_api!(format!(
+                "/futures/{}/order_book?contract={}&limit=200",
+                settle, symbol
+            ))
+        }
 
-    /// Get open interest.
-    ///
-    /// For example:
-    /// - <https://api.gateio.ws/api/v4/futures/btc/contract_stats?contract=BTC_USD&interval=5m>
-    /// - <https://api.gateio.ws/api/v4/futures/usdt/contract_stats?contract=BTC_USDT&interval=5m>
-    pub fn fetch_open_interest(symbol: &str) -> Result<String> {
-        let settle = if symbol.ends_with("_USD") {
-            "btc"
-        } else if symbol.ends_with("_USDT") {
-            "usdt"
-        } else {
-            panic!("Unknown symbol {}", symbol);
-        };
-        gen_api!(format!(
-            "/futures/{}/contract_stats?contract={}&interval=5m",
-            settle, symbol
-        ))
+        /// Get candlesticks.
+        ///
+        /// At most 2000 candlesticks are returned, `from` and `to` are in seconds.
+        ///
+        /// For example:
+        ///
+        /// - <https://api.gateio.ws/api/v4/futures/btc/candlesticks?contract=BTC_USD&interval=1m>
+        /// - <https://api.gateio.ws/api/v4/futures/usdt/candlesticks?contract=BTC_USDT&interval=1m>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            interval: &str,
+            from: Option<u64>,
+            to: Option<u64>,
+        ) -> Result<String> {
+            let settle = if symbol.ends_with("_USD") {
+                "btc"
+            } else if symbol.ends_with("_USDT") {
+                "usdt"
+            } else {
+                return Err(Error(format!("Unknown symbol {}", symbol)));
+            };
+            let contract = Some(symbol);
+            let interval = Some(interval);
+            gen_api!(
+                format!("/futures/{}/candlesticks", settle),
+                contract,
+                interval,
+                from,
+                to
+            )
+        }
+
+        /// Get open interest.
+        ///
+        /// For example:
+        /// - <https://api.gateio.ws/api/v4/futures/btc/contract_stats?contract=BTC_USD&interval=5m>
+        /// - <https://api.gateio.ws/api/v4/futures/usdt/contract_stats?contract=BTC_USDT&interval=5m>
+        pub async fn fetch_open_interest(symbol: &str) -> Result<String> {
+            let settle = if symbol.ends_with("_USD") {
+                "btc"
+            } else if symbol.ends_with("_USDT") {
+                "usdt"
+            } else {
+                panic!("Unknown symbol {}", symbol);
+            };
+            gen_api!(format!(
+                "/futures/{}/contract_stats?contract={}&interval=5m",
+                settle, symbol
+            ))
+        }
     }
 }
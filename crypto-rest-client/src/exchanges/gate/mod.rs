@@ -1,34 +1,165 @@
-mod gate_future;
-mod gate_spot;
-mod gate_swap;
+pub(crate) mod gate_future;
+pub(crate) mod gate_spot;
+pub(crate) mod gate_swap;
 
-pub use gate_future::GateFutureRestClient;
-pub use gate_spot::GateSpotRestClient;
-pub use gate_swap::GateSwapRestClient;
-
-use crate::error::Result;
+use super::utils::{calc_quantity_and_volume, new_kline_msg};
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
+use crypto_msg_parser::KlineMsg;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+// see https://www.gate.io/docs/developers/apiv4/en/#get-futures-candlesticks
+#[derive(Serialize, Deserialize)]
+struct FuturesCandlestick {
+    t: i64,    // Unix timestamp in seconds
+    v: f64,    // size volume, in contracts
+    c: String, // close price
+    h: String, // highest price
+    l: String, // lowest price
+    o: String, // open price
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => gate_spot::GateSpotRestClient::fetch_l2_snapshot,
+fn parse_f64(s: &str) -> Result<f64> {
+    s.parse::<f64>()
+        .map_err(|_| Error(format!("Failed to parse {} as f64", s)))
+}
+
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => gate_spot::GateSpotRestClient::fetch_l2_snapshot(symbol).await,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            gate_swap::GateSwapRestClient::fetch_l2_snapshot
+            gate_swap::GateSwapRestClient::fetch_l2_snapshot(symbol).await
         }
-        MarketType::LinearFuture => gate_future::GateFutureRestClient::fetch_l2_snapshot,
-        _ => panic!("Gate unknown market_type: {}", market_type),
-    };
-
-    func(symbol)
+        MarketType::LinearFuture => {
+            gate_future::GateFutureRestClient::fetch_l2_snapshot(symbol).await
+        }
+        _ => Err(Error(format!("Gate unknown market_type: {}", market_type))),
+    }
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
+pub(crate) async fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            gate_swap::GateSwapRestClient::fetch_open_interest
+            gate_swap::GateSwapRestClient::fetch_open_interest(symbol).await
+        }
+        _ => Err(Error(format!(
+            "Gate {} does NOT have open interest data",
+            market_type
+        ))),
+    }
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let interval_str = match interval {
+        10 => "10s",
+        60 => "1m",
+        300 => "5m",
+        900 => "15m",
+        1800 => "30m",
+        3600 => "1h",
+        14400 => "4h",
+        28800 => "8h",
+        86400 => "1d",
+        604800 => "7d",
+        _ => {
+            return Err(Error(format!(
+                "Gate does NOT support candlestick interval {}",
+                interval
+            )))
         }
-        _ => panic!("Gate {} does NOT have open interest data", market_type),
     };
+    let from = Some(((start_time + 999) / 1000) as u64);
+    let to = Some((end_time / 1000) as u64);
+    let pair = crypto_pair::normalize_pair(symbol, "gate")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
 
-    func(symbol)
+    let mut klines = Vec::new();
+    if market_type == MarketType::Spot {
+        let text =
+            gate_spot::GateSpotRestClient::fetch_candlesticks(symbol, interval_str, from, to)
+                .await?;
+        // [timestamp, quote volume, close, high, low, open, base volume]
+        let raw_klines = serde_json::from_str::<Vec<Vec<String>>>(&text)?;
+        for raw_kline in raw_klines {
+            if raw_kline.len() < 7 {
+                return Err(Error(format!("Malformed candlestick {:?}", raw_kline)));
+            }
+            klines.push(new_kline_msg(
+                "gate",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                raw_kline[0]
+                    .parse::<i64>()
+                    .map_err(|err| Error(err.to_string()))?
+                    * 1000,
+                [
+                    parse_f64(&raw_kline[5])?,
+                    parse_f64(&raw_kline[3])?,
+                    parse_f64(&raw_kline[4])?,
+                    parse_f64(&raw_kline[2])?,
+                ],
+                parse_f64(&raw_kline[6])?,
+                Some(parse_f64(&raw_kline[1])?),
+                serde_json::to_string(&raw_kline).unwrap(),
+            ));
+        }
+    } else {
+        let text = match market_type {
+            MarketType::InverseSwap | MarketType::LinearSwap => {
+                gate_swap::GateSwapRestClient::fetch_candlesticks(symbol, interval_str, from, to)
+                    .await?
+            }
+            MarketType::LinearFuture => {
+                gate_future::GateFutureRestClient::fetch_candlesticks(
+                    symbol,
+                    interval_str,
+                    from,
+                    to,
+                )
+                .await?
+            }
+            _ => {
+                return Err(Error(format!(
+                    "Gate {} does NOT have candlesticks",
+                    market_type
+                )))
+            }
+        };
+        let raw_klines = serde_json::from_str::<Vec<FuturesCandlestick>>(&text)?;
+        for raw_kline in raw_klines {
+            let close = parse_f64(&raw_kline.c)?;
+            let (volume, quote_volume, _) =
+                calc_quantity_and_volume("gate", market_type, &pair, close, raw_kline.v);
+            klines.push(new_kline_msg(
+                "gate",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                raw_kline.t * 1000,
+                [
+                    parse_f64(&raw_kline.o)?,
+                    parse_f64(&raw_kline.h)?,
+                    parse_f64(&raw_kline.l)?,
+                    close,
+                ],
+                volume,
+                Some(quote_volume),
+                serde_json::to_string(&raw_kline).unwrap(),
+            ));
+        }
+    }
+    Ok(klines)
 }
@@ -4,40 +4,70 @@ use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api.hbdm.com";
 
-/// Huobi Future market.
-///
-/// * REST API doc: <https://huobiapi.github.io/docs/dm/v1/en/>
-/// * Trading at: <https://futures.huobi.com/en-us/contract/exchange/>
-/// * Rate Limits: <https://huobiapi.github.io/docs/dm/v1/en/#api-rate-limit-illustration>
-///   * For restful interfaces：all products(futures, coin margined swap, usdt margined swap ) 800 times/second for one IP at most
-pub struct HuobiFutureRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl_contract!(HuobiFutureRestClient);
-
-impl HuobiFutureRestClient {
-    /// Get the latest Level2 orderbook snapshot.
-    ///
-    /// Top 150 bids and asks (aggregated) are returned.
+rest_client! {
+    /// Huobi Future market.
     ///
-    /// For example: <https://api.hbdm.com/market/depth?symbol=BTC_CQ&type=step0>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/market/depth?symbol={}&type=step0", symbol))
+    /// * REST API doc: <https://huobiapi.github.io/docs/dm/v1/en/>
+    /// * Trading at: <https://futures.huobi.com/en-us/contract/exchange/>
+    /// * Rate Limits: <https://huobiapi.github.io/docs/dm/v1/en/#api-rate-limit-illustration>
+    ///   * For restful interfaces：all products(futures, coin margined swap, usdt margined swap ) 800 times/second for one IP at most
+    pub struct HuobiFutureRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
     }
 
-    /// Get open interest.
-    ///
-    /// For example: <https://api.hbdm.com/api/v1/contract_open_interest?contract_code=BTC211231>
-    pub fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
-        if let Some(symbol) = symbol {
-            gen_api!(format!(
-                "/api/v1/contract_open_interest?contract_code={}",
-                symbol
-            ))
-        } else {
-            gen_api!("/api/v1/contract_open_interest")
+    impl HuobiFutureRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            HuobiFutureRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get the most recent trades.
+        ///
+        /// Equivalent to `/market/history/trade` with `size=2000`
+        ///
+        /// For example: <https://api.hbdm.com/market/history/trade?symbol=BTC_CQ&size=2000>
+        pub async fn fetch_trades(symbol: &str) -> Result<String> {
+            gen_api!(format!("/market/history/trade?symbol={}&size=2000", symbol))
+        }
+
+        /// Get the latest Level2 orderbook snapshot.
+        ///
+        /// Top 150 bids and asks (aggregated) are returned.
+        ///
+        /// For example: <https://api.hbdm.com/market/depth?symbol=BTC_CQ&type=step0>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/market/depth?symbol={}&type=step0", symbol))
+        }
+
+        /// Get candlesticks.
+        ///
+        /// Equivalent to `/market/history/kline`, `from` and `to` are Unix timestamps in seconds,
+        /// at most 2000 candlesticks are returned.
+        ///
+        /// For example: <https://api.hbdm.com/market/history/kline?symbol=BTC_CQ&period=1min&from=1633046400&to=1633050000>
+        pub async fn fetch_candlesticks(symbol: &str, period: &str, from: u64, to: u64) -> Result<String> {
+            let symbol = Some(symbol);
+            let period = Some(period);
+            let from = Some(from);
+            let to = Some(to);
+            gen_api!("/market/history/kline", symbol, period, from, to)
+        }
+
+        /// Get open interest.
+        ///
+        /// For example: <https://api.hbdm.com/api/v1/contract_open_interest?contract_code=BTC211231>
+        pub async fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
+            if let Some(symbol) = symbol {
+                gen_api!(format!(
+                    "/api/v1/contract_open_interest?contract_code={}",
+                    symbol
+                ))
+            } else {
+                gen_api!("/api/v1/contract_open_interest")
+            }
         }
     }
 }
@@ -4,45 +4,129 @@ use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api.hbdm.com";
 
-/// Huobi Inverse Swap market.
-///
-/// Inverse Swap market uses coins like BTC as collateral.
-///
-/// * REST API doc: <https://huobiapi.github.io/docs/coin_margined_swap/v1/en/>
-/// * Trading at: <https://futures.huobi.com/en-us/swap/exchange/>
-/// * Rate Limits: <https://huobiapi.github.io/docs/coin_margined_swap/v1/en/#api-rate-limit-illustration>
-///  * For restful interfaces：all products(futures, coin margined swap, usdt margined swap) 800 times/second for one IP at most
-pub struct HuobiInverseSwapRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl_contract!(HuobiInverseSwapRestClient);
-
-impl HuobiInverseSwapRestClient {
-    /// Get the latest Level2 orderbook snapshot.
+rest_client! {
+    /// Huobi Inverse Swap market.
     ///
-    /// Top 150 bids and asks (aggregated) are returned.
+    /// Inverse Swap market uses coins like BTC as collateral.
     ///
-    /// For example: <https://api.hbdm.com/swap-ex/market/depth?contract_code=BTC-USD&type=step0>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/swap-ex/market/depth?contract_code={}&type=step0",
-            symbol
-        ))
+    /// * REST API doc: <https://huobiapi.github.io/docs/coin_margined_swap/v1/en/>
+    /// * Trading at: <https://futures.huobi.com/en-us/swap/exchange/>
+    /// * Rate Limits: <https://huobiapi.github.io/docs/coin_margined_swap/v1/en/#api-rate-limit-illustration>
+    ///  * For restful interfaces：all products(futures, coin margined swap, usdt margined swap) 800 times/second for one IP at most
+    pub struct HuobiInverseSwapRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
     }
 
-    /// Get open interest.
-    ///
-    /// For example: <https://api.hbdm.com/swap-api/v1/swap_open_interest?contract_code=BTC-USD>
-    pub fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
-        if let Some(symbol) = symbol {
+    impl HuobiInverseSwapRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            HuobiInverseSwapRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get the most recent trades.
+        ///
+        /// Equivalent to `/market/history/trade` with `size=2000`
+        ///
+        /// For example: <https://api.hbdm.com/market/history/trade?symbol=BTC_CQ&size=2000>
+        pub async fn fetch_trades(symbol: &str) -> Result<String> {
+            gen_api!(format!("/market/history/trade?symbol={}&size=2000", symbol))
+        }
+
+        /// Get the latest Level2 orderbook snapshot.
+        ///
+        /// Top 150 bids and asks (aggregated) are returned.
+        ///
+        /// For example: <https://api.hbdm.com/swap-ex/market/depth?contract_code=BTC-USD&type=step0>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
             gen_api!(format!(
-                "/swap-api/v1/swap_open_interest?contract_code={}",
+                "/swap-ex/market/depth?contract_code={}&type=step0",
                 symbol
             ))
-        } else {
-            gen_api!("/swap-api/v1/swap_open_interest")
+        }
+
+        /// Get candlesticks.
+        ///
+        /// Equivalent to `/swap-ex/market/history/kline`, `from` and `to` are Unix timestamps in seconds,
+        /// at most 2000 candlesticks are returned.
+        ///
+        /// For example: <https://api.hbdm.com/swap-ex/market/history/kline?contract_code=BTC-USD&period=1min&from=1633046400&to=1633050000>
+        pub async fn fetch_candlesticks(symbol: &str, period: &str, from: u64, to: u64) -> Result<String> {
+            let contract_code = Some(symbol);
+            let period = Some(period);
+            let from = Some(from);
+            let to = Some(to);
+            gen_api!(
+                "/swap-ex/market/history/kline",
+                contract_code,
+                period,
+                from,
+                to
+            )
+        }
+
+        /// Get the latest mark price candlesticks.
+        ///
+        /// Equivalent to `/swap-ex/market/history/mark_price_kline`, at most 2000 candlesticks
+        /// are returned, no time range is supported.
+        ///
+        /// For example: <https://api.hbdm.com/swap-ex/market/history/mark_price_kline?contract_code=BTC-USD&period=1min&size=2000>
+        pub async fn fetch_mark_price_klines(symbol: &str, period: &str, size: u32) -> Result<String> {
+            let contract_code = Some(symbol);
+            let period = Some(period);
+            let size = Some(size);
+            gen_api!(
+                "/swap-ex/market/history/mark_price_kline",
+                contract_code,
+                period,
+                size
+            )
+        }
+
+        /// Get the latest premium index candlesticks.
+        ///
+        /// Equivalent to `/index/market/history/swap_premium_index_kline`, at most 2000 candlesticks
+        /// are returned, no time range is supported.
+        ///
+        /// For example: <https://api.hbdm.com/index/market/history/swap_premium_index_kline?contract_code=BTC-USD&period=1min&size=2000>
+        pub async fn fetch_premium_index_klines(symbol: &str, period: &str, size: u32) -> Result<String> {
+            let contract_code = Some(symbol);
+            let period = Some(period);
+            let size = Some(size);
+            gen_api!(
+                "/index/market/history/swap_premium_index_kline",
+                contract_code,
+                period,
+                size
+            )
+        }
+
+        /// Get funding rate history, in descending order of time.
+        ///
+        /// Equivalent to `/swap-api/v1/swap_historical_funding_rate` with `page_size=50`
+        ///
+        /// For example: <https://api.hbdm.com/swap-api/v1/swap_historical_funding_rate?contract_code=BTC-USD&page_index=1&page_size=50>
+        pub async fn fetch_funding_rates(symbol: &str, page_index: u32) -> Result<String> {
+            gen_api!(format!(
+                "/swap-api/v1/swap_historical_funding_rate?contract_code={}&page_index={}&page_size=50",
+                symbol, page_index
+            ))
+        }
+
+        /// Get open interest.
+        ///
+        /// For example: <https://api.hbdm.com/swap-api/v1/swap_open_interest?contract_code=BTC-USD>
+        pub async fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
+            if let Some(symbol) = symbol {
+                gen_api!(format!(
+                    "/swap-api/v1/swap_open_interest?contract_code={}",
+                    symbol
+                ))
+            } else {
+                gen_api!("/swap-api/v1/swap_open_interest")
+            }
         }
     }
 }
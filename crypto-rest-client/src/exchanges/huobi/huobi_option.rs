@@ -4,27 +4,43 @@ use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://api.hbdm.com/option-ex";
 
-/// Huobi Option market.
-///
-///
-/// * REST API doc: <https://huobiapi.github.io/docs/option/v1/en/>
-/// * Trading at: <https://futures.huobi.com/en-us/option/exchange/>
-/// * Rate Limits: <https://huobiapi.github.io/docs/option/v1/en/#api-rate-limit-illustration>
-///   * For restful interfaces：all products(futures, coin margined swap, usdt margined swap and option) 800 times/second for one IP at most
-pub struct HuobiOptionRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
-
-impl_contract!(HuobiOptionRestClient);
-
-impl HuobiOptionRestClient {
-    /// Get the latest Level2 orderbook snapshot.
+rest_client! {
+    /// Huobi Option market.
     ///
-    /// Top 150 bids and asks (aggregated) are returned.
     ///
-    /// For example: <https://api.hbdm.com/option-ex/market/depth?contract_code=BTC-USDT-210326-C-32000&type=step0>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/market/depth?contract_code={}&type=step0", symbol))
+    /// * REST API doc: <https://huobiapi.github.io/docs/option/v1/en/>
+    /// * Trading at: <https://futures.huobi.com/en-us/option/exchange/>
+    /// * Rate Limits: <https://huobiapi.github.io/docs/option/v1/en/#api-rate-limit-illustration>
+    ///   * For restful interfaces：all products(futures, coin margined swap, usdt margined swap and option) 800 times/second for one IP at most
+    pub struct HuobiOptionRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
+
+    impl HuobiOptionRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            HuobiOptionRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get the most recent trades.
+        ///
+        /// Equivalent to `/market/history/trade` with `size=2000`
+        ///
+        /// For example: <https://api.hbdm.com/market/history/trade?symbol=BTC_CQ&size=2000>
+        pub async fn fetch_trades(symbol: &str) -> Result<String> {
+            gen_api!(format!("/market/history/trade?symbol={}&size=2000", symbol))
+        }
+
+        /// Get the latest Level2 orderbook snapshot.
+        ///
+        /// Top 150 bids and asks (aggregated) are returned.
+        ///
+        /// For example: <https://api.hbdm.com/option-ex/market/depth?contract_code=BTC-USDT-210326-C-32000&type=step0>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/market/depth?contract_code={}&type=step0", symbol))
+        }
     }
 }
@@ -1,39 +1,345 @@
-#[macro_use]
-mod utils;
-
 pub(crate) mod huobi_future;
 pub(crate) mod huobi_inverse_swap;
 pub(crate) mod huobi_linear_swap;
 pub(crate) mod huobi_option;
 pub(crate) mod huobi_spot;
 
-use crate::error::Result;
+use super::utils::{calc_quantity_and_volume, new_kline_msg};
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg, MarkPriceMsg};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+// see https://huobiapi.github.io/docs/usdt_swap/v1/en/#general-get-kline-data
+#[derive(Serialize, Deserialize)]
+struct RawKline {
+    id: i64,
+    open: f64,
+    close: f64,
+    low: f64,
+    high: f64,
+    amount: f64, // base volume
+    vol: f64,    // number of contracts
+    trade_turnover: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KlineResponse {
+    status: String,
+    data: Option<Vec<RawKline>>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://huobiapi.github.io/docs/coin_margined_swap/v1/en/#query-historical-funding-rate
+#[derive(Serialize, Deserialize)]
+struct RawFundingRate {
+    contract_code: String,
+    funding_rate: String,
+    funding_time: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FundingRatePage {
+    total_page: u32,
+    current_page: u32,
+    data: Vec<RawFundingRate>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FundingRateResponse {
+    status: String,
+    data: Option<FundingRatePage>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => huobi_spot::HuobiSpotRestClient::fetch_l2_snapshot,
-        MarketType::InverseFuture => huobi_future::HuobiFutureRestClient::fetch_l2_snapshot,
-        MarketType::LinearSwap => huobi_linear_swap::HuobiLinearSwapRestClient::fetch_l2_snapshot,
+// see https://huobiapi.github.io/docs/usdt_swap/v1/en/#general-get-kline-data-of-mark-price
+#[derive(Serialize, Deserialize)]
+struct RawPriceKline {
+    id: i64,
+    close: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PriceKlineResponse {
+    status: String,
+    data: Option<Vec<RawPriceKline>>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+fn to_period(interval: usize) -> Result<&'static str> {
+    match interval {
+        60 => Ok("1min"),
+        300 => Ok("5min"),
+        900 => Ok("15min"),
+        1800 => Ok("30min"),
+        3600 => Ok("60min"),
+        14400 => Ok("4hour"),
+        86400 => Ok("1day"),
+        604800 => Ok("1week"),
+        _ => Err(Error(format!(
+            "Huobi does NOT support candlestick interval {}",
+            interval
+        ))),
+    }
+}
+
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => huobi_spot::HuobiSpotRestClient::fetch_l2_snapshot(symbol).await,
+        MarketType::InverseFuture => {
+            huobi_future::HuobiFutureRestClient::fetch_l2_snapshot(symbol).await
+        }
+        MarketType::LinearSwap => {
+            huobi_linear_swap::HuobiLinearSwapRestClient::fetch_l2_snapshot(symbol).await
+        }
         MarketType::InverseSwap => {
-            huobi_inverse_swap::HuobiInverseSwapRestClient::fetch_l2_snapshot
+            huobi_inverse_swap::HuobiInverseSwapRestClient::fetch_l2_snapshot(symbol).await
         }
-        MarketType::EuropeanOption => huobi_option::HuobiOptionRestClient::fetch_l2_snapshot,
-        _ => panic!("Binance unknown market_type: {}", market_type),
-    };
+        MarketType::EuropeanOption => {
+            huobi_option::HuobiOptionRestClient::fetch_l2_snapshot(symbol).await
+        }
+        _ => Err(Error(format!("Huobi unknown market_type: {}", market_type))),
+    }
+}
 
-    func(symbol)
+pub(crate) async fn fetch_open_interest(
+    market_type: MarketType,
+    symbol: Option<&str>,
+) -> Result<String> {
+    match market_type {
+        MarketType::InverseFuture => {
+            huobi_future::HuobiFutureRestClient::fetch_open_interest(symbol).await
+        }
+        MarketType::LinearSwap => {
+            huobi_linear_swap::HuobiLinearSwapRestClient::fetch_open_interest(symbol).await
+        }
+        MarketType::InverseSwap => {
+            huobi_inverse_swap::HuobiInverseSwapRestClient::fetch_open_interest(symbol).await
+        }
+        _ => Err(Error(format!(
+            "Huobi {} does not have open interest",
+            market_type
+        ))),
+    }
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: Option<&str>) -> Result<String> {
-    let func = match market_type {
-        MarketType::InverseFuture => huobi_future::HuobiFutureRestClient::fetch_open_interest,
-        MarketType::LinearSwap => huobi_linear_swap::HuobiLinearSwapRestClient::fetch_open_interest,
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let period = to_period(interval)?;
+    // The spot market doesn't support time ranges
+    let (from, to) = (((start_time + 999) / 1000) as u64, (end_time / 1000) as u64);
+    let text = match market_type {
+        MarketType::InverseFuture => {
+            huobi_future::HuobiFutureRestClient::fetch_candlesticks(symbol, period, from, to)
+                .await?
+        }
         MarketType::InverseSwap => {
-            huobi_inverse_swap::HuobiInverseSwapRestClient::fetch_open_interest
+            huobi_inverse_swap::HuobiInverseSwapRestClient::fetch_candlesticks(
+                symbol, period, from, to,
+            )
+            .await?
+        }
+        MarketType::LinearSwap => {
+            huobi_linear_swap::HuobiLinearSwapRestClient::fetch_candlesticks(
+                symbol, period, from, to,
+            )
+            .await?
+        }
+        _ => {
+            return Err(Error(format!(
+                "Huobi {} does NOT have historical candlesticks",
+                market_type
+            )))
+        }
+    };
+    let resp = serde_json::from_str::<KlineResponse>(&text)?;
+    if resp.status != "ok" {
+        return Err(Error(text));
+    }
+
+    let pair = crypto_pair::normalize_pair(symbol, "huobi")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = resp
+        .data
+        .unwrap_or_default()
+        .into_iter()
+        .map(|raw_kline| {
+            let quote_volume = if market_type == MarketType::LinearSwap {
+                raw_kline.trade_turnover
+            } else {
+                let (_, quote_volume, _) = calc_quantity_and_volume(
+                    "huobi",
+                    market_type,
+                    &pair,
+                    raw_kline.close,
+                    raw_kline.vol,
+                );
+                Some(quote_volume)
+            };
+            new_kline_msg(
+                "huobi",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                raw_kline.id * 1000,
+                [
+                    raw_kline.open,
+                    raw_kline.high,
+                    raw_kline.low,
+                    raw_kline.close,
+                ],
+                raw_kline.amount,
+                quote_volume,
+                serde_json::to_string(&raw_kline).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
+}
+
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    _end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    if market_type != MarketType::InverseSwap && market_type != MarketType::LinearSwap {
+        return Err(Error(format!(
+            "Huobi {} does NOT have funding rates",
+            market_type
+        )));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, "huobi")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+
+    // Pages are in descending order of time, stop once a page goes beyond start_time
+    let mut funding_rates = Vec::new();
+    let mut page_index = 1;
+    loop {
+        let text = if market_type == MarketType::InverseSwap {
+            huobi_inverse_swap::HuobiInverseSwapRestClient::fetch_funding_rates(symbol, page_index)
+                .await?
+        } else {
+            huobi_linear_swap::HuobiLinearSwapRestClient::fetch_funding_rates(symbol, page_index)
+                .await?
+        };
+        let resp = serde_json::from_str::<FundingRateResponse>(&text)?;
+        let page = match resp.data {
+            Some(page) if resp.status == "ok" => page,
+            _ => return Err(Error(text)),
+        };
+        let mut reached_start = page.data.is_empty();
+        for raw_funding_rate in page.data {
+            let funding_time = raw_funding_rate.funding_time.parse::<i64>().unwrap();
+            if funding_time < start_time {
+                reached_start = true;
+            }
+            funding_rates.push(FundingRateMsg {
+                exchange: "huobi".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::FundingRate,
+                timestamp: funding_time,
+                funding_rate: raw_funding_rate.funding_rate.parse::<f64>().unwrap(),
+                funding_time,
+                estimated_rate: None,
+                mark_price: None,
+                json: serde_json::to_string(&raw_funding_rate).unwrap(),
+            });
+        }
+        if reached_start || page.current_page >= page.total_page {
+            break;
+        }
+        page_index += 1;
+    }
+    Ok(funding_rates)
+}
+
+// Returns (open time, close price, raw candlestick) of each candlestick
+fn parse_price_klines(text: &str) -> Result<Vec<(i64, f64, RawPriceKline)>> {
+    let resp = serde_json::from_str::<PriceKlineResponse>(text)?;
+    let raw_klines = match resp.data {
+        Some(data) if resp.status == "ok" => data,
+        _ => return Err(Error(text.to_string())),
+    };
+    let mut prices = Vec::with_capacity(raw_klines.len());
+    for raw_kline in raw_klines {
+        let close = raw_kline
+            .close
+            .parse::<f64>()
+            .map_err(|_| Error(format!("Failed to parse {} as f64", raw_kline.close)))?;
+        prices.push((raw_kline.id * 1000, close, raw_kline));
+    }
+    Ok(prices)
+}
+
+// Huobi serves only the latest 2000 mark prices, the caller filters them by time.
+pub(crate) async fn fetch_mark_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    _start_time: i64,
+    _end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    use huobi_inverse_swap::HuobiInverseSwapRestClient as Inverse;
+    use huobi_linear_swap::HuobiLinearSwapRestClient as Linear;
+    let period = to_period(interval)?;
+    let (mark_text, premium_text) = match market_type {
+        MarketType::InverseSwap => (
+            Inverse::fetch_mark_price_klines(symbol, period, 2000).await?,
+            Inverse::fetch_premium_index_klines(symbol, period, 2000).await?,
+        ),
+        MarketType::LinearSwap => (
+            Linear::fetch_mark_price_klines(symbol, period, 2000).await?,
+            Linear::fetch_premium_index_klines(symbol, period, 2000).await?,
+        ),
+        _ => {
+            return Err(Error(format!(
+                "Huobi {} does NOT have mark prices",
+                market_type
+            )))
         }
-        _ => panic!("Huobi {} does not have open interest", market_type),
     };
+    let pair = crypto_pair::normalize_pair(symbol, "huobi")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
 
-    func(symbol)
+    let marks = parse_price_klines(&mark_text)?;
+    let premiums: HashMap<i64, f64> = parse_price_klines(&premium_text)?
+        .into_iter()
+        .map(|(timestamp, premium, _)| (timestamp, premium))
+        .collect();
+    Ok(marks
+        .into_iter()
+        .map(|(timestamp, mark_price, raw_kline)| MarkPriceMsg {
+            exchange: "huobi".to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair: pair.clone(),
+            msg_type: MessageType::MarkPrice,
+            timestamp,
+            json: serde_json::to_string(&raw_kline).unwrap(),
+            mark_price: Some(mark_price),
+            index_price: None,
+            premium_index: premiums.get(&timestamp).copied(),
+        })
+        .collect())
 }
@@ -1,46 +1,228 @@
-use super::utils::http_get;
-use crate::error::Result;
-use std::collections::BTreeMap;
+use super::utils::{http_get, new_kline_msg};
+use crate::error::{Error, Result};
+use crate::history::TradeCursor;
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{KlineMsg, TradeMsg, TradeSide};
+use crypto_msg_type::MessageType;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 const BASE_URL: &str = "https://api.kraken.com";
 
-/// The WebSocket client for Kraken.
-///
-/// Kraken has only Spot market.
-///
-/// * REST API doc: <https://docs.kraken.com/rest/>
-/// * Trading at: <https://trade.kraken.com/>
-/// * Rate Limits: <https://docs.kraken.com/rest/#section/Rate-Limits/REST-API-Rate-Limits>
-///   * 15 requests per 45 seconds
-pub struct KrakenRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
+rest_client! {
+    /// The WebSocket client for Kraken.
+    ///
+    /// Kraken has only Spot market.
+    ///
+    /// * REST API doc: <https://docs.kraken.com/rest/>
+    /// * Trading at: <https://trade.kraken.com/>
+    /// * Rate Limits: <https://docs.kraken.com/rest/#section/Rate-Limits/REST-API-Rate-Limits>
+    ///   * 15 requests per 45 seconds
+    pub struct KrakenRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
 
-impl KrakenRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        KrakenRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+    impl KrakenRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            KrakenRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get most recent trades.
+        ///
+        /// If `since` is provided, return trade data since given id (exclusive).
+        ///
+        /// For example: <https://api.kraken.com/0/public/Trades?pair=XXBTZUSD&since=1609893937598797338>
+        #[allow(non_snake_case)]
+        pub async fn fetch_trades(symbol: &str, since: Option<String>) -> Result<String> {
+            let pair = Some(symbol);
+            gen_api!("/0/public/Trades", pair, since)
+        }
+
+        /// Get candlesticks.
+        ///
+        /// `interval` is in minutes, `since` is a Unix timestamp in seconds.
+        /// Only the most recent 720 candlesticks are served, no matter what `since` is.
+        ///
+        /// For example: <https://api.kraken.com/0/public/OHLC?pair=XXBTZUSD&interval=1>
+        pub async fn fetch_candlesticks(symbol: &str, interval: u32, since: Option<u64>) -> Result<String> {
+            let pair = Some(symbol);
+            let interval = Some(interval);
+            gen_api!("/0/public/OHLC", pair, interval, since)
+        }
+
+        /// Get a Level2 snapshot of orderbook.
+        ///
+        /// Top 500 bids and asks are returned.
+        ///
+        /// For example: <https://api.kraken.com/0/public/Depth?pair=XXBTZUSD&count=500>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!("/0/public/Depth?pair={}&count=500", symbol))
         }
     }
+}
 
-    /// Get most recent trades.
-    ///
-    /// If `since` is provided, return trade data since given id (exclusive).
-    ///
-    /// For example: <https://api.kraken.com/0/public/Trades?pair=XXBTZUSD&since=1609893937598797338>
-    #[allow(non_snake_case)]
-    pub fn fetch_trades(symbol: &str, since: Option<String>) -> Result<String> {
-        gen_api!(format!("/0/public/Trades?pair={}", symbol), since)
+// Returns the array in `result`, whose key is the pair name of Kraken.
+fn parse_result(text: &str) -> Result<Vec<Value>> {
+    let resp = serde_json::from_str::<HashMap<String, Value>>(text)?;
+    let result = match resp.get("result").and_then(|result| result.as_object()) {
+        Some(result) => result.clone(),
+        None => return Err(Error(text.to_string())),
+    };
+    match result.into_iter().find(|(key, _)| key != "last") {
+        Some((_, Value::Array(arr))) => Ok(arr),
+        _ => Ok(Vec::new()),
     }
+}
 
-    /// Get a Level2 snapshot of orderbook.
-    ///
-    /// Top 500 bids and asks are returned.
-    ///
-    /// For example: <https://api.kraken.com/0/public/Depth?pair=XXBTZUSD&count=500>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/0/public/Depth?pair={}&count=500", symbol))
+fn parse_f64(v: &Value) -> Result<f64> {
+    v.as_str()
+        .and_then(|s| s.parse::<f64>().ok())
+        .ok_or_else(|| Error(format!("Failed to parse {} as f64", v)))
+}
+
+// trade_id was added in 2022, older trades are identified by time, price,
+// volume and side, since many trades can share the same time
+fn parse_trade_id(raw_trade: &Value) -> String {
+    match raw_trade.get(6).and_then(|id| id.as_i64()) {
+        Some(id) => id.to_string(),
+        None => format!(
+            "{}-{}-{}-{}",
+            raw_trade[2], raw_trade[0], raw_trade[1], raw_trade[3]
+        )
+        .replace('"', ""),
+    }
+}
+
+pub(crate) async fn fetch_trades_page(
+    market_type: MarketType,
+    symbol: &str,
+    cursor: &TradeCursor,
+) -> Result<Vec<TradeMsg>> {
+    if !symbol.contains('/') {
+        return Err(Error(format!(
+            "Kraken symbol {} should be in the format of XBT/USD",
+            symbol
+        )));
+    }
+    // since is a Unix timestamp in nanoseconds, exclusive
+    let since = cursor.start_time * 1_000_000 - 1;
+    let text =
+        KrakenRestClient::fetch_trades(&symbol.replace('/', ""), Some(since.to_string())).await?;
+    // [price, volume, time, buy/sell, market/limit, miscellaneous, trade_id]
+    let raw_trades = parse_result(&text)?;
+
+    let pair = crypto_pair::normalize_pair(symbol, "kraken")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let mut trades = Vec::with_capacity(raw_trades.len());
+    for raw_trade in raw_trades {
+        let price = parse_f64(&raw_trade[0])?;
+        let quantity = parse_f64(&raw_trade[1])?;
+        let timestamp = raw_trade[2]
+            .as_f64()
+            .map(|time| (time * 1000.0) as i64)
+            .ok_or_else(|| Error(format!("Failed to parse time of {}", raw_trade)))?;
+        trades.push(TradeMsg {
+            exchange: "kraken".to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair: pair.clone(),
+            msg_type: MessageType::Trade,
+            timestamp,
+            price,
+            quantity_base: quantity,
+            quantity_quote: price * quantity,
+            quantity_contract: None,
+            side: if raw_trade[3].as_str() == Some("s") {
+                TradeSide::Sell
+            } else {
+                TradeSide::Buy
+            },
+            trade_id: parse_trade_id(&raw_trade),
+            json: serde_json::to_string(&raw_trade).unwrap(),
+        });
+    }
+    Ok(trades)
+}
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    _end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    if ![60, 300, 900, 1800, 3600, 14400, 86400, 604800, 1296000].contains(&interval) {
+        return Err(Error(format!(
+            "Kraken does NOT support candlestick interval {}",
+            interval
+        )));
+    }
+    if !symbol.contains('/') {
+        return Err(Error(format!(
+            "Kraken symbol {} should be in the format of XBT/USD",
+            symbol
+        )));
+    }
+    // since is exclusive
+    let text = KrakenRestClient::fetch_candlesticks(
+        &symbol.replace('/', ""),
+        (interval / 60) as u32,
+        Some((start_time / 1000 - 1) as u64),
+    )
+    .await?;
+    // [time, open, high, low, close, vwap, volume, count]
+    let raw_klines = parse_result(&text)?;
+
+    let pair = crypto_pair::normalize_pair(symbol, "kraken")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let mut klines = Vec::with_capacity(raw_klines.len());
+    for raw_kline in raw_klines {
+        let timestamp = raw_kline[0]
+            .as_i64()
+            .ok_or_else(|| Error(format!("Failed to parse time of {}", raw_kline)))?;
+        klines.push(new_kline_msg(
+            "kraken",
+            market_type,
+            symbol,
+            &pair,
+            interval,
+            timestamp * 1000,
+            [
+                parse_f64(&raw_kline[1])?,
+                parse_f64(&raw_kline[2])?,
+                parse_f64(&raw_kline[3])?,
+                parse_f64(&raw_kline[4])?,
+            ],
+            parse_f64(&raw_kline[6])?,
+            None,
+            serde_json::to_string(&raw_kline).unwrap(),
+        ));
+    }
+    Ok(klines)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_trade_id() {
+        let raw_trade = json!(["30000.1", "0.1", 1616663618.2113, "b", "m", "", 42]);
+        assert_eq!("42", super::parse_trade_id(&raw_trade));
+
+        let raw_trade = json!(["30000.1", "0.1", 1616663618.2113, "b", "m", ""]);
+        let other = json!(["30000.2", "0.1", 1616663618.2113, "b", "m", ""]);
+        assert_eq!(
+            "1616663618.2113-30000.1-0.1-b",
+            super::parse_trade_id(&raw_trade)
+        );
+        assert_ne!(
+            super::parse_trade_id(&raw_trade),
+            super::parse_trade_id(&other)
+        );
     }
 }
@@ -1,41 +1,131 @@
-mod kucoin_spot;
-mod kucoin_swap;
+pub(crate) mod kucoin_spot;
+pub(crate) mod kucoin_swap;
 
-pub use kucoin_spot::KuCoinSpotRestClient;
-pub use kucoin_swap::KuCoinSwapRestClient;
-
-use crate::error::Result;
+use super::utils::new_kline_msg;
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
+use crypto_msg_parser::KlineMsg;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize)]
+struct CandlesResponse {
+    code: String,
+    data: Option<Vec<Vec<String>>>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => kucoin_spot::KuCoinSpotRestClient::fetch_l2_snapshot,
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => kucoin_spot::KuCoinSpotRestClient::fetch_l2_snapshot(symbol).await,
         MarketType::InverseSwap | MarketType::LinearSwap | MarketType::InverseFuture => {
-            kucoin_swap::KuCoinSwapRestClient::fetch_l2_snapshot
+            kucoin_swap::KuCoinSwapRestClient::fetch_l2_snapshot(symbol).await
         }
-        _ => panic!("Bitget unknown market_type: {}", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!(
+            "KuCoin unknown market_type: {}",
+            market_type
+        ))),
+    }
 }
 
-pub(crate) fn fetch_l3_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => kucoin_spot::KuCoinSpotRestClient::fetch_l3_snapshot,
+pub(crate) async fn fetch_l3_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => kucoin_spot::KuCoinSpotRestClient::fetch_l3_snapshot(symbol).await,
         MarketType::InverseSwap | MarketType::LinearSwap | MarketType::InverseFuture => {
-            kucoin_swap::KuCoinSwapRestClient::fetch_l3_snapshot
+            kucoin_swap::KuCoinSwapRestClient::fetch_l3_snapshot(symbol).await
         }
-        _ => panic!("Bitget unknown market_type: {}", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!(
+            "KuCoin unknown market_type: {}",
+            market_type
+        ))),
+    }
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType) -> Result<String> {
+pub(crate) async fn fetch_open_interest(market_type: MarketType) -> Result<String> {
     match market_type {
         MarketType::InverseSwap | MarketType::LinearSwap | MarketType::Unknown => {
-            kucoin_swap::KuCoinSwapRestClient::fetch_open_interest()
+            kucoin_swap::KuCoinSwapRestClient::fetch_open_interest().await
         }
-        _ => panic!("kucoin {} does not have open interest", market_type),
+        _ => Err(Error(format!(
+            "kucoin {} does not have open interest",
+            market_type
+        ))),
     }
 }
+
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let candle_type = match interval {
+        60 => "1min",
+        180 => "3min",
+        300 => "5min",
+        900 => "15min",
+        1800 => "30min",
+        3600 => "1hour",
+        7200 => "2hour",
+        14400 => "4hour",
+        21600 => "6hour",
+        28800 => "8hour",
+        43200 => "12hour",
+        86400 => "1day",
+        604800 => "1week",
+        _ => {
+            return Err(Error(format!(
+                "KuCoin does NOT support candlestick interval {}",
+                interval
+            )))
+        }
+    };
+    if market_type != MarketType::Spot {
+        return Err(Error(format!(
+            "KuCoin {} does NOT have historical candlesticks",
+            market_type
+        )));
+    }
+    let text = kucoin_spot::KuCoinSpotRestClient::fetch_candlesticks(
+        symbol,
+        candle_type,
+        ((start_time + 999) / 1000) as u64,
+        (end_time / 1000) as u64,
+    )
+    .await?;
+    let resp = serde_json::from_str::<CandlesResponse>(&text)?;
+    if resp.code != "200000" {
+        return Err(Error(text));
+    }
+
+    let pair = crypto_pair::normalize_pair(symbol, "kucoin")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    // [time, open, close, high, low, volume, turnover], in descending order of time
+    let klines = resp
+        .data
+        .unwrap_or_default()
+        .into_iter()
+        .map(|raw_kline| {
+            let nums: Vec<f64> = raw_kline
+                .iter()
+                .map(|x| x.parse::<f64>().unwrap())
+                .collect();
+            new_kline_msg(
+                "kucoin",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                nums[0] as i64 * 1000,
+                [nums[1], nums[3], nums[4], nums[2]],
+                nums[5],
+                Some(nums[6]),
+                serde_json::to_string(&raw_kline).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
+}
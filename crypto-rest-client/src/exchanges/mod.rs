@@ -20,3 +20,5 @@ pub(super) mod kucoin;
 pub(super) mod mxc;
 pub(super) mod okex;
 pub(super) mod zbg;
+
+pub(crate) use utils::{block_on, sleep};
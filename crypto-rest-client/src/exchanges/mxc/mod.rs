@@ -1,17 +1,185 @@
 pub(crate) mod mxc_spot;
 pub(crate) mod mxc_swap;
 
-use crate::error::Result;
+use super::utils::{calc_quantity_and_volume, new_kline_msg};
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
+use crypto_msg_parser::KlineMsg;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => mxc_spot::MxcSpotRestClient::fetch_l2_snapshot,
+// [time, open, close, high, low, volume, amount]
+type RawSpotKline = (i64, String, String, String, String, String, String);
+
+// see https://mxcdevelop.github.io/APIDoc/open.api.v2.en.html#k-line
+#[derive(Serialize, Deserialize)]
+struct SpotKlineResponse {
+    code: Value,
+    data: Option<Vec<RawSpotKline>>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://mxcdevelop.github.io/APIDoc/contract.api.cn.html#k
+#[derive(Serialize, Deserialize)]
+struct SwapKlines {
+    time: Vec<i64>,
+    open: Vec<f64>,
+    close: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    vol: Vec<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SwapKlineResponse {
+    success: bool,
+    data: Option<SwapKlines>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+fn parse_f64(s: &str) -> Result<f64> {
+    s.parse::<f64>()
+        .map_err(|_| Error(format!("Failed to parse {} as f64", s)))
+}
+
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => mxc_spot::MxcSpotRestClient::fetch_l2_snapshot(symbol).await,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            mxc_swap::MxcSwapRestClient::fetch_l2_snapshot
+            mxc_swap::MxcSwapRestClient::fetch_l2_snapshot(symbol).await
         }
-        _ => panic!("MXC unknown market_type: {}", market_type),
-    };
+        _ => Err(Error(format!("MXC unknown market_type: {}", market_type))),
+    }
+}
 
-    func(symbol)
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    let pair = crypto_pair::normalize_pair(symbol, "mxc")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let start = ((start_time + 999) / 1000) as u64;
+    let mut klines = Vec::new();
+    match market_type {
+        MarketType::Spot => {
+            let interval_str = match interval {
+                60 => "1m",
+                300 => "5m",
+                900 => "15m",
+                1800 => "30m",
+                3600 => "60m",
+                14400 => "4h",
+                86400 => "1d",
+                _ => {
+                    return Err(Error(format!(
+                        "MXC Spot does NOT support candlestick interval {}",
+                        interval
+                    )))
+                }
+            };
+            let text =
+                mxc_spot::MxcSpotRestClient::fetch_candlesticks(symbol, interval_str, Some(start))
+                    .await?;
+            let resp = serde_json::from_str::<SpotKlineResponse>(&text)?;
+            if resp.code.as_i64() != Some(200) && resp.code.as_str() != Some("200") {
+                return Err(Error(text));
+            }
+            for raw_kline in resp.data.unwrap_or_default() {
+                klines.push(new_kline_msg(
+                    "mxc",
+                    market_type,
+                    symbol,
+                    &pair,
+                    interval,
+                    raw_kline.0 * 1000,
+                    [
+                        parse_f64(&raw_kline.1)?,
+                        parse_f64(&raw_kline.3)?,
+                        parse_f64(&raw_kline.4)?,
+                        parse_f64(&raw_kline.2)?,
+                    ],
+                    parse_f64(&raw_kline.5)?,
+                    Some(parse_f64(&raw_kline.6)?),
+                    serde_json::to_string(&raw_kline).unwrap(),
+                ));
+            }
+        }
+        MarketType::InverseSwap | MarketType::LinearSwap => {
+            let interval_str = match interval {
+                60 => "Min1",
+                300 => "Min5",
+                900 => "Min15",
+                1800 => "Min30",
+                3600 => "Min60",
+                14400 => "Hour4",
+                28800 => "Hour8",
+                86400 => "Day1",
+                604800 => "Week1",
+                _ => {
+                    return Err(Error(format!(
+                        "MXC Swap does NOT support candlestick interval {}",
+                        interval
+                    )))
+                }
+            };
+            let text = mxc_swap::MxcSwapRestClient::fetch_candlesticks(
+                symbol,
+                interval_str,
+                Some(start),
+                Some((end_time / 1000) as u64),
+            )
+            .await?;
+            let resp = serde_json::from_str::<SwapKlineResponse>(&text)?;
+            let data = match resp.data {
+                Some(data) if resp.success => data,
+                _ => return Err(Error(text)),
+            };
+            let n = data.time.len();
+            if [&data.open, &data.close, &data.high, &data.low, &data.vol]
+                .iter()
+                .any(|arr| arr.len() != n)
+            {
+                return Err(Error(text));
+            }
+            for (i, time) in data.time.iter().enumerate() {
+                let (volume, quote_volume, _) =
+                    calc_quantity_and_volume("mxc", market_type, &pair, data.close[i], data.vol[i]);
+                klines.push(new_kline_msg(
+                    "mxc",
+                    market_type,
+                    symbol,
+                    &pair,
+                    interval,
+                    time * 1000,
+                    [data.open[i], data.high[i], data.low[i], data.close[i]],
+                    volume,
+                    Some(quote_volume),
+                    serde_json::json!({
+                        "time": time,
+                        "open": data.open[i],
+                        "close": data.close[i],
+                        "high": data.high[i],
+                        "low": data.low[i],
+                        "vol": data.vol[i],
+                    })
+                    .to_string(),
+                ));
+            }
+        }
+        _ => {
+            return Err(Error(format!(
+                "MXC {} does NOT have candlesticks",
+                market_type
+            )))
+        }
+    }
+    Ok(klines)
 }
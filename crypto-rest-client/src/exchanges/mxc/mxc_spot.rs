@@ -4,47 +4,71 @@ use std::collections::BTreeMap;
 
 const BASE_URL: &str = "https://www.mexc.com";
 
-/// MXC Spot market.
-///
-/// * REST API doc: <https://mxcdevelop.github.io/APIDoc/>
-/// * Trading at: <https://www.mxc.com/trade/pro>
-/// * Rate Limits: <https://mxcdevelop.github.io/APIDoc/open.api.v2.en.html#rate-limit>
-///   * The default rate limiting rule for an endpoint is 20 times per second.
-pub struct MxcSpotRestClient {
-    _access_key: String,
-    _secret_key: Option<String>,
-}
+rest_client! {
+    /// MXC Spot market.
+    ///
+    /// * REST API doc: <https://mxcdevelop.github.io/APIDoc/>
+    /// * Trading at: <https://www.mxc.com/trade/pro>
+    /// * Rate Limits: <https://mxcdevelop.github.io/APIDoc/open.api.v2.en.html#rate-limit>
+    ///   * The default rate limiting rule for an endpoint is 20 times per second.
+    pub struct MxcSpotRestClient {
+        _access_key: String,
+        _secret_key: Option<String>,
+    }
 
-impl MxcSpotRestClient {
-    pub fn new(access_key: String, secret_key: Option<String>) -> Self {
-        MxcSpotRestClient {
-            _access_key: access_key,
-            _secret_key: secret_key,
+    impl MxcSpotRestClient {
+        pub fn new(access_key: String, secret_key: Option<String>) -> Self {
+            MxcSpotRestClient {
+                _access_key: access_key,
+                _secret_key: secret_key,
+            }
         }
-    }
 
-    /// Get latest trades.
-    ///
-    /// 1000 trades are returned.
-    ///
-    /// For example: <https://www.mxc.co/open/api/v2/market/deals?symbol=BTC_USDT&limit=1000>
-    #[allow(non_snake_case)]
-    pub fn fetch_trades(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/open/api/v2/market/deals?symbol={}&limit=1000",
-            symbol
-        ))
-    }
+        /// Get latest trades.
+        ///
+        /// 1000 trades are returned.
+        ///
+        /// For example: <https://www.mxc.co/open/api/v2/market/deals?symbol=BTC_USDT&limit=1000>
+        #[allow(non_snake_case)]
+        pub async fn fetch_trades(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/open/api/v2/market/deals?symbol={}&limit=1000",
+                symbol
+            ))
+        }
 
-    /// Get latest Level2 snapshot of orderbook.
-    ///
-    /// Top 2000 bids and asks will be returned.
-    ///
-    /// For example: <https://www.mxc.co/open/api/v2/market/depth?symbol=BTC_USDT&depth=2000>
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/open/api/v2/market/depth?symbol={}&depth=2000",
-            symbol
-        ))
+        /// Get candlesticks.
+        ///
+        /// At most 1000 candlesticks are returned, `start_time` is in seconds.
+        ///
+        /// For example: <https://www.mexc.com/open/api/v2/market/kline?symbol=BTC_USDT&interval=1m&limit=1000>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            interval: &str,
+            start_time: Option<u64>,
+        ) -> Result<String> {
+            let symbol = Some(symbol);
+            let interval = Some(interval);
+            let limit = Some(1000);
+            gen_api!(
+                "/open/api/v2/market/kline",
+                symbol,
+                interval,
+                start_time,
+                limit
+            )
+        }
+
+        /// Get latest Level2 snapshot of orderbook.
+        ///
+        /// Top 2000 bids and asks will be returned.
+        ///
+        /// For example: <https://www.mxc.co/open/api/v2/market/depth?symbol=BTC_USDT&depth=2000>
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/open/api/v2/market/depth?symbol={}&depth=2000",
+                symbol
+            ))
+        }
     }
 }
@@ -1,88 +1,443 @@
-use super::utils::http_get;
-use crate::error::Result;
-use std::collections::BTreeMap;
+use super::utils::{
+    calc_quantity_and_volume, http_get, millis_to_iso, new_kline_msg, time_windows,
+};
+use crate::error::{Error, Result};
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, KlineMsg, MarkPriceMsg};
+use crypto_msg_type::MessageType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 const BASE_URL: &str = "https://www.okex.com/api";
 
-/// The REST client for OKEx.
-///
-/// OKEx has Spot, Future, Swap and Option markets.
-///
-/// * WebSocket API doc: <https://www.okex.com/docs/en/>
-/// * Trading at:
-///     * Spot <https://www.bitmex.com/app/trade/>
-///     * Future <https://www.okex.com/derivatives/futures>
-///     * Swap <https://www.okex.com/derivatives/swap>
-///     * Option <https://www.okex.com/derivatives/options>
-pub struct OkexRestClient {
-    _api_key: Option<String>,
-    _api_secret: Option<String>,
-}
+rest_client! {
+    /// The REST client for OKEx.
+    ///
+    /// OKEx has Spot, Future, Swap and Option markets.
+    ///
+    /// * WebSocket API doc: <https://www.okex.com/docs/en/>
+    /// * Trading at:
+    ///     * Spot <https://www.bitmex.com/app/trade/>
+    ///     * Future <https://www.okex.com/derivatives/futures>
+    ///     * Swap <https://www.okex.com/derivatives/swap>
+    ///     * Option <https://www.okex.com/derivatives/options>
+    pub struct OkexRestClient {
+        _api_key: Option<String>,
+        _api_secret: Option<String>,
+    }
+
+    impl OkexRestClient {
+        pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
+            OkexRestClient {
+                _api_key: api_key,
+                _api_secret: api_secret,
+            }
+        }
+
+        /// Get most recent trades.
+        ///
+        /// 100 trades are returned.
+        ///
+        /// For example: <https://www.okex.com/api/spot/v3/instruments/BTC-USDT/trades>
+        pub async fn fetch_trades(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/{}/v3/instruments/{}/trades",
+                pair_to_market_type(symbol),
+                symbol
+            ))
+        }
 
-impl OkexRestClient {
-    pub fn new(api_key: Option<String>, api_secret: Option<String>) -> Self {
-        OkexRestClient {
-            _api_key: api_key,
-            _api_secret: api_secret,
+        /// Get candlesticks.
+        ///
+        /// At most 300 candlesticks are returned, `granularity` is in seconds,
+        /// `start` and `end` are in ISO 8601.
+        ///
+        /// For example:
+        /// * <https://www.okex.com/api/spot/v3/instruments/BTC-USDT/candles?granularity=60>
+        /// * <https://www.okex.com/api/swap/v3/instruments/BTC-USDT-SWAP/candles?granularity=60>
+        pub async fn fetch_candlesticks(
+            symbol: &str,
+            granularity: u32,
+            start: Option<String>,
+            end: Option<String>,
+        ) -> Result<String> {
+            let granularity = Some(granularity);
+            gen_api!(
+                format!(
+                    "/{}/v3/instruments/{}/candles",
+                    pair_to_market_type(symbol),
+                    symbol
+                ),
+                granularity,
+                start,
+                end
+            )
+        }
+
+        /// Get the latest Level2 snapshot of orderbook.
+        ///
+        /// Top 200 bids and asks are returned.
+        ///
+        /// For example:
+        /// * <https://www.okex.com/api/spot/v3/instruments/BTC-USDT/book?size=200>,
+        /// * <https://www.okex.com/api/swap/v3/instruments/BTC-USDT-SWAP/depth?size=200>
+        ///
+        /// Rate limit: 20 requests per 2 seconds
+        pub async fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/{}/v3/instruments/{}/{}?size=200",
+                pair_to_market_type(symbol),
+                symbol,
+                if symbol.ends_with("-SWAP") {
+                    "depth"
+                } else {
+                    "book"
+                }
+            ))
+        }
+
+        /// Get funding rate history of perpetual swaps.
+        ///
+        /// Only the latest 100 funding rates are returned.
+        ///
+        /// For example: <https://www.okex.com/api/swap/v3/instruments/BTC-USD-SWAP/historical_funding_rate?limit=100>
+        pub async fn fetch_funding_rates(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/swap/v3/instruments/{}/historical_funding_rate?limit=100",
+                symbol
+            ))
+        }
+
+        /// Get mark price candlesticks.
+        ///
+        /// At most 100 candlesticks are returned, in descending order of time.
+        /// `after` and `before` are Unix timestamps in milliseconds, both exclusive.
+        ///
+        /// For example: <https://www.okx.com/api/v5/market/history-mark-price-candles?instId=BTC-USDT-SWAP&bar=1m&limit=100>
+        #[allow(non_snake_case)]
+        pub async fn fetch_mark_price_candles(
+            symbol: &str,
+            bar: &str,
+            after: Option<i64>,
+            before: Option<i64>,
+        ) -> Result<String> {
+            let instId = Some(symbol);
+            let bar = Some(bar);
+            let limit = Some(100);
+            gen_api!(
+                "https://www.okx.com/api/v5/market/history-mark-price-candles",
+                instId,
+                bar,
+                after,
+                before,
+                limit
+            )
+        }
+
+        /// Get index price candlesticks.
+        ///
+        /// At most 100 candlesticks are returned, in descending order of time.
+        /// `after` and `before` are Unix timestamps in milliseconds, both exclusive.
+        ///
+        /// For example: <https://www.okx.com/api/v5/market/history-index-candles?instId=BTC-USDT&bar=1m&limit=100>
+        #[allow(non_snake_case)]
+        pub async fn fetch_index_candles(
+            index_name: &str,
+            bar: &str,
+            after: Option<i64>,
+            before: Option<i64>,
+        ) -> Result<String> {
+            let instId = Some(index_name);
+            let bar = Some(bar);
+            let limit = Some(100);
+            gen_api!(
+                "https://www.okx.com/api/v5/market/history-index-candles",
+                instId,
+                bar,
+                after,
+                before,
+                limit
+            )
+        }
+
+        /// Get option underlying.
+        pub async fn fetch_option_underlying() -> Result<Vec<String>> {
+            let txt = http_get(
+                "https://www.okex.com/api/option/v3/underlying",
+                &BTreeMap::new(),
+            )
+            .await?;
+            let underlying_indexes = serde_json::from_str::<Vec<String>>(&txt)?;
+            Ok(underlying_indexes)
+        }
+
+        /// Get open interest.
+        ///
+        /// For example:
+        /// - <https://www.okex.com/api/futures/v3/instruments/BTC-USD-211231/open_interest>
+        pub async fn fetch_open_interest(symbol: &str) -> Result<String> {
+            gen_api!(format!(
+                "/{}/v3/instruments/{}/open_interest",
+                pair_to_market_type(symbol),
+                symbol
+            ))
         }
     }
+}
 
-    /// Get most recent trades.
-    ///
-    /// 100 trades are returned.
-    ///
-    /// For example: <https://www.okex.com/api/spot/v3/instruments/BTC-USDT/trades>
-    pub fn fetch_trades(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/{}/v3/instruments/{}/trades",
-            pair_to_market_type(symbol),
-            symbol
-        ))
+pub(crate) async fn fetch_candlesticks_page(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<KlineMsg>> {
+    if ![
+        60, 180, 300, 900, 1800, 3600, 7200, 14400, 21600, 43200, 86400, 604800,
+    ]
+    .contains(&interval)
+    {
+        return Err(Error(format!(
+            "OKEx does NOT support candlestick interval {}",
+            interval
+        )));
+    }
+    if market_type == MarketType::EuropeanOption {
+        return Err(Error(
+            "OKEx option markets do NOT have candlesticks".to_string(),
+        ));
     }
+    let text = OkexRestClient::fetch_candlesticks(
+        symbol,
+        interval as u32,
+        Some(millis_to_iso(start_time)),
+        Some(millis_to_iso(end_time)),
+    )
+    .await?;
+    // [time, open, high, low, close, volume], futures and swap markets have an
+    // extra currency_volume in base currency, while volume is the number of contracts
+    let raw_klines = serde_json::from_str::<Vec<Vec<String>>>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "okex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let klines = raw_klines
+        .into_iter()
+        .map(|raw_kline| {
+            let timestamp = chrono::DateTime::parse_from_rfc3339(&raw_kline[0]).unwrap();
+            let nums: Vec<f64> = raw_kline[1..]
+                .iter()
+                .map(|x| x.parse::<f64>().unwrap())
+                .collect();
+            let (volume, quote_volume) = match market_type {
+                MarketType::Spot => (nums[4], None),
+                MarketType::InverseFuture | MarketType::InverseSwap => {
+                    let (_, quote_volume, _) =
+                        calc_quantity_and_volume("okex", market_type, &pair, nums[3], nums[4]);
+                    (nums[5], Some(quote_volume))
+                }
+                _ => (nums[5], None),
+            };
+            new_kline_msg(
+                "okex",
+                market_type,
+                symbol,
+                &pair,
+                interval,
+                timestamp.timestamp_millis(),
+                [nums[0], nums[1], nums[2], nums[3]],
+                volume,
+                quote_volume,
+                serde_json::to_string(&raw_kline).unwrap(),
+            )
+        })
+        .collect();
+    Ok(klines)
+}
 
-    /// Get the latest Level2 snapshot of orderbook.
-    ///
-    /// Top 200 bids and asks are returned.
-    ///
-    /// For example:
-    /// * <https://www.okex.com/api/spot/v3/instruments/BTC-USDT/book?size=200>,
-    /// * <https://www.okex.com/api/swap/v3/instruments/BTC-USDT-SWAP/depth?size=200>
-    ///
-    /// Rate limit: 20 requests per 2 seconds
-    pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/{}/v3/instruments/{}/{}?size=200",
-            pair_to_market_type(symbol),
-            symbol,
-            if symbol.ends_with("-SWAP") {
-                "depth"
-            } else {
-                "book"
+// see https://www.okex.com/docs/en/#swap-swap---funding_history
+#[derive(Serialize, Deserialize)]
+struct RawFundingRate {
+    instrument_id: String,
+    funding_rate: String,
+    funding_time: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// OKEx serves only the latest 100 funding rates, the caller filters them by time.
+pub(crate) async fn fetch_funding_rates(
+    market_type: MarketType,
+    symbol: &str,
+    _start_time: i64,
+    _end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    if market_type != MarketType::InverseSwap && market_type != MarketType::LinearSwap {
+        return Err(Error(format!(
+            "OKEx {} does NOT have funding rates",
+            market_type
+        )));
+    }
+    let text = OkexRestClient::fetch_funding_rates(symbol).await?;
+    let raw_funding_rates = serde_json::from_str::<Vec<RawFundingRate>>(&text)?;
+    let pair = crypto_pair::normalize_pair(symbol, "okex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let funding_rates = raw_funding_rates
+        .into_iter()
+        .map(|raw_funding_rate| {
+            let funding_time = chrono::DateTime::parse_from_rfc3339(&raw_funding_rate.funding_time)
+                .unwrap()
+                .timestamp_millis();
+            FundingRateMsg {
+                exchange: "okex".to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::FundingRate,
+                timestamp: funding_time,
+                funding_rate: raw_funding_rate.funding_rate.parse::<f64>().unwrap(),
+                funding_time,
+                estimated_rate: None,
+                mark_price: None,
+                json: serde_json::to_string(&raw_funding_rate).unwrap(),
             }
-        ))
+        })
+        .collect();
+    Ok(funding_rates)
+}
+
+#[derive(Serialize, Deserialize)]
+struct V5Response {
+    code: String,
+    // [ts, open, high, low, close, ...]
+    data: Option<Vec<Vec<String>>>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+fn to_bar(interval: usize) -> Result<&'static str> {
+    match interval {
+        60 => Ok("1m"),
+        180 => Ok("3m"),
+        300 => Ok("5m"),
+        900 => Ok("15m"),
+        1800 => Ok("30m"),
+        3600 => Ok("1H"),
+        7200 => Ok("2H"),
+        14400 => Ok("4H"),
+        21600 => Ok("6Hutc"),
+        43200 => Ok("12Hutc"),
+        86400 => Ok("1Dutc"),
+        604800 => Ok("1Wutc"),
+        _ => Err(Error(format!(
+            "OKEx does NOT support candlestick interval {}",
+            interval
+        ))),
     }
+}
 
-    /// Get option underlying.
-    pub fn fetch_option_underlying() -> Result<Vec<String>> {
-        let txt = http_get(
-            "https://www.okex.com/api/option/v3/underlying",
-            &BTreeMap::new(),
-        )?;
-        let underlying_indexes = serde_json::from_str::<Vec<String>>(&txt)?;
-        Ok(underlying_indexes)
+// Returns (open time, close price, raw candlestick) of each mark price candlestick
+// if `mark` is true, otherwise of each index candlestick
+async fn fetch_price_candles(
+    mark: bool,
+    inst_id: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<(i64, f64, Vec<String>)>> {
+    let bar = to_bar(interval)?;
+    let mut prices = Vec::new();
+    for (window_start, window_end) in
+        time_windows(start_time, end_time, 100 * interval as i64 * 1000)
+    {
+        let (after, before) = (Some(window_end + 1), Some(window_start - 1));
+        let text = if mark {
+            OkexRestClient::fetch_mark_price_candles(inst_id, bar, after, before).await?
+        } else {
+            OkexRestClient::fetch_index_candles(inst_id, bar, after, before).await?
+        };
+        let resp = serde_json::from_str::<V5Response>(&text)?;
+        if resp.code != "0" {
+            return Err(Error(text));
+        }
+        for raw_candle in resp.data.unwrap_or_default() {
+            if raw_candle.len() < 5 {
+                return Err(Error(format!("Malformed candlestick {:?}", raw_candle)));
+            }
+            let malformed = || Error(format!("Malformed candlestick {:?}", raw_candle));
+            let timestamp = raw_candle[0].parse::<i64>().map_err(|_| malformed())?;
+            let close = raw_candle[4].parse::<f64>().map_err(|_| malformed())?;
+            prices.push((timestamp, close, raw_candle));
+        }
     }
+    Ok(prices)
+}
 
-    /// Get open interest.
-    ///
-    /// For example:
-    /// - <https://www.okex.com/api/futures/v3/instruments/BTC-USD-211231/open_interest>
-    pub fn fetch_open_interest(symbol: &str) -> Result<String> {
-        gen_api!(format!(
-            "/{}/v3/instruments/{}/open_interest",
-            pair_to_market_type(symbol),
-            symbol
-        ))
+pub(crate) async fn fetch_mark_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    if market_type == MarketType::Spot {
+        return Err(Error(format!(
+            "OKEx {} does NOT have mark prices",
+            market_type
+        )));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, "okex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    let candles = fetch_price_candles(true, symbol, interval, start_time, end_time).await?;
+    Ok(candles
+        .into_iter()
+        .map(|(timestamp, mark_price, raw_candle)| MarkPriceMsg {
+            exchange: "okex".to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair: pair.clone(),
+            msg_type: MessageType::MarkPrice,
+            timestamp,
+            json: serde_json::to_string(&raw_candle).unwrap(),
+            mark_price: Some(mark_price),
+            index_price: None,
+            premium_index: None,
+        })
+        .collect())
+}
+
+pub(crate) async fn fetch_index_prices(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    if market_type == MarketType::Spot {
+        return Err(Error(format!(
+            "OKEx {} does NOT have index prices",
+            market_type
+        )));
     }
+    let pair = crypto_pair::normalize_pair(symbol, "okex")
+        .ok_or_else(|| Error(format!("Failed to normalize {}", symbol)))?;
+    // BTC-USDT-SWAP, BTC-USD-211231 and BTC-USD-211231-72000-C track the index BTC-USD or BTC-USDT
+    let index_name = symbol.split('-').take(2).collect::<Vec<&str>>().join("-");
+    let candles = fetch_price_candles(false, &index_name, interval, start_time, end_time).await?;
+    Ok(candles
+        .into_iter()
+        .map(|(timestamp, index_price, raw_candle)| MarkPriceMsg {
+            exchange: "okex".to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair: pair.clone(),
+            msg_type: MessageType::IndexPrice,
+            timestamp,
+            json: serde_json::to_string(&raw_candle).unwrap(),
+            mark_price: None,
+            index_price: Some(index_price),
+            premium_index: None,
+        })
+        .collect())
 }
 
 fn pair_to_market_type(pair: &str) -> &'static str {
@@ -1,10 +1,110 @@
-use reqwest::{blocking::Response, header};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use crypto_market_type::MarketType;
+#[cfg(feature = "async")]
+use lazy_static::lazy_static;
 
 use crate::error::{Error, Result};
+use crate::history::interval_to_period;
+use crypto_msg_parser::KlineMsg;
+use crypto_msg_type::MessageType;
 use std::collections::BTreeMap;
+use std::future::Future;
 
-// Returns the raw response directly.
-pub(super) fn http_get_raw(url: &str, params: &BTreeMap<String, String>) -> Result<Response> {
+#[cfg(feature = "async")]
+lazy_static! {
+    // Drives requests of the blocking API, see block_on()
+    static ref RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("crypto-rest-client")
+        .enable_all()
+        .build()
+        .unwrap();
+    // Pooled connections are bound to the runtime which opened them, so
+    // RUNTIME has its own client instead of the shared one of callers
+    static ref RUNTIME_CLIENT: std::sync::RwLock<Option<(u64, reqwest::Client)>> =
+        std::sync::RwLock::new(None);
+}
+
+#[cfg(feature = "async")]
+tokio::task_local! {
+    // Set while a future is driven by RUNTIME
+    static ON_RUNTIME: ();
+}
+
+// Runs a future to completion, which is how the blocking API is built on top of the async one.
+//
+// A runtime can NOT be blocked on within another runtime, so if the caller is
+// already inside one, the future is blocked on from a scoped thread instead.
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    let future = ON_RUNTIME.scope((), future);
+    if tokio::runtime::Handle::try_current().is_ok() {
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| RUNTIME.block_on(future))
+                .join()
+                .unwrap_or_else(|err| std::panic::resume_unwind(err))
+        })
+    } else {
+        RUNTIME.block_on(future)
+    }
+}
+
+// Runs a future to completion without any async runtime.
+//
+// Without the async feature, http_get() and sleep() block the current thread,
+// so futures of this crate never wait for a wakeup and finish on the first poll.
+#[cfg(not(feature = "async"))]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+// Sleeps without blocking the runtime if the async feature is enabled.
+pub(crate) async fn sleep(duration: std::time::Duration) {
+    #[cfg(feature = "async")]
+    tokio::time::sleep(duration).await;
+    #[cfg(not(feature = "async"))]
+    std::thread::sleep(duration);
+}
+
+// The client of RUNTIME, rebuilt once set_http_config() changes the configuration
+#[cfg(feature = "async")]
+fn runtime_client() -> reqwest::Result<reqwest::Client> {
+    let version = crypto_http::http_config_version();
+    if let Some((cached_version, client)) = RUNTIME_CLIENT.read().unwrap().as_ref() {
+        if *cached_version == version {
+            return Ok(client.clone());
+        }
+    }
+    let client = crypto_http::http_config().build_async_client()?;
+    *RUNTIME_CLIENT.write().unwrap() = Some((version, client.clone()));
+    Ok(client)
+}
+
+fn build_url(url: &str, params: &BTreeMap<String, String>) -> String {
     let mut full_url = url.to_string();
     let mut first = true;
     for (k, v) in params.iter() {
@@ -15,31 +115,106 @@ pub(super) fn http_get_raw(url: &str, params: &BTreeMap<String, String>) -> Resu
             full_url.push_str(format!("&{}={}", k, v).as_str());
         }
     }
-    // println!("{}", full_url);
-
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
-
-    let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
-    let response = client.get(full_url.as_str()).send()?;
-    Ok(response)
+    full_url
 }
 
 // Returns the text in response.
-pub(super) fn http_get(url: &str, params: &BTreeMap<String, String>) -> Result<String> {
-    match http_get_raw(url, params) {
-        Ok(response) => match response.error_for_status() {
-            Ok(resp) => Ok(resp.text()?),
+pub(super) async fn http_get(url: &str, params: &BTreeMap<String, String>) -> Result<String> {
+    let full_url = build_url(url, params);
+    // println!("{}", full_url);
+
+    #[cfg(feature = "async")]
+    {
+        let client = if ON_RUNTIME.try_with(|_| ()).is_ok() {
+            runtime_client()?
+        } else {
+            crypto_http::http_client_async()?
+        };
+        let response = client.get(full_url.as_str()).send().await?;
+        match response.error_for_status() {
+            Ok(resp) => Ok(resp.text().await?),
             Err(error) => Err(Error::from(error)),
-        },
-        Err(err) => Err(err),
+        }
+    }
+    #[cfg(not(feature = "async"))]
+    {
+        crypto_http::http_get(full_url.as_str()).map_err(Error::from)
+    }
+}
+
+// returns (quantity_base, quantity_quote, quantity_contract)
+pub(super) fn calc_quantity_and_volume(
+    exchange: &str,
+    market_type: MarketType,
+    pair: &str,
+    price: f64,
+    quantity: f64,
+) -> (f64, f64, Option<f64>) {
+    let contract_value =
+        crypto_contract_value::get_contract_value(exchange, market_type, pair).unwrap();
+
+    match market_type {
+        MarketType::Spot => (quantity, quantity * price, None),
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            let quantity_quote = quantity * contract_value;
+            (quantity_quote / price, quantity_quote, Some(quantity))
+        }
+        MarketType::LinearSwap | MarketType::LinearFuture | MarketType::EuropeanOption => {
+            let quantity_base = quantity * contract_value;
+            (quantity_base, quantity_base * price, Some(quantity))
+        }
+        _ => panic!("Unknown market_type {}", market_type),
+    }
+}
+
+// Converts a Unix timestamp in milliseconds to ISO 8601, e.g., 2021-06-01T00:00:00.000Z
+pub(super) fn millis_to_iso(timestamp: i64) -> String {
+    Utc.timestamp_millis_opt(timestamp)
+        .unwrap()
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+// Splits [start_time, end_time] into consecutive windows of at most `window` milliseconds
+pub(super) fn time_windows(start_time: i64, end_time: i64, window: i64) -> Vec<(i64, i64)> {
+    let mut windows = Vec::new();
+    let mut window_start = start_time;
+    while window_start <= end_time {
+        let window_end = std::cmp::min(window_start + window - 1, end_time);
+        windows.push((window_start, window_end));
+        window_start = window_end + 1;
+    }
+    windows
+}
+
+// Builds a KlineMsg, timestamp is the open time of the candlestick
+#[allow(clippy::too_many_arguments)]
+pub(super) fn new_kline_msg(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    pair: &str,
+    interval: usize,
+    timestamp: i64,
+    ohlc: [f64; 4],
+    volume: f64,
+    quote_volume: Option<f64>,
+    json: String,
+) -> KlineMsg {
+    KlineMsg {
+        exchange: exchange.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair: pair.to_string(),
+        msg_type: MessageType::Candlestick,
+        timestamp,
+        json,
+        open: ohlc[0],
+        high: ohlc[1],
+        low: ohlc[2],
+        close: ohlc[3],
+        volume,
+        period: interval_to_period(interval),
+        quote_volume,
     }
 }
 
@@ -54,24 +229,93 @@ macro_rules! gen_api {
                 }
             )*
             let url = if $path.starts_with("http") { $path.to_string() } else { format!("{}{}",BASE_URL, $path) };
-            http_get(&url, &params)
+            http_get(&url, &params).await
         }
     }
 }
 
+// Defines a RESTful client whose endpoints are async functions, along with a
+// blocking client of the same name in the `blocking` submodule, whose endpoints
+// run the async ones to completion with block_on().
+macro_rules! rest_client {
+    (
+        $(#[$struct_meta:meta])*
+        pub struct $client:ident {
+            $($field:ident: $field_ty:ty),* $(,)?
+        }
+
+        impl $impl_client:ident {
+            pub fn new($($new_arg:ident: $new_arg_ty:ty),* $(,)?) -> Self $new_body:block
+
+            $(
+                $(#[$fn_meta:meta])*
+                pub async fn $fn_name:ident($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty $body:block
+            )*
+        }
+    ) => {
+        $(#[$struct_meta])*
+        pub struct $client {
+            $($field: $field_ty),*
+        }
+
+        impl $client {
+            // only the blocking client is constructed without the async feature
+            #[cfg_attr(not(feature = "async"), allow(dead_code))]
+            pub fn new($($new_arg: $new_arg_ty),*) -> Self $new_body
+
+            $(
+                $(#[$fn_meta])*
+                pub async fn $fn_name($($arg: $arg_ty),*) -> $ret $body
+            )*
+        }
+
+        pub mod blocking {
+            use super::*;
+
+            $(#[$struct_meta])*
+            pub struct $client {
+                $($field: $field_ty),*
+            }
+
+            impl $client {
+                pub fn new($($new_arg: $new_arg_ty),*) -> Self $new_body
+
+                $(
+                    $(#[$fn_meta])*
+                    pub fn $fn_name($($arg: $arg_ty),*) -> $ret {
+                        $crate::exchanges::block_on(super::$client::$fn_name($($arg),*))
+                    }
+                )*
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
     use serde_json::Value;
 
+    #[test]
+    fn block_on_sleep() {
+        let value = super::block_on(async {
+            super::sleep(std::time::Duration::from_millis(1)).await;
+            1
+        });
+        assert_eq!(1, value);
+    }
+
     // System proxies are enabled by default, see <https://docs.rs/reqwest/latest/reqwest/#proxies>
     #[test]
     #[ignore]
     fn use_system_socks_proxy() {
         std::env::set_var("https_proxy", "socks5://127.0.0.1:9050");
-        let text =
-            super::http_get("https://check.torproject.org/api/ip", &BTreeMap::new()).unwrap();
+        let text = super::block_on(super::http_get(
+            "https://check.torproject.org/api/ip",
+            &BTreeMap::new(),
+        ))
+        .unwrap();
         let obj = serde_json::from_str::<BTreeMap<String, Value>>(&text).unwrap();
         assert!(obj.get("IsTor").unwrap().as_bool().unwrap());
     }
@@ -80,8 +324,11 @@ mod tests {
     #[ignore]
     fn use_system_https_proxy() {
         std::env::set_var("https_proxy", "http://127.0.0.1:8118");
-        let text =
-            super::http_get("https://check.torproject.org/api/ip", &BTreeMap::new()).unwrap();
+        let text = super::block_on(super::http_get(
+            "https://check.torproject.org/api/ip",
+            &BTreeMap::new(),
+        ))
+        .unwrap();
         let obj = serde_json::from_str::<BTreeMap<String, Value>>(&text).unwrap();
         assert!(obj.get("IsTor").unwrap().as_bool().unwrap());
     }
@@ -1,31 +1,27 @@
-mod zbg_spot;
-mod zbg_swap;
+pub(crate) mod zbg_spot;
+pub(crate) mod zbg_swap;
 
-pub use zbg_spot::ZbgSpotRestClient;
-pub use zbg_swap::ZbgSwapRestClient;
-
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => zbg_spot::ZbgSpotRestClient::fetch_l2_snapshot,
+pub(crate) async fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
+        MarketType::Spot => zbg_spot::ZbgSpotRestClient::fetch_l2_snapshot(symbol).await,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            zbg_swap::ZbgSwapRestClient::fetch_l2_snapshot
+            zbg_swap::ZbgSwapRestClient::fetch_l2_snapshot(symbol).await
         }
-        _ => panic!("ZBG unknown market_type: {}", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!("ZBG unknown market_type: {}", market_type))),
+    }
 }
 
-pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
+pub(crate) async fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
+    match market_type {
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            zbg_swap::ZbgSwapRestClient::fetch_open_interest
+            zbg_swap::ZbgSwapRestClient::fetch_open_interest(symbol).await
         }
-        _ => panic!("ZBG {} does NOT have open interest data", market_type),
-    };
-
-    func(symbol)
+        _ => Err(Error(format!(
+            "ZBG {} does NOT have open interest data",
+            market_type
+        ))),
+    }
 }
@@ -0,0 +1,319 @@
+use std::collections::VecDeque;
+
+use crate::error::{Error, Result};
+use crate::exchanges;
+use crypto_market_type::MarketType;
+use crypto_msg_parser::KlineMsg;
+
+/// Convert an interval in seconds to the `period` of `KlineMsg`, e.g., 60 to `1m`.
+pub(crate) fn interval_to_period(interval: usize) -> String {
+    if interval % 604800 == 0 {
+        format!("{}W", interval / 604800)
+    } else if interval % 86400 == 0 {
+        format!("{}D", interval / 86400)
+    } else if interval % 3600 == 0 {
+        format!("{}H", interval / 3600)
+    } else if interval % 60 == 0 {
+        format!("{}m", interval / 60)
+    } else {
+        format!("{}s", interval)
+    }
+}
+
+/// An async iterator over historical candlesticks of a symbol within a time range.
+///
+/// The time range is split into windows no larger than what one request can
+/// return, windows are fetched lazily in ascending order of time. The
+/// iterator stops after the first error.
+pub struct KlineIterator {
+    exchange: String,
+    market_type: MarketType,
+    symbol: String,
+    interval: usize,
+    // max number of candlesticks per request
+    limit: i64,
+    // open time of the next candlestick
+    cursor: i64,
+    end_time: i64,
+    buffer: VecDeque<KlineMsg>,
+    done: bool,
+}
+
+impl KlineIterator {
+    pub(crate) fn new(
+        exchange: &str,
+        market_type: MarketType,
+        symbol: &str,
+        interval: usize,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Self> {
+        let limit = match exchange {
+            "binance" => 1000,
+            "bitfinex" => 10000,
+            "bitmex" => 1000,
+            "bitstamp" => 1000,
+            "bybit" => 200,
+            "coinbase_pro" => 300,
+            "deribit" => 1000,
+            "dydx" => 100,
+            "ftx" => 1500,
+            "gate" => 1000,
+            "huobi" => 2000,
+            "kraken" => 720,
+            "kucoin" => 1500,
+            "mxc" => 1000,
+            "okex" => 300,
+            // bitget, bithumb, bitz and zbg don't serve candlesticks within a time range
+            _ => {
+                return Err(Error(format!(
+                    "{} does NOT support paginated historical candlesticks",
+                    exchange
+                )))
+            }
+        };
+        if interval == 0 {
+            return Err(Error("interval must be greater than 0".to_string()));
+        }
+        Ok(KlineIterator {
+            exchange: exchange.to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            interval,
+            limit,
+            cursor: start_time,
+            end_time,
+            buffer: VecDeque::new(),
+            done: start_time > end_time,
+        })
+    }
+
+    // Fetch candlesticks whose open time is within [start_time, end_time], in milliseconds.
+    async fn fetch_page(&self, start_time: i64, end_time: i64) -> Result<Vec<KlineMsg>> {
+        let (market_type, symbol, interval) =
+            (self.market_type, self.symbol.as_str(), self.interval);
+        match self.exchange.as_str() {
+            "binance" => {
+                exchanges::binance::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "bitfinex" => {
+                exchanges::bitfinex::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "bitmex" => {
+                exchanges::bitmex::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "bitstamp" => {
+                exchanges::bitstamp::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "bybit" => {
+                exchanges::bybit::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "coinbase_pro" => {
+                exchanges::coinbase_pro::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "deribit" => {
+                exchanges::deribit::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "dydx" => {
+                exchanges::dydx::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "ftx" => {
+                exchanges::ftx::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "gate" => {
+                exchanges::gate::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "huobi" => {
+                exchanges::huobi::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "kraken" => {
+                exchanges::kraken::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "kucoin" => {
+                exchanges::kucoin::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "mxc" => {
+                exchanges::mxc::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            "okex" => {
+                exchanges::okex::fetch_candlesticks_page(
+                    market_type,
+                    symbol,
+                    interval,
+                    start_time,
+                    end_time,
+                )
+                .await
+            }
+            _ => Err(Error(format!(
+                "{} does NOT support paginated historical candlesticks",
+                self.exchange
+            ))),
+        }
+    }
+
+    async fn fetch_next_page(&mut self) -> Result<()> {
+        let window_end = std::cmp::min(
+            self.cursor + self.limit * (self.interval as i64) * 1000 - 1,
+            self.end_time,
+        );
+        let mut klines = self.fetch_page(self.cursor, window_end).await?;
+        // Some exchanges return candlesticks in descending order
+        klines.sort_by_key(|kline| kline.timestamp);
+        klines.dedup_by_key(|kline| kline.timestamp);
+        let cursor = self.cursor;
+        self.buffer.extend(
+            klines
+                .into_iter()
+                .filter(|kline| kline.timestamp >= cursor && kline.timestamp <= window_end),
+        );
+
+        self.cursor = window_end + 1;
+        if self.cursor > self.end_time {
+            self.done = true;
+        }
+        Ok(())
+    }
+
+    /// Returns the next candlestick, or `None` once the time range is exhausted.
+    pub async fn next(&mut self) -> Option<Result<KlineMsg>> {
+        while self.buffer.is_empty() && !self.done {
+            if let Err(err) = self.fetch_next_page().await {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+pub mod blocking {
+    use crate::error::Result;
+    use crate::exchanges::block_on;
+    use crypto_msg_parser::KlineMsg;
+
+    /// An iterator over historical candlesticks of a symbol within a time range.
+    ///
+    /// It drives the async [`crate::nonblocking::KlineIterator`], see
+    /// [`crate::fetch_candlesticks`].
+    pub struct KlineIterator(pub(crate) super::KlineIterator);
+
+    impl Iterator for KlineIterator {
+        type Item = Result<KlineMsg>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            block_on(self.0.next())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_interval_to_period() {
+        assert_eq!("1m", super::interval_to_period(60));
+        assert_eq!("15m", super::interval_to_period(900));
+        assert_eq!("4H", super::interval_to_period(14400));
+        assert_eq!("1D", super::interval_to_period(86400));
+        assert_eq!("1W", super::interval_to_period(604800));
+        assert_eq!("10s", super::interval_to_period(10));
+    }
+}
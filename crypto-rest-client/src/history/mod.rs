@@ -0,0 +1,12 @@
+mod candlestick;
+mod trade;
+
+pub(crate) use candlestick::interval_to_period;
+pub use candlestick::KlineIterator;
+pub(crate) use trade::TradeCursor;
+pub use trade::TradeIterator;
+
+pub mod blocking {
+    pub use super::candlestick::blocking::KlineIterator;
+    pub use super::trade::blocking::TradeIterator;
+}
@@ -0,0 +1,148 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::error::{Error, Result};
+use crate::exchanges;
+use crypto_market_type::MarketType;
+use crypto_msg_parser::TradeMsg;
+
+/// The position of the next page of historical trades.
+pub(crate) struct TradeCursor {
+    /// Unix timestamp in milliseconds, inclusive
+    pub start_time: i64,
+    /// Unix timestamp in milliseconds, inclusive
+    pub end_time: i64,
+    /// ID of the last received trade, used by exchanges which page by trade ID
+    pub last_id: Option<String>,
+}
+
+/// An async iterator over historical trades of a symbol within a time range.
+///
+/// Pages are fetched lazily from RESTful APIs in ascending order of time,
+/// trades seen on the previous page are skipped. The iterator stops after
+/// the first error.
+pub struct TradeIterator {
+    exchange: String,
+    market_type: MarketType,
+    symbol: String,
+    cursor: TradeCursor,
+    buffer: VecDeque<TradeMsg>,
+    // IDs of received trades whose timestamp equals cursor.start_time
+    boundary_ids: HashSet<String>,
+    done: bool,
+}
+
+impl TradeIterator {
+    pub(crate) fn new(
+        exchange: &str,
+        market_type: MarketType,
+        symbol: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Self> {
+        if !["binance", "bitfinex", "bitmex", "kraken"].contains(&exchange) {
+            return Err(Error(format!(
+                "{} does NOT support paginated historical trades",
+                exchange
+            )));
+        }
+        Ok(TradeIterator {
+            exchange: exchange.to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            cursor: TradeCursor {
+                start_time,
+                end_time,
+                last_id: None,
+            },
+            buffer: VecDeque::new(),
+            boundary_ids: HashSet::new(),
+            done: start_time > end_time,
+        })
+    }
+
+    async fn fetch_page(&self) -> Result<Vec<TradeMsg>> {
+        let (market_type, symbol, cursor) = (self.market_type, self.symbol.as_str(), &self.cursor);
+        match self.exchange.as_str() {
+            "binance" => exchanges::binance::fetch_trades_page(market_type, symbol, cursor).await,
+            "bitfinex" => exchanges::bitfinex::fetch_trades_page(market_type, symbol, cursor).await,
+            "bitmex" => exchanges::bitmex::fetch_trades_page(market_type, symbol, cursor).await,
+            "kraken" => exchanges::kraken::fetch_trades_page(market_type, symbol, cursor).await,
+            _ => Err(Error(format!(
+                "{} does NOT support paginated historical trades",
+                self.exchange
+            ))),
+        }
+    }
+
+    async fn fetch_next_page(&mut self) -> Result<()> {
+        let page = self.fetch_page().await?;
+        if page.is_empty() {
+            self.done = true;
+            return Ok(());
+        }
+        let last_timestamp = page.last().unwrap().timestamp;
+        let new_trades: Vec<TradeMsg> = page
+            .into_iter()
+            .filter(|trade| {
+                trade.timestamp >= self.cursor.start_time
+                    && !self.boundary_ids.contains(&trade.trade_id)
+            })
+            .collect();
+        if new_trades.is_empty() {
+            // A whole page of trades within one millisecond, skip to the next millisecond
+            self.cursor.start_time = last_timestamp + 1;
+            self.cursor.last_id = None;
+            self.boundary_ids.clear();
+            return Ok(());
+        }
+
+        let last_trade = new_trades.last().unwrap();
+        if last_trade.timestamp != self.cursor.start_time {
+            self.boundary_ids.clear();
+        }
+        self.cursor.start_time = last_trade.timestamp;
+        self.cursor.last_id = Some(last_trade.trade_id.clone());
+        for trade in new_trades {
+            if trade.timestamp > self.cursor.end_time {
+                self.done = true;
+                break;
+            }
+            if trade.timestamp == self.cursor.start_time {
+                self.boundary_ids.insert(trade.trade_id.clone());
+            }
+            self.buffer.push_back(trade);
+        }
+        Ok(())
+    }
+
+    /// Returns the next trade, or `None` once the time range is exhausted.
+    pub async fn next(&mut self) -> Option<Result<TradeMsg>> {
+        while self.buffer.is_empty() && !self.done {
+            if let Err(err) = self.fetch_next_page().await {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+pub mod blocking {
+    use crate::error::Result;
+    use crate::exchanges::block_on;
+    use crypto_msg_parser::TradeMsg;
+
+    /// An iterator over historical trades of a symbol within a time range.
+    ///
+    /// It drives the async [`crate::nonblocking::TradeIterator`], see
+    /// [`crate::fetch_trades`].
+    pub struct TradeIterator(pub(crate) super::TradeIterator);
+
+    impl Iterator for TradeIterator {
+        type Item = Result<TradeMsg>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            block_on(self.0.next())
+        }
+    }
+}
@@ -1,109 +1,192 @@
 mod error;
 mod exchanges;
+mod history;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+#[cfg(not(feature = "async"))]
+#[allow(unused_imports)]
+mod nonblocking;
 
+pub use crypto_http::{set_http_config, HttpConfig};
 pub use error::Error;
-pub use exchanges::binance::binance_inverse::BinanceInverseRestClient;
-pub use exchanges::binance::binance_linear::BinanceLinearRestClient;
-pub use exchanges::binance::binance_option::BinanceOptionRestClient;
-pub use exchanges::binance::binance_spot::BinanceSpotRestClient;
-pub use exchanges::bitfinex::BitfinexRestClient;
-pub use exchanges::bitget::*;
-pub use exchanges::bithumb::*;
-pub use exchanges::bitmex::BitmexRestClient;
-pub use exchanges::bitstamp::BitstampRestClient;
-pub use exchanges::bitz::*;
-pub use exchanges::bybit::BybitRestClient;
-pub use exchanges::coinbase_pro::CoinbaseProRestClient;
-pub use exchanges::deribit::DeribitRestClient;
-pub use exchanges::dydx::dydx_swap::DydxSwapRestClient;
-pub use exchanges::ftx::FtxRestClient;
-pub use exchanges::gate::*;
-pub use exchanges::huobi::huobi_future::HuobiFutureRestClient;
-pub use exchanges::huobi::huobi_inverse_swap::HuobiInverseSwapRestClient;
-pub use exchanges::huobi::huobi_linear_swap::HuobiLinearSwapRestClient;
-pub use exchanges::huobi::huobi_option::HuobiOptionRestClient;
-pub use exchanges::huobi::huobi_spot::HuobiSpotRestClient;
-pub use exchanges::kraken::KrakenRestClient;
-pub use exchanges::kucoin::*;
-pub use exchanges::mxc::mxc_spot::MxcSpotRestClient;
-pub use exchanges::mxc::mxc_swap::MxcSwapRestClient;
-pub use exchanges::okex::OkexRestClient;
-pub use exchanges::zbg::*;
+pub use exchanges::binance::binance_inverse::blocking::BinanceInverseRestClient;
+pub use exchanges::binance::binance_linear::blocking::BinanceLinearRestClient;
+pub use exchanges::binance::binance_option::blocking::BinanceOptionRestClient;
+pub use exchanges::binance::binance_spot::blocking::BinanceSpotRestClient;
+pub use exchanges::bitfinex::blocking::BitfinexRestClient;
+pub use exchanges::bitget::bitget_spot::blocking::BitgetSpotRestClient;
+pub use exchanges::bitget::bitget_swap::blocking::BitgetSwapRestClient;
+pub use exchanges::bithumb::blocking::BithumbRestClient;
+pub use exchanges::bitmex::blocking::BitmexRestClient;
+pub use exchanges::bitstamp::blocking::BitstampRestClient;
+pub use exchanges::bitz::bitz_spot::blocking::BitzSpotRestClient;
+pub use exchanges::bitz::bitz_swap::blocking::BitzSwapRestClient;
+pub use exchanges::bybit::blocking::BybitRestClient;
+pub use exchanges::coinbase_pro::blocking::CoinbaseProRestClient;
+pub use exchanges::deribit::blocking::DeribitRestClient;
+pub use exchanges::dydx::dydx_swap::blocking::DydxSwapRestClient;
+pub use exchanges::ftx::blocking::FtxRestClient;
+pub use exchanges::gate::gate_future::blocking::GateFutureRestClient;
+pub use exchanges::gate::gate_spot::blocking::GateSpotRestClient;
+pub use exchanges::gate::gate_swap::blocking::GateSwapRestClient;
+pub use exchanges::huobi::huobi_future::blocking::HuobiFutureRestClient;
+pub use exchanges::huobi::huobi_inverse_swap::blocking::HuobiInverseSwapRestClient;
+pub use exchanges::huobi::huobi_linear_swap::blocking::HuobiLinearSwapRestClient;
+pub use exchanges::huobi::huobi_option::blocking::HuobiOptionRestClient;
+pub use exchanges::huobi::huobi_spot::blocking::HuobiSpotRestClient;
+pub use exchanges::kraken::blocking::KrakenRestClient;
+pub use exchanges::kucoin::kucoin_spot::blocking::KuCoinSpotRestClient;
+pub use exchanges::kucoin::kucoin_swap::blocking::KuCoinSwapRestClient;
+pub use exchanges::mxc::mxc_spot::blocking::MxcSpotRestClient;
+pub use exchanges::mxc::mxc_swap::blocking::MxcSwapRestClient;
+pub use exchanges::okex::blocking::OkexRestClient;
+pub use exchanges::zbg::zbg_spot::blocking::ZbgSpotRestClient;
+pub use exchanges::zbg::zbg_swap::blocking::ZbgSwapRestClient;
+pub use history::blocking::{KlineIterator, TradeIterator};
 
 use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, MarkPriceMsg};
 use error::Result;
-use log::*;
-use std::time::{Duration, SystemTime};
+use exchanges::block_on;
 
-fn fetch_l2_snapshot_internal(
+/// Fetch open interest.
+///
+/// `symbol` None means fetch all symbols.
+pub fn fetch_open_interest(
     exchange: &str,
     market_type: MarketType,
-    symbol: &str,
+    symbol: Option<&str>,
 ) -> Result<String> {
-    match exchange {
-        "binance" => exchanges::binance::fetch_l2_snapshot(market_type, symbol),
-        "bitfinex" => exchanges::bitfinex::BitfinexRestClient::fetch_l2_snapshot(symbol),
-        "bitget" => exchanges::bitget::fetch_l2_snapshot(market_type, symbol),
-        "bithumb" => exchanges::bithumb::BithumbRestClient::fetch_l2_snapshot(symbol),
-        "bitmex" => exchanges::bitmex::BitmexRestClient::fetch_l2_snapshot(symbol),
-        "bitstamp" => exchanges::bitstamp::BitstampRestClient::fetch_l2_snapshot(symbol),
-        "bitz" => exchanges::bitz::fetch_l2_snapshot(market_type, symbol),
-        "bybit" => exchanges::bybit::BybitRestClient::fetch_l2_snapshot(symbol),
-        "coinbase_pro" => exchanges::coinbase_pro::CoinbaseProRestClient::fetch_l2_snapshot(symbol),
-        "deribit" => exchanges::deribit::DeribitRestClient::fetch_l2_snapshot(symbol),
-        "dydx" => exchanges::dydx::fetch_l2_snapshot(market_type, symbol),
-        "ftx" => exchanges::ftx::FtxRestClient::fetch_l2_snapshot(symbol),
-        "gate" => exchanges::gate::fetch_l2_snapshot(market_type, symbol),
-        "huobi" => exchanges::huobi::fetch_l2_snapshot(market_type, symbol),
-        "kraken" => exchanges::kraken::KrakenRestClient::fetch_l2_snapshot(symbol),
-        "kucoin" => exchanges::kucoin::fetch_l2_snapshot(market_type, symbol),
-        "mxc" => exchanges::mxc::fetch_l2_snapshot(market_type, symbol),
-        "okex" => exchanges::okex::OkexRestClient::fetch_l2_snapshot(symbol),
-        "zbg" => exchanges::zbg::fetch_l2_snapshot(market_type, symbol),
-        _ => panic!("Unknown exchange {}", exchange),
-    }
+    block_on(nonblocking::fetch_open_interest(
+        exchange,
+        market_type,
+        symbol,
+    ))
 }
 
-pub fn fetch_l3_snapshot_internal(
+/// Fetch historical trades between `start_time` and `end_time`.
+///
+/// Both timestamps are Unix milliseconds and inclusive. The returned iterator
+/// walks through the whole range page by page, so only exchanges which can page
+/// by trade ID or timestamp are supported, i.e., binance, bitfinex, bitmex and kraken.
+/// Kraken symbols should be in the format of `XBT/USD`.
+pub fn fetch_trades(
     exchange: &str,
     market_type: MarketType,
     symbol: &str,
-) -> Result<String> {
-    match exchange {
-        "bitfinex" => exchanges::bitfinex::BitfinexRestClient::fetch_l3_snapshot(symbol),
-        "bitstamp" => exchanges::bitstamp::BitstampRestClient::fetch_l3_snapshot(symbol),
-        "coinbase_pro" => exchanges::coinbase_pro::CoinbaseProRestClient::fetch_l3_snapshot(symbol),
-        "kucoin" => exchanges::kucoin::fetch_l3_snapshot(market_type, symbol),
-        _ => panic!(
-            "{} {} does NOT provide level3 orderbook data",
-            exchange, market_type
-        ),
-    }
+    start_time: i64,
+    end_time: i64,
+) -> Result<TradeIterator> {
+    nonblocking::fetch_trades(exchange, market_type, symbol, start_time, end_time)
+        .map(TradeIterator)
 }
 
-/// Fetch open interest.
+/// Fetch historical candlesticks between `start_time` and `end_time`.
 ///
-/// `symbol` None means fetch all symbols.
-pub fn fetch_open_interest(
+/// `interval` is in seconds, e.g., 60 for 1-minute candlesticks, and each exchange
+/// only accepts its own set of intervals. Both timestamps are Unix milliseconds and
+/// inclusive, and are compared with the open time of candlesticks.
+///
+/// Supported exchanges are binance, bitfinex, bitmex, bitstamp, bybit, coinbase_pro,
+/// deribit, dydx, ftx, gate, huobi (contracts only), kraken (latest 720 only),
+/// kucoin (spot only), mxc and okex.
+///
+/// Not supported are bitget, bithumb, bitz and zbg, whose RESTful APIs used
+/// by this crate only serve the latest candlesticks, without a time range.
+pub fn fetch_candlesticks(
     exchange: &str,
     market_type: MarketType,
-    symbol: Option<&str>,
-) -> Result<String> {
-    match exchange {
-        "binance" => exchanges::binance::fetch_open_interest(market_type, symbol.unwrap()),
-        "bitget" => exchanges::bitget::fetch_open_interest(market_type, symbol.unwrap()),
-        "bybit" => exchanges::bybit::BybitRestClient::fetch_open_interest(symbol.unwrap()),
-        "bitz" => exchanges::bitz::fetch_open_interest(market_type, symbol),
-        "deribit" => exchanges::deribit::DeribitRestClient::fetch_open_interest(symbol),
-        "dydx" => exchanges::dydx::fetch_open_interest(market_type),
-        "ftx" => exchanges::ftx::FtxRestClient::fetch_open_interest(),
-        "gate" => exchanges::gate::fetch_open_interest(market_type, symbol.unwrap()),
-        "huobi" => exchanges::huobi::fetch_open_interest(market_type, symbol),
-        "kucoin" => exchanges::kucoin::fetch_open_interest(market_type),
-        "okex" => exchanges::okex::OkexRestClient::fetch_open_interest(symbol.unwrap()),
-        "zbg" => exchanges::zbg::fetch_open_interest(market_type, symbol.unwrap()),
-        _ => panic!("{} does NOT have open interest RESTful API", exchange),
-    }
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<KlineIterator> {
+    nonblocking::fetch_candlesticks(
+        exchange,
+        market_type,
+        symbol,
+        interval,
+        start_time,
+        end_time,
+    )
+    .map(KlineIterator)
+}
+
+/// Fetch historical funding rates of perpetual swaps between `start_time` and `end_time`.
+///
+/// Both timestamps are Unix milliseconds and inclusive, funding rates are sorted
+/// by `funding_time` in ascending order.
+///
+/// Supported exchanges are binance, bitmex, bybit, deribit, dydx, ftx, huobi and okex,
+/// while okex only serves the latest 100 funding rates.
+pub fn fetch_funding_rates(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    block_on(nonblocking::fetch_funding_rates(
+        exchange,
+        market_type,
+        symbol,
+        start_time,
+        end_time,
+    ))
+}
+
+/// Fetch historical mark prices between `start_time` and `end_time`.
+///
+/// `interval` is in seconds, each returned `MarkPriceMsg` is the close of a mark price
+/// candlestick, along with the premium index if the exchange publishes it.
+/// Both timestamps are Unix milliseconds and inclusive, and are compared with
+/// the open time of candlesticks.
+///
+/// Supported exchanges are binance, bybit, huobi and okex, while huobi only serves
+/// the latest 2000 candlesticks. BitMEX, dYdX and FTX don't publish historical mark prices.
+pub fn fetch_mark_prices(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    block_on(nonblocking::fetch_mark_prices(
+        exchange,
+        market_type,
+        symbol,
+        interval,
+        start_time,
+        end_time,
+    ))
+}
+
+/// Fetch historical index prices between `start_time` and `end_time`.
+///
+/// `interval` is in seconds, each returned `MarkPriceMsg` is the close of an index price
+/// candlestick. Both timestamps are Unix milliseconds and inclusive, and are compared
+/// with the open time of candlesticks.
+///
+/// Supported exchanges are binance, bitmex, bybit, dydx, ftx and okex.
+/// dYdX publishes index prices along with hourly funding rates, so its `interval`
+/// must be 3600. Huobi doesn't publish historical index prices.
+pub fn fetch_index_prices(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    block_on(nonblocking::fetch_index_prices(
+        exchange,
+        market_type,
+        symbol,
+        interval,
+        start_time,
+        end_time,
+    ))
 }
 
 /// Fetch level2 orderbook snapshot.
@@ -115,13 +198,12 @@ pub fn fetch_l2_snapshot(
     symbol: &str,
     retry: Option<u64>,
 ) -> Result<String> {
-    retriable(
+    block_on(nonblocking::fetch_l2_snapshot(
         exchange,
         market_type,
         symbol,
-        fetch_l2_snapshot_internal,
         retry,
-    )
+    ))
 }
 
 /// Fetch level3 orderbook snapshot.
@@ -133,67 +215,10 @@ pub fn fetch_l3_snapshot(
     symbol: &str,
     retry: Option<u64>,
 ) -> Result<String> {
-    retriable(
+    block_on(nonblocking::fetch_l3_snapshot(
         exchange,
         market_type,
         symbol,
-        fetch_l3_snapshot_internal,
         retry,
-    )
-}
-
-// `retry` None means no retry; Some(0) means retry unlimited times; Some(n) means retry n times.
-fn retriable(
-    exchange: &str,
-    market_type: MarketType,
-    symbol: &str,
-    crawl_func: fn(&str, MarketType, &str) -> Result<String>,
-    retry: Option<u64>,
-) -> Result<String> {
-    let retry_count = {
-        let count = retry.unwrap_or(1);
-        if count == 0 {
-            u64::MAX
-        } else {
-            count
-        }
-    };
-    if retry_count == 1 {
-        return crawl_func(exchange, market_type, symbol);
-    }
-    let mut backoff_factor = 0;
-    let cooldown_time = Duration::from_secs(2);
-    for _ in 0..retry_count {
-        let resp = crawl_func(exchange, market_type, symbol);
-        match resp {
-            Ok(msg) => return Ok(msg),
-            Err(err) => {
-                let current_timestamp = SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64;
-                warn!(
-                    "{} {} {} {} {}, error: {}, back off for {} milliseconds",
-                    current_timestamp,
-                    backoff_factor,
-                    exchange,
-                    market_type,
-                    symbol,
-                    err,
-                    (backoff_factor * cooldown_time).as_millis()
-                );
-                std::thread::sleep(backoff_factor * cooldown_time);
-                if err.0.contains("429") {
-                    backoff_factor += 1;
-                } else {
-                    // Handle 403, 418, etc.
-                    backoff_factor *= 2;
-                }
-            }
-        }
-    }
-    Err(Error(format!(
-        "Failed {} {} {} after retrying {} times",
-        exchange, market_type, symbol, retry_count
-    )))
+    ))
 }
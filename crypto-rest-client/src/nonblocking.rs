@@ -0,0 +1,429 @@
+//! Async versions of crate-level APIs and RESTful clients, enabled by the
+//! `async` feature.
+//!
+//! All requests share a pooled `reqwest::Client` and must run within a tokio
+//! runtime. The blocking APIs at the crate root drive these functions on an
+//! internal runtime with its own client. Without the `async` feature, they
+//! send requests through the blocking client instead, so tokio isn't needed.
+
+use crate::error::{Error, Result};
+use crate::exchanges;
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{FundingRateMsg, MarkPriceMsg};
+use log::*;
+use std::time::{Duration, SystemTime};
+
+pub use crate::exchanges::binance::binance_inverse::BinanceInverseRestClient;
+pub use crate::exchanges::binance::binance_linear::BinanceLinearRestClient;
+pub use crate::exchanges::binance::binance_option::BinanceOptionRestClient;
+pub use crate::exchanges::binance::binance_spot::BinanceSpotRestClient;
+pub use crate::exchanges::bitfinex::BitfinexRestClient;
+pub use crate::exchanges::bitget::bitget_spot::BitgetSpotRestClient;
+pub use crate::exchanges::bitget::bitget_swap::BitgetSwapRestClient;
+pub use crate::exchanges::bithumb::BithumbRestClient;
+pub use crate::exchanges::bitmex::BitmexRestClient;
+pub use crate::exchanges::bitstamp::BitstampRestClient;
+pub use crate::exchanges::bitz::bitz_spot::BitzSpotRestClient;
+pub use crate::exchanges::bitz::bitz_swap::BitzSwapRestClient;
+pub use crate::exchanges::bybit::BybitRestClient;
+pub use crate::exchanges::coinbase_pro::CoinbaseProRestClient;
+pub use crate::exchanges::deribit::DeribitRestClient;
+pub use crate::exchanges::dydx::dydx_swap::DydxSwapRestClient;
+pub use crate::exchanges::ftx::FtxRestClient;
+pub use crate::exchanges::gate::gate_future::GateFutureRestClient;
+pub use crate::exchanges::gate::gate_spot::GateSpotRestClient;
+pub use crate::exchanges::gate::gate_swap::GateSwapRestClient;
+pub use crate::exchanges::huobi::huobi_future::HuobiFutureRestClient;
+pub use crate::exchanges::huobi::huobi_inverse_swap::HuobiInverseSwapRestClient;
+pub use crate::exchanges::huobi::huobi_linear_swap::HuobiLinearSwapRestClient;
+pub use crate::exchanges::huobi::huobi_option::HuobiOptionRestClient;
+pub use crate::exchanges::huobi::huobi_spot::HuobiSpotRestClient;
+pub use crate::exchanges::kraken::KrakenRestClient;
+pub use crate::exchanges::kucoin::kucoin_spot::KuCoinSpotRestClient;
+pub use crate::exchanges::kucoin::kucoin_swap::KuCoinSwapRestClient;
+pub use crate::exchanges::mxc::mxc_spot::MxcSpotRestClient;
+pub use crate::exchanges::mxc::mxc_swap::MxcSwapRestClient;
+pub use crate::exchanges::okex::OkexRestClient;
+pub use crate::exchanges::zbg::zbg_spot::ZbgSpotRestClient;
+pub use crate::exchanges::zbg::zbg_swap::ZbgSwapRestClient;
+pub use crate::history::{KlineIterator, TradeIterator};
+
+// Orderbook snapshots which can be retried by `retriable()`.
+#[derive(Clone, Copy)]
+enum Snapshot {
+    L2,
+    L3,
+}
+
+async fn fetch_l2_snapshot_internal(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+) -> Result<String> {
+    match exchange {
+        "binance" => exchanges::binance::fetch_l2_snapshot(market_type, symbol).await,
+        "bitfinex" => BitfinexRestClient::fetch_l2_snapshot(symbol).await,
+        "bitget" => exchanges::bitget::fetch_l2_snapshot(market_type, symbol).await,
+        "bithumb" => BithumbRestClient::fetch_l2_snapshot(symbol).await,
+        "bitmex" => BitmexRestClient::fetch_l2_snapshot(symbol).await,
+        "bitstamp" => BitstampRestClient::fetch_l2_snapshot(symbol).await,
+        "bitz" => exchanges::bitz::fetch_l2_snapshot(market_type, symbol).await,
+        "bybit" => BybitRestClient::fetch_l2_snapshot(symbol).await,
+        "coinbase_pro" => CoinbaseProRestClient::fetch_l2_snapshot(symbol).await,
+        "deribit" => DeribitRestClient::fetch_l2_snapshot(symbol).await,
+        "dydx" => exchanges::dydx::fetch_l2_snapshot(market_type, symbol).await,
+        "ftx" => FtxRestClient::fetch_l2_snapshot(symbol).await,
+        "gate" => exchanges::gate::fetch_l2_snapshot(market_type, symbol).await,
+        "huobi" => exchanges::huobi::fetch_l2_snapshot(market_type, symbol).await,
+        "kraken" => KrakenRestClient::fetch_l2_snapshot(symbol).await,
+        "kucoin" => exchanges::kucoin::fetch_l2_snapshot(market_type, symbol).await,
+        "mxc" => exchanges::mxc::fetch_l2_snapshot(market_type, symbol).await,
+        "okex" => OkexRestClient::fetch_l2_snapshot(symbol).await,
+        "zbg" => exchanges::zbg::fetch_l2_snapshot(market_type, symbol).await,
+        _ => Err(Error(format!("Unknown exchange {}", exchange))),
+    }
+}
+
+async fn fetch_l3_snapshot_internal(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+) -> Result<String> {
+    match exchange {
+        "bitfinex" => BitfinexRestClient::fetch_l3_snapshot(symbol).await,
+        "bitstamp" => BitstampRestClient::fetch_l3_snapshot(symbol).await,
+        "coinbase_pro" => CoinbaseProRestClient::fetch_l3_snapshot(symbol).await,
+        "kucoin" => exchanges::kucoin::fetch_l3_snapshot(market_type, symbol).await,
+        _ => Err(Error(format!(
+            "{} {} does NOT provide level3 orderbook data",
+            exchange, market_type
+        ))),
+    }
+}
+
+/// Fetch open interest.
+///
+/// `symbol` None means fetch all symbols, not all exchanges support this.
+pub async fn fetch_open_interest(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: Option<&str>,
+) -> Result<String> {
+    let required_symbol = || {
+        symbol.ok_or_else(|| {
+            Error(format!(
+                "{} {} open interest requires a symbol",
+                exchange, market_type
+            ))
+        })
+    };
+    match exchange {
+        "binance" => exchanges::binance::fetch_open_interest(market_type, required_symbol()?).await,
+        "bitget" => exchanges::bitget::fetch_open_interest(market_type, required_symbol()?).await,
+        "bybit" => BybitRestClient::fetch_open_interest(required_symbol()?).await,
+        "bitz" => exchanges::bitz::fetch_open_interest(market_type, symbol).await,
+        "deribit" => DeribitRestClient::fetch_open_interest(symbol).await,
+        "dydx" => exchanges::dydx::fetch_open_interest(market_type).await,
+        "ftx" => FtxRestClient::fetch_open_interest().await,
+        "gate" => exchanges::gate::fetch_open_interest(market_type, required_symbol()?).await,
+        "huobi" => exchanges::huobi::fetch_open_interest(market_type, symbol).await,
+        "kucoin" => exchanges::kucoin::fetch_open_interest(market_type).await,
+        "okex" => OkexRestClient::fetch_open_interest(required_symbol()?).await,
+        "zbg" => exchanges::zbg::fetch_open_interest(market_type, required_symbol()?).await,
+        _ => Err(Error(format!(
+            "{} does NOT have open interest RESTful API",
+            exchange
+        ))),
+    }
+}
+
+/// Fetch historical trades between `start_time` and `end_time`.
+///
+/// See [`crate::fetch_trades`], the returned iterator fetches pages asynchronously.
+pub fn fetch_trades(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<TradeIterator> {
+    TradeIterator::new(exchange, market_type, symbol, start_time, end_time)
+}
+
+/// Fetch historical candlesticks between `start_time` and `end_time`.
+///
+/// See [`crate::fetch_candlesticks`], the returned iterator fetches pages asynchronously.
+pub fn fetch_candlesticks(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<KlineIterator> {
+    KlineIterator::new(
+        exchange,
+        market_type,
+        symbol,
+        interval,
+        start_time,
+        end_time,
+    )
+}
+
+/// Fetch historical funding rates of perpetual swaps between `start_time` and `end_time`.
+///
+/// See [`crate::fetch_funding_rates`].
+pub async fn fetch_funding_rates(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<FundingRateMsg>> {
+    let mut funding_rates = match exchange {
+        "binance" => {
+            exchanges::binance::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        "bitmex" => {
+            exchanges::bitmex::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        "bybit" => {
+            exchanges::bybit::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        "deribit" => {
+            exchanges::deribit::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        "dydx" => {
+            exchanges::dydx::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        "ftx" => {
+            exchanges::ftx::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        "huobi" => {
+            exchanges::huobi::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        "okex" => {
+            exchanges::okex::fetch_funding_rates(market_type, symbol, start_time, end_time).await
+        }
+        _ => Err(Error(format!(
+            "{} does NOT support historical funding rates",
+            exchange
+        ))),
+    }?;
+    funding_rates.retain(|x| x.funding_time >= start_time && x.funding_time <= end_time);
+    funding_rates.sort_by_key(|x| x.funding_time);
+    funding_rates.dedup_by_key(|x| x.funding_time);
+    Ok(funding_rates)
+}
+
+/// Fetch historical mark prices between `start_time` and `end_time`.
+///
+/// See [`crate::fetch_mark_prices`].
+pub async fn fetch_mark_prices(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    let mark_prices = match exchange {
+        "binance" => {
+            exchanges::binance::fetch_mark_prices(
+                market_type,
+                symbol,
+                interval,
+                start_time,
+                end_time,
+            )
+            .await
+        }
+        "bybit" => {
+            exchanges::bybit::fetch_mark_prices(market_type, symbol, interval, start_time, end_time)
+                .await
+        }
+        "huobi" => {
+            exchanges::huobi::fetch_mark_prices(market_type, symbol, interval, start_time, end_time)
+                .await
+        }
+        "okex" => {
+            exchanges::okex::fetch_mark_prices(market_type, symbol, interval, start_time, end_time)
+                .await
+        }
+        _ => Err(Error(format!(
+            "{} does NOT support historical mark prices",
+            exchange
+        ))),
+    }?;
+    Ok(sort_price_msgs(mark_prices, start_time, end_time))
+}
+
+/// Fetch historical index prices between `start_time` and `end_time`.
+///
+/// See [`crate::fetch_index_prices`].
+pub async fn fetch_index_prices(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MarkPriceMsg>> {
+    let index_prices = match exchange {
+        "binance" => {
+            exchanges::binance::fetch_index_prices(
+                market_type,
+                symbol,
+                interval,
+                start_time,
+                end_time,
+            )
+            .await
+        }
+        "bybit" => {
+            exchanges::bybit::fetch_index_prices(
+                market_type,
+                symbol,
+                interval,
+                start_time,
+                end_time,
+            )
+            .await
+        }
+        "bitmex" => {
+            exchanges::bitmex::fetch_index_prices(
+                market_type,
+                symbol,
+                interval,
+                start_time,
+                end_time,
+            )
+            .await
+        }
+        "dydx" => {
+            exchanges::dydx::fetch_index_prices(market_type, symbol, interval, start_time, end_time)
+                .await
+        }
+        "ftx" => {
+            exchanges::ftx::fetch_index_prices(market_type, symbol, interval, start_time, end_time)
+                .await
+        }
+        "okex" => {
+            exchanges::okex::fetch_index_prices(market_type, symbol, interval, start_time, end_time)
+                .await
+        }
+        _ => Err(Error(format!(
+            "{} does NOT support historical index prices",
+            exchange
+        ))),
+    }?;
+    Ok(sort_price_msgs(index_prices, start_time, end_time))
+}
+
+fn sort_price_msgs(
+    mut msgs: Vec<MarkPriceMsg>,
+    start_time: i64,
+    end_time: i64,
+) -> Vec<MarkPriceMsg> {
+    msgs.retain(|x| x.timestamp >= start_time && x.timestamp <= end_time);
+    msgs.sort_by_key(|x| x.timestamp);
+    msgs.dedup_by_key(|x| x.timestamp);
+    msgs
+}
+
+/// Fetch level2 orderbook snapshot.
+///
+/// `retry` None means no retry; Some(0) means retry unlimited times; Some(n) means retry n times.
+pub async fn fetch_l2_snapshot(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    retry: Option<u64>,
+) -> Result<String> {
+    retriable(exchange, market_type, symbol, Snapshot::L2, retry).await
+}
+
+/// Fetch level3 orderbook snapshot.
+///
+/// `retry` None means no retry; Some(0) means retry unlimited times; Some(n) means retry n times.
+pub async fn fetch_l3_snapshot(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    retry: Option<u64>,
+) -> Result<String> {
+    retriable(exchange, market_type, symbol, Snapshot::L3, retry).await
+}
+
+async fn fetch_snapshot(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    snapshot: Snapshot,
+) -> Result<String> {
+    match snapshot {
+        Snapshot::L2 => fetch_l2_snapshot_internal(exchange, market_type, symbol).await,
+        Snapshot::L3 => fetch_l3_snapshot_internal(exchange, market_type, symbol).await,
+    }
+}
+
+// `retry` None means no retry; Some(0) means retry unlimited times; Some(n) means retry n times.
+async fn retriable(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    snapshot: Snapshot,
+    retry: Option<u64>,
+) -> Result<String> {
+    // an unknown exchange never succeeds, don't retry it
+    if exchange.parse::<crypto_market_type::Exchange>().is_err() {
+        return Err(Error(format!("Unknown exchange {}", exchange)));
+    }
+    let retry_count = {
+        let count = retry.unwrap_or(1);
+        if count == 0 {
+            u64::MAX
+        } else {
+            count
+        }
+    };
+    if retry_count == 1 {
+        return fetch_snapshot(exchange, market_type, symbol, snapshot).await;
+    }
+    let mut backoff_factor = 0;
+    let cooldown_time = Duration::from_secs(2);
+    for _ in 0..retry_count {
+        let resp = fetch_snapshot(exchange, market_type, symbol, snapshot).await;
+        match resp {
+            Ok(msg) => return Ok(msg),
+            Err(err) => {
+                let current_timestamp = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis() as u64;
+                warn!(
+                    "{} {} {} {} {}, error: {}, back off for {} milliseconds",
+                    current_timestamp,
+                    backoff_factor,
+                    exchange,
+                    market_type,
+                    symbol,
+                    err,
+                    (backoff_factor * cooldown_time).as_millis()
+                );
+                crate::exchanges::sleep(backoff_factor * cooldown_time).await;
+                if err.0.contains("429") {
+                    backoff_factor += 1;
+                } else {
+                    // Handle 403, 418, etc.
+                    backoff_factor *= 2;
+                }
+            }
+        }
+    }
+    Err(Error(format!(
+        "Failed {} {} {} after retrying {} times",
+        exchange, market_type, symbol, retry_count
+    )))
+}
@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod linear_swap {
     use crypto_market_type::MarketType;
-    use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest, BinanceLinearRestClient};
+    use crypto_msg_type::MessageType;
+    use crypto_rest_client::{
+        fetch_funding_rates, fetch_index_prices, fetch_l2_snapshot, fetch_mark_prices,
+        fetch_open_interest, BinanceLinearRestClient,
+    };
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn test_agg_trades() {
@@ -21,6 +26,65 @@ mod linear_swap {
         let text = fetch_open_interest("binance", MarketType::LinearSwap, Some("BTCUSDT")).unwrap();
         assert!(text.starts_with("{"));
     }
+
+    #[test]
+    fn test_funding_rates() {
+        let end_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        // 3 funding rates per day
+        let start_time = end_time - 30 * 24 * 3600 * 1000;
+        let funding_rates = fetch_funding_rates(
+            "binance",
+            MarketType::LinearSwap,
+            "BTCUSDT",
+            start_time,
+            end_time,
+        )
+        .unwrap();
+        assert!(funding_rates.len() >= 89);
+        for window in funding_rates.windows(2) {
+            assert!(window[0].funding_time < window[1].funding_time);
+        }
+        assert_eq!(MessageType::FundingRate, funding_rates[0].msg_type);
+        assert_eq!("BTC/USDT", funding_rates[0].pair);
+    }
+
+    #[test]
+    fn test_mark_and_index_prices() {
+        let end_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let start_time = end_time - 2000 * 60 * 1000;
+        let mark_prices = fetch_mark_prices(
+            "binance",
+            MarketType::LinearSwap,
+            "BTCUSDT",
+            60,
+            start_time,
+            end_time,
+        )
+        .unwrap();
+        assert!(mark_prices.len() > 1500);
+        assert_eq!(MessageType::MarkPrice, mark_prices[0].msg_type);
+        assert!(mark_prices[0].mark_price.unwrap() > 0.0);
+        assert!(mark_prices[0].premium_index.is_some());
+
+        let index_prices = fetch_index_prices(
+            "binance",
+            MarketType::LinearSwap,
+            "BTCUSDT",
+            60,
+            start_time,
+            end_time,
+        )
+        .unwrap();
+        assert!(index_prices.len() > 1500);
+        assert_eq!(MessageType::IndexPrice, index_prices[0].msg_type);
+        assert!(index_prices[0].index_price.unwrap() > 0.0);
+    }
 }
 
 #[cfg(test)]
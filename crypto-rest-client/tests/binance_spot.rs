@@ -1,5 +1,8 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, BinanceSpotRestClient};
+use crypto_rest_client::{
+    fetch_candlesticks, fetch_l2_snapshot, fetch_trades, BinanceSpotRestClient,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[test]
 fn test_agg_trades() {
@@ -7,6 +10,55 @@ fn test_agg_trades() {
     assert!(text.starts_with("[{"));
 }
 
+#[test]
+fn test_historical_trades() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 2 * 3600 * 1000;
+    let trades = fetch_trades("binance", MarketType::Spot, "BTCUSDT", start_time, end_time)
+        .unwrap()
+        .map(|trade| trade.unwrap())
+        .collect::<Vec<_>>();
+    assert!(trades.len() > 1000);
+    for window in trades.windows(2) {
+        assert!(window[0].timestamp <= window[1].timestamp);
+        assert!(
+            window[0].trade_id.parse::<u64>().unwrap() < window[1].trade_id.parse::<u64>().unwrap()
+        );
+    }
+    assert!(trades[0].timestamp >= start_time);
+    assert!(trades[trades.len() - 1].timestamp <= end_time);
+}
+
+#[test]
+fn test_candlesticks() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 3000 * 60 * 1000;
+    let klines = fetch_candlesticks(
+        "binance",
+        MarketType::Spot,
+        "BTCUSDT",
+        60,
+        start_time,
+        end_time,
+    )
+    .unwrap()
+    .map(|kline| kline.unwrap())
+    .collect::<Vec<_>>();
+    assert!(klines.len() > 2000);
+    for window in klines.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert!(klines[0].timestamp >= start_time);
+    assert!(klines[klines.len() - 1].timestamp <= end_time);
+    assert_eq!("1m", klines[0].period);
+}
+
 #[test]
 fn test_l2_snapshot() {
     let text = fetch_l2_snapshot("binance", MarketType::Spot, "BTCUSDT", Some(3)).unwrap();
@@ -1,5 +1,11 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, BitmexRestClient};
+use crypto_msg_type::MessageType;
+use crypto_rest_client::{
+    fetch_candlesticks, fetch_funding_rates, fetch_index_prices, fetch_l2_snapshot, fetch_trades,
+    BitmexRestClient,
+};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[test]
 fn test_trades() {
@@ -7,8 +13,107 @@ fn test_trades() {
     assert!(text.starts_with("[{"));
 }
 
+#[test]
+fn test_historical_trades() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 3600 * 1000;
+    let trades = fetch_trades(
+        "bitmex",
+        MarketType::InverseSwap,
+        "XBTUSD",
+        start_time,
+        end_time,
+    )
+    .unwrap()
+    .map(|trade| trade.unwrap())
+    .collect::<Vec<_>>();
+    assert!(!trades.is_empty());
+    let trade_ids: HashSet<&String> = trades.iter().map(|trade| &trade.trade_id).collect();
+    assert_eq!(trade_ids.len(), trades.len());
+    for window in trades.windows(2) {
+        assert!(window[0].timestamp <= window[1].timestamp);
+    }
+}
+
+#[test]
+fn test_candlesticks() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 2000 * 60 * 1000;
+    let klines = fetch_candlesticks(
+        "bitmex",
+        MarketType::InverseSwap,
+        "XBTUSD",
+        60,
+        start_time,
+        end_time,
+    )
+    .unwrap()
+    .map(|kline| kline.unwrap())
+    .collect::<Vec<_>>();
+    assert!(klines.len() > 1000);
+    for window in klines.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert!(klines[0].timestamp >= start_time);
+    assert!(klines[klines.len() - 1].timestamp <= end_time);
+    assert_eq!("1m", klines[0].period);
+}
+
+#[test]
+fn test_funding_rates() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    // 3 funding rates per day, more than one page
+    let start_time = end_time - 200 * 24 * 3600 * 1000;
+    let funding_rates = fetch_funding_rates(
+        "bitmex",
+        MarketType::InverseSwap,
+        "XBTUSD",
+        start_time,
+        end_time,
+    )
+    .unwrap();
+    assert!(funding_rates.len() >= 599);
+    for window in funding_rates.windows(2) {
+        assert!(window[0].funding_time < window[1].funding_time);
+    }
+}
+
 #[test]
 fn test_l2_snapshot() {
     let text = fetch_l2_snapshot("bitmex", MarketType::InverseSwap, "XBTUSD", Some(3)).unwrap();
     assert!(text.starts_with("[{"));
 }
+
+#[test]
+fn test_index_prices() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 1500 * 60 * 1000;
+    let index_prices = fetch_index_prices(
+        "bitmex",
+        MarketType::InverseSwap,
+        "XBTUSD",
+        60,
+        start_time,
+        end_time,
+    )
+    .unwrap();
+    assert!(index_prices.len() > 1400);
+    for window in index_prices.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert_eq!(MessageType::IndexPrice, index_prices[0].msg_type);
+    assert_eq!("BTC/USD", index_prices[0].pair);
+    assert!(index_prices[0].index_price.unwrap() > 0.0);
+}
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest};
+use crypto_msg_type::MessageType;
+use crypto_rest_client::{fetch_funding_rates, fetch_l2_snapshot, fetch_open_interest};
 use serde_json::Value;
 use test_case::test_case;
 
@@ -29,3 +31,21 @@ fn test_open_interest(market_type: MarketType, symbol: &str) {
 
     assert!(!result.is_empty());
 }
+
+#[test_case(MarketType::InverseSwap, "BTCUSD")]
+#[test_case(MarketType::LinearSwap, "BTCUSDT")]
+fn test_funding_rates(market_type: MarketType, symbol: &str) {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    // 3 funding rates per day, more than one page
+    let start_time = end_time - 90 * 24 * 3600 * 1000;
+    let funding_rates =
+        fetch_funding_rates("bybit", market_type, symbol, start_time, end_time).unwrap();
+    assert!(funding_rates.len() >= 269);
+    for window in funding_rates.windows(2) {
+        assert!(window[0].funding_time < window[1].funding_time);
+    }
+    assert_eq!(MessageType::FundingRate, funding_rates[0].msg_type);
+}
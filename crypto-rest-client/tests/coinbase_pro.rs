@@ -1,5 +1,8 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_l3_snapshot, CoinbaseProRestClient};
+use crypto_rest_client::{
+    fetch_candlesticks, fetch_l2_snapshot, fetch_l3_snapshot, CoinbaseProRestClient,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[test]
 fn test_trades() {
@@ -7,6 +10,33 @@ fn test_trades() {
     assert!(text.starts_with("[{"));
 }
 
+#[test]
+fn test_candlesticks() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 600 * 60 * 1000;
+    let klines = fetch_candlesticks(
+        "coinbase_pro",
+        MarketType::Spot,
+        "BTC-USD",
+        60,
+        start_time,
+        end_time,
+    )
+    .unwrap()
+    .map(|kline| kline.unwrap())
+    .collect::<Vec<_>>();
+    assert!(klines.len() > 300);
+    for window in klines.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert!(klines[0].timestamp >= start_time);
+    assert!(klines[klines.len() - 1].timestamp <= end_time);
+    assert_eq!("1m", klines[0].period);
+}
+
 #[test]
 fn test_l2_snapshot() {
     let text = fetch_l2_snapshot("coinbase_pro", MarketType::Spot, "BTC-USD", Some(3)).unwrap();
@@ -1,7 +1,9 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest};
+use crypto_msg_type::MessageType;
+use crypto_rest_client::{fetch_index_prices, fetch_l2_snapshot, fetch_open_interest};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 use test_case::test_case;
 
 #[test_case(MarketType::LinearSwap, "BTC-USD")]
@@ -22,3 +24,28 @@ fn test_open_interest(market_type: MarketType) {
     let obj = serde_json::from_str::<HashMap<String, Value>>(&text).unwrap();
     assert!(obj.contains_key("markets"));
 }
+
+#[test]
+fn test_index_prices() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    // one index price per hour, more than one page
+    let start_time = end_time - 10 * 24 * 3600 * 1000;
+    let index_prices = fetch_index_prices(
+        "dydx",
+        MarketType::LinearSwap,
+        "BTC-USD",
+        3600,
+        start_time,
+        end_time,
+    )
+    .unwrap();
+    assert!(index_prices.len() >= 239);
+    for window in index_prices.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert_eq!(MessageType::IndexPrice, index_prices[0].msg_type);
+    assert!(index_prices[0].index_price.unwrap() > 0.0);
+}
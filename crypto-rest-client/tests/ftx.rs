@@ -1,7 +1,8 @@
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest};
+use crypto_rest_client::{fetch_candlesticks, fetch_l2_snapshot, fetch_open_interest};
 use serde_json::Value;
 use test_case::test_case;
 
@@ -27,3 +28,23 @@ fn test_open_interest() {
     let result = obj.get("result").unwrap().as_array().unwrap();
     assert!(!result.is_empty())
 }
+
+#[test_case(MarketType::Spot, "BTC/USD")]
+#[test_case(MarketType::LinearSwap, "BTC-PERP")]
+fn test_candlesticks(market_type: MarketType, symbol: &str) {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 2000 * 60 * 1000;
+    let klines = fetch_candlesticks("ftx", market_type, symbol, 60, start_time, end_time)
+        .unwrap()
+        .map(|kline| kline.unwrap())
+        .collect::<Vec<_>>();
+    assert!(klines.len() > 1500);
+    for window in klines.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert!(klines[0].timestamp >= start_time);
+    assert_eq!("1m", klines[0].period);
+}
@@ -1,7 +1,8 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest};
+use crypto_rest_client::{fetch_candlesticks, fetch_l2_snapshot, fetch_open_interest};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 use test_case::test_case;
 
 #[test_case(MarketType::Spot, "BTC_USDT")]
@@ -26,3 +27,23 @@ fn test_open_interest(market_type: MarketType, symbol: &str) {
     let arr = serde_json::from_str::<Vec<Value>>(&text).unwrap();
     assert!(!arr.is_empty());
 }
+
+#[test_case(MarketType::Spot, "BTC_USDT")]
+#[test_case(MarketType::LinearSwap, "BTC_USDT")]
+fn test_candlesticks(market_type: MarketType, symbol: &str) {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 1500 * 60 * 1000;
+    let klines = fetch_candlesticks("gate", market_type, symbol, 60, start_time, end_time)
+        .unwrap()
+        .map(|kline| kline.unwrap())
+        .collect::<Vec<_>>();
+    assert!(klines.len() > 1000);
+    for window in klines.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert!(klines[0].timestamp >= start_time);
+    assert_eq!("1m", klines[0].period);
+}
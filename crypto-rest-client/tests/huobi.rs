@@ -1,7 +1,9 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest};
+use crypto_msg_type::MessageType;
+use crypto_rest_client::{fetch_l2_snapshot, fetch_mark_prices, fetch_open_interest};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 use test_case::test_case;
 
 #[test_case(MarketType::Spot, "btcusdt")]
@@ -57,6 +59,26 @@ fn test_open_interest(market_type: MarketType) {
     assert!(!arr.is_empty());
 }
 
+#[test_case(MarketType::InverseSwap, "BTC-USD")]
+#[test_case(MarketType::LinearSwap, "BTC-USDT")]
+fn test_mark_prices(market_type: MarketType, symbol: &str) {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    // only the latest 2000 candlesticks are served
+    let start_time = end_time - 1000 * 60 * 1000;
+    let mark_prices =
+        fetch_mark_prices("huobi", market_type, symbol, 60, start_time, end_time).unwrap();
+    assert!(mark_prices.len() > 900);
+    for window in mark_prices.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert_eq!(MessageType::MarkPrice, mark_prices[0].msg_type);
+    assert!(mark_prices[0].mark_price.unwrap() > 0.0);
+    assert!(mark_prices[0].premium_index.is_some());
+}
+
 #[cfg(test)]
 mod huobi_spot {
     use crypto_rest_client::HuobiSpotRestClient;
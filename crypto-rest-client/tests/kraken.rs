@@ -1,5 +1,7 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, KrakenRestClient};
+use crypto_rest_client::{fetch_l2_snapshot, fetch_trades, KrakenRestClient};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[test]
 fn test_trades() {
@@ -7,6 +9,26 @@ fn test_trades() {
     assert!(text.starts_with("{"));
 }
 
+#[test]
+fn test_historical_trades() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 3600 * 1000;
+    let trades = fetch_trades("kraken", MarketType::Spot, "XBT/USD", start_time, end_time)
+        .unwrap()
+        .map(|trade| trade.unwrap())
+        .collect::<Vec<_>>();
+    assert!(!trades.is_empty());
+    let trade_ids: HashSet<&String> = trades.iter().map(|trade| &trade.trade_id).collect();
+    assert_eq!(trade_ids.len(), trades.len());
+    for window in trades.windows(2) {
+        assert!(window[0].timestamp <= window[1].timestamp);
+    }
+    assert!(trades[0].timestamp >= start_time);
+}
+
 #[test]
 fn test_l2_snapshot() {
     let text = fetch_l2_snapshot("kraken", MarketType::Spot, "XXBTZUSD", Some(3)).unwrap();
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod mxc_spot {
-    use crypto_rest_client::MxcSpotRestClient;
+    use crypto_market_type::MarketType;
+    use crypto_rest_client::{fetch_candlesticks, MxcSpotRestClient};
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn test_trades() {
@@ -13,12 +15,38 @@ mod mxc_spot {
         let text = MxcSpotRestClient::fetch_l2_snapshot("BTC_USDT").unwrap();
         assert!(text.starts_with("{"));
     }
+
+    #[test]
+    fn test_candlesticks() {
+        let end_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let start_time = end_time - 1500 * 60 * 1000;
+        let klines = fetch_candlesticks(
+            "mxc",
+            MarketType::Spot,
+            "BTC_USDT",
+            60,
+            start_time,
+            end_time,
+        )
+        .unwrap()
+        .map(|kline| kline.unwrap())
+        .collect::<Vec<_>>();
+        assert!(klines.len() > 1000);
+        for window in klines.windows(2) {
+            assert!(window[0].timestamp < window[1].timestamp);
+        }
+        assert!(klines[0].timestamp >= start_time);
+    }
 }
 
 #[cfg(test)]
 mod mxc_swap {
     use crypto_market_type::MarketType;
-    use crypto_rest_client::{fetch_l2_snapshot, MxcSwapRestClient};
+    use crypto_rest_client::{fetch_candlesticks, fetch_l2_snapshot, MxcSwapRestClient};
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn test_trades() {
@@ -31,4 +59,29 @@ mod mxc_swap {
         let text = fetch_l2_snapshot("mxc", MarketType::LinearSwap, "BTC_USDT", Some(3)).unwrap();
         assert!(text.starts_with("{"));
     }
+
+    #[test]
+    fn test_candlesticks() {
+        let end_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let start_time = end_time - 1500 * 60 * 1000;
+        let klines = fetch_candlesticks(
+            "mxc",
+            MarketType::LinearSwap,
+            "BTC_USDT",
+            60,
+            start_time,
+            end_time,
+        )
+        .unwrap()
+        .map(|kline| kline.unwrap())
+        .collect::<Vec<_>>();
+        assert!(klines.len() > 1000);
+        for window in klines.windows(2) {
+            assert!(window[0].timestamp < window[1].timestamp);
+        }
+        assert!(klines[0].timestamp >= start_time);
+    }
 }
@@ -0,0 +1,151 @@
+#![cfg(feature = "async")]
+
+use crypto_market_type::MarketType;
+use crypto_rest_client::nonblocking::{
+    fetch_candlesticks, fetch_funding_rates, fetch_l2_snapshot, fetch_l3_snapshot,
+    fetch_open_interest, fetch_trades, BinanceSpotRestClient,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Runtime::new().unwrap().block_on(future)
+}
+
+#[test]
+fn test_l2_snapshot() {
+    let text = block_on(fetch_l2_snapshot(
+        "binance",
+        MarketType::Spot,
+        "BTCUSDT",
+        Some(3),
+    ))
+    .unwrap();
+    assert!(text.starts_with("{"));
+}
+
+#[test]
+fn test_l3_snapshot() {
+    let text = block_on(fetch_l3_snapshot(
+        "coinbase_pro",
+        MarketType::Spot,
+        "BTC-USD",
+        Some(3),
+    ))
+    .unwrap();
+    assert!(text.starts_with("{"));
+}
+
+#[test]
+fn test_open_interest() {
+    let text = block_on(fetch_open_interest(
+        "binance",
+        MarketType::LinearSwap,
+        Some("BTCUSDT"),
+    ))
+    .unwrap();
+    assert!(text.starts_with("{"));
+
+    // Two requests, one per currency
+    let text = block_on(fetch_open_interest("deribit", MarketType::Unknown, None)).unwrap();
+    assert_eq!(2, text.split('\n').count());
+}
+
+#[test]
+fn test_concurrent_requests() {
+    let texts = block_on(async {
+        let handles: Vec<_> = ["BTCUSDT", "ETHUSDT", "BNBUSDT"]
+            .iter()
+            .map(|symbol| {
+                tokio::spawn(async move {
+                    fetch_l2_snapshot("binance", MarketType::Spot, symbol, Some(3)).await
+                })
+            })
+            .collect();
+        let mut texts = Vec::new();
+        for handle in handles {
+            texts.push(handle.await.unwrap().unwrap());
+        }
+        texts
+    });
+    assert_eq!(3, texts.len());
+}
+
+#[test]
+fn test_rest_client() {
+    let text = block_on(BinanceSpotRestClient::fetch_l2_snapshot("BTCUSDT")).unwrap();
+    assert!(text.starts_with('{'));
+}
+
+#[test]
+fn test_trades() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 10 * 1000;
+    let trades = block_on(async {
+        let mut iter =
+            fetch_trades("binance", MarketType::Spot, "BTCUSDT", start_time, end_time).unwrap();
+        let mut trades = Vec::new();
+        while let Some(trade) = iter.next().await {
+            trades.push(trade.unwrap());
+        }
+        trades
+    });
+    assert!(!trades.is_empty());
+    for trade in trades.iter() {
+        assert!(trade.timestamp >= start_time && trade.timestamp <= end_time);
+    }
+}
+
+#[test]
+fn test_candlesticks() {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 3600 * 1000;
+    let klines = block_on(async {
+        let mut iter = fetch_candlesticks(
+            "binance",
+            MarketType::Spot,
+            "BTCUSDT",
+            60,
+            start_time,
+            end_time,
+        )
+        .unwrap();
+        let mut klines = Vec::new();
+        while let Some(kline) = iter.next().await {
+            klines.push(kline.unwrap());
+        }
+        klines
+    });
+    assert!(klines.len() >= 59);
+    assert!(klines.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+}
+
+#[test]
+fn test_funding_rates() {
+    let end_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let start_time = end_time - Duration::from_secs(3 * 86400);
+    let funding_rates = block_on(fetch_funding_rates(
+        "binance",
+        MarketType::LinearSwap,
+        "BTCUSDT",
+        start_time.as_millis() as i64,
+        end_time.as_millis() as i64,
+    ))
+    .unwrap();
+    assert!(funding_rates.len() >= 8);
+}
+
+#[test]
+fn test_blocking_within_runtime() {
+    // The blocking API must not panic inside a tokio runtime
+    let text = block_on(async {
+        crypto_rest_client::fetch_l2_snapshot("binance", MarketType::Spot, "BTCUSDT", Some(3))
+    })
+    .unwrap();
+    assert!(text.starts_with('{'));
+}
@@ -1,5 +1,9 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest};
+use crypto_msg_type::MessageType;
+use crypto_rest_client::{
+    fetch_index_prices, fetch_l2_snapshot, fetch_mark_prices, fetch_open_interest,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
 use test_case::test_case;
 
 #[test_case(MarketType::Spot, "BTC-USDT")]
@@ -22,6 +26,30 @@ fn test_open_interest(market_type: MarketType, symbol: &str) {
     assert!(text.starts_with("{"));
 }
 
+#[test_case(MarketType::InverseSwap, "BTC-USD-SWAP")]
+#[test_case(MarketType::LinearSwap, "BTC-USDT-SWAP")]
+fn test_mark_and_index_prices(market_type: MarketType, symbol: &str) {
+    let end_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let start_time = end_time - 300 * 60 * 1000;
+    let mark_prices =
+        fetch_mark_prices("okex", market_type, symbol, 60, start_time, end_time).unwrap();
+    assert!(mark_prices.len() > 250);
+    for window in mark_prices.windows(2) {
+        assert!(window[0].timestamp < window[1].timestamp);
+    }
+    assert_eq!(MessageType::MarkPrice, mark_prices[0].msg_type);
+    assert!(mark_prices[0].mark_price.unwrap() > 0.0);
+
+    let index_prices =
+        fetch_index_prices("okex", market_type, symbol, 60, start_time, end_time).unwrap();
+    assert!(index_prices.len() > 250);
+    assert_eq!(MessageType::IndexPrice, index_prices[0].msg_type);
+    assert!(index_prices[0].index_price.unwrap() > 0.0);
+}
+
 #[cfg(test)]
 mod okex_swap {
     use crypto_rest_client::OkexRestClient;
@@ -5,7 +5,7 @@ use std::sync::mpsc::Sender;
 use super::utils::ensure_frame_size;
 use super::{
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 use log::*;
 use serde_json::Value;
@@ -129,6 +129,8 @@ impl_trait!(Trade, BinanceWSClient, subscribe_trade, "aggTrade", to_raw_channel)
 #[rustfmt::skip]
 impl_trait!(Ticker, BinanceWSClient, subscribe_ticker, "ticker", to_raw_channel);
 #[rustfmt::skip]
+impl_trait!(MarkPrice, BinanceWSClient, subscribe_mark_price, "markPrice", to_raw_channel);
+#[rustfmt::skip]
 impl_trait!(BBO, BinanceWSClient, subscribe_bbo, "bookTicker", to_raw_channel);
 #[rustfmt::skip]
 impl_trait!(OrderBook, BinanceWSClient, subscribe_orderbook, "depth@100ms", to_raw_channel);
@@ -209,6 +211,10 @@ macro_rules! define_market_client {
                 <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
             }
 
+            fn subscribe_mark_price(&self, channels: &[String]) {
+                <$struct_name as MarkPrice>::subscribe_mark_price(self, channels);
+            }
+
             fn subscribe(&self, channels: &[String]) {
                 self.client.client.subscribe(channels);
             }
@@ -320,6 +326,20 @@ panic_l3_orderbook!(BinanceSpotWSClient);
 panic_l3_orderbook!(BinanceInverseWSClient);
 panic_l3_orderbook!(BinanceLinearWSClient);
 
+macro_rules! impl_mark_price {
+    ($struct_name:ident) => {
+        impl MarkPrice for $struct_name {
+            fn subscribe_mark_price(&self, pairs: &[String]) {
+                self.client.subscribe_mark_price(pairs);
+            }
+        }
+    };
+}
+
+panic_mark_price!(BinanceSpotWSClient);
+impl_mark_price!(BinanceInverseWSClient);
+impl_mark_price!(BinanceLinearWSClient);
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -4,7 +4,7 @@ use std::sync::mpsc::Sender;
 
 use super::{
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 use log::*;
 use serde_json::Value;
@@ -123,3 +123,4 @@ fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
 impl_candlestick!(BinanceOptionWSClient);
 
 panic_l3_orderbook!(BinanceOptionWSClient);
+panic_mark_price!(BinanceOptionWSClient);
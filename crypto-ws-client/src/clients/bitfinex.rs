@@ -15,7 +15,7 @@ use std::{
 
 use super::{
     utils::{connect_with_retry, CHANNEL_PAIR_DELIMITER},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
 use log::*;
@@ -220,6 +220,8 @@ impl Candlestick for BitfinexWSClient {
     }
 }
 
+panic_mark_price!(BitfinexWSClient);
+
 impl BitfinexWSClient {
     fn subscribe_or_unsubscribe(&self, channels: &[String], subscribe: bool) {
         let mut diff = Vec::<String>::new();
@@ -436,6 +438,10 @@ impl WSClient for BitfinexWSClient {
         <Self as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
     }
 
+    fn subscribe_mark_price(&self, channels: &[String]) {
+        <Self as MarkPrice>::subscribe_mark_price(self, channels);
+    }
+
     fn subscribe(&self, channels: &[String]) {
         self.subscribe_or_unsubscribe(channels, true);
     }
@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use crate::clients::utils::ensure_frame_size;
 
 use log::*;
@@ -121,6 +123,7 @@ fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
 impl_candlestick!(BitgetSwapWSClient);
 
 panic_l3_orderbook!(BitgetSwapWSClient);
+panic_mark_price!(BitgetSwapWSClient);
 
 impl_new_constructor!(
     BitgetSwapWSClient,
@@ -5,7 +5,7 @@ use std::sync::mpsc::Sender;
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
 use log::*;
@@ -108,6 +108,7 @@ impl Candlestick for BithumbWSClient {
 }
 
 panic_l3_orderbook!(BithumbWSClient);
+panic_mark_price!(BithumbWSClient);
 
 impl_new_constructor!(
     BithumbWSClient,
@@ -5,7 +5,7 @@ use std::{collections::HashMap, time::Duration};
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 use log::*;
 use serde_json::Value;
@@ -114,6 +114,8 @@ impl_trait!(BBO, BitmexWSClient, subscribe_bbo, "quote", to_raw_channel);
 impl_trait!(OrderBook, BitmexWSClient, subscribe_orderbook, "orderBookL2_25", to_raw_channel);
 #[rustfmt::skip]
 impl_trait!(OrderBookTopK, BitmexWSClient, subscribe_orderbook_topk, "orderBook10", to_raw_channel);
+#[rustfmt::skip]
+impl_trait!(MarkPrice, BitmexWSClient, subscribe_mark_price, "instrument", to_raw_channel);
 
 impl Ticker for BitmexWSClient {
     fn subscribe_ticker(&self, _pairs: &[String]) {
@@ -134,6 +136,43 @@ fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
 
 impl_candlestick!(BitmexWSClient);
 
+// Subscribes to all pairs if `pairs` is empty.
+fn to_raw_channels(channel: &str, pairs: &[String]) -> Vec<String> {
+    if pairs.is_empty() {
+        vec![channel.to_string()]
+    } else {
+        pairs
+            .iter()
+            .map(|pair| to_raw_channel(channel, pair))
+            .collect()
+    }
+}
+
+impl BitmexWSClient {
+    /// Subscribes to the `insurance` channel, which pushes daily balances of
+    /// insurance funds of all settlement currencies.
+    pub fn subscribe_insurance(&self) {
+        self.client.subscribe(&["insurance".to_string()]);
+    }
+
+    /// Subscribes to `settlement` channels, which push settlement and
+    /// delivery prices of futures.
+    ///
+    /// An empty `pairs` means all pairs.
+    pub fn subscribe_settlement(&self, pairs: &[String]) {
+        self.client.subscribe(&to_raw_channels("settlement", pairs));
+    }
+
+    /// Subscribes to `instrument` channels, which push updates of
+    /// instruments, including state changes such as `Open`, `Closed`,
+    /// `Settled` and `Unlisted`.
+    ///
+    /// An empty `pairs` means all pairs.
+    pub fn subscribe_instrument(&self, pairs: &[String]) {
+        self.client.subscribe(&to_raw_channels("instrument", pairs));
+    }
+}
+
 panic_l3_orderbook!(BitmexWSClient);
 
 impl_new_constructor!(
@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use log::*;
 use serde_json::Value;
 
@@ -107,6 +109,8 @@ impl Candlestick for BitstampWSClient {
     }
 }
 
+panic_mark_price!(BitstampWSClient);
+
 impl_new_constructor!(
     BitstampWSClient,
     EXCHANGE_NAME,
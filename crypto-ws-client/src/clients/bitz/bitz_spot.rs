@@ -4,7 +4,9 @@ use std::sync::mpsc::Sender;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
 use log::*;
 use serde_json::Value;
@@ -157,6 +159,7 @@ fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
 impl_candlestick!(BitzSpotWSClient);
 
 panic_l3_orderbook!(BitzSpotWSClient);
+panic_mark_price!(BitzSpotWSClient);
 
 impl_new_constructor!(
     BitzSpotWSClient,
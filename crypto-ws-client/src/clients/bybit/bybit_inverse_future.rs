@@ -2,9 +2,12 @@ use crate::WSClient;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
-    channels_to_commands, on_misc_msg, to_raw_channel, CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
+    channels_to_commands, on_misc_msg, to_insurance_raw_channels, to_raw_channel,
+    CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
 };
 
 const WEBSOCKET_URL: &str = "wss://stream.bybit.com/realtime";
@@ -45,9 +48,21 @@ fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
 
 impl_candlestick!(BybitInverseFutureWSClient);
 
+impl BybitInverseFutureWSClient {
+    /// Subscribes to `insurance` channels, which push daily balances of
+    /// insurance funds, e.g., `BTC`, `ETH`.
+    ///
+    /// An empty `currencies` means all currencies.
+    pub fn subscribe_insurance(&self, currencies: &[String]) {
+        self.client
+            .subscribe(&to_insurance_raw_channels(currencies));
+    }
+}
+
 panic_l2_topk!(BybitInverseFutureWSClient);
 panic_bbo!(BybitInverseFutureWSClient);
 panic_l3_orderbook!(BybitInverseFutureWSClient);
+panic_mark_price!(BybitInverseFutureWSClient);
 
 impl_new_constructor!(
     BybitInverseFutureWSClient,
@@ -2,7 +2,9 @@ use crate::WSClient;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_raw_channel, CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
 };
@@ -48,6 +50,7 @@ impl_candlestick!(BybitLinearSwapWSClient);
 panic_l2_topk!(BybitLinearSwapWSClient);
 panic_bbo!(BybitLinearSwapWSClient);
 panic_l3_orderbook!(BybitLinearSwapWSClient);
+panic_mark_price!(BybitLinearSwapWSClient);
 
 impl_new_constructor!(
     BybitLinearSwapWSClient,
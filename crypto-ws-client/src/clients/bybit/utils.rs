@@ -63,3 +63,15 @@ pub(super) fn on_misc_msg(msg: &str) -> MiscMessage {
 pub(super) fn to_raw_channel(channel: &str, pair: &str) -> String {
     format!("{}.{}", channel, pair)
 }
+
+// Subscribes to all currencies if `currencies` is empty.
+pub(super) fn to_insurance_raw_channels(currencies: &[String]) -> Vec<String> {
+    if currencies.is_empty() {
+        vec!["insurance".to_string()]
+    } else {
+        currencies
+            .iter()
+            .map(|currency| to_raw_channel("insurance", currency))
+            .collect()
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::mpsc::Sender;
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
 use log::*;
@@ -154,6 +154,8 @@ impl Level3OrderBook for CoinbaseProWSClient {
     }
 }
 
+panic_mark_price!(CoinbaseProWSClient);
+
 impl_new_constructor!(
     CoinbaseProWSClient,
     EXCHANGE_NAME,
@@ -33,6 +33,11 @@ pub(super) trait Level3OrderBook {
     fn subscribe_l3_orderbook(&self, symbols: &[String]);
 }
 
+// Mark price and index price of derivative contracts
+pub(super) trait MarkPrice {
+    fn subscribe_mark_price(&self, pairs: &[String]);
+}
+
 pub(super) trait Candlestick {
     /// Subscribes to candlestick channels which send OHLCV messages.
     ///
@@ -119,6 +124,19 @@ macro_rules! panic_l3_orderbook {
     };
 }
 
+macro_rules! panic_mark_price {
+    ($struct_name:ident) => {
+        impl MarkPrice for $struct_name {
+            fn subscribe_mark_price(&self, _symbols: &[String]) {
+                panic!(
+                    "{} does NOT have the mark price websocket channel",
+                    EXCHANGE_NAME
+                );
+            }
+        }
+    };
+}
+
 macro_rules! panic_candlestick {
     ($struct_name:ident) => {
         impl Candlestick for $struct_name {
@@ -4,7 +4,9 @@ use std::sync::mpsc::Sender;
 
 use super::utils::ensure_frame_size;
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
 use log::*;
 use serde_json::Value;
@@ -113,6 +115,8 @@ impl_trait!(OrderBook, DeribitWSClient, subscribe_orderbook, "orderbook", to_raw
 impl_trait!(OrderBookTopK, DeribitWSClient, subscribe_orderbook_topk, "orderbook_snapshot", to_raw_channel);
 #[rustfmt::skip]
 impl_trait!(BBO, DeribitWSClient, subscribe_bbo, "bbo", to_raw_channel);
+#[rustfmt::skip]
+impl_trait!(MarkPrice, DeribitWSClient, subscribe_mark_price, "ticker", to_raw_channel);
 
 fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
     let interval_str = match interval {
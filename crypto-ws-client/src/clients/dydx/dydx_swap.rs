@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use crate::clients::utils::CHANNEL_PAIR_DELIMITER;
 
 use log::*;
@@ -97,6 +99,7 @@ panic_ticker!(DydxSwapWSClient);
 panic_bbo!(DydxSwapWSClient);
 panic_l2_topk!(DydxSwapWSClient);
 panic_l3_orderbook!(DydxSwapWSClient);
+panic_mark_price!(DydxSwapWSClient);
 panic_candlestick!(DydxSwapWSClient);
 
 impl_new_constructor!(
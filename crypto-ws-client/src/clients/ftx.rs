@@ -6,7 +6,9 @@ use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
 };
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
 use log::*;
 use serde_json::Value;
@@ -108,6 +110,7 @@ impl Candlestick for FtxWSClient {
 }
 
 panic_l3_orderbook!(FtxWSClient);
+panic_mark_price!(FtxWSClient);
 
 impl_new_constructor!(
     FtxWSClient,
@@ -2,7 +2,9 @@ use crate::WSClient;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_candlestick_raw_channel_shared, to_raw_channel,
     CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
@@ -71,7 +73,9 @@ impl_candlestick!(GateInverseFutureWSClient);
 impl_candlestick!(GateLinearFutureWSClient);
 
 panic_l3_orderbook!(GateInverseFutureWSClient);
+panic_mark_price!(GateInverseFutureWSClient);
 panic_l3_orderbook!(GateLinearFutureWSClient);
+panic_mark_price!(GateLinearFutureWSClient);
 
 impl_new_constructor!(
     GateInverseFutureWSClient,
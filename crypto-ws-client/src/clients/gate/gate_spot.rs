@@ -2,7 +2,9 @@ use crate::WSClient;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_candlestick_raw_channel_shared, to_raw_channel,
     EXCHANGE_NAME,
@@ -39,6 +41,7 @@ fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
 impl_candlestick!(GateSpotWSClient);
 
 panic_l3_orderbook!(GateSpotWSClient);
+panic_mark_price!(GateSpotWSClient);
 
 impl_new_constructor!(
     GateSpotWSClient,
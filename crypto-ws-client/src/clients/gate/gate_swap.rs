@@ -2,7 +2,9 @@ use crate::WSClient;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_candlestick_raw_channel_shared, to_raw_channel,
     CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
@@ -57,7 +59,9 @@ impl_candlestick!(GateInverseSwapWSClient);
 impl_candlestick!(GateLinearSwapWSClient);
 
 panic_l3_orderbook!(GateInverseSwapWSClient);
+panic_mark_price!(GateInverseSwapWSClient);
 panic_l3_orderbook!(GateLinearSwapWSClient);
+panic_mark_price!(GateLinearSwapWSClient);
 
 impl_new_constructor!(
     GateInverseSwapWSClient,
@@ -7,7 +7,9 @@ use serde_json::Value;
 use tungstenite::Message;
 
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
 pub(super) const EXCHANGE_NAME: &str = "huobi";
 
@@ -277,6 +279,10 @@ macro_rules! define_market_client {
                 <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
             }
 
+            fn subscribe_mark_price(&self, channels: &[String]) {
+                <$struct_name as MarkPrice>::subscribe_mark_price(self, channels);
+            }
+
             fn subscribe(&self, channels: &[String]) {
                 self.client.subscribe(channels);
             }
@@ -411,6 +417,12 @@ panic_l3_orderbook!(HuobiInverseSwapWSClient);
 panic_l3_orderbook!(HuobiLinearSwapWSClient);
 panic_l3_orderbook!(HuobiOptionWSClient);
 
+panic_mark_price!(HuobiSpotWSClient);
+panic_mark_price!(HuobiFutureWSClient);
+panic_mark_price!(HuobiInverseSwapWSClient);
+panic_mark_price!(HuobiLinearSwapWSClient);
+panic_mark_price!(HuobiOptionWSClient);
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -5,7 +5,7 @@ use std::sync::mpsc::Sender;
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
 use log::*;
@@ -170,6 +170,7 @@ panic_bbo!(KrakenFuturesWSClient);
 panic_candlestick!(KrakenFuturesWSClient);
 
 panic_l3_orderbook!(KrakenFuturesWSClient);
+panic_mark_price!(KrakenFuturesWSClient);
 
 impl_new_constructor!(
     KrakenFuturesWSClient,
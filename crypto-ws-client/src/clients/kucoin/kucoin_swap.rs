@@ -2,7 +2,9 @@ use crate::WSClient;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, fetch_ws_token, on_misc_msg, to_raw_channel,
     CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
@@ -88,4 +90,6 @@ impl Level3OrderBook for KuCoinSwapWSClient {
     }
 }
 
+panic_mark_price!(KuCoinSwapWSClient);
+
 impl_ws_client_trait!(KuCoinSwapWSClient);
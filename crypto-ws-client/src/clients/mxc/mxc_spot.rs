@@ -5,7 +5,7 @@ use crate::WSClient;
 use super::super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 use super::EXCHANGE_NAME;
 
@@ -130,6 +130,7 @@ panic_bbo!(MxcSpotWSClient);
 panic_ticker!(MxcSpotWSClient);
 panic_l2_topk!(MxcSpotWSClient);
 panic_l3_orderbook!(MxcSpotWSClient);
+panic_mark_price!(MxcSpotWSClient);
 
 impl_new_constructor!(
     MxcSpotWSClient,
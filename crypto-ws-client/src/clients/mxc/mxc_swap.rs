@@ -6,7 +6,7 @@ use crate::WSClient;
 use super::super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 use super::EXCHANGE_NAME;
 
@@ -127,6 +127,7 @@ impl Candlestick for MxcSwapWSClient {
 
 panic_bbo!(MxcSwapWSClient);
 panic_l3_orderbook!(MxcSwapWSClient);
+panic_mark_price!(MxcSwapWSClient);
 
 impl_new_constructor!(
     MxcSwapWSClient,
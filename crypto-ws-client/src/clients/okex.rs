@@ -4,7 +4,9 @@ use std::sync::mpsc::Sender;
 
 use super::utils::ensure_frame_size;
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
 use log::*;
 use serde_json::Value;
@@ -116,6 +118,26 @@ impl_trait!(Ticker, OkexWSClient, subscribe_ticker, "ticker", to_raw_channel);
 impl_trait!(OrderBook, OkexWSClient, subscribe_orderbook, "depth_l2_tbt", to_raw_channel);
 #[rustfmt::skip]
 impl_trait!(OrderBookTopK, OkexWSClient, subscribe_orderbook_topk, "depth5", to_raw_channel);
+#[rustfmt::skip]
+impl_trait!(MarkPrice, OkexWSClient, subscribe_mark_price, "mark_price", to_raw_channel);
+
+impl OkexWSClient {
+    /// Subscribes to `index/ticker` channels, which push index prices.
+    ///
+    /// `pairs` are contract symbols, e.g., `BTC-USD-SWAP`, each of them is
+    /// mapped to its underlying index, e.g., `BTC-USD`.
+    pub fn subscribe_index_price(&self, pairs: &[String]) {
+        let mut channels: Vec<String> = Vec::new();
+        for pair in pairs.iter() {
+            let underlying = pair.split('-').take(2).collect::<Vec<&str>>().join("-");
+            let channel = format!("index/ticker:{}", underlying);
+            if !channels.contains(&channel) {
+                channels.push(channel);
+            }
+        }
+        self.client.subscribe(&channels);
+    }
+}
 
 fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
     let valid_set: Vec<usize> = vec![
@@ -481,6 +481,10 @@ macro_rules! impl_ws_client_trait {
                 <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
             }
 
+            fn subscribe_mark_price(&self, channels: &[String]) {
+                <$struct_name as MarkPrice>::subscribe_mark_price(self, channels);
+            }
+
             fn subscribe(&self, channels: &[String]) {
                 self.client.subscribe(channels);
             }
@@ -2,7 +2,9 @@ use crate::WSClient;
 use std::{collections::HashMap, sync::mpsc::Sender, sync::RwLock};
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Candlestick, Level3OrderBook, MarkPrice, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::fetch_symbol_contract_id_map_swap;
 
 use lazy_static::lazy_static;
@@ -124,6 +126,7 @@ fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
 impl_candlestick!(ZbgSwapWSClient);
 
 panic_l3_orderbook!(ZbgSwapWSClient);
+panic_mark_price!(ZbgSwapWSClient);
 
 impl_new_constructor!(
     ZbgSwapWSClient,
@@ -185,6 +185,20 @@ pub trait WSClient {
     /// and CoinbasePro.
     fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]);
 
+    /// Subscribes to mark price channels.
+    ///
+    /// Only derivative markets have mark prices, some channels carry index
+    /// prices too:
+    ///
+    /// * Binance `markPrice`, with index prices in linear markets
+    /// * BitMEX `instrument`, with index prices in `indicativeSettlePrice`
+    /// * Deribit `ticker`, with index prices
+    /// * OKEx `mark_price`, index prices are in the `index/ticker` channel
+    ///
+    /// Other exchanges don't have mark price channels, calling this function
+    /// with them will panic.
+    fn subscribe_mark_price(&self, pairs: &[String]);
+
     /// Subscribes to raw channels, lower level API.
     ///
     /// A `raw_channel` can be:
@@ -407,6 +407,15 @@ mod binance_inverse_swap {
             &vec!["!markPrice@arr".to_string()]
         );
     }
+
+    #[test]
+    fn subscribe_mark_price() {
+        gen_test_code!(
+            BinanceInverseWSClient,
+            subscribe_mark_price,
+            &vec!["btcusd_perp".to_string()]
+        );
+    }
 }
 
 #[cfg(test)]
@@ -518,4 +527,13 @@ mod binance_linear_swap {
             &vec!["!markPrice@arr".to_string()]
         );
     }
+
+    #[test]
+    fn subscribe_mark_price() {
+        gen_test_code!(
+            BinanceLinearWSClient,
+            subscribe_mark_price,
+            &vec!["btcusdt".to_string()]
+        );
+    }
 }
@@ -9,6 +9,33 @@ fn bitmex_instrument() {
     gen_test_code!(BitmexWSClient, subscribe, &vec!["instrument".to_string()]);
 }
 
+#[test]
+fn bitmex_insurance() {
+    let (tx, rx): (Sender<String>, Receiver<String>) = std::sync::mpsc::channel();
+    {
+        let ws_client = BitmexWSClient::new(tx, None);
+        ws_client.subscribe_insurance();
+        ws_client.run(Some(0));
+        ws_client.close();
+    }
+    let messages = rx.into_iter().collect::<Vec<String>>();
+    assert!(!messages.is_empty());
+}
+
+#[test]
+fn bitmex_settlement() {
+    gen_test_code!(BitmexWSClient, subscribe_settlement, &Vec::new());
+}
+
+#[test]
+fn bitmex_instrument_status() {
+    gen_test_code!(
+        BitmexWSClient,
+        subscribe_instrument,
+        &vec!["XBTUSD".to_string()]
+    );
+}
+
 #[cfg(test)]
 mod bitmex_inverse_swap {
     use crypto_ws_client::{BitmexWSClient, WSClient};
@@ -42,6 +69,15 @@ mod bitmex_inverse_swap {
         gen_test_code!(BitmexWSClient, subscribe_bbo, &vec!["XBTUSD".to_string()]);
     }
 
+    #[test]
+    fn subscribe_mark_price() {
+        gen_test_code!(
+            BitmexWSClient,
+            subscribe_mark_price,
+            &vec!["XBTUSD".to_string()]
+        );
+    }
+
     #[test]
     fn subscribe_orderbook() {
         gen_test_code!(
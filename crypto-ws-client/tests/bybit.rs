@@ -51,6 +51,15 @@ mod bybit_inverse_future {
         );
     }
 
+    #[test]
+    fn subscribe_insurance() {
+        gen_test_code!(
+            BybitInverseFutureWSClient,
+            subscribe_insurance,
+            &vec!["BTC".to_string()]
+        );
+    }
+
     #[test]
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
@@ -114,6 +123,15 @@ mod bybit_inverse_swap {
         );
     }
 
+    #[test]
+    fn subscribe_insurance() {
+        gen_test_code!(
+            BybitInverseSwapWSClient,
+            subscribe_insurance,
+            &vec!["BTC".to_string()]
+        );
+    }
+
     #[test]
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
@@ -106,6 +106,15 @@ mod deribit_inverse_swap {
         );
     }
 
+    #[test]
+    fn subscribe_mark_price() {
+        gen_test_code!(
+            DeribitWSClient,
+            subscribe_mark_price,
+            &vec!["BTC-PERPETUAL".to_string()]
+        );
+    }
+
     #[test]
     fn subscribe_orderbook() {
         gen_test_code!(
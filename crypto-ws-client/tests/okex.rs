@@ -172,6 +172,24 @@ mod okex_swap {
         );
     }
 
+    #[test]
+    fn subscribe_mark_price() {
+        gen_test_code!(
+            OkexWSClient,
+            subscribe_mark_price,
+            &vec!["BTC-USDT-SWAP".to_string()]
+        );
+    }
+
+    #[test]
+    fn subscribe_index_price() {
+        gen_test_code!(
+            OkexWSClient,
+            subscribe_index_price,
+            &vec!["BTC-USDT-SWAP".to_string()]
+        );
+    }
+
     #[test]
     fn subscribe_orderbook() {
         gen_test_code!(